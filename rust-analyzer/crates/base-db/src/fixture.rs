@@ -409,6 +409,7 @@ fn parse_crate(crate_str: String) -> (String, CrateOrigin, Option<String>) {
         let crate_origin = match &*crate_str {
             "std" => CrateOrigin::Lang(LangCrateOrigin::Std),
             "core" => CrateOrigin::Lang(LangCrateOrigin::Core),
+            "alloc" => CrateOrigin::Lang(LangCrateOrigin::Alloc),
             _ => CrateOrigin::CratesIo { repo: None },
         };
         (crate_str, crate_origin, None)
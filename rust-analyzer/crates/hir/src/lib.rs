@@ -39,7 +39,7 @@ use arrayvec::ArrayVec;
 use base_db::{CrateDisplayName, CrateId, CrateOrigin, Edition, FileId, ProcMacroKind};
 use either::Either;
 use hir_def::{
-    adt::{ReprData, VariantData},
+    adt::{ReprData, ReprKind, VariantData},
     body::{BodyDiagnostic, SyntheticSyntax},
     expr::{BindingAnnotation, LabelId, Pat, PatId},
     generics::{TypeOrConstParamData, TypeParamProvenance},
@@ -967,6 +967,19 @@ impl Enum {
     pub fn is_data_carrying(self, db: &dyn HirDatabase) -> bool {
         self.variants(db).iter().any(|v| !matches!(v.kind(db), StructKind::Unit))
     }
+
+    /// The integer spelled out by `#[repr(..)]`, unlike `variant_body_ty` there is no `isize` fallback.
+    pub fn repr_int(self, db: &dyn HirDatabase) -> Option<BuiltinType> {
+        let builtin = match db.enum_data(self.id).repr {
+            Some(ReprData { kind: ReprKind::BuiltinInt { builtin, .. }, .. }) => builtin,
+            _ => return None,
+        };
+        let inner = match builtin {
+            Either::Left(builtin) => hir_def::builtin_type::BuiltinType::Int(builtin),
+            Either::Right(builtin) => hir_def::builtin_type::BuiltinType::Uint(builtin),
+        };
+        Some(BuiltinType { inner })
+    }
 }
 
 impl HasVisibility for Enum {
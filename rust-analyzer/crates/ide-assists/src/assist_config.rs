@@ -4,7 +4,7 @@
 //! module, and we use to statically check that we only produce snippet
 //! assists if we are allowed to.
 
-use ide_db::{imports::insert_use::InsertUseConfig, SnippetCap};
+use ide_db::{imports::insert_use::InsertUseConfig, FxHashSet, SnippetCap};
 
 use crate::AssistKind;
 
@@ -14,4 +14,7 @@ pub struct AssistConfig {
     pub allowed: Option<Vec<AssistKind>>,
     pub insert_use: InsertUseConfig,
     pub prefer_no_std: bool,
+    pub suggest_safe_wrappers: bool,
+    pub disabled_unsafe_patterns: Option<FxHashSet<String>>,
+    pub annotate_risky_conversions: bool,
 }
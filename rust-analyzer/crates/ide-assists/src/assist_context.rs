@@ -2,7 +2,7 @@
 
 use hir::Semantics;
 use ide_db::base_db::{FileId, FileRange};
-use ide_db::{label::Label, RootDatabase};
+use ide_db::{label::Label, RootDatabase, SnippetCap};
 use syntax::{
     algo::{self, find_node_at_offset, find_node_at_range},
     AstNode, AstToken, Direction, SourceFile, SyntaxElement, SyntaxKind, SyntaxToken, TextRange,
@@ -133,6 +133,7 @@ pub(crate) struct Assists {
     resolve: AssistResolveStrategy,
     buf: Vec<Assist>,
     allowed: Option<Vec<AssistKind>>,
+    snippet_cap: Option<SnippetCap>,
 }
 
 impl Assists {
@@ -142,9 +143,14 @@ impl Assists {
             file: ctx.frange.file_id,
             buf: Vec::new(),
             allowed: ctx.config.allowed.clone(),
+            snippet_cap: ctx.config.snippet_cap,
         }
     }
 
+    pub(crate) fn snippet_cap(&self) -> Option<SnippetCap> {
+        self.snippet_cap
+    }
+
     pub(crate) fn finish(mut self) -> Vec<Assist> {
         self.buf.sort_by_key(|assist| assist.target.len());
         self.buf
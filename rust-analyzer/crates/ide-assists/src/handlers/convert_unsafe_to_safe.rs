@@ -1,22 +1,27 @@
-use crate::{
-    assist_context::{AssistContext, Assists},
-    AssistId, AssistKind,
-};
+use std::str::FromStr;
 
-use syntax::{
-    ast::{IndexExpr, BlockExpr, MethodCallExpr, ExprStmt, CallExpr, edit_in_place::Indent, LetStmt, BinExpr},
-    SyntaxKind::{STMT_LIST, EXPR_STMT, INDEX_EXPR, LET_STMT, PATH_EXPR, BIN_EXPR, PREFIX_EXPR}, 
-    TextSize, Direction
-};
+use hir::{AsAssocItem, HasSource, HirDisplay, Semantics};
+use ide_db::{defs::Definition, famous_defs::FamousDefs, RootDatabase};
 use itertools::Itertools;
 use stdx::format_to;
 use syntax::{
     ast::{
-        self,
-        AstNode,
-        HasArgList,
+        self, edit::IndentLevel, edit_in_place::Indent, make, AstNode, AstToken, BinExpr,
+        BlockExpr, CallExpr, ExprStmt, HasArgList, HasAttrs, HasLoopBody, HasName, IndexExpr,
+        LetStmt, MethodCallExpr,
+    },
+    Direction, NodeOrToken,
+    SyntaxKind::{
+        BIN_EXPR, COMMENT, ERROR, EXPR_STMT, FIELD_EXPR, IDENT, INDEX_EXPR, LET_STMT,
+        LIFETIME_IDENT, METHOD_CALL_EXPR, PATH_EXPR, PREFIX_EXPR, REF_EXPR, STMT_LIST, TRY_EXPR,
+        WHITESPACE,
     },
-    SyntaxNode, TextRange, T,
+    SyntaxNode, TextRange, TextSize, T,
+};
+
+use crate::{
+    assist_context::{AssistContext, Assists},
+    AssistId, AssistKind, AssistResolveStrategy,
 };
 
 // Assist: convert_unsafe_to_safe
@@ -25,11 +30,9 @@ use syntax::{
 //
 // ```
 // fn main() {
-//
 //     let mut buffer = Vec::with_capacity(cap);
-//
-//     unsafe {
-//         buffer.set_len(cap); 
+//     unsafe$0 {
+//         buffer.set_len(cap);
 //         foo();
 //     }
 // }
@@ -37,16 +40,23 @@ use syntax::{
 // ->
 // ```
 // fn main() {
-//    
 //     let mut buffer = vec![0; cap];
-//  
 //     unsafe {
 //         foo();
 //     }
-//     
 // }
 // ```
 
+/// How closely the safe replacement of a pattern follows the unsafe code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConversionClass {
+    /// The replacement does exactly what the unsafe code did.
+    AlwaysEquivalent,
+    /// The replacement may panic or produce a different value where the unsafe code had UB.
+    BehaviorChanging,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UnsafePattern {
     SetVecCapacity,
     ReserveVec,
@@ -69,19 +79,552 @@ pub enum UnsafePattern {
     ReadUnaligned,
     AsPtr,
     FromU32Unchecked,
-    STDFromU32Unchecked
+    STDFromU32Unchecked,
+    StrGetUnchecked,
+    MemZeroed,
+    TransmuteLifetime,
+    MemUninitialized,
+    MaybeUninitAssumeInit,
+    MaybeUninitAssumeInitRef,
+    MaybeUninitAssumeInitMut,
+    SwapUnchecked,
+    SplitAtUnchecked,
+    SplitAtMutUnchecked,
+    SafeWrapperCall,
+    UncheckedAdd,
+    UncheckedSub,
+    UncheckedMul,
+    FloatToIntUnchecked,
+    VecFromRawParts,
+    RcFromRaw,
+    ArcFromRaw,
+    StaticMutAccess,
+    PtrReadDefault,
+    DropInPlace,
+    RawPtrIndexDeref,
+    LibcMemcpy,
+    LibcMemmove,
+    LibcMemset,
+    PtrSwap,
+    CStrFromBytesWithNul,
+    PtrReplace,
+    TransmuteSameLayout,
+    TransmuteFloatBits,
+    TransmuteIntToEnum,
+    StringAsMutVec,
+    PinNewUnchecked,
+    StaticMutAtomic,
+    BoxLeakRoundTrip,
+    PtrOffsetFrom,
+    StrBytesGetUnchecked,
+    WriteUnaligned,
+    AsBytesMut,
+    AllocToVec,
+    TransmuteEnumDiscriminant,
+    GetUncheckMutPair,
+    AsChunksUnchecked,
+    PtrAsRef,
+    PtrAsMut,
+    ManuallyDropDrop,
+    SliceFromRawParts,
+}
+
+impl UnsafePattern {
+    /// Every pattern, in declaration order.
+    pub const ALL: &'static [UnsafePattern] = &[
+        UnsafePattern::SetVecCapacity,
+        UnsafePattern::ReserveVec,
+        UnsafePattern::WriteVec,
+        UnsafePattern::UnitializedVec,
+        UnsafePattern::CopyWithin,
+        UnsafePattern::GetUncheck,
+        UnsafePattern::GetUncheckMut,
+        UnsafePattern::CopyNonOverlap,
+        UnsafePattern::CStringFromVec,
+        UnsafePattern::CStringLength,
+        UnsafePattern::StringBytesToUTFString,
+        UnsafePattern::BytesToUTFString,
+        UnsafePattern::CoreBytesToUTFString,
+        UnsafePattern::STDBytesToUTFString,
+        UnsafePattern::BytesToUTFStringMut,
+        UnsafePattern::CoreBytesToUTFStringMut,
+        UnsafePattern::STDBytesToUTFStringMut,
+        UnsafePattern::TransmuteTo,
+        UnsafePattern::ReadUnaligned,
+        UnsafePattern::AsPtr,
+        UnsafePattern::FromU32Unchecked,
+        UnsafePattern::STDFromU32Unchecked,
+        UnsafePattern::StrGetUnchecked,
+        UnsafePattern::MemZeroed,
+        UnsafePattern::TransmuteLifetime,
+        UnsafePattern::MemUninitialized,
+        UnsafePattern::MaybeUninitAssumeInit,
+        UnsafePattern::MaybeUninitAssumeInitRef,
+        UnsafePattern::MaybeUninitAssumeInitMut,
+        UnsafePattern::SwapUnchecked,
+        UnsafePattern::SplitAtUnchecked,
+        UnsafePattern::SplitAtMutUnchecked,
+        UnsafePattern::SafeWrapperCall,
+        UnsafePattern::UncheckedAdd,
+        UnsafePattern::UncheckedSub,
+        UnsafePattern::UncheckedMul,
+        UnsafePattern::FloatToIntUnchecked,
+        UnsafePattern::VecFromRawParts,
+        UnsafePattern::RcFromRaw,
+        UnsafePattern::ArcFromRaw,
+        UnsafePattern::StaticMutAccess,
+        UnsafePattern::PtrReadDefault,
+        UnsafePattern::DropInPlace,
+        UnsafePattern::RawPtrIndexDeref,
+        UnsafePattern::LibcMemcpy,
+        UnsafePattern::LibcMemmove,
+        UnsafePattern::LibcMemset,
+        UnsafePattern::PtrSwap,
+        UnsafePattern::CStrFromBytesWithNul,
+        UnsafePattern::PtrReplace,
+        UnsafePattern::TransmuteSameLayout,
+        UnsafePattern::TransmuteFloatBits,
+        UnsafePattern::TransmuteIntToEnum,
+        UnsafePattern::StringAsMutVec,
+        UnsafePattern::PinNewUnchecked,
+        UnsafePattern::StaticMutAtomic,
+        UnsafePattern::BoxLeakRoundTrip,
+        UnsafePattern::PtrOffsetFrom,
+        UnsafePattern::StrBytesGetUnchecked,
+        UnsafePattern::WriteUnaligned,
+        UnsafePattern::AsBytesMut,
+        UnsafePattern::AllocToVec,
+        UnsafePattern::TransmuteEnumDiscriminant,
+        UnsafePattern::GetUncheckMutPair,
+        UnsafePattern::AsChunksUnchecked,
+        UnsafePattern::PtrAsRef,
+        UnsafePattern::PtrAsMut,
+        UnsafePattern::ManuallyDropDrop,
+        UnsafePattern::SliceFromRawParts,
+    ];
+
+    /// Stable snake_case name of the pattern, used to switch it off in the config.
+    ///
+    /// Unlike `Display`, which is meant for people, this never changes.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UnsafePattern::SetVecCapacity => "with_capacity",
+            UnsafePattern::ReserveVec => "reserve",
+            UnsafePattern::WriteVec => "write",
+            UnsafePattern::UnitializedVec => "uninitialized_vec",
+            UnsafePattern::CopyWithin => "ptr_copy",
+            UnsafePattern::GetUncheck => "get_unchecked",
+            UnsafePattern::GetUncheckMut => "get_unchecked_mut",
+            UnsafePattern::CopyNonOverlap => "ptr_copy_nonoverlapping",
+            UnsafePattern::CStringFromVec => "cstring_from_vec_unchecked",
+            UnsafePattern::CStringLength => "cstring_strlen",
+            UnsafePattern::StringBytesToUTFString => "string_from_utf8_unchecked",
+            UnsafePattern::BytesToUTFString => "str_from_utf8_unchecked",
+            UnsafePattern::CoreBytesToUTFString => "core_str_from_utf8_unchecked",
+            UnsafePattern::STDBytesToUTFString => "std_str_from_utf8_unchecked",
+            UnsafePattern::BytesToUTFStringMut => "str_from_utf8_unchecked_mut",
+            UnsafePattern::CoreBytesToUTFStringMut => "core_str_from_utf8_unchecked_mut",
+            UnsafePattern::STDBytesToUTFStringMut => "std_str_from_utf8_unchecked_mut",
+            UnsafePattern::TransmuteTo => "transmute",
+            UnsafePattern::ReadUnaligned => "read_unaligned",
+            UnsafePattern::AsPtr => "as_ptr",
+            UnsafePattern::FromU32Unchecked => "char_from_u32_unchecked",
+            UnsafePattern::STDFromU32Unchecked => "std_char_from_u32_unchecked",
+            UnsafePattern::StrGetUnchecked => "str_get_unchecked",
+            UnsafePattern::MemZeroed => "mem_zeroed",
+            UnsafePattern::TransmuteLifetime => "transmute_lifetime",
+            UnsafePattern::MemUninitialized => "mem_uninitialized",
+            UnsafePattern::MaybeUninitAssumeInit => "maybe_uninit_assume_init",
+            UnsafePattern::MaybeUninitAssumeInitRef => "maybe_uninit_assume_init_ref",
+            UnsafePattern::MaybeUninitAssumeInitMut => "maybe_uninit_assume_init_mut",
+            UnsafePattern::SwapUnchecked => "swap_unchecked",
+            UnsafePattern::SplitAtUnchecked => "split_at_unchecked",
+            UnsafePattern::SplitAtMutUnchecked => "split_at_mut_unchecked",
+            UnsafePattern::SafeWrapperCall => "safe_wrapper_call",
+            UnsafePattern::UncheckedAdd => "unchecked_add",
+            UnsafePattern::UncheckedSub => "unchecked_sub",
+            UnsafePattern::UncheckedMul => "unchecked_mul",
+            UnsafePattern::FloatToIntUnchecked => "to_int_unchecked",
+            UnsafePattern::VecFromRawParts => "vec_from_raw_parts",
+            UnsafePattern::RcFromRaw => "rc_from_raw",
+            UnsafePattern::ArcFromRaw => "arc_from_raw",
+            UnsafePattern::StaticMutAccess => "static_mut_access",
+            UnsafePattern::PtrReadDefault => "ptr_read_default",
+            UnsafePattern::DropInPlace => "drop_in_place",
+            UnsafePattern::RawPtrIndexDeref => "raw_ptr_index_deref",
+            UnsafePattern::LibcMemcpy => "libc_memcpy",
+            UnsafePattern::LibcMemmove => "libc_memmove",
+            UnsafePattern::LibcMemset => "libc_memset",
+            UnsafePattern::PtrSwap => "ptr_swap",
+            UnsafePattern::CStrFromBytesWithNul => "cstr_from_bytes_with_nul_unchecked",
+            UnsafePattern::PtrReplace => "ptr_replace",
+            UnsafePattern::TransmuteSameLayout => "transmute_same_layout",
+            UnsafePattern::TransmuteFloatBits => "transmute_float_bits",
+            UnsafePattern::TransmuteIntToEnum => "transmute_int_to_enum",
+            UnsafePattern::StringAsMutVec => "string_as_mut_vec",
+            UnsafePattern::PinNewUnchecked => "pin_new_unchecked",
+            UnsafePattern::StaticMutAtomic => "static_mut_atomic",
+            UnsafePattern::BoxLeakRoundTrip => "box_leak_round_trip",
+            UnsafePattern::PtrOffsetFrom => "ptr_offset_from",
+            UnsafePattern::StrBytesGetUnchecked => "str_bytes_get_unchecked",
+            UnsafePattern::WriteUnaligned => "write_unaligned",
+            UnsafePattern::AsBytesMut => "as_bytes_mut",
+            UnsafePattern::AllocToVec => "alloc_to_vec",
+            UnsafePattern::TransmuteEnumDiscriminant => "transmute_enum_discriminant",
+            UnsafePattern::GetUncheckMutPair => "get_unchecked_mut_pair",
+            UnsafePattern::AsChunksUnchecked => "as_chunks_unchecked",
+            UnsafePattern::PtrAsRef => "ptr_as_ref",
+            UnsafePattern::PtrAsMut => "ptr_as_mut",
+            UnsafePattern::ManuallyDropDrop => "manually_drop_drop",
+            UnsafePattern::SliceFromRawParts => "slice_from_raw_parts",
+        }
+    }
+
+    /// Source substrings that identify the pattern: the callee path or method name
+    /// as written, plus the shorter forms left behind by a `use` import.
+    ///
+    /// Empty for the patterns recognized by their shape rather than by a name.
+    pub fn detection_needles(&self) -> &'static [&'static str] {
+        match self {
+            UnsafePattern::SetVecCapacity => &["with_capacity"],
+            UnsafePattern::ReserveVec => &["reserve"],
+            UnsafePattern::WriteVec => &["write"],
+            UnsafePattern::UnitializedVec => &["set_len"],
+            UnsafePattern::CopyWithin => {
+                &["ptr::copy", "std::ptr::copy", "core::ptr::copy", "copy"]
+            }
+            UnsafePattern::GetUncheck => &["get_unchecked"],
+            UnsafePattern::GetUncheckMut => &["get_unchecked_mut"],
+            UnsafePattern::CopyNonOverlap => &[
+                "ptr::copy_nonoverlapping",
+                "std::ptr::copy_nonoverlapping",
+                "core::ptr::copy_nonoverlapping",
+                "copy_nonoverlapping",
+            ],
+            UnsafePattern::CStringFromVec => &["CString::from_vec_unchecked"],
+            UnsafePattern::CStringLength => &["libc::strlen"],
+            UnsafePattern::StringBytesToUTFString => &["String::from_utf8_unchecked"],
+            UnsafePattern::BytesToUTFString => &["str::from_utf8_unchecked"],
+            UnsafePattern::STDBytesToUTFString => &["std::str::from_utf8_unchecked"],
+            UnsafePattern::CoreBytesToUTFString => &["core::str::from_utf8_unchecked"],
+            UnsafePattern::BytesToUTFStringMut => &["str::from_utf8_unchecked_mut"],
+            UnsafePattern::STDBytesToUTFStringMut => &["std::str::from_utf8_unchecked_mut"],
+            UnsafePattern::CoreBytesToUTFStringMut => &["core::str::from_utf8_unchecked_mut"],
+            UnsafePattern::TransmuteTo => &["mem::transmute"],
+            UnsafePattern::ReadUnaligned => {
+                &["ptr::read_unaligned", "std::ptr::read_unaligned", "core::ptr::read_unaligned"]
+            }
+            UnsafePattern::AsPtr => &["as_ptr"],
+            UnsafePattern::FromU32Unchecked => &["char::from_u32_unchecked"],
+            UnsafePattern::STDFromU32Unchecked => &["std::char::from_u32_unchecked"],
+            UnsafePattern::StrGetUnchecked => &["get_unchecked", "get_unchecked_mut"],
+            UnsafePattern::MemZeroed => &["mem::zeroed", "std::mem::zeroed", "core::mem::zeroed"],
+            UnsafePattern::TransmuteLifetime => &["transmute"],
+            UnsafePattern::MemUninitialized => {
+                &["mem::uninitialized", "std::mem::uninitialized", "core::mem::uninitialized"]
+            }
+            UnsafePattern::MaybeUninitAssumeInit => &["assume_init"],
+            UnsafePattern::MaybeUninitAssumeInitRef => &["assume_init_ref"],
+            UnsafePattern::MaybeUninitAssumeInitMut => &["assume_init_mut"],
+            UnsafePattern::SwapUnchecked => &["swap_unchecked"],
+            UnsafePattern::SplitAtUnchecked => &["split_at_unchecked"],
+            UnsafePattern::SplitAtMutUnchecked => &["split_at_mut_unchecked"],
+            UnsafePattern::SafeWrapperCall => &[],
+            UnsafePattern::UncheckedAdd => &["unchecked_add"],
+            UnsafePattern::UncheckedSub => &["unchecked_sub"],
+            UnsafePattern::UncheckedMul => &["unchecked_mul"],
+            UnsafePattern::FloatToIntUnchecked => &["to_int_unchecked"],
+            UnsafePattern::VecFromRawParts => &["Vec::from_raw_parts"],
+            UnsafePattern::RcFromRaw => &["Rc::from_raw"],
+            UnsafePattern::ArcFromRaw => &["Arc::from_raw"],
+            UnsafePattern::StaticMutAccess => &[],
+            UnsafePattern::PtrReadDefault => &["ptr::read"],
+            UnsafePattern::DropInPlace => &["ptr::drop_in_place"],
+            UnsafePattern::RawPtrIndexDeref => &[],
+            UnsafePattern::LibcMemcpy => &["libc::memcpy"],
+            UnsafePattern::LibcMemmove => &["libc::memmove"],
+            UnsafePattern::LibcMemset => &["libc::memset"],
+            UnsafePattern::PtrSwap => &["ptr::swap"],
+            UnsafePattern::CStrFromBytesWithNul => &["CStr::from_bytes_with_nul_unchecked"],
+            UnsafePattern::PtrReplace => &["ptr::replace"],
+            UnsafePattern::TransmuteSameLayout => &["transmute"],
+            UnsafePattern::TransmuteFloatBits => &["transmute"],
+            UnsafePattern::TransmuteIntToEnum => &["transmute"],
+            UnsafePattern::StringAsMutVec => &["as_mut_vec"],
+            UnsafePattern::PinNewUnchecked => &["Pin::new_unchecked"],
+            UnsafePattern::StaticMutAtomic => &[],
+            UnsafePattern::BoxLeakRoundTrip => &["Box::from_raw"],
+            UnsafePattern::PtrOffsetFrom => &["offset_from"],
+            UnsafePattern::StrBytesGetUnchecked => &["get_unchecked"],
+            UnsafePattern::WriteUnaligned => {
+                &["ptr::write_unaligned", "std::ptr::write_unaligned", "core::ptr::write_unaligned"]
+            }
+            UnsafePattern::AsBytesMut => &["as_bytes_mut"],
+            UnsafePattern::AllocToVec => &["alloc", "alloc::alloc", "std::alloc::alloc"],
+            UnsafePattern::TransmuteEnumDiscriminant => &["transmute"],
+            UnsafePattern::GetUncheckMutPair => &["get_unchecked_mut"],
+            UnsafePattern::AsChunksUnchecked => &["as_chunks_unchecked"],
+            UnsafePattern::PtrAsRef => &["as_ref"],
+            UnsafePattern::PtrAsMut => &["as_mut"],
+            UnsafePattern::ManuallyDropDrop => &[
+                "ManuallyDrop::drop",
+                "mem::ManuallyDrop::drop",
+                "std::mem::ManuallyDrop::drop",
+                "core::mem::ManuallyDrop::drop",
+            ],
+            UnsafePattern::SliceFromRawParts => &[
+                "slice::from_raw_parts",
+                "std::slice::from_raw_parts",
+                "core::slice::from_raw_parts",
+                "from_raw_parts",
+                "slice::from_raw_parts_mut",
+                "std::slice::from_raw_parts_mut",
+                "core::slice::from_raw_parts_mut",
+                "from_raw_parts_mut",
+            ],
+        }
+    }
+
+    /// Whether `text`, the callee or method name under inspection, is one of the needles.
+    pub fn matches(&self, text: &str) -> bool {
+        return self.detection_needles().contains(&text.trim());
+    }
+
+    /// Whether any needle shows up somewhere inside `text`.
+    pub fn found_in(&self, text: &str) -> bool {
+        return self.detection_needles().iter().any(|needle| text.contains(needle));
+    }
+
+    /// Whether the safe replacement can behave differently from the unsafe code it replaces,
+    /// by panicking on a check the original skipped or by producing a different value.
+    pub fn is_behavior_changing(&self) -> bool {
+        return matches!(
+            self,
+            UnsafePattern::CopyWithin
+                | UnsafePattern::GetUncheck
+                | UnsafePattern::GetUncheckMut
+                | UnsafePattern::GetUncheckMutPair
+                | UnsafePattern::CopyNonOverlap
+                | UnsafePattern::CStringFromVec
+                | UnsafePattern::StringBytesToUTFString
+                | UnsafePattern::BytesToUTFString
+                | UnsafePattern::CoreBytesToUTFString
+                | UnsafePattern::STDBytesToUTFString
+                | UnsafePattern::BytesToUTFStringMut
+                | UnsafePattern::CoreBytesToUTFStringMut
+                | UnsafePattern::STDBytesToUTFStringMut
+                | UnsafePattern::TransmuteTo
+                | UnsafePattern::ReadUnaligned
+                | UnsafePattern::WriteUnaligned
+                | UnsafePattern::FromU32Unchecked
+                | UnsafePattern::STDFromU32Unchecked
+                | UnsafePattern::StrGetUnchecked
+                | UnsafePattern::StrBytesGetUnchecked
+                | UnsafePattern::MemUninitialized
+                | UnsafePattern::MaybeUninitAssumeInit
+                | UnsafePattern::SwapUnchecked
+                | UnsafePattern::SplitAtUnchecked
+                | UnsafePattern::SplitAtMutUnchecked
+                | UnsafePattern::SafeWrapperCall
+                | UnsafePattern::UncheckedAdd
+                | UnsafePattern::UncheckedSub
+                | UnsafePattern::UncheckedMul
+                | UnsafePattern::FloatToIntUnchecked
+                | UnsafePattern::RawPtrIndexDeref
+                | UnsafePattern::LibcMemcpy
+                | UnsafePattern::LibcMemmove
+                | UnsafePattern::LibcMemset
+                | UnsafePattern::CStrFromBytesWithNul
+                | UnsafePattern::TransmuteIntToEnum
+                | UnsafePattern::AsChunksUnchecked
+        );
+    }
+
+    /// Only the always-equivalent conversions are reported as diagnostics, the others stay
+    /// manual, as an assist or in the hover.
+    pub fn class(&self) -> ConversionClass {
+        return match self.is_behavior_changing() {
+            true => ConversionClass::BehaviorChanging,
+            false => ConversionClass::AlwaysEquivalent,
+        };
+    }
+
+    /// Why the safe replacement is equivalent, shown below the diff in the hover.
+    ///
+    /// `None` for the patterns that only get a warning and for the helper needles.
+    pub fn rationale(&self) -> Option<&'static str> {
+        let rationale = match self {
+            UnsafePattern::UnitializedVec => "The safe version only ever exposes elements that were written, where `set_len` trusted the memory below the new length to be initialized.",
+            UnsafePattern::CopyWithin => "Both copy `count` elements within the same allocation handling overlap; `copy_within` bounds-checks the indices.",
+            UnsafePattern::CopyNonOverlap => "Both copy `count` elements between two buffers that don't overlap; `copy_from_slice` checks that the lengths match and the ranges are in bounds.",
+            UnsafePattern::CStringFromVec => "`CString::new` builds the same string and checks for the interior nul bytes `from_vec_unchecked` assumed away.",
+            UnsafePattern::CStringLength => "`as_bytes().len()` is the length `strlen` walks the memory for, and the `CString` already knows it.",
+            UnsafePattern::GetUncheck => "Indexing returns the same element and checks the bound `get_unchecked` assumed.",
+            UnsafePattern::GetUncheckMut => "`get_mut` returns the same element mutably and checks the bound `get_unchecked_mut` assumed.",
+            UnsafePattern::GetUncheckMutPair => "`split_at_mut` hands out the same two elements from halves the borrow checker knows are disjoint.",
+            UnsafePattern::StrGetUnchecked => "`str::get` returns the same substring and checks the bounds and `char` boundaries `get_unchecked` assumed.",
+            UnsafePattern::StringBytesToUTFString
+            | UnsafePattern::BytesToUTFString
+            | UnsafePattern::CoreBytesToUTFString
+            | UnsafePattern::STDBytesToUTFString => "`from_utf8` reinterprets the same bytes and validates the UTF-8 `from_utf8_unchecked` assumed.",
+            UnsafePattern::BytesToUTFStringMut
+            | UnsafePattern::CoreBytesToUTFStringMut
+            | UnsafePattern::STDBytesToUTFStringMut => "`from_utf8_mut` reinterprets the same bytes through the same `&mut` borrow and validates the UTF-8 `from_utf8_unchecked_mut` assumed.",
+            UnsafePattern::TransmuteTo => "The typed conversion reads the same bytes or bits, and its signature states the layout `transmute` took on trust.",
+            UnsafePattern::ReadUnaligned => "`from_ne_bytes` reads the same bytes in the same native order from a checked slice, and a byte array has no alignment to respect.",
+            UnsafePattern::FromU32Unchecked | UnsafePattern::STDFromU32Unchecked => "`char::from_u32` returns the same `char` and checks that the value is a valid Unicode scalar.",
+            UnsafePattern::MemZeroed => "The zero value is spelled out for the type, so no invalid bit pattern can be produced.",
+            UnsafePattern::MemUninitialized => "An initialized value takes the place of memory that was never valid to read.",
+            UnsafePattern::MaybeUninitAssumeInit => "Every element is initialized up front, so nothing is assumed about memory that wasn't written.",
+            UnsafePattern::MaybeUninitAssumeInitRef | UnsafePattern::MaybeUninitAssumeInitMut => "The value is written before the reference is taken, so the reference always points at initialized memory.",
+            UnsafePattern::SwapUnchecked => "`swap` exchanges the same two elements and checks both indices.",
+            UnsafePattern::SplitAtUnchecked | UnsafePattern::SplitAtMutUnchecked => "`split_at` returns the same two halves and checks that the index is in bounds.",
+            UnsafePattern::AsChunksUnchecked => "`as_chunks` splits into the same `N`-element chunks and keeps any leftover apart instead of assuming there is none.",
+            UnsafePattern::SafeWrapperCall => "The wrapper takes the same arguments and checks the preconditions of the `unsafe fn` before calling it.",
+            UnsafePattern::UncheckedAdd | UnsafePattern::UncheckedSub | UnsafePattern::UncheckedMul => "The checked operation computes the same result and reports the overflow the unchecked one assumed could not happen.",
+            UnsafePattern::FloatToIntUnchecked => "An `as` cast gives the same integer for every value in range and saturates where `to_int_unchecked` was undefined.",
+            UnsafePattern::VecFromRawParts => "The original `Vec` already owns the buffer with that pointer, length and capacity, so nothing has to be rebuilt.",
+            UnsafePattern::RcFromRaw | UnsafePattern::ArcFromRaw => "The original pointer still owns the same reference count, so it can be kept instead of being rebuilt from a raw pointer.",
+            UnsafePattern::PtrReadDefault => "`mem::take` moves the value out and leaves a default behind, so the place can't be dropped or read twice.",
+            UnsafePattern::DropInPlace => "`drop` runs the same destructor on an owned value, which can then no longer be used.",
+            UnsafePattern::RawPtrIndexDeref => "Indexing the slice reads the same element as the pointer arithmetic and checks the bound.",
+            UnsafePattern::LibcMemcpy | UnsafePattern::LibcMemmove => "The slice copy moves the same bytes and checks the lengths and bounds the C function trusted the caller with.",
+            UnsafePattern::LibcMemset => "`fill` writes the same byte over the same range and checks the bounds.",
+            UnsafePattern::PtrSwap => "`mem::swap` exchanges the same two values through references the borrow checker knows are distinct.",
+            UnsafePattern::CStrFromBytesWithNul => "`CStr::from_bytes_with_nul` borrows the same bytes and checks for the single trailing nul.",
+            UnsafePattern::PtrReplace => "`mem::replace` moves the old value out and the new one in through a reference, so nothing is read or dropped twice.",
+            UnsafePattern::TransmuteSameLayout | UnsafePattern::TransmuteEnumDiscriminant => "The cast gives the same value the transmute read, without reinterpreting memory.",
+            UnsafePattern::TransmuteFloatBits => "`to_bits` and `from_bits` reinterpret the same bits and are defined for every value.",
+            UnsafePattern::TransmuteIntToEnum => "`TryFrom` maps the same integers to their variants and rejects the ones without a variant instead of creating an invalid enum.",
+            UnsafePattern::StringAsMutVec => "The `String` methods change the same bytes and keep the contents valid UTF-8.",
+            UnsafePattern::PinNewUnchecked => "`Box::pin` pins the same heap value and guarantees it is never moved out of.",
+            UnsafePattern::StaticMutAtomic => "An atomic has the layout of the integer and makes every access well-defined, even from several threads.",
+            UnsafePattern::BoxLeakRoundTrip => "The `Box` owns the same allocation the whole time, so it is freed exactly once.",
+            UnsafePattern::PtrOffsetFrom => "Both pointers come from one buffer, so the difference of their indices is the distance `offset_from` computes.",
+            UnsafePattern::PtrAsRef | UnsafePattern::PtrAsMut => "The pointer was made from that reference, so it is non-null and points at a live value.",
+            UnsafePattern::StrBytesGetUnchecked => "Indexing `as_bytes()` reads the same byte and checks the bound.",
+            UnsafePattern::WriteUnaligned => "`to_ne_bytes` writes the same bytes in the same order, and a byte slice has no alignment to respect.",
+            UnsafePattern::AsBytesMut => "The `str` method changes the same bytes and keeps the string valid UTF-8.",
+            UnsafePattern::AllocToVec => "A `Vec` allocates and frees the same buffer with a matching layout, and keeps track of what was written.",
+            UnsafePattern::SliceFromRawParts => "A slice of length one over a single value is what `from_ref`/`from_mut` build, from a borrow instead of a raw pointer.",
+            UnsafePattern::SetVecCapacity
+            | UnsafePattern::ReserveVec
+            | UnsafePattern::WriteVec
+            | UnsafePattern::AsPtr
+            | UnsafePattern::TransmuteLifetime
+            | UnsafePattern::StaticMutAccess
+            | UnsafePattern::ManuallyDropDrop => return None,
+        };
+
+        return Some(rationale);
+    }
+
+    /// The std item the safe replacement calls and a link to its documentation.
+    ///
+    /// `None` when the replacement is plain syntax, a cast or several calls.
+    pub fn safe_docs(&self) -> Option<(&'static str, &'static str)> {
+        let docs = match self {
+            UnsafePattern::CopyWithin => (
+                "slice::copy_within",
+                "https://doc.rust-lang.org/std/primitive.slice.html#method.copy_within",
+            ),
+            UnsafePattern::CopyNonOverlap => (
+                "slice::copy_from_slice",
+                "https://doc.rust-lang.org/std/primitive.slice.html#method.copy_from_slice",
+            ),
+            UnsafePattern::CStringFromVec => {
+                ("CString::new", "https://doc.rust-lang.org/std/ffi/struct.CString.html#method.new")
+            }
+            UnsafePattern::GetUncheckMut => (
+                "slice::get_mut",
+                "https://doc.rust-lang.org/std/primitive.slice.html#method.get_mut",
+            ),
+            UnsafePattern::GetUncheckMutPair | UnsafePattern::SplitAtMutUnchecked => (
+                "slice::split_at_mut",
+                "https://doc.rust-lang.org/std/primitive.slice.html#method.split_at_mut",
+            ),
+            UnsafePattern::SplitAtUnchecked => (
+                "slice::split_at",
+                "https://doc.rust-lang.org/std/primitive.slice.html#method.split_at",
+            ),
+            UnsafePattern::StrGetUnchecked => {
+                ("str::get", "https://doc.rust-lang.org/std/primitive.str.html#method.get")
+            }
+            UnsafePattern::StringBytesToUTFString => (
+                "String::from_utf8",
+                "https://doc.rust-lang.org/std/string/struct.String.html#method.from_utf8",
+            ),
+            UnsafePattern::BytesToUTFString
+            | UnsafePattern::CoreBytesToUTFString
+            | UnsafePattern::STDBytesToUTFString => {
+                ("str::from_utf8", "https://doc.rust-lang.org/std/str/fn.from_utf8.html")
+            }
+            UnsafePattern::BytesToUTFStringMut
+            | UnsafePattern::CoreBytesToUTFStringMut
+            | UnsafePattern::STDBytesToUTFStringMut => {
+                ("str::from_utf8_mut", "https://doc.rust-lang.org/std/str/fn.from_utf8_mut.html")
+            }
+            UnsafePattern::FromU32Unchecked | UnsafePattern::STDFromU32Unchecked => (
+                "char::from_u32",
+                "https://doc.rust-lang.org/std/primitive.char.html#method.from_u32",
+            ),
+            UnsafePattern::SwapUnchecked => {
+                ("slice::swap", "https://doc.rust-lang.org/std/primitive.slice.html#method.swap")
+            }
+            UnsafePattern::AsChunksUnchecked => (
+                "slice::as_chunks",
+                "https://doc.rust-lang.org/std/primitive.slice.html#method.as_chunks",
+            ),
+            UnsafePattern::LibcMemset => {
+                ("slice::fill", "https://doc.rust-lang.org/std/primitive.slice.html#method.fill")
+            }
+            UnsafePattern::PtrReadDefault => {
+                ("mem::take", "https://doc.rust-lang.org/std/mem/fn.take.html")
+            }
+            UnsafePattern::PtrSwap => {
+                ("mem::swap", "https://doc.rust-lang.org/std/mem/fn.swap.html")
+            }
+            UnsafePattern::PtrReplace => {
+                ("mem::replace", "https://doc.rust-lang.org/std/mem/fn.replace.html")
+            }
+            UnsafePattern::CStrFromBytesWithNul => (
+                "CStr::from_bytes_with_nul",
+                "https://doc.rust-lang.org/std/ffi/struct.CStr.html#method.from_bytes_with_nul",
+            ),
+            UnsafePattern::PinNewUnchecked => {
+                ("Box::pin", "https://doc.rust-lang.org/std/boxed/struct.Box.html#method.pin")
+            }
+            UnsafePattern::SliceFromRawParts => {
+                ("slice::from_ref", "https://doc.rust-lang.org/std/slice/fn.from_ref.html")
+            }
+            _ => return None,
+        };
+
+        return Some(docs);
+    }
+}
+
+impl FromStr for UnsafePattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match UnsafePattern::ALL.iter().find(|pattern| pattern.as_str() == s) {
+            Some(pattern) => Ok(*pattern),
+            None => Err(format!("Unknown UnsafePattern: '{}'", s)),
+        }
+    }
 }
 
 impl std::fmt::Display for UnsafePattern {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            UnsafePattern::SetVecCapacity => write!(f, "with_capacity"),
-            UnsafePattern::ReserveVec => write!(f, "reserve"),
-            UnsafePattern::WriteVec => write!(f, "write"),
-            UnsafePattern::UnitializedVec => write!(f, "set_len"),
+            UnsafePattern::SetVecCapacity => write!(f, "Vec::with_capacity"),
+            UnsafePattern::ReserveVec => write!(f, "Vec::reserve"),
+            UnsafePattern::WriteVec => write!(f, "ptr::write"),
+            UnsafePattern::UnitializedVec => write!(f, "Vec::set_len"),
             UnsafePattern::CopyWithin => write!(f, "ptr::copy"),
-            UnsafePattern::GetUncheck => write!(f, "get_unchecked"),
-            UnsafePattern::GetUncheckMut => write!(f, "get_unchecked_mut"),
+            UnsafePattern::GetUncheck => write!(f, "slice::get_unchecked"),
+            UnsafePattern::GetUncheckMut => write!(f, "slice::get_unchecked_mut"),
             UnsafePattern::CopyNonOverlap => write!(f, "ptr::copy_nonoverlapping"),
             UnsafePattern::CStringFromVec => write!(f, "CString::from_vec_unchecked"),
             UnsafePattern::CStringLength => write!(f, "libc::strlen"),
@@ -91,12 +634,65 @@ impl std::fmt::Display for UnsafePattern {
             UnsafePattern::CoreBytesToUTFString => write!(f, "core::str::from_utf8_unchecked"),
             UnsafePattern::BytesToUTFStringMut => write!(f, "str::from_utf8_unchecked_mut"),
             UnsafePattern::STDBytesToUTFStringMut => write!(f, "std::str::from_utf8_unchecked_mut"),
-            UnsafePattern::CoreBytesToUTFStringMut => write!(f, "core::str::from_utf8_unchecked_mut"),
+            UnsafePattern::CoreBytesToUTFStringMut => {
+                write!(f, "core::str::from_utf8_unchecked_mut")
+            }
             UnsafePattern::TransmuteTo => write!(f, "mem::transmute"),
             UnsafePattern::ReadUnaligned => write!(f, "ptr::read_unaligned"),
             UnsafePattern::AsPtr => write!(f, "as_ptr"),
             UnsafePattern::FromU32Unchecked => write!(f, "char::from_u32_unchecked"),
             UnsafePattern::STDFromU32Unchecked => write!(f, "std::char::from_u32_unchecked"),
+            UnsafePattern::StrGetUnchecked => write!(f, "str::get_unchecked"),
+            UnsafePattern::MemZeroed => write!(f, "mem::zeroed"),
+            UnsafePattern::TransmuteLifetime => write!(f, "mem::transmute (lifetime extension)"),
+            UnsafePattern::MemUninitialized => write!(f, "mem::uninitialized"),
+            UnsafePattern::MaybeUninitAssumeInit => write!(f, "MaybeUninit::assume_init"),
+            UnsafePattern::MaybeUninitAssumeInitRef => write!(f, "MaybeUninit::assume_init_ref"),
+            UnsafePattern::MaybeUninitAssumeInitMut => write!(f, "MaybeUninit::assume_init_mut"),
+            UnsafePattern::SwapUnchecked => write!(f, "slice::swap_unchecked"),
+            UnsafePattern::SplitAtUnchecked => write!(f, "slice::split_at_unchecked"),
+            UnsafePattern::SplitAtMutUnchecked => write!(f, "slice::split_at_mut_unchecked"),
+            UnsafePattern::SafeWrapperCall => write!(f, "*_safe wrapper call"),
+            UnsafePattern::UncheckedAdd => write!(f, "unchecked_add"),
+            UnsafePattern::UncheckedSub => write!(f, "unchecked_sub"),
+            UnsafePattern::UncheckedMul => write!(f, "unchecked_mul"),
+            UnsafePattern::FloatToIntUnchecked => write!(f, "f64::to_int_unchecked"),
+            UnsafePattern::VecFromRawParts => write!(f, "Vec::from_raw_parts"),
+            UnsafePattern::RcFromRaw => write!(f, "Rc::from_raw"),
+            UnsafePattern::ArcFromRaw => write!(f, "Arc::from_raw"),
+            UnsafePattern::StaticMutAccess => write!(f, "static mut access"),
+            UnsafePattern::PtrReadDefault => write!(f, "ptr::read"),
+            UnsafePattern::DropInPlace => write!(f, "ptr::drop_in_place"),
+            UnsafePattern::RawPtrIndexDeref => write!(f, "*slice.as_ptr().add(i)"),
+            UnsafePattern::LibcMemcpy => write!(f, "libc::memcpy"),
+            UnsafePattern::LibcMemmove => write!(f, "libc::memmove"),
+            UnsafePattern::LibcMemset => write!(f, "libc::memset"),
+            UnsafePattern::PtrSwap => write!(f, "ptr::swap"),
+            UnsafePattern::CStrFromBytesWithNul => write!(f, "CStr::from_bytes_with_nul_unchecked"),
+            UnsafePattern::PtrReplace => write!(f, "ptr::replace"),
+            UnsafePattern::TransmuteSameLayout => write!(f, "mem::transmute (same layout)"),
+            UnsafePattern::TransmuteFloatBits => write!(f, "mem::transmute (float bits)"),
+            UnsafePattern::TransmuteIntToEnum => write!(f, "mem::transmute (integer to enum)"),
+            UnsafePattern::StringAsMutVec => write!(f, "String::as_mut_vec"),
+            UnsafePattern::PinNewUnchecked => write!(f, "Pin::new_unchecked"),
+            UnsafePattern::StaticMutAtomic => write!(f, "static mut integer"),
+            UnsafePattern::BoxLeakRoundTrip => write!(f, "Box::leak round trip"),
+            UnsafePattern::PtrOffsetFrom => write!(f, "pointer::offset_from"),
+            UnsafePattern::StrBytesGetUnchecked => write!(f, "str::as_bytes().get_unchecked"),
+            UnsafePattern::WriteUnaligned => write!(f, "ptr::write_unaligned"),
+            UnsafePattern::AsBytesMut => write!(f, "str::as_bytes_mut"),
+            UnsafePattern::AllocToVec => write!(f, "alloc::alloc"),
+            UnsafePattern::TransmuteEnumDiscriminant => {
+                write!(f, "mem::transmute (enum to discriminant)")
+            }
+            UnsafePattern::GetUncheckMutPair => {
+                write!(f, "slice::get_unchecked_mut (two elements)")
+            }
+            UnsafePattern::AsChunksUnchecked => write!(f, "slice::as_chunks_unchecked"),
+            UnsafePattern::PtrAsRef => write!(f, "pointer::as_ref"),
+            UnsafePattern::PtrAsMut => write!(f, "pointer::as_mut"),
+            UnsafePattern::ManuallyDropDrop => write!(f, "ManuallyDrop::drop"),
+            UnsafePattern::SliceFromRawParts => write!(f, "slice::from_raw_parts (one element)"),
         }
     }
 }
@@ -104,8 +700,6 @@ impl std::fmt::Display for UnsafePattern {
 enum TargetTypes {
     String,
     Bytes,
-    U8,
-    U16,
     U32,
     U64,
     F32,
@@ -118,8 +712,6 @@ impl std::fmt::Display for TargetTypes {
         match self {
             TargetTypes::String => write!(f, "&str"),
             TargetTypes::Bytes => write!(f, "&[u8]"),
-            TargetTypes::U8 => write!(f, "u8"),
-            TargetTypes::U16 => write!(f, "u16"),
             TargetTypes::U32 => write!(f, "u32"),
             TargetTypes::U64 => write!(f, "u64"),
             TargetTypes::F32 => write!(f, "f32"),
@@ -146,16 +738,29 @@ pub fn generate_safevec_format(mcall: &MethodCallExpr) -> Option<String> {
 
 }
 
-pub fn generate_resizevec_format(mcall: &MethodCallExpr) -> Option<String> {
-
+pub fn generate_resizevec_format(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &MethodCallExpr,
+) -> Option<String> {
     // Obtain the variable Expr that presents the buffer/vector
     let receiver = mcall.receiver()?;
 
     let closure_body = mcall.arg_list()?.args().exactly_one().ok()?;
 
+    // The fill has to have the element type, a zero literal for scalars or the "Default" value
+    let elem_ty = sema
+        .type_of_expr(&receiver)
+        .and_then(|info| info.original.strip_references().type_arguments().next())
+        .filter(|ty| !ty.is_unknown());
+
+    let fill = match elem_ty {
+        Some(ty) => generate_zeroed_scalar(sema, &ty, false, true, mcall.syntax())?,
+        None => "0".to_string(),
+    };
+
     let mut buf = String::new();
 
-    format_to!(buf, "{}.resize({}, 0);", receiver, closure_body);
+    format_to!(buf, "{}.resize({}, {});", receiver, closure_body, fill);
 
     buf.push('\n');
 
@@ -172,8 +777,12 @@ fn check_single_expr(target_expr: &ExprStmt) -> bool {
     return false;
 }
 
-fn delet_replace_source_code(acc: &mut Assists, let_target: TextRange, target_range: TextRange, buf: &String) {
-
+fn delet_replace_source_code(
+    acc: &mut Assists,
+    let_target: TextRange,
+    target_range: TextRange,
+    buf: &String,
+) {
     acc.add(
         AssistId("convert_unsafe_to_safe", AssistKind::RefactorRewrite),
         "Convert Unsafe to Safe",
@@ -185,13 +794,358 @@ fn delet_replace_source_code(acc: &mut Assists, let_target: TextRange, target_ra
     );
 }
 
-fn convert_to_auto_vec_initialization(acc: &mut Assists, target_expr: &SyntaxNode, unsafe_range: TextRange, unsafe_expr: &BlockExpr) -> Option<()> {
+struct FillLoopInfo {
+    pub for_expr: ast::ForExpr,
+    pub fill: ast::Expr,
+    pub constant: bool,
+}
 
-    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+fn collect_fill_loop(mcall: &MethodCallExpr, unsafe_expr: &BlockExpr) -> Option<FillLoopInfo> {
+    let receiver = mcall.receiver()?.to_string();
+
+    let len = mcall.arg_list()?.args().exactly_one().ok()?.to_string();
+
+    let mut forward_list = unsafe_expr.syntax().siblings(Direction::Next);
+
+    if unsafe_expr.syntax().parent()?.kind() != STMT_LIST {
+        forward_list = unsafe_expr.syntax().parent()?.siblings(Direction::Next);
+    }
+
+    for iter in forward_list.skip(1) {
+        // The loop is either a statement or the tail of the enclosing block
+        let for_expr = match ast::ForExpr::cast(iter.clone())
+            .or_else(|| iter.children().find_map(ast::ForExpr::cast))
+        {
+            Some(for_expr)
+                if ast::ExprStmt::can_cast(iter.kind()) || iter == *for_expr.syntax() =>
+            {
+                for_expr
+            }
+            // Any other use of the buffer before the loop would see it uninitialized
+            _ if check_ident_mentioned(&iter, &receiver) => return None,
+            _ => continue,
+        };
+
+        let index = match for_expr.pat()? {
+            ast::Pat::IdentPat(ident_pat) => ident_pat.name()?.to_string(),
+            _ => return None,
+        };
+
+        // The loop has to cover the whole "0..len" range that "set_len" exposed
+        let range = match for_expr.iterable()? {
+            ast::Expr::RangeExpr(range) => range,
+            _ => return None,
+        };
+
+        if range.start()?.to_string() != "0" || range.op_kind()? != ast::RangeOp::Exclusive {
+            return None;
+        }
+
+        let end = range.end()?.to_string();
+
+        if end != len && end != format!("{}.len()", receiver) {
+            return None;
+        }
+
+        // Only an unconditional "v[i] = ..." body counts, anything nested may skip elements
+        let body = for_expr.loop_body()?.stmt_list()?;
+
+        if body.tail_expr().is_some() {
+            return None;
+        }
+
+        let assign = match body.statements().exactly_one().ok()? {
+            ast::Stmt::ExprStmt(expr_stmt) => match expr_stmt.expr()? {
+                ast::Expr::BinExpr(bin_expr) => bin_expr,
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        if !matches!(assign.op_kind()?, ast::BinaryOp::Assignment { op: None }) {
+            return None;
+        }
+
+        let lhs = match assign.lhs()? {
+            ast::Expr::IndexExpr(index_expr) => index_expr,
+            _ => return None,
+        };
+
+        if lhs.base()?.to_string() != receiver || lhs.index()?.to_string() != index {
+            return None;
+        }
+
+        let fill = assign.rhs()?;
+
+        let constant = matches!(fill, ast::Expr::Literal(_));
+
+        return Some(FillLoopInfo { for_expr, fill, constant });
+    }
+
+    return None;
+}
+
+fn generate_constant_fill(mcall: &MethodCallExpr, fill_loop: &FillLoopInfo) -> Option<String> {
+    let receiver = mcall.receiver()?;
+
+    let len = mcall.arg_list()?.args().exactly_one().ok()?;
+
+    let mut buf = String::new();
+
+    format_to!(buf, "{}.resize({}, {});", receiver, len, fill_loop.fill);
+
+    return Some(buf);
+}
+
+fn convert_to_constant_fill(
+    acc: &mut Assists,
+    mcall: &MethodCallExpr,
+    fill_loop: &FillLoopInfo,
+    target_range: TextRange,
+) -> Option<()> {
+    let buf = generate_constant_fill(mcall, fill_loop)?;
+
+    // The fill loop becomes redundant, remove it together with its indentation
+    let loop_stmt = match fill_loop.for_expr.syntax().parent()? {
+        parent if parent.kind() == EXPR_STMT => parent,
+        _ => fill_loop.for_expr.syntax().clone(),
+    };
+
+    let loop_range = collect_range_with_indent(&loop_stmt);
+
+    delet_replace_source_code(acc, target_range, loop_range, &buf);
+
+    return None;
+}
+
+struct RawWriteGrowth {
+    pub set_len: MethodCallExpr,
+    pub receiver: String,
+    pub len: ast::Expr,
+    /// The loop variable when a single loop writes every element, `None` for one write per element.
+    pub index: Option<String>,
+    pub values: Vec<ast::Expr>,
+}
+
+fn collect_raw_write_target(ptr: &ast::Expr) -> Option<(String, String)> {
+    let mut ptr_call = match ptr {
+        ast::Expr::MethodCallExpr(ptr_call) => ptr_call.clone(),
+        _ => return None,
+    };
+
+    // "buf.as_mut_ptr()" is the first element, ".add(i)" any later one
+    let mut index = String::from("0");
+
+    if ptr_call.name_ref()?.to_string() == "add" {
+        index = ptr_call.arg_list()?.args().exactly_one().ok()?.to_string();
+
+        ptr_call = match ptr_call.receiver()? {
+            ast::Expr::MethodCallExpr(ptr_call) => ptr_call,
+            _ => return None,
+        };
+    }
+
+    if ptr_call.name_ref()?.to_string() != "as_mut_ptr" {
+        return None;
+    }
+
+    return Some((ptr_call.receiver()?.to_string(), index));
+}
+
+fn collect_raw_write(expr: &ast::Expr) -> Option<(String, String, ast::Expr)> {
+    match expr {
+        // "*buf.as_mut_ptr().add(i) = value"
+        ast::Expr::BinExpr(assign)
+            if assign.op_kind()? == (ast::BinaryOp::Assignment { op: None }) =>
+        {
+            let prefix = match assign.lhs()? {
+                ast::Expr::PrefixExpr(prefix) if prefix.op_kind()? == ast::UnaryOp::Deref => prefix,
+                _ => return None,
+            };
+
+            let (base, index) = collect_raw_write_target(&prefix.expr()?)?;
+
+            return Some((base, index, assign.rhs()?));
+        }
+        // "buf.as_mut_ptr().add(i).write(value)"
+        ast::Expr::MethodCallExpr(write_call)
+            if UnsafePattern::WriteVec.matches(&write_call.name_ref()?.text()) =>
+        {
+            let (base, index) = collect_raw_write_target(&write_call.receiver()?)?;
+
+            return Some((base, index, write_call.arg_list()?.args().exactly_one().ok()?));
+        }
+        _ => return None,
+    }
+}
+
+fn check_closed_form_value(value: &ast::Expr, receiver: &str) -> bool {
+    // Arithmetic on the index and other locals only, a call may panic halfway or need "unsafe" itself
+    let opaque = value.syntax().descendants().any(|node| match ast::Expr::cast(node) {
+        Some(ast::Expr::PrefixExpr(prefix)) => prefix.op_kind() == Some(ast::UnaryOp::Deref),
+        Some(
+            ast::Expr::CallExpr(_)
+            | ast::Expr::MethodCallExpr(_)
+            | ast::Expr::MacroExpr(_)
+            | ast::Expr::BlockExpr(_)
+            | ast::Expr::ClosureExpr(_),
+        ) => true,
+        _ => false,
+    });
+
+    return !opaque && !check_ident_mentioned(value.syntax(), receiver);
+}
+
+fn collect_raw_write_growth(unsafe_expr: &BlockExpr) -> Option<RawWriteGrowth> {
+    let stmt_list = unsafe_expr.stmt_list()?;
+
+    let mut exprs = Vec::new();
+
+    for stmt in stmt_list.statements() {
+        match stmt {
+            ast::Stmt::ExprStmt(expr_stmt) => exprs.push(expr_stmt.expr()?),
+            _ => return None,
+        }
+    }
+
+    exprs.extend(stmt_list.tail_expr());
+
+    // The block ends in "buf.set_len(n)", everything in front of it writes the new elements
+    let set_len = match exprs.pop()? {
+        ast::Expr::MethodCallExpr(set_len)
+            if UnsafePattern::UnitializedVec.matches(&set_len.name_ref()?.text()) =>
+        {
+            set_len
+        }
+        _ => return None,
+    };
+
+    let receiver = set_len.receiver()?.to_string();
+
+    let len = set_len.arg_list()?.args().exactly_one().ok()?;
+
+    // "for i in 0..n { *buf.as_mut_ptr().add(i) = ...; }"
+    if let [ast::Expr::ForExpr(for_expr)] = exprs.as_slice() {
+        let index = match for_expr.pat()? {
+            ast::Pat::IdentPat(ident_pat) => ident_pat.name()?.to_string(),
+            _ => return None,
+        };
+
+        let range = match for_expr.iterable()? {
+            ast::Expr::RangeExpr(range) => range,
+            _ => return None,
+        };
+
+        if range.start()?.to_string() != "0"
+            || range.op_kind()? != ast::RangeOp::Exclusive
+            || range.end()?.to_string() != len.to_string()
+        {
+            return None;
+        }
+
+        let body = for_expr.loop_body()?.stmt_list()?;
+
+        let write = match (body.statements().collect::<Vec<_>>().as_slice(), body.tail_expr()) {
+            ([ast::Stmt::ExprStmt(expr_stmt)], None) => expr_stmt.expr()?,
+            ([], Some(tail_expr)) => tail_expr,
+            _ => return None,
+        };
+
+        let (base, write_index, value) = collect_raw_write(&write)?;
+
+        if base != receiver || write_index != index || !check_closed_form_value(&value, &receiver) {
+            return None;
+        }
+
+        return Some(RawWriteGrowth {
+            set_len,
+            receiver,
+            len,
+            index: Some(index),
+            values: vec![value],
+        });
+    }
+
+    // Otherwise one write per element, in order from the first one up to the new length
+    let mut values = Vec::new();
+
+    for (position, expr) in exprs.iter().enumerate() {
+        let (base, write_index, value) = collect_raw_write(expr)?;
+
+        if base != receiver
+            || write_index.trim_end_matches("usize").parse::<usize>().ok()? != position
+            || !check_closed_form_value(&value, &receiver)
+        {
+            return None;
+        }
+
+        values.push(value);
+    }
+
+    if values.is_empty() || collect_offset_literal(&Some(len.clone()))? != values.len() as u128 {
+        return None;
+    }
+
+    return Some(RawWriteGrowth { set_len, receiver, len, index: None, values });
+}
+
+fn generate_raw_write_growth(growth: &RawWriteGrowth, indent_level: IndentLevel) -> Option<String> {
+    let mut buf = String::new();
+
+    match &growth.index {
+        Some(index) => {
+            let value = growth.values.first()?;
+
+            let binding =
+                if check_ident_mentioned(value.syntax(), index) { index.as_str() } else { "_" };
+
+            format_to!(
+                buf,
+                "{}.extend((0..{}).map(|{}| {}));",
+                growth.receiver,
+                growth.len,
+                binding,
+                value
+            );
+        }
+        None => {
+            let mut pushes =
+                growth.values.iter().map(|value| format!("{}.push({});", growth.receiver, value));
+
+            buf.push_str(&pushes.join(&format!("\n{}", indent_level)));
+        }
+    }
+
+    return Some(buf);
+}
+
+fn convert_to_raw_write_growth(
+    acc: &mut Assists,
+    growth: &RawWriteGrowth,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let buf = generate_raw_write_growth(growth, unsafe_expr.indent_level())?;
 
-    let buf = if let Some(buffer) = generate_safevec_format(&mcall) {buffer} else { return None; };
+    replace_source_code_with_label(acc, "Push the written elements instead", unsafe_range, &buf);
 
-    let buf_resize = if let Some(buffer) = generate_resizevec_format(&mcall) {buffer} else { return None; };
+    return None;
+}
+
+fn convert_to_auto_vec_initialization(
+    acc: &mut Assists,
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+
+    let buf = if let Some(buffer) = generate_safevec_format(&mcall) {
+        buffer
+    } else {
+        return None;
+    };
 
     let mut target_range = unsafe_range;
 
@@ -205,6 +1159,35 @@ fn convert_to_auto_vec_initialization(acc: &mut Assists, target_expr: &SyntaxNod
         }
     }
 
+    if check_empty_unsafe_block(unsafe_expr, target_range)? {
+        target_range = unsafe_range;
+    }
+
+    // Elements written through the raw pointer are kept, pushing them grows the vector the same way
+    if let Some(growth) = collect_raw_write_growth(unsafe_expr)
+        .filter(|growth| growth.set_len.syntax() == mcall.syntax())
+    {
+        return convert_to_raw_write_growth(acc, &growth, unsafe_range, unsafe_expr);
+    }
+
+    // "set_len" followed by a loop that fills every element with the same value
+    if let Some(fill_loop) = collect_fill_loop(&mcall, unsafe_expr) {
+        if fill_loop.constant {
+            return convert_to_constant_fill(acc, &mcall, &fill_loop, target_range);
+        }
+    }
+
+    // Nothing is left of the block, it goes away together with its line
+    if target_range == unsafe_range {
+        let unsafe_stmt = match unsafe_expr.syntax().parent()? {
+            parent if parent.kind() == STMT_LIST => unsafe_expr.syntax().clone(),
+            parent => parent,
+        };
+        target_range = collect_range_with_indent(&unsafe_stmt);
+    } else if let Some(set_len_stmt) = mcall.syntax().parent().filter(|it| it.kind() == EXPR_STMT) {
+        target_range = collect_range_with_indent(&set_len_stmt);
+    }
+
     let mut backward_list = unsafe_expr.syntax().siblings(Direction::Prev);
 
     if unsafe_expr.syntax().parent()?.kind() != STMT_LIST {
@@ -213,26 +1196,35 @@ fn convert_to_auto_vec_initialization(acc: &mut Assists, target_expr: &SyntaxNod
 
     // for iter in unsafe_expr.syntax().parent()?.siblings(Direction::Prev) {
     for iter in backward_list {
-
-        if iter.to_string().contains(&UnsafePattern::SetVecCapacity.to_string()) && iter.to_string().contains(&mcall.receiver()?.to_string()) {
-
+        if UnsafePattern::SetVecCapacity.found_in(&iter.to_string())
+            && iter.to_string().contains(&mcall.receiver()?.to_string())
+        {
             let let_expr = ast::LetStmt::cast(iter)?;
 
             let let_target = let_expr.syntax().text_range();
             // Delete the "set_len" expression in unsafe code block and insert the auto initialized vec/buf
-            delet_replace_source_code(acc, let_target, target_range, &buf);
+            delet_replace_source_code(acc, let_target, target_range, &buf.trim_end().to_string());
 
             return None;
 
         }
 
-        if iter.to_string().contains(&UnsafePattern::ReserveVec.to_string()) && iter.to_string().contains(&mcall.receiver()?.to_string()) {
-
+        if UnsafePattern::ReserveVec.found_in(&iter.to_string())
+            && iter.to_string().contains(&mcall.receiver()?.to_string())
+        {
             let expr_stmt = ast::ExprStmt::cast(iter)?;
 
+            // An element type without a zero or "Default" value has nothing to resize with
+            let buf_resize = generate_resizevec_format(sema, &mcall)?;
+
             let expr_target = expr_stmt.syntax().text_range();
             // Delete the "set_len" expression in unsafe code block and insert the auto initialized vec/buf
-            delet_replace_source_code(acc, expr_target, target_range, &buf_resize);
+            delet_replace_source_code(
+                acc,
+                expr_target,
+                target_range,
+                &buf_resize.trim_end().to_string(),
+            );
 
             return None;
         }
@@ -240,11 +1232,83 @@ fn convert_to_auto_vec_initialization(acc: &mut Assists, target_expr: &SyntaxNod
     return None;
 }
 
-pub fn generate_copywithin_string(base_expr: String, start_pos: String, end_pos: String, count_expr: String) -> String {
+fn make_postfix_operand(expr: ast::Expr) -> ast::Expr {
+    // Receivers and index bases bind tighter than anything but postfix expressions
+    match expr {
+        ast::Expr::PathExpr(_)
+        | ast::Expr::FieldExpr(_)
+        | ast::Expr::MethodCallExpr(_)
+        | ast::Expr::CallExpr(_)
+        | ast::Expr::IndexExpr(_)
+        | ast::Expr::ParenExpr(_)
+        | ast::Expr::Literal(_)
+        | ast::Expr::MacroExpr(_) => return expr,
+        _ => return make::expr_paren(expr),
+    }
+}
+
+fn make_add_operand(expr: ast::Expr, rhs: bool) -> ast::Expr {
+    let needs_parens = match &expr {
+        ast::Expr::BinExpr(bin_expr) => match bin_expr.op_kind() {
+            Some(ast::BinaryOp::ArithOp(
+                ast::ArithOp::Mul | ast::ArithOp::Div | ast::ArithOp::Rem,
+            )) => false,
+            // "a - b" is fine in front of "+", but not behind it
+            Some(ast::BinaryOp::ArithOp(ast::ArithOp::Add | ast::ArithOp::Sub)) => rhs,
+            _ => true,
+        },
+        ast::Expr::RangeExpr(_) | ast::Expr::ClosureExpr(_) => true,
+        _ => false,
+    };
+
+    if needs_parens {
+        return make::expr_paren(expr);
+    }
+
+    return expr;
+}
+
+fn make_offset_range(offset: Option<ast::Expr>, count: ast::Expr) -> ast::Expr {
+    // No offset means the range starts at the beginning of the buffer
+    let offset = match offset {
+        Some(offset) => offset,
+        None => return make::expr_range(None, Some(count)),
+    };
+
+    let end = make::expr_bin_op(
+        make_add_operand(offset.clone(), false),
+        ast::BinaryOp::ArithOp(ast::ArithOp::Add),
+        make_add_operand(count, true),
+    );
+
+    return make::expr_range(Some(offset), Some(end));
+}
+
+fn generate_copywithin_node(
+    base_expr: ast::Expr,
+    start_pos: Option<ast::Expr>,
+    end_pos: Option<ast::Expr>,
+    count_expr: ast::Expr,
+) -> String {
+    let start_pos = start_pos.unwrap_or_else(|| make::expr_literal("0").into());
+
+    let end_pos = end_pos.unwrap_or_else(|| make::expr_literal("0").into());
+
+    // A zero start keeps the count as the end of the range
+    let range = match start_pos.to_string().as_str() {
+        "0" => make::expr_range(Some(start_pos), Some(count_expr)),
+        _ => make_offset_range(Some(start_pos), count_expr),
+    };
+
+    let copy_within = make::expr_method_call(
+        make_postfix_operand(base_expr),
+        make::name_ref("copy_within"),
+        make::arg_list([range, end_pos]),
+    );
 
     let mut buf = String::new();
 
-    format_to!(buf, "{}.copy_within({}..{}, {});", base_expr, start_pos, count_expr, end_pos);
+    format_to!(buf, "{};", copy_within);
 
     buf.push('\n');
 
@@ -253,40 +1317,115 @@ pub fn generate_copywithin_string(base_expr: String, start_pos: String, end_pos:
 }
 
 struct CpyWithinInfo {
-    base_expr: String,
-    start_pos: String,
-    end_pos: String,
-    count_expr: String,
+    base_expr: ast::Expr,
+    start_pos: Option<ast::Expr>,
+    end_pos: Option<ast::Expr>,
+    count_expr: ast::Expr,
+}
+
+fn collect_index_range(index_expr: &IndexExpr) -> Option<ast::RangeExpr> {
+    match index_expr.index()? {
+        ast::Expr::RangeExpr(range) => return Some(range),
+        _ => return None,
+    }
+}
+
+fn check_copy_base_ty(
+    sema: &Semantics<'_, RootDatabase>,
+    base_expr: &ast::Expr,
+    scope: &SyntaxNode,
+) -> bool {
+    // A base read back from "addr_of!" isn't part of the file, its name is resolved instead
+    let ty = match base_expr.syntax().ancestors().last() == scope.ancestors().last() {
+        true => sema.type_of_expr(base_expr).map(|info| info.original),
+        false => match base_expr {
+            ast::Expr::PathExpr(path_expr) => match path_expr
+                .path()
+                .and_then(|path| sema.scope(scope)?.speculative_resolve(&path))
+            {
+                Some(hir::PathResolution::Local(local)) => Some(local.ty(sema.db)),
+                _ => None,
+            },
+            _ => None,
+        },
+    };
+
+    // Without a type to go by, the shape of the pointers has to do
+    let ty = match ty.filter(|ty| !ty.is_unknown()) {
+        Some(ty) => ty.strip_references(),
+        None => return true,
+    };
+
+    // "copy_within" and "copy_from_slice" are slice methods, arrays and "Vec" reach them by deref
+    return ty.is_slice()
+        || ty.is_array()
+        || ty.as_adt().map_or(false, |adt| adt.name(sema.db).to_string() == "Vec");
 }
 
-fn collect_cpy_within_info(mcall: &CallExpr, src_expr: IndexExpr, dst_expr: IndexExpr) -> Option<CpyWithinInfo> {
+fn collect_cpy_within_info(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+    src_expr: IndexExpr,
+    dst_expr: IndexExpr,
+) -> Option<CpyWithinInfo> {
+    let count_expr = mcall.arg_list()?.args().nth(2)?;
 
-    let count_expr = mcall.arg_list()?.args().nth(2)?.to_string();
+    let base_expr = src_expr.base()?;
 
-    let base_expr = src_expr.base()?.to_string();
+    if !check_copy_base_ty(sema, &base_expr, mcall.syntax()) {
+        return None;
+    }
 
-    let start_pos = src_expr.index()?.to_string().trim_matches('.').to_string();
+    let start_pos = collect_index_range(&src_expr)?.start();
 
-    let end_pos = dst_expr.index()?.to_string().trim_matches('.').to_string();
+    let end_pos = collect_index_range(&dst_expr)?.start();
 
-    return Some(CpyWithinInfo {base_expr, start_pos, end_pos, count_expr});
+    return Some(CpyWithinInfo { base_expr, start_pos, end_pos, count_expr });
 }
 
-fn delet_insert_source_code(acc: &mut Assists, target_range: TextRange, position: TextSize, new_buf: &String) {
+fn delet_insert_source_code(
+    acc: &mut Assists,
+    label: &str,
+    target_range: TextRange,
+    position: TextSize,
+    new_buf: &String,
+) {
+    let cap = acc.snippet_cap();
 
     acc.add(
         AssistId("convert_unsafe_to_safe", AssistKind::RefactorRewrite),
-        "Convert Unsafe to Safe",
+        label,
         target_range,
         |edit| {
             edit.delete(target_range);
-            edit.insert(position + TextSize::of('\n'), new_buf)
+            match cap {
+                // Leave the caret on the hoisted statement, past its indentation
+                Some(cap) => edit.insert_snippet(cap, position, generate_cursor_snippet(new_buf)),
+                None => edit.insert(position, new_buf),
+            }
         },
     );
 }
 
-fn collect_ptrcpy_path_info(mcall: &CallExpr, index: usize, unsafe_expr: &BlockExpr) -> Option<IndexExpr> {
-    
+fn generate_cursor_snippet(new_buf: &str) -> String {
+    let indent_len = new_buf.len() - new_buf.trim_start_matches(|c| c == ' ' || c == '\t').len();
+
+    let (indent, stmt) = new_buf.split_at(indent_len);
+
+    let mut escaped = stmt.to_string();
+    stdx::replace(&mut escaped, '\\', r"\\");
+    stdx::replace(&mut escaped, '{', r"\{");
+    stdx::replace(&mut escaped, '}', r"\}");
+    stdx::replace(&mut escaped, '$', r"\$");
+
+    return format!("{}$0{}", indent, escaped);
+}
+
+fn collect_ptrcpy_path_info(
+    mcall: &CallExpr,
+    index: usize,
+    unsafe_expr: &BlockExpr,
+) -> Option<IndexExpr> {
     let src_expr;
 
     let mut backward_list = unsafe_expr.syntax().siblings(Direction::Prev);
@@ -302,7 +1441,9 @@ fn collect_ptrcpy_path_info(mcall: &CallExpr, index: usize, unsafe_expr: &BlockE
                 src_expr = ast::IndexExpr::cast(src_def.syntax().last_child()?.children().nth(0)?)?;
                 return Some(src_expr);
             } else {
-                src_expr = ast::IndexExpr::cast(src_def.syntax().last_child()?.children().nth(0)?.children().nth(0)?)?;
+                src_expr = ast::IndexExpr::cast(
+                    src_def.syntax().last_child()?.children().nth(0)?.children().nth(0)?,
+                )?;
                 return Some(src_expr);
             }
         }
@@ -316,56 +1457,163 @@ fn collect_ptrcpy_expr_info(mcall: &CallExpr, index: usize) -> Option<IndexExpr>
     if mcall.arg_list()?.args().nth(index)?.syntax().children().nth(0)?.kind() == INDEX_EXPR {
         src_expr = ast::IndexExpr::cast(mcall.arg_list()?.args().nth(index)?.syntax().children().nth(0)?)?;
     } else {
-        src_expr = ast::IndexExpr::cast(mcall.arg_list()?.args().nth(index)?.syntax().children().nth(0)?.children().nth(0)?)?;
+        src_expr = ast::IndexExpr::cast(
+            mcall.arg_list()?.args().nth(index)?.syntax().children().nth(0)?.children().nth(0)?,
+        )?;
     }
     return Some(src_expr);
 }
 
-pub fn generate_copywithin_format(mcall: &CallExpr, unsafe_expr: &BlockExpr) -> Option<String> {
-
-    let src_expr;
-    if mcall.arg_list()?.args().nth(0)?.syntax().kind() == PATH_EXPR {
-        src_expr = collect_ptrcpy_path_info(&mcall, 0, &unsafe_expr)?;
-    } else {
-        src_expr = collect_ptrcpy_expr_info(&mcall, 0)?;
+pub fn generate_copywithin_format(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+    unsafe_expr: &BlockExpr,
+) -> Option<String> {
+    if let Some(buf) = generate_copywithin_slice_format(sema, mcall, unsafe_expr) {
+        return Some(buf);
     }
 
-    let dst_expr;
+    // Whole collections, "v.as_ptr()", and pointers moved along with ".add(i)"
+    let src = collect_traced_ptr(&mcall.arg_list()?.args().next()?, mcall.syntax())?;
+
+    let dst = collect_traced_ptr(&mcall.arg_list()?.args().nth(1)?, mcall.syntax())?;
+
+    if src.base_expr.to_string() != dst.base_expr.to_string()
+        || !check_copy_base_ty(sema, &src.base_expr, mcall.syntax())
+    {
+        return None;
+    }
+
+    let count_expr = mcall.arg_list()?.args().nth(2)?;
+
+    return Some(generate_copywithin_node(src.base_expr, src.offset, dst.offset, count_expr));
+}
+
+fn generate_copywithin_slice_format(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+    unsafe_expr: &BlockExpr,
+) -> Option<String> {
+    let src_expr;
+    if mcall.arg_list()?.args().nth(0)?.syntax().kind() == PATH_EXPR {
+        src_expr = collect_ptrcpy_path_info(&mcall, 0, &unsafe_expr)?;
+    } else {
+        src_expr = collect_ptrcpy_expr_info(&mcall, 0)?;
+    }
+
+    let dst_expr;
     if mcall.arg_list()?.args().nth(1)?.syntax().kind() == PATH_EXPR {
         dst_expr = collect_ptrcpy_path_info(&mcall, 1, &unsafe_expr)?;
     } else {
         dst_expr = collect_ptrcpy_expr_info(&mcall, 1)?;
     }
 
-    let CpyWithinInfo { base_expr, start_pos, end_pos, count_expr} = collect_cpy_within_info(&mcall, src_expr, dst_expr)?;
+    let CpyWithinInfo { base_expr, start_pos, end_pos, count_expr } =
+        collect_cpy_within_info(sema, mcall, src_expr, dst_expr)?;
 
-    let buf = generate_copywithin_string(base_expr, start_pos, end_pos, count_expr);
+    let buf = generate_copywithin_node(base_expr, start_pos, end_pos, count_expr);
 
     return Some(buf);
 
 }
 
 fn replace_source_code(acc: &mut Assists, target_range: TextRange, buf: &String) {
+    replace_source_code_with_label(acc, "Convert Unsafe to Safe", target_range, buf);
+}
+
+fn replace_source_code_with_label(
+    acc: &mut Assists,
+    label: &str,
+    target_range: TextRange,
+    buf: &String,
+) {
     acc.add(
         AssistId("convert_unsafe_to_safe", AssistKind::RefactorRewrite),
-        "Convert Unsafe to Safe",
+        label,
         target_range,
-        |edit| {
-            edit.replace(target_range, buf)
-        },
+        |edit| edit.replace(target_range, buf),
     );
 }
 
-fn reindent_expr(unsafe_expr: &BlockExpr, acc: &mut Assists, target_range: TextRange, buf: &String) -> Option<()> {
+fn collect_range_with_indent(stmt: &SyntaxNode) -> TextRange {
+    // Take the line break and indentation in front along, a deleted statement leaves no blank line behind
+    match stmt.prev_sibling_or_token().filter(|it| it.kind() == WHITESPACE) {
+        Some(whitespace) => {
+            return TextRange::new(whitespace.text_range().start(), stmt.text_range().end())
+        }
+        None => return stmt.text_range(),
+    }
+}
+
+fn collect_unsafe_range(unsafe_expr: &BlockExpr) -> Option<TextRange> {
+    let mut unsafe_range = unsafe_expr.syntax().text_range();
+
+    if unsafe_expr.syntax().parent()?.kind() != STMT_LIST {
+        unsafe_range = unsafe_expr.syntax().parent()?.text_range();
+    }
 
-    let position;
+    return Some(unsafe_range);
+}
 
-    if unsafe_expr.syntax().prev_sibling().is_none() {
-        position = unsafe_expr.syntax().parent()?.prev_sibling()?.text_range().end();
-    } else {
-        position = unsafe_expr.syntax().prev_sibling()?.text_range().end();
+fn check_empty_unsafe_block(unsafe_expr: &BlockExpr, target_range: TextRange) -> Option<bool> {
+    // The target already covers the "unsafe" block itself, nothing can be left behind
+    if target_range.contains_range(unsafe_expr.syntax().text_range()) {
+        return Some(false);
     }
 
+    let stmt_list = unsafe_expr.stmt_list()?;
+
+    // Check if any statement survives once the target range is deleted
+    let remaining = stmt_list
+        .statements()
+        .map(|stmt| stmt.syntax().text_range())
+        .chain(stmt_list.tail_expr().map(|expr| expr.syntax().text_range()))
+        .any(|range| !target_range.contains_range(range));
+
+    return Some(!remaining);
+}
+
+fn collect_insert_position(unsafe_expr: &BlockExpr) -> Option<TextSize> {
+    // The statement holding the "unsafe" block, or the block itself as the tail expression
+    let stmt = unsafe_expr
+        .syntax()
+        .ancestors()
+        .find(|node| node.parent().map_or(false, |parent| parent.kind() == STMT_LIST))?;
+
+    // Step back over the indentation only, so comments or blank lines above the block stay above the hoisted line
+    let whitespace = stmt.prev_sibling_or_token().filter(|it| it.kind() == WHITESPACE)?.to_string();
+
+    let indent = &whitespace[whitespace.rfind('\n')? + 1..];
+
+    return Some(stmt.text_range().start() - TextSize::of(indent));
+}
+
+fn reindent_expr(
+    unsafe_expr: &BlockExpr,
+    acc: &mut Assists,
+    target_range: TextRange,
+    buf: &String,
+) -> Option<()> {
+    return reindent_expr_with_label(unsafe_expr, acc, "Convert Unsafe to Safe", target_range, buf);
+}
+
+fn reindent_expr_with_label(
+    unsafe_expr: &BlockExpr,
+    acc: &mut Assists,
+    label: &str,
+    target_range: TextRange,
+    buf: &String,
+) -> Option<()> {
+    // Drop the whole "unsafe" block when the conversion leaves it empty
+    if check_empty_unsafe_block(unsafe_expr, target_range)? {
+        // The block's own line break follows it, the rewrite's would leave a blank line behind
+        let buf = buf.trim_end_matches('\n').to_string();
+        replace_source_code_with_label(acc, label, collect_unsafe_range(unsafe_expr)?, &buf);
+        return Some(());
+    }
+
+    let position = collect_insert_position(unsafe_expr)?;
+
     let indent_level = unsafe_expr.indent_level();
 
     let mut new_buf = String::new();
@@ -374,27 +1622,33 @@ fn reindent_expr(unsafe_expr: &BlockExpr, acc: &mut Assists, target_range: TextR
 
     new_buf.push('\n');
 
-    delet_insert_source_code(acc, target_range, position, &new_buf);
+    // The kept block loses the indentation in front of the target too, no blank line is left inside
+    let target_range =
+        match unsafe_expr.syntax().token_at_offset(target_range.start()).left_biased() {
+            Some(token) if token.kind() == WHITESPACE && token.text().contains('\n') => {
+                TextRange::new(token.text_range().start(), target_range.end())
+            }
+            _ => target_range,
+        };
 
-    return None;
+    delet_insert_source_code(acc, label, target_range, position, &new_buf);
 
+    return Some(());
 }
 
-fn convert_to_copy_within(acc: &mut Assists, target_expr: &SyntaxNode, unsafe_range: TextRange, unsafe_expr: &BlockExpr) -> Option<()> {
-
+fn convert_to_copy_within(
+    acc: &mut Assists,
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
     let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
 
     let target_expr = mcall.syntax().parent().and_then(ast::ExprStmt::cast)?;
 
-    let mut target_range = target_expr.syntax().text_range();
-
-    let buf = generate_copywithin_format(&mcall, &unsafe_expr)?;
+    let target_range = target_expr.syntax().text_range();
 
-    if check_single_expr(&target_expr) {
-        target_range = unsafe_range;
-        replace_source_code(acc, target_range, &buf);
-        return None;
-    }
+    let buf = generate_copywithin_format(sema, &mcall, unsafe_expr)?;
 
     return reindent_expr(unsafe_expr, acc, target_range, &buf);
 }
@@ -406,41 +1660,79 @@ pub fn generate_let_get_mut(mcall: &MethodCallExpr, let_expr: &LetStmt) -> Optio
 
     let closure_body = mcall.arg_list()?.args().exactly_one().ok()?;
 
-    let pat = let_expr.pat()?;
+    let mut pat = let_expr.pat()?.to_string();
+
+    // ".unwrap()" yields the same "&T" / "&mut T" as the unchecked call, so the annotation still fits
+    if let Some(ty) = let_expr.ty() {
+        format_to!(pat, ": {}", ty);
+    }
 
     let mut buf = String::new();
 
     if let_expr.initializer()?.to_string().contains("mut") {
-        format_to!(buf, "let {} = {}.get_mut({}).unwrap();", pat, receiver, closure_body);
+        format_to!(buf, "let {} = {}.get_mut({}).unwrap()", pat, receiver, closure_body);
     } else {
-        format_to!(buf, "let {} = {}.get({}).unwrap();", pat, receiver, closure_body);
+        format_to!(buf, "let {} = {}.get({}).unwrap()", pat, receiver, closure_body);
     }
 
-    buf.push('\n');
+    if let_expr.semicolon_token().is_some() {
+        buf.push_str(";\n");
+    }
 
     return Some(buf);
 }
 
 pub fn generate_get_prefix_mut_expr(mcall: &MethodCallExpr) -> Option<String> {
-
     // Obtain the variable Expr that presents the buffer/vector
     let receiver = mcall.receiver()?;
 
     let closure_body = mcall.arg_list()?.args().exactly_one().ok()?;
 
-    let mut buf = String::new();
+    // "*v.get_unchecked(i)" reads the element itself, which is just what indexing does
+    let prefix_expr = mcall.syntax().parent().and_then(ast::PrefixExpr::cast)?;
 
-    if mcall.to_string().contains("mut") {
-        format_to!(buf, "*{}.get_mut({}).unwrap()", receiver, closure_body);
-    } else {
-        format_to!(buf, "*{}.get({}).unwrap()", receiver, closure_body);
+    if prefix_expr.op_kind()? != ast::UnaryOp::Deref {
+        return None;
     }
-    
+
+    let mut buf = String::new();
+
+    format_to!(buf, "{}[{}]", make_postfix_operand(receiver), closure_body);
+
     return Some(buf);
 }
 
-pub fn generate_get_mut_expr(mcall: &MethodCallExpr) -> Option<String> {
+fn check_get_range_in_place(mcall: &MethodCallExpr, index: &ast::Expr) -> bool {
+    // "let s = unsafe { v.get_unchecked(a..b) };" keeps the ".get(a..b)" form like the other "let"s,
+    // and so does a "let" or an assignment inside the block
+    let let_sign = mcall
+        .syntax()
+        .ancestors()
+        .find_map(ast::BlockExpr::cast)
+        .and_then(|block| block.syntax().parent())
+        .map_or(false, |parent| parent.kind() == LET_STMT)
+        || mcall
+            .syntax()
+            .parent()
+            .map_or(false, |parent| matches!(parent.kind(), LET_STMT | BIN_EXPR));
+
+    return matches!(index, ast::Expr::RangeExpr(_)) && !let_sign;
+}
+
+fn collect_block_receiver_call(mcall: &MethodCallExpr) -> Option<SyntaxNode> {
+    let block = mcall.syntax().ancestors().find_map(ast::BlockExpr::cast)?;
+
+    // "unsafe { v.get_unchecked_mut(i) }.push(x)", the chain goes on past the block
+    let parent = block.syntax().parent()?;
+
+    if !matches!(parent.kind(), METHOD_CALL_EXPR | FIELD_EXPR | TRY_EXPR) {
+        return None;
+    }
+
+    return Some(parent);
+}
 
+pub fn generate_get_mut_expr(mcall: &MethodCallExpr) -> Option<String> {
     // Obtain the variable Expr that presents the buffer/vector
     let receiver = mcall.receiver()?;
 
@@ -448,6 +1740,22 @@ pub fn generate_get_mut_expr(mcall: &MethodCallExpr) -> Option<String> {
 
     let mut buf = String::new();
 
+    // A range yields a sub-slice, which indexing hands out directly
+    if check_get_range_in_place(mcall, &closure_body) {
+        if mcall.to_string().contains("mut") {
+            format_to!(buf, "&mut {}[{}]", make_postfix_operand(receiver), closure_body);
+        } else {
+            format_to!(buf, "&{}[{}]", make_postfix_operand(receiver), closure_body);
+        }
+
+        // The reference binds looser than the call continuing after the block
+        if collect_block_receiver_call(mcall).is_some() {
+            buf = format!("({})", buf);
+        }
+
+        return Some(buf);
+    }
+
     if mcall.to_string().contains("mut") {
         format_to!(buf, "{}.get_mut({}).unwrap()", receiver, closure_body);
     } else {
@@ -469,419 +1777,614 @@ pub fn generate_get_mut(mcall: &MethodCallExpr, expr: &BinExpr) -> Option<String
     let mut buf = String::new();
 
     if expr.rhs()?.to_string().contains("mut") {
-        format_to!(buf, "{} = {}.get_mut({}).unwrap();", pat, receiver, closure_body);
+        format_to!(buf, "{} = {}.get_mut({}).unwrap()", pat, receiver, closure_body);
     } else {
-        format_to!(buf, "{} = {}.get({}).unwrap();", pat, receiver, closure_body);
+        format_to!(buf, "{} = {}.get({}).unwrap()", pat, receiver, closure_body);
     }
 
-    buf.push('\n');
+    if check_stmt_terminator(expr.syntax()) {
+        buf.push_str(";\n");
+    }
 
     return Some(buf);
 }
 
-fn check_single_let_expr(target_expr: &LetStmt) -> bool {
+fn check_stmt_terminator(expr: &SyntaxNode) -> bool {
+    // A statement keeps the ";" it was written with
+    if let Some(stmt) = expr.parent().and_then(ast::ExprStmt::cast) {
+        return stmt.semicolon_token().is_some();
+    }
 
-    // Check if the unsafe bloack only contains one expr
-    if target_expr.syntax().prev_sibling().is_none() && target_expr.syntax().next_sibling().is_none() {
+    // Moved out in front of the block, a tail becomes a statement of its own
+    if expr.prev_sibling().is_some() || expr.next_sibling().is_some() {
         return true;
     }
-    return false;
+
+    // A lone tail takes the place of the "unsafe" block, it only stays a tail where the block was one
+    let unsafe_expr = match expr.ancestors().find_map(ast::BlockExpr::cast) {
+        Some(unsafe_expr) => unsafe_expr,
+        None => return true,
+    };
+
+    return unsafe_expr.syntax().parent().map_or(true, |parent| parent.kind() != STMT_LIST);
 }
 
-fn convert_to_get_mut(acc: &mut Assists, target_expr: &SyntaxNode, unsafe_range: TextRange, unsafe_expr: &BlockExpr) -> Option<()> {
+fn generate_str_get(mcall: &MethodCallExpr) -> Option<String> {
+    let receiver = mcall.receiver()?;
 
-    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+    let range = mcall.arg_list()?.args().exactly_one().ok()?;
 
-    if mcall.syntax().parent()?.kind() == STMT_LIST {
-        let target_expr = &mcall;
+    let mut_sign = mcall.name_ref()?.to_string() == "get_unchecked_mut";
 
-        let target_range = target_expr.syntax().parent()?.parent()?.text_range();
+    let mut buf = String::new();
 
-        let buf = generate_get_mut_expr(&mcall)?;
-        
-        if check_single_methodcall_expr(&target_expr)? == true {
-            replace_source_code(acc, target_range, &buf);
-            return None;
+    // Slicing a "str" panics off a char boundary as well, so the plain index is just as safe
+    if check_get_range_in_place(mcall, &range) {
+        match mut_sign {
+            true => format_to!(buf, "&mut {}[{}]", make_postfix_operand(receiver), range),
+            false => format_to!(buf, "&{}[{}]", make_postfix_operand(receiver), range),
         }
-        return reindent_expr(unsafe_expr, acc, target_range, &buf);
+
+        return Some(buf);
     }
 
-    if mcall.syntax().parent()?.kind() == PREFIX_EXPR {
-    let target_expr = &mcall;
+    match mut_sign {
+        true => format_to!(buf, "{}.get_mut({}).expect(\"not a char boundary\")", receiver, range),
+        false => format_to!(buf, "{}.get({}).expect(\"not a char boundary\")", receiver, range),
+    }
 
-    let target_range = target_expr.syntax().parent()?.parent()?.parent()?.text_range();
+    return Some(buf);
+}
 
-    let buf = generate_get_prefix_mut_expr(&mcall)?;
-    
-    if check_single_methodcall_expr(&target_expr)? == true {
-        replace_source_code(acc, target_range, &buf);
-        return None;
+fn generate_let_str_get(mcall: &MethodCallExpr, let_expr: &LetStmt) -> Option<String> {
+    let pat = let_expr.pat()?;
+
+    let mut buf = String::new();
+
+    match let_expr.ty() {
+        Some(ty) => format_to!(buf, "let {}: {} = {};", pat, ty, generate_str_get(mcall)?),
+        None => format_to!(buf, "let {} = {};", pat, generate_str_get(mcall)?),
     }
-    return reindent_expr(unsafe_expr, acc, target_range, &buf);
+
+    return Some(buf);
 }
 
-    if mcall.syntax().parent()?.kind() == BIN_EXPR {
-        let target_expr = mcall.syntax().parent().and_then(ast::BinExpr::cast)?;
+fn generate_str_get_assign(mcall: &MethodCallExpr, bin_expr: &BinExpr) -> Option<String> {
+    let mut buf = String::new();
 
-        let mut target_range = target_expr.syntax().parent()?.text_range();
+    format_to!(buf, "{} = {};", bin_expr.lhs()?, generate_str_get(mcall)?);
 
-        let buf = generate_get_mut(&mcall, &target_expr)?;
-        
-        if check_single_bin_expr(&target_expr)? == true {
-            target_range = unsafe_range;
-            replace_source_code(acc, target_range, &buf);
+    return Some(buf);
+}
+
+fn convert_to_str_get(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+
+    // "let sub = unsafe { s.get_unchecked(a..b) };" or the sub-string used inline
+    if mcall.syntax().parent()?.kind() == STMT_LIST {
+        if !check_single_methodcall_expr(&mcall)? {
             return None;
         }
-        return reindent_expr(unsafe_expr, acc, target_range, &buf);
+
+        let buf = generate_str_get(&mcall)?;
+        replace_source_code(acc, unsafe_expr.syntax().text_range(), &buf);
+        return None;
+    }
+
+    // "sub = s.get_unchecked(a..b);"
+    if let Some(bin_expr) = mcall.syntax().parent().and_then(ast::BinExpr::cast) {
+        let expr_stmt = bin_expr.syntax().parent().and_then(ast::ExprStmt::cast)?;
+
+        let mut buf = generate_str_get_assign(&mcall, &bin_expr)?;
+
+        if check_single_expr(&expr_stmt) {
+            replace_source_code(acc, unsafe_range, &buf);
+            return None;
+        }
+
+        buf.push('\n');
+
+        return reindent_expr(unsafe_expr, acc, expr_stmt.syntax().text_range(), &buf);
     }
 
     let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
 
-    let buf = generate_let_get_mut(&mcall, &let_expr)?;
+    let mut buf = generate_let_str_get(&mcall, &let_expr)?;
 
-    let mut target_range = let_expr.syntax().text_range();
     if check_single_let_expr(&let_expr) {
-        target_range = unsafe_range;
-        replace_source_code(acc, target_range, &buf);
+        replace_source_code(acc, unsafe_range, &buf);
         return None;
     }
 
-    return reindent_expr(unsafe_expr, acc, target_range, &buf);
+    buf.push('\n');
+
+    return reindent_expr(unsafe_expr, acc, let_expr.syntax().text_range(), &buf);
 }
 
-pub fn generate_from_utf8(mcall: &CallExpr, expr: &BinExpr, mut_sign: bool, string_sign: bool) -> Option<String> {
+fn check_as_bytes_receiver(mcall: &MethodCallExpr) -> Option<bool> {
+    // The receiver is itself a call, "s.as_bytes()", not a plain buffer
+    let bytes_call = match mcall.receiver()? {
+        ast::Expr::MethodCallExpr(bytes_call) => bytes_call,
+        _ => return Some(false),
+    };
 
-    // Obtain the variable Expr that presents the string
-    let receiver = mcall.arg_list()?.args().nth(0)?;
+    return Some(
+        bytes_call.name_ref()?.to_string() == "as_bytes"
+            && bytes_call.arg_list()?.args().next().is_none(),
+    );
+}
 
-    let pat = expr.lhs()?;
+fn generate_str_bytes_access(mcall: &MethodCallExpr) -> Option<(SyntaxNode, String)> {
+    let receiver = mcall.receiver()?;
+
+    let index = mcall.arg_list()?.args().exactly_one().ok()?;
 
     let mut buf = String::new();
 
-    if string_sign {
-        format_to!(buf, "{} = String::from_utf8({}).unwrap();", pat, receiver);
+    // "*s.as_bytes().get_unchecked(i)" reads the byte, which plain indexing does too
+    if let Some(prefix) = mcall.syntax().parent().and_then(ast::PrefixExpr::cast) {
+        if prefix.op_kind()? == ast::UnaryOp::Deref {
+            format_to!(buf, "{}[{}]", receiver, index);
+            return Some((prefix.syntax().clone(), buf));
+        }
+    }
 
-        buf.push('\n');
+    format_to!(buf, "{}.get({}).copied().unwrap()", receiver, index);
 
-        return Some(buf);
-    }
+    return Some((mcall.syntax().clone(), buf));
+}
 
-    if !mut_sign {
-        format_to!(buf, "{} = std::str::from_utf8({}).unwrap();", pat, receiver);
-    } else {
-        format_to!(buf, "{} = std::str::from_utf8_mut({}).unwrap();", pat, receiver);
-    }
+fn generate_let_str_bytes_access(mcall: &MethodCallExpr, let_expr: &LetStmt) -> Option<String> {
+    let pat = let_expr.pat()?;
 
-    buf.push('\n');
+    let (access_node, access) = generate_str_bytes_access(mcall)?;
+
+    let mut buf = String::new();
+
+    // The copied byte is a "u8", so a "&u8" annotation on the reference would no longer fit
+    match let_expr.ty() {
+        Some(ty) if &access_node != mcall.syntax() => {
+            format_to!(buf, "let {}: {} = {};", pat, ty, access)
+        }
+        _ => format_to!(buf, "let {} = {};", pat, access),
+    }
 
     return Some(buf);
 }
 
-pub fn generate_from_utf8_expr_stmt(mcall: &CallExpr, mut_sign: bool, string_sign: bool) -> Option<String> {
-
-    // Obtain the variable Expr that presents the string
-    let receiver = mcall.arg_list()?.args().nth(0)?;
+fn convert_to_str_bytes_index(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
 
-    let mut buf = String::new();
+    let (access_node, buf) = generate_str_bytes_access(&mcall)?;
 
-    if string_sign {
-        format_to!(buf, "String::from_utf8({}).unwrap();", receiver);
+    // "let b = unsafe { *s.as_bytes().get_unchecked(i) };" or the byte used inline
+    if access_node.parent()?.kind() == STMT_LIST {
+        if access_node.prev_sibling().is_some() || access_node.next_sibling().is_some() {
+            return None;
+        }
 
-        return Some(buf);
+        replace_source_code(acc, unsafe_expr.syntax().text_range(), &buf);
+        return None;
     }
 
-    if !mut_sign {
-        format_to!(buf, "std::str::from_utf8({}).unwrap()", receiver);
-    } else {
-        format_to!(buf, "std::str::from_utf8_mut({}).unwrap()", receiver);
+    let let_expr = access_node.parent().and_then(ast::LetStmt::cast)?;
+
+    let mut buf = generate_let_str_bytes_access(&mcall, &let_expr)?;
+
+    if check_single_let_expr(&let_expr) {
+        replace_source_code(acc, unsafe_range, &buf);
+        return None;
     }
 
-    return Some(buf);
+    buf.push('\n');
+
+    return reindent_expr(unsafe_expr, acc, let_expr.syntax().text_range(), &buf);
 }
 
-pub fn generate_let_from_utf8(mcall: &CallExpr, let_expr: &LetStmt, mut_sign: bool, string_sign: bool) -> Option<String> {
+struct AsciiBytesMutInfo {
+    pub receiver: ast::Expr,
+    pub method: String,
+    // The index and the one character text of a single ASCII byte written through the bytes
+    pub byte_write: Option<(ast::Expr, String)>,
+    // The "let bytes = ..." binding, when the bytes are not used right away
+    pub binding: Option<SyntaxNode>,
+    pub use_stmt: ExprStmt,
+}
 
-    // Obtain the variable Expr that presents the string
-    let receiver = mcall.arg_list()?.args().nth(0)?;
+fn collect_ascii_case_call(expr_stmt: &ExprStmt) -> Option<MethodCallExpr> {
+    // Only these keep every byte on its side of 0x80, so the UTF-8 stays intact
+    let mcall = match expr_stmt.expr()? {
+        ast::Expr::MethodCallExpr(mcall) => mcall,
+        _ => return None,
+    };
 
-    let pat = let_expr.pat()?;
+    if !matches!(mcall.name_ref()?.text().as_str(), "make_ascii_uppercase" | "make_ascii_lowercase")
+    {
+        return None;
+    }
 
-    let mut buf = String::new();
+    if mcall.arg_list()?.args().next().is_some() {
+        return None;
+    }
 
-    if string_sign {
-        format_to!(buf, "{} = String::from_utf8({}).unwrap();", pat, receiver);
+    return Some(mcall);
+}
 
-        buf.push('\n');
+fn collect_ascii_byte_write(expr_stmt: &ExprStmt) -> Option<(ast::IndexExpr, String)> {
+    let assign = match expr_stmt.expr()? {
+        ast::Expr::BinExpr(assign) => assign,
+        _ => return None,
+    };
 
-        return Some(buf);
+    if assign.op_kind()? != (ast::BinaryOp::Assignment { op: None }) {
+        return None;
     }
 
-    if !mut_sign {
-        format_to!(buf, "let {} = std::str::from_utf8({}).unwrap();", pat, receiver);
-    } else {
-        format_to!(buf, "let {} = std::str::from_utf8_mut({}).unwrap();", pat, receiver);
+    let index_expr = match assign.lhs()? {
+        ast::Expr::IndexExpr(index_expr) => index_expr,
+        _ => return None,
+    };
+
+    let byte = match assign.rhs()? {
+        ast::Expr::Literal(literal) => match literal.kind() {
+            ast::LiteralKind::Byte(byte) => byte.value()?,
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    // An ASCII byte is a whole character on its own, anything else would split one
+    if !byte.is_ascii() {
+        return None;
     }
 
-    buf.push('\n');
+    return Some((index_expr, format!("{:?}", char::from(byte).to_string())));
+}
 
-    return Some(buf);
+fn check_string_receiver(sema: &Semantics<'_, RootDatabase>, receiver: &ast::Expr) -> Option<bool> {
+    let ty = sema.type_of_expr(receiver)?.original.strip_references();
+
+    // "replace_range" is only on "String", a plain "str" cannot change its length
+    return Some(ty.as_adt().map_or(false, |adt| adt.name(sema.db).to_string() == "String"));
 }
 
-fn convert_to_from_utf8(acc: &mut Assists, target_expr: &SyntaxNode, unsafe_range: TextRange, unsafe_expr: &BlockExpr, mut_sign: bool, string_sign: bool) -> Option<()> {
+fn collect_ascii_bytes_mut(
+    mcall: &MethodCallExpr,
+    unsafe_expr: &BlockExpr,
+) -> Option<AsciiBytesMutInfo> {
+    let receiver = mcall.receiver()?;
+
+    // "s.as_bytes_mut().make_ascii_uppercase();"
+    if let Some(case_call) = mcall.syntax().parent().and_then(ast::MethodCallExpr::cast) {
+        let use_stmt = case_call.syntax().parent().and_then(ast::ExprStmt::cast)?;
 
-    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+        let method = collect_ascii_case_call(&use_stmt)?.name_ref()?.to_string();
 
-    if mcall.syntax().parent()?.kind() == STMT_LIST{
-        let target_expr = &mcall;
+        return Some(AsciiBytesMutInfo {
+            receiver,
+            method,
+            byte_write: None,
+            binding: None,
+            use_stmt,
+        });
+    }
 
-        let target_range = target_expr.syntax().parent()?.parent()?.text_range();
+    // "s.as_bytes_mut()[0] = b'H';"
+    if let Some(index_expr) = mcall.syntax().parent().and_then(ast::IndexExpr::cast) {
+        let use_stmt = index_expr.syntax().ancestors().find_map(ast::ExprStmt::cast)?;
 
-        let buf = generate_from_utf8_expr_stmt(&mcall, mut_sign, string_sign)?;
-        
-        if check_single_call_expr(&target_expr)? == true {
-            // target_range = unsafe_range;
-            replace_source_code(acc, target_range, &buf);
+        let (write_target, text) = collect_ascii_byte_write(&use_stmt)?;
+
+        if write_target.syntax() != index_expr.syntax() {
             return None;
         }
-        return reindent_expr(unsafe_expr, acc, target_range, &buf);
-    }
 
-    if mcall.syntax().parent()?.kind() == EXPR_STMT {
-        let target_expr = mcall.syntax().parent().and_then(ast::ExprStmt::cast)?;
+        let byte_write = Some((index_expr.index()?, text));
 
-        let target_range = target_expr.syntax().parent()?.parent()?.text_range();
+        return Some(AsciiBytesMutInfo {
+            receiver,
+            method: "replace_range".to_string(),
+            byte_write,
+            binding: None,
+            use_stmt,
+        });
+    }
 
-        let buf = generate_from_utf8_expr_stmt(&mcall, mut_sign, string_sign)?;
-        
-        if check_single_expr_stmt(&target_expr)? == true {
-            // target_range = unsafe_range;
-            replace_source_code(acc, target_range, &buf);
-            return None;
+    // "let bytes = unsafe { s.as_bytes_mut() };" or the "let" alone inside the block
+    let (let_expr, binding) = match unsafe_expr.syntax().parent().and_then(ast::LetStmt::cast) {
+        Some(let_expr) => {
+            if mcall.syntax().parent()?.kind() != STMT_LIST || !check_single_methodcall_expr(mcall)?
+            {
+                return None;
+            }
+
+            (let_expr.clone(), let_expr.syntax().clone())
         }
-        return reindent_expr(unsafe_expr, acc, target_range, &buf);
-    }
+        None => {
+            let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
 
-    if mcall.syntax().parent()?.kind() == BIN_EXPR {
-        let target_expr = mcall.syntax().parent().and_then(ast::BinExpr::cast)?;
+            if !check_single_let_expr(&let_expr)
+                || unsafe_expr.syntax().parent()?.kind() != STMT_LIST
+            {
+                return None;
+            }
 
-        let mut target_range = target_expr.syntax().parent()?.text_range();
+            (let_expr, unsafe_expr.syntax().clone())
+        }
+    };
 
-        let buf = generate_from_utf8(&mcall, &target_expr, mut_sign, string_sign)?;
-        
-        if check_single_bin_expr(&target_expr)? == true {
-            target_range = unsafe_range;
-            replace_source_code(acc, target_range, &buf);
+    let name = match let_expr.pat()? {
+        ast::Pat::IdentPat(ident_pat) => ident_pat.name()?.to_string(),
+        _ => return None,
+    };
+
+    // The bytes may be touched exactly once afterwards, by the ASCII case change itself
+    let mut uses =
+        binding.siblings(Direction::Next).skip(1).filter(|iter| check_ident_mentioned(iter, &name));
+
+    let use_stmt = ast::ExprStmt::cast(uses.next()?)?;
+
+    if uses.next().is_some() {
+        return None;
+    }
+
+    if let Some((index_expr, text)) = collect_ascii_byte_write(&use_stmt) {
+        if index_expr.base()?.to_string() != name {
             return None;
         }
-        return reindent_expr(unsafe_expr, acc, target_range, &buf);
-    }
 
-    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
+        let byte_write = Some((index_expr.index()?, text));
 
-    let buf = generate_let_from_utf8(&mcall, &let_expr, mut_sign, string_sign)?;
+        return Some(AsciiBytesMutInfo {
+            receiver,
+            method: "replace_range".to_string(),
+            byte_write,
+            binding: Some(binding),
+            use_stmt,
+        });
+    }
 
-    let mut target_range = let_expr.syntax().text_range();
-    if check_single_let_expr(&let_expr) {
-        target_range = unsafe_range;
-        replace_source_code(acc, target_range, &buf);
+    let case_call = collect_ascii_case_call(&use_stmt)?;
+
+    if case_call.receiver()?.to_string() != name {
         return None;
     }
 
-    return reindent_expr(unsafe_expr, acc, target_range, &buf);
+    let method = case_call.name_ref()?.to_string();
+
+    return Some(AsciiBytesMutInfo {
+        receiver,
+        method,
+        byte_write: None,
+        binding: Some(binding),
+        use_stmt,
+    });
 }
 
-pub fn generate_from_transmute(mcall: &CallExpr, let_expr: &LetStmt, unsafe_expr: &BlockExpr) -> Option<String> {
+fn generate_ascii_case_call(info: &AsciiBytesMutInfo) -> String {
+    let mut buf = String::new();
 
-    // Obtain the variable Expr that presents the string
-    let receiver = mcall.arg_list()?.args().nth(0)?;
+    let receiver = make_postfix_operand(info.receiver.clone());
+
+    match &info.byte_write {
+        // The range covers exactly the one byte, which is a whole character since it was ASCII
+        Some((index, text)) => match collect_offset_literal(&Some(index.clone())) {
+            Some(offset) => format_to!(
+                buf,
+                "{}.{}({}..{}, {});",
+                receiver,
+                info.method,
+                offset,
+                offset + 1,
+                text
+            ),
+            None => {
+                format_to!(buf, "{}.{}({}..{} + 1, {});", receiver, info.method, index, index, text)
+            }
+        },
+        None => format_to!(buf, "{}.{}();", receiver, info.method),
+    }
 
-    let pat = let_expr.pat()?;
+    return buf;
+}
 
-    let mut buf = String::new();
+fn convert_to_str_ascii_case(
+    acc: &mut Assists,
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
 
-    if let_expr.to_string().contains(&TargetTypes::String.to_string()) {
-        format_to!(buf, "let {} = str::from_utf8({}).unwrap();", pat, receiver);
-        buf.push('\n');
-        return Some(buf);
-    }
+    let info = collect_ascii_bytes_mut(&mcall, unsafe_expr)?;
 
-    if let_expr.to_string().contains(&TargetTypes::Bytes.to_string()) {
-        format_to!(buf, "let {} = {}.as_bytes();", pat, receiver);
-        buf.push('\n');
-        return Some(buf);
+    if info.byte_write.is_some() && !check_string_receiver(sema, &info.receiver)? {
+        return None;
     }
 
-    if let_expr.to_string().contains(&TargetTypes::U64.to_string()) {
-        format_to!(buf, "let {} = {}.to_bits();", pat, receiver);
-        buf.push('\n');
-        return Some(buf);
-    }
+    let mut buf = generate_ascii_case_call(&info);
 
-    if let_expr.to_string().contains(&TargetTypes::F32.to_string()) {
-        format_to!(buf, "let {} = f32::from_bits({});", pat, receiver);
-        buf.push('\n');
-        return Some(buf);
+    // The binding goes away and its one use calls the "str" method directly
+    if let Some(binding) = &info.binding {
+        delet_replace_source_code(
+            acc,
+            info.use_stmt.syntax().text_range(),
+            collect_stmt_range(binding),
+            &buf,
+        );
+        return None;
     }
 
-    if let_expr.to_string().contains(&TargetTypes::F64.to_string()) {
-        format_to!(buf, "let {} = f64::from_bits({});", pat, receiver);
-        buf.push('\n');
-        return Some(buf);
+    if check_single_expr(&info.use_stmt) {
+        replace_source_code(acc, unsafe_range, &buf);
+        return None;
     }
 
-    if let_expr.to_string().contains(&TargetTypes::Char.to_string()) {
-        format_to!(buf, "let {} = char::from_u32({}).unwrap();", pat, receiver);
-        buf.push('\n');
-        return Some(buf);
-    }
+    buf.push('\n');
 
-    if let_expr.to_string().contains(&TargetTypes::U32.to_string()) {
-        let mut backward_list = unsafe_expr.syntax().siblings(Direction::Prev);
+    return reindent_expr(unsafe_expr, acc, info.use_stmt.syntax().text_range(), &buf);
+}
 
-        if unsafe_expr.syntax().parent()?.kind() != STMT_LIST {
-            backward_list = unsafe_expr.syntax().parent()?.siblings(Direction::Prev);
-        }
-    
-        for backward_slice in backward_list {
-            let statement = backward_slice.to_string();
-            if statement.contains(&receiver.to_string()) && backward_slice.kind() == LET_STMT {
-                if statement.contains(&TargetTypes::Char.to_string()) {
-                    format_to!(buf, "let {} = {} as u32;", pat, receiver);
-                    buf.push('\n');
-                    return Some(buf);
-                }
-                if statement.contains(&TargetTypes::F32.to_string()) {
-                    format_to!(buf, "let {} = {}.to_bits();", pat, receiver);
-                    buf.push('\n');
-                    return Some(buf);
-                }
-            }
-        }
+fn check_single_let_expr(target_expr: &LetStmt) -> bool {
+
+    // Check if the unsafe bloack only contains one expr
+    if target_expr.syntax().prev_sibling().is_none() && target_expr.syntax().next_sibling().is_none() {
+        return true;
     }
-    return None;
+    return false;
 }
 
-fn transmute_convertion(acc: &mut Assists, target_expr: &SyntaxNode, unsafe_range: TextRange, unsafe_expr: &BlockExpr) -> Option<()> {
-
-    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+fn generate_if_let_get(
+    mcall: &MethodCallExpr,
+    pat: &ast::Pat,
+    indent_level: IndentLevel,
+) -> Option<String> {
+    // Obtain the variable Expr that presents the buffer/vector
+    let receiver = mcall.receiver()?;
 
-    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
+    let closure_body = mcall.arg_list()?.args().exactly_one().ok()?;
 
-    let buf = generate_from_transmute(&mcall, &let_expr, &unsafe_expr)?;
+    let mut buf = String::new();
 
-    let mut target_range = let_expr.syntax().text_range();
-    if check_single_let_expr(&let_expr) {
-        target_range = unsafe_range;
-        replace_source_code(acc, target_range, &buf);
-        return None;
+    if UnsafePattern::GetUncheckMut.matches(&mcall.name_ref()?.to_string()) {
+        format_to!(buf, "if let Some({}) = {}.get_mut({}) {{\n", pat, receiver, closure_body);
+    } else {
+        format_to!(buf, "if let Some({}) = {}.get({}) {{\n", pat, receiver, closure_body);
     }
 
-    return reindent_expr(unsafe_expr, acc, target_range, &buf);
+    // Leave both branches for the user to fill in instead of panicking on "None"
+    format_to!(buf, "{}    /* use {} */\n", indent_level, pat);
+    format_to!(buf, "{}}} else {{\n", indent_level);
+    format_to!(buf, "{}    /* handle */\n", indent_level);
+    format_to!(buf, "{}}}", indent_level);
+
+    return Some(buf);
 }
 
-pub fn generate_bytes_to_convert(mcall: &CallExpr, unsafe_expr: &BlockExpr, let_sign: bool) -> Option<String> {
+fn convert_to_if_let_get(
+    acc: &mut Assists,
+    mcall: &MethodCallExpr,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let label = "Convert to if let with None handling";
 
-    let mut buf = String::new();
+    // "let x = unsafe { v.get_unchecked(i) };"
+    if mcall.syntax().parent()?.kind() == STMT_LIST {
+        if !check_single_methodcall_expr(mcall)? {
+            return None;
+        }
 
-    let lhs_expr;
+        let let_expr = unsafe_expr.syntax().parent().and_then(ast::LetStmt::cast)?;
 
-    if !let_sign {
-        let target_expr = mcall.syntax().parent().and_then(ast::BinExpr::cast)?;
-        lhs_expr = target_expr.lhs()?.to_string();   
-    } else {
-        let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
-        lhs_expr = let_expr.pat()?.to_string();
-    }
+        let buf = generate_if_let_get(
+            mcall,
+            &let_expr.pat()?,
+            IndentLevel::from_node(let_expr.syntax()),
+        )?;
 
-    let arg = mcall.arg_list()?.args().nth(0)?.syntax().children().nth(0)?.to_string();
-    let mut receiver;
+        add_if_let_get(acc, label, mcall, unsafe_range, &buf);
+        return None;
+    }
 
-    if arg.contains(&UnsafePattern::AsPtr.to_string()) {
-        receiver = arg.split(".").nth(0)?.to_string();
-    } else {
-        receiver = arg.to_string();
-        let mut backward_list = unsafe_expr.syntax().siblings(Direction::Prev);
+    // "unsafe { let x = v.get_unchecked(i); }"
+    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
 
-        if unsafe_expr.syntax().parent()?.kind() != STMT_LIST {
-            backward_list = unsafe_expr.syntax().parent()?.siblings(Direction::Prev);
-        }
-    
-        for backward_slice in backward_list {
-            if backward_slice.to_string().contains(&arg) && backward_slice.kind() == LET_STMT {
-                let src_def = ast::LetStmt::cast(backward_slice)?;
-                let rhs_expr = src_def.initializer()?.to_string();
-                receiver = rhs_expr.split(".").nth(0)?.to_string();
-                break;
-            }
-        }
+    if !check_single_let_expr(&let_expr) {
+        return None;
     }
 
-    let target_type = mcall.arg_list()?.args().nth(0)?.syntax().children().nth(1)?.to_string();
+    let buf = generate_if_let_get(mcall, &let_expr.pat()?, unsafe_expr.indent_level())?;
 
-    if target_type.contains(&TargetTypes::U8.to_string()) {
-        if !let_sign {
-            format_to!(buf, "{} = u8::from_ne_bytes({}[..1].try_into().unwrap());", lhs_expr, receiver);
-            buf.push('\n');
-            return Some(buf);
-        } else {
-            format_to!(buf, "let {} = u8::from_ne_bytes({}[..1].try_into().unwrap());", lhs_expr, receiver);
-            buf.push('\n');
-            return Some(buf);
-        }
-    } 
+    add_if_let_get(acc, label, mcall, unsafe_range, &buf);
+    return None;
+}
 
-    if target_type.contains(&TargetTypes::U16.to_string()) {
-        if !let_sign {
-            format_to!(buf, "{} = u16::from_ne_bytes({}[..2].try_into().unwrap());", lhs_expr, receiver);
-            buf.push('\n');
-            return Some(buf);
-        } else {
-            format_to!(buf, "let {} = u16::from_ne_bytes({}[..2].try_into().unwrap());", lhs_expr, receiver);
-            buf.push('\n');
-            return Some(buf);
+fn add_if_let_get(
+    acc: &mut Assists,
+    label: &str,
+    mcall: &MethodCallExpr,
+    unsafe_range: TextRange,
+    buf: &String,
+) {
+    // Targeting just the call sorts the scaffold ahead of the ".unwrap()" conversion, which stays the primary one
+    acc.add(
+        AssistId("convert_to_if_let_get", AssistKind::RefactorRewrite),
+        label,
+        mcall.syntax().text_range(),
+        |edit| edit.replace(unsafe_range, buf),
+    );
+}
+
+fn convert_to_get_mut(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+
+    convert_to_if_let_get(acc, &mcall, unsafe_range, unsafe_expr);
+
+    if mcall.syntax().parent()?.kind() == STMT_LIST {
+        let target_expr = &mcall;
+
+        let target_range = target_expr.syntax().parent()?.parent()?.text_range();
+
+        let buf = generate_get_mut_expr(&mcall)?;
+        
+        if check_single_methodcall_expr(&target_expr)? == true {
+            replace_source_code(acc, target_range, &buf);
+            return None;
         }
+        return reindent_expr(unsafe_expr, acc, target_range, &buf);
     }
-    
-    if target_type.contains(&TargetTypes::U32.to_string()) {
-        if !let_sign {
-            format_to!(buf, "{} = u32::from_ne_bytes({}[..4].try_into().unwrap());", lhs_expr, receiver);
-            buf.push('\n');
-            return Some(buf);
-        } else {
-            format_to!(buf, "let {} = u32::from_ne_bytes({}[..4].try_into().unwrap());", lhs_expr, receiver);
-            buf.push('\n');
-            return Some(buf);
-        }
-    } 
 
-    if target_type.contains(&TargetTypes::U64.to_string()) {
-        if !let_sign {
-            format_to!(buf, "{} = u64::from_ne_bytes({}[..8].try_into().unwrap());", lhs_expr, receiver);
-            buf.push('\n');
-            return Some(buf);
-        } else {
-            format_to!(buf, "let {} = u64::from_ne_bytes({}[..8].try_into().unwrap());", lhs_expr, receiver);
-            buf.push('\n');
-            return Some(buf);
+    if mcall.syntax().parent()?.kind() == PREFIX_EXPR {
+        let prefix_expr = mcall.syntax().parent()?;
+
+        // Only the dereference that is the block's value is replaced, not one inside a "let"
+        if prefix_expr.parent()?.kind() != STMT_LIST {
+            return None;
         }
-    } 
 
-    return None;
-}
+        let buf = generate_get_prefix_mut_expr(&mcall)?;
 
-fn convert_to_from_ne_bytes(acc: &mut Assists, target_expr: &SyntaxNode, unsafe_range: TextRange, unsafe_expr: &BlockExpr) -> Option<()> {
+        // Next to other statements the block stays, only its value is indexed instead
+        let target_range = match prefix_expr.prev_sibling() {
+            Some(_) => prefix_expr.text_range(),
+            None => prefix_expr.parent()?.parent()?.text_range(),
+        };
 
-    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
-    
-    if mcall.syntax().parent()?.kind() == BIN_EXPR {
+        replace_source_code(acc, target_range, &buf);
+        return None;
+    }
 
+    if mcall.syntax().parent()?.kind() == BIN_EXPR {
         let target_expr = mcall.syntax().parent().and_then(ast::BinExpr::cast)?;
 
-        let mut target_range = target_expr.syntax().parent()?.text_range();
+        // The block's tail has no statement around it, the assignment is all there is to replace
+        let stmt = match target_expr.syntax().parent()?.kind() {
+            EXPR_STMT => target_expr.syntax().parent()?,
+            _ => target_expr.syntax().clone(),
+        };
 
-        let buf = generate_bytes_to_convert(&mcall, unsafe_expr, false)?;
-        
-        if check_single_bin_expr(&target_expr)? == true {
+        let mut target_range = stmt.text_range();
+
+        let buf = generate_get_mut(&mcall, &target_expr)?;
+
+        if stmt.prev_sibling().is_none() && stmt.next_sibling().is_none() {
             target_range = unsafe_range;
             replace_source_code(acc, target_range, &buf);
             return None;
@@ -889,10 +2392,10 @@ fn convert_to_from_ne_bytes(acc: &mut Assists, target_expr: &SyntaxNode, unsafe_
         return reindent_expr(unsafe_expr, acc, target_range, &buf);
     }
 
-    let buf = generate_bytes_to_convert(&mcall, unsafe_expr, true)?;
-
     let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
 
+    let buf = generate_let_get_mut(&mcall, &let_expr)?;
+
     let mut target_range = let_expr.syntax().text_range();
     if check_single_let_expr(&let_expr) {
         target_range = unsafe_range;
@@ -903,60 +2406,268 @@ fn convert_to_from_ne_bytes(acc: &mut Assists, target_expr: &SyntaxNode, unsafe_
     return reindent_expr(unsafe_expr, acc, target_range, &buf);
 }
 
-pub fn generate_from_u32(mcall: &CallExpr, expr: &BinExpr) -> Option<String> {
+pub struct UncheckedMutPairInfo {
+    pub receiver: String,
+    pub first_stmt: LetStmt,
+    pub second_stmt: LetStmt,
+    pub first_index: ast::Expr,
+    pub second_index: ast::Expr,
+}
 
-    // Obtain the variable Expr that presents the string
-    let receiver = mcall.arg_list()?.args().nth(0)?;
+fn collect_unchecked_mut_let(stmt: &SyntaxNode) -> Option<(LetStmt, MethodCallExpr)> {
+    let let_expr = ast::LetStmt::cast(stmt.clone())?;
 
-    let pat = expr.lhs()?;
+    let mcall = match let_expr.initializer()? {
+        ast::Expr::MethodCallExpr(mcall) => mcall,
+        _ => return None,
+    };
 
-    let mut buf = String::new();
+    if !UnsafePattern::GetUncheckMut.matches(&mcall.name_ref()?.to_string()) {
+        return None;
+    }
 
-    format_to!(buf, "{} = char::from_u32({}).unwrap();", pat, receiver);
+    return Some((let_expr, mcall));
+}
 
-    buf.push('\n');
+pub fn collect_unchecked_mut_pair(
+    mcall: &MethodCallExpr,
+    unsafe_expr: &BlockExpr,
+) -> Option<UncheckedMutPairInfo> {
+    let (first_stmt, first_call) = collect_unchecked_mut_let(&mcall.syntax().parent()?)?;
 
-    return Some(buf);
-}
+    if first_call.syntax() != mcall.syntax()
+        || first_stmt.syntax().parent()? != *unsafe_expr.stmt_list()?.syntax()
+    {
+        return None;
+    }
 
-pub fn generate_from_u32_expr_stmt(mcall: &CallExpr) -> Option<String> {
+    // Only the very next statement, anything in between may already use the first reference
+    let (second_stmt, second_call) =
+        collect_unchecked_mut_let(&first_stmt.syntax().next_sibling()?)?;
 
-    // Obtain the variable Expr that presents the string
-    let receiver = mcall.arg_list()?.args().nth(0)?;
+    let receiver = first_call.receiver()?.to_string();
 
-    let mut buf = String::new();
+    if second_call.receiver()?.to_string() != receiver {
+        return None;
+    }
 
-    format_to!(buf, "std::char::from_u32({}).unwrap()", receiver);
+    let first_index = first_call.arg_list()?.args().exactly_one().ok()?;
 
-    return Some(buf);
-}
+    let second_index = second_call.arg_list()?.args().exactly_one().ok()?;
 
-pub fn generate_let_from_u32(mcall: &CallExpr, let_expr: &LetStmt) -> Option<String> {
+    // Sub-slices may overlap in any way, and the same index hands out the same element twice
+    if matches!(first_index, ast::Expr::RangeExpr(_))
+        || matches!(second_index, ast::Expr::RangeExpr(_))
+        || first_index.to_string() == second_index.to_string()
+    {
+        return None;
+    }
 
-    // Obtain the variable Expr that presents the string
-    let receiver = mcall.arg_list()?.args().nth(0)?;
+    // The halves are bound in front of the block, where they stay in scope for the rest of the function
+    let body = unsafe_expr.syntax().ancestors().filter_map(ast::StmtList::cast).last()?;
 
-    let pat = let_expr.pat()?;
+    if check_ident_mentioned(body.syntax(), "lo") || check_ident_mentioned(body.syntax(), "hi") {
+        return None;
+    }
 
-    let mut buf = String::new();
+    return Some(UncheckedMutPairInfo {
+        receiver,
+        first_stmt,
+        second_stmt,
+        first_index,
+        second_index,
+    });
+}
 
-    format_to!(buf, "let {} = char::from_u32({}).unwrap();", pat, receiver);
+fn generate_split_let(let_expr: &LetStmt, elem: &str) -> Option<String> {
+    let mut buf = String::new();
 
-    buf.push('\n');
+    match let_expr.ty() {
+        Some(ty) => format_to!(buf, "let {}: {} = {};", let_expr.pat()?, ty, elem),
+        None => format_to!(buf, "let {} = {};", let_expr.pat()?, elem),
+    }
 
     return Some(buf);
 }
 
-fn convert_to_from_u32(acc: &mut Assists, target_expr: &SyntaxNode, unsafe_range: TextRange, unsafe_expr: &BlockExpr) -> Option<()> {
+fn generate_split_at_mut_branch(
+    info: &UncheckedMutPairInfo,
+    first_low: bool,
+    indent_level: IndentLevel,
+) -> Option<String> {
+    // "hi" starts at the larger index, the smaller one is still in "lo"
+    let (split, first_elem, second_elem) = match first_low {
+        true => (
+            &info.second_index,
+            format!("&mut lo[{}]", info.first_index),
+            String::from("&mut hi[0]"),
+        ),
+        false => (
+            &info.first_index,
+            String::from("&mut hi[0]"),
+            format!("&mut lo[{}]", info.second_index),
+        ),
+    };
 
-    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+    let mut buf = String::new();
 
-    if mcall.syntax().parent()?.kind() == STMT_LIST{
+    format_to!(buf, "let (lo, hi) = {}.split_at_mut({});\n", info.receiver, split);
+    format_to!(buf, "{}{}\n", indent_level, generate_split_let(&info.first_stmt, &first_elem)?);
+    format_to!(buf, "{}{}", indent_level, generate_split_let(&info.second_stmt, &second_elem)?);
+
+    return Some(buf);
+}
+
+fn generate_split_at_mut_pair(
+    info: &UncheckedMutPairInfo,
+    indent_level: IndentLevel,
+) -> Option<String> {
+    // Which element comes first is only known for literal indices
+    let first = collect_offset_literal(&Some(info.first_index.clone()))?;
+
+    let second = collect_offset_literal(&Some(info.second_index.clone()))?;
+
+    if first == second {
+        return None;
+    }
+
+    return generate_split_at_mut_branch(info, first < second, indent_level);
+}
+
+fn convert_to_split_at_mut_pair(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+
+    let info = collect_unchecked_mut_pair(&mcall, unsafe_expr)?;
+
+    let buf = generate_split_at_mut_pair(&info, unsafe_expr.indent_level())?;
+
+    let target_range = TextRange::new(
+        info.first_stmt.syntax().text_range().start(),
+        info.second_stmt.syntax().text_range().end(),
+    );
+
+    return reindent_expr_with_label(
+        unsafe_expr,
+        acc,
+        "Convert to split_at_mut",
+        target_range,
+        &buf,
+    );
+}
+
+fn collect_utf8_bytes_arg(mcall: &CallExpr) -> Option<String> {
+    // Copy the bytes argument verbatim, sub-slices like "&buf[2..10]" have to keep their range
+    let arg = mcall.arg_list()?.args().exactly_one().ok()?;
+
+    return Some(arg.syntax().text().to_string());
+}
+
+pub fn generate_from_utf8(
+    mcall: &CallExpr,
+    expr: &BinExpr,
+    mut_sign: bool,
+    string_sign: bool,
+) -> Option<String> {
+    let receiver = collect_utf8_bytes_arg(mcall)?;
+
+    let pat = expr.lhs()?;
+
+    let mut buf = String::new();
+
+    if string_sign {
+        format_to!(buf, "{} = String::from_utf8({}).unwrap();", pat, receiver);
+
+        buf.push('\n');
+
+        return Some(buf);
+    }
+
+    if !mut_sign {
+        format_to!(buf, "{} = std::str::from_utf8({}).unwrap();", pat, receiver);
+    } else {
+        format_to!(buf, "{} = std::str::from_utf8_mut({}).unwrap();", pat, receiver);
+    }
+
+    buf.push('\n');
+
+    return Some(buf);
+}
+
+pub fn generate_from_utf8_expr_stmt(
+    mcall: &CallExpr,
+    mut_sign: bool,
+    string_sign: bool,
+) -> Option<String> {
+    let receiver = collect_utf8_bytes_arg(mcall)?;
+
+    let mut buf = String::new();
+
+    if string_sign {
+        format_to!(buf, "String::from_utf8({}).unwrap();", receiver);
+
+        return Some(buf);
+    }
+
+    if !mut_sign {
+        format_to!(buf, "std::str::from_utf8({}).unwrap()", receiver);
+    } else {
+        format_to!(buf, "std::str::from_utf8_mut({}).unwrap()", receiver);
+    }
+
+    return Some(buf);
+}
+
+pub fn generate_let_from_utf8(
+    mcall: &CallExpr,
+    let_expr: &LetStmt,
+    mut_sign: bool,
+    string_sign: bool,
+) -> Option<String> {
+    let receiver = collect_utf8_bytes_arg(mcall)?;
+
+    let pat = let_expr.pat()?;
+
+    let mut buf = String::new();
+
+    if string_sign {
+        format_to!(buf, "{} = String::from_utf8({}).unwrap();", pat, receiver);
+
+        buf.push('\n');
+
+        return Some(buf);
+    }
+
+    if !mut_sign {
+        format_to!(buf, "let {} = std::str::from_utf8({}).unwrap();", pat, receiver);
+    } else {
+        format_to!(buf, "let {} = std::str::from_utf8_mut({}).unwrap();", pat, receiver);
+    }
+
+    buf.push('\n');
+
+    return Some(buf);
+}
+
+fn convert_to_from_utf8(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+    mut_sign: bool,
+    string_sign: bool,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    if mcall.syntax().parent()?.kind() == STMT_LIST{
         let target_expr = &mcall;
 
         let target_range = target_expr.syntax().parent()?.parent()?.text_range();
 
-        let buf = generate_from_u32_expr_stmt(&mcall)?;
+        let buf = generate_from_utf8_expr_stmt(&mcall, mut_sign, string_sign)?;
         
         if check_single_call_expr(&target_expr)? == true {
             // target_range = unsafe_range;
@@ -971,7 +2682,7 @@ fn convert_to_from_u32(acc: &mut Assists, target_expr: &SyntaxNode, unsafe_range
 
         let target_range = target_expr.syntax().parent()?.parent()?.text_range();
 
-        let buf = generate_from_u32_expr_stmt(&mcall)?;
+        let buf = generate_from_utf8_expr_stmt(&mcall, mut_sign, string_sign)?;
         
         if check_single_expr_stmt(&target_expr)? == true {
             // target_range = unsafe_range;
@@ -986,7 +2697,7 @@ fn convert_to_from_u32(acc: &mut Assists, target_expr: &SyntaxNode, unsafe_range
 
         let mut target_range = target_expr.syntax().parent()?.text_range();
 
-        let buf = generate_from_u32(&mcall, &target_expr)?;
+        let buf = generate_from_utf8(&mcall, &target_expr, mut_sign, string_sign)?;
         
         if check_single_bin_expr(&target_expr)? == true {
             target_range = unsafe_range;
@@ -998,7 +2709,7 @@ fn convert_to_from_u32(acc: &mut Assists, target_expr: &SyntaxNode, unsafe_range
 
     let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
 
-    let buf = generate_let_from_u32(&mcall, &let_expr)?;
+    let buf = generate_let_from_utf8(&mcall, &let_expr, mut_sign, string_sign)?;
 
     let mut target_range = let_expr.syntax().text_range();
     if check_single_let_expr(&let_expr) {
@@ -1010,249 +2721,774 @@ fn convert_to_from_u32(acc: &mut Assists, target_expr: &SyntaxNode, unsafe_range
     return reindent_expr(unsafe_expr, acc, target_range, &buf);
 }
 
-struct CpyNonOverlapInfo {
-    src_expr: IndexExpr,
-    dst_expr: IndexExpr,
-    count: String,
-}
-
-fn extract_index_expr(backward_list: impl Iterator<Item = SyntaxNode>, expr_variable: &String) -> Option<IndexExpr>{
-    for iter in backward_list {
-        if iter.kind() == LET_STMT && iter.to_string().contains(expr_variable) {
-            let let_expr = ast::LetStmt::cast(iter)?;
-            let expr = ast::IndexExpr::cast(let_expr.syntax().last_child()?.first_child()?)?;
-            return Some(expr);
-        }
-    }
-    return None;
-}
+pub fn generate_from_transmute(
+    mcall: &CallExpr,
+    let_expr: &LetStmt,
+    unsafe_expr: &BlockExpr,
+) -> Option<String> {
+    // Obtain the variable Expr that presents the string
+    let receiver = mcall.arg_list()?.args().nth(0)?;
 
-fn collect_cpy_nonoverlap_info(mcall: &CallExpr, unsafe_expr: &BlockExpr) -> Option<CpyNonOverlapInfo> {
+    let pat = let_expr.pat()?;
 
-    let src_expr;
+    let mut buf = String::new();
 
-    let dst_expr;
+    if let_expr.to_string().contains(&TargetTypes::String.to_string()) {
+        format_to!(buf, "let {} = str::from_utf8({}).unwrap();", pat, receiver);
+        buf.push('\n');
+        return Some(buf);
+    }
 
-    let mut backward_list_src = unsafe_expr.syntax().siblings(Direction::Prev);
+    if let_expr.to_string().contains(&TargetTypes::Bytes.to_string()) {
+        format_to!(buf, "let {} = {}.as_bytes();", pat, receiver);
+        buf.push('\n');
+        return Some(buf);
+    }
 
-    let mut backward_list_dst = unsafe_expr.syntax().siblings(Direction::Prev);
+    if let_expr.to_string().contains(&TargetTypes::U64.to_string()) {
+        format_to!(buf, "let {} = {}.to_bits();", pat, receiver);
+        buf.push('\n');
+        return Some(buf);
+    }
 
-    if unsafe_expr.syntax().parent()?.kind() != STMT_LIST {
-        backward_list_src = unsafe_expr.syntax().parent()?.siblings(Direction::Prev);
-        backward_list_dst = unsafe_expr.syntax().parent()?.siblings(Direction::Prev);
+    if let_expr.to_string().contains(&TargetTypes::F32.to_string()) {
+        format_to!(buf, "let {} = f32::from_bits({});", pat, receiver);
+        buf.push('\n');
+        return Some(buf);
     }
 
-    if mcall.arg_list()?.args().nth(0)?.syntax().kind() == PATH_EXPR {
-        src_expr = extract_index_expr(&mut backward_list_src, &mcall.arg_list()?.args().nth(0)?.to_string())?;
-    } else {
-        src_expr = ast::IndexExpr::cast(mcall.arg_list()?.args().nth(0)?.syntax().children().nth(0)?)?;
+    if let_expr.to_string().contains(&TargetTypes::F64.to_string()) {
+        format_to!(buf, "let {} = f64::from_bits({});", pat, receiver);
+        buf.push('\n');
+        return Some(buf);
     }
 
-    if mcall.arg_list()?.args().nth(1)?.syntax().kind() == PATH_EXPR {
-        dst_expr = extract_index_expr(&mut backward_list_dst, &mcall.arg_list()?.args().nth(1)?.to_string())?;
-    } else {
-        dst_expr = ast::IndexExpr::cast(mcall.arg_list()?.args().nth(1)?.syntax().children().nth(0)?)?;
+    if let_expr.to_string().contains(&TargetTypes::Char.to_string()) {
+        format_to!(buf, "let {} = char::from_u32({}).unwrap();", pat, receiver);
+        buf.push('\n');
+        return Some(buf);
     }
 
-    let count = mcall.arg_list()?.args().nth(2)?.to_string();
+    if let_expr.to_string().contains(&TargetTypes::U32.to_string()) {
+        let mut backward_list = unsafe_expr.syntax().siblings(Direction::Prev);
 
-    return Some(CpyNonOverlapInfo {src_expr, dst_expr, count});
+        if unsafe_expr.syntax().parent()?.kind() != STMT_LIST {
+            backward_list = unsafe_expr.syntax().parent()?.siblings(Direction::Prev);
+        }
+    
+        for backward_slice in backward_list {
+            let statement = backward_slice.to_string();
+            if statement.contains(&receiver.to_string()) && backward_slice.kind() == LET_STMT {
+                if statement.contains(&TargetTypes::Char.to_string()) {
+                    format_to!(buf, "let {} = {} as u32;", pat, receiver);
+                    buf.push('\n');
+                    return Some(buf);
+                }
+                if statement.contains(&TargetTypes::F32.to_string()) {
+                    format_to!(buf, "let {} = {}.to_bits();", pat, receiver);
+                    buf.push('\n');
+                    return Some(buf);
+                }
+            }
+        }
+    }
+    return None;
 }
 
-fn format_index_expr(expr_index: &IndexExpr, count: &String) -> Option<String> {
+fn transmute_convertion(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
+
+    let buf = generate_from_transmute(&mcall, &let_expr, &unsafe_expr)?;
+
+    let mut target_range = let_expr.syntax().text_range();
+    if check_single_let_expr(&let_expr) {
+        target_range = unsafe_range;
+        replace_source_code(acc, target_range, &buf);
+        return None;
+    }
 
-    let index = expr_index.index()?.to_string();
+    return reindent_expr(unsafe_expr, acc, target_range, &buf);
+}
 
-    let index_content = index.split("..");
+fn collect_transmute_call(target_expr: &SyntaxNode) -> Option<(CallExpr, ast::PathSegment)> {
+    let path_expr = ast::PathExpr::cast(target_expr.clone())?;
 
-    let vec: Vec<&str> = index_content.collect();
+    let path = path_expr.path()?;
 
-    let lhs = vec[0];
-    // let rhs = vec[1];
+    let segment = path.segment()?;
 
-    let mut buf = String::new();
+    if !UnsafePattern::TransmuteSameLayout.matches(&segment.name_ref()?.to_string()) {
+        return None;
+    }
 
-    if lhs.is_empty() {
-        format_to!(buf, "{}[{}..{}]", expr_index.base()?, lhs.to_string(), count);
-    } else {
-        format_to!(buf, "{}[{}..{} + {}]", expr_index.base()?, lhs.to_string(), lhs.to_string(), count);
+    if let Some(qualifier) = path.qualifier() {
+        let qualifier = qualifier.to_string();
+        if qualifier != "mem" && qualifier != "std::mem" && qualifier != "core::mem" {
+            return None;
+        }
     }
 
-    return Some(buf);
+    let mcall = path_expr.syntax().parent().and_then(ast::CallExpr::cast)?;
 
+    return Some((mcall, segment));
 }
 
-pub fn generate_copy_from_slice_string(src_expr: IndexExpr, dst_expr: IndexExpr, count: String) -> Option<String> {
-
-    let mut buf = String::new();
+fn collect_transmute_target_ty(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+    unsafe_expr: &BlockExpr,
+    dst_ty: Option<&ast::Type>,
+) -> Option<hir::Type> {
+    let ty = sema.type_of_expr(&ast::Expr::CallExpr(mcall.clone())).map(|info| info.original);
 
-    format_to!(buf, "{}.copy_from_slice(&{});", format_index_expr(&dst_expr, &count)?, format_index_expr(&src_expr, &count)?);
+    if let Some(ty) = ty.filter(|ty| !ty.is_unknown()) {
+        return Some(ty);
+    }
 
-    buf.push('\n');
+    // An unresolved "transmute" still has its target named by the turbofish or the "let"
+    if let Some(dst_ty) = dst_ty {
+        return sema.resolve_type(dst_ty);
+    }
 
-    return Some(buf);
+    let let_expr = match mcall.syntax().parent().and_then(ast::LetStmt::cast) {
+        Some(let_expr) => let_expr,
+        None => unsafe_expr.syntax().parent().and_then(ast::LetStmt::cast)?,
+    };
 
+    return sema.resolve_type(&let_expr.ty()?);
 }
 
-pub fn generate_copy_from_slice_format(mcall: &CallExpr, unsafe_expr: &BlockExpr) -> Option<String> {    
-
-    let CpyNonOverlapInfo { src_expr, dst_expr, count} = collect_cpy_nonoverlap_info(&mcall, &unsafe_expr)?;
+fn collect_builtin_width(builtin: &hir::BuiltinType) -> Option<String> {
+    // "u32" -> "32", "usize" and "isize" share "size"
+    return builtin.name().to_string().get(1..).map(|width| width.to_string());
+}
 
-    let buf = generate_copy_from_slice_string(src_expr, dst_expr, count)?;
+fn make_cast_operand(expr: &ast::Expr) -> String {
+    match expr {
+        ast::Expr::PathExpr(_)
+        | ast::Expr::Literal(_)
+        | ast::Expr::FieldExpr(_)
+        | ast::Expr::CallExpr(_)
+        | ast::Expr::MethodCallExpr(_)
+        | ast::Expr::ParenExpr(_) => return expr.to_string(),
+        _ => return format!("({})", expr),
+    }
+}
 
-    return Some(buf);
+struct TransmuteTypeInfo {
+    arg: ast::Expr,
+    src: hir::Type,
+    dst: hir::Type,
 }
 
-fn convert_to_copy_from_slice(acc: &mut Assists, target_expr: &SyntaxNode, unsafe_range: TextRange, unsafe_expr: &BlockExpr) -> Option<()> {
+fn collect_transmute_types(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+    unsafe_expr: &BlockExpr,
+) -> Option<TransmuteTypeInfo> {
+    let segment = ast::PathExpr::cast(mcall.expr()?.syntax().clone())?.path()?.segment()?;
 
-    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+    let type_args = collect_transmute_type_args(&segment);
 
-    let target_expr = mcall.syntax().parent().and_then(ast::ExprStmt::cast)?;
+    let arg = mcall.arg_list()?.args().exactly_one().ok()?;
 
-    let mut target_range = target_expr.syntax().text_range();
+    let src = match sema.type_of_expr(&arg).map(|info| info.original).filter(|ty| !ty.is_unknown())
+    {
+        Some(src) => src,
+        None => sema.resolve_type(&type_args.as_ref()?.0)?,
+    };
 
-    let buf = generate_copy_from_slice_format(&mcall, &unsafe_expr)?;
+    let dst = collect_transmute_target_ty(
+        sema,
+        mcall,
+        unsafe_expr,
+        type_args.as_ref().map(|(_, dst_ty)| dst_ty),
+    )?;
 
-    if check_single_expr(&target_expr) {
-        target_range = unsafe_range;
-        replace_source_code(acc, target_range, &buf);
+    if src.is_unknown() || dst.is_unknown() {
         return None;
     }
 
-    return reindent_expr(unsafe_expr, acc, target_range, &buf);
+    return Some(TransmuteTypeInfo { arg, src, dst });
 }
 
-pub fn generate_cstring_new_string(pat: String, input_argument: String, let_sign: bool) -> String {
+fn generate_transmute_cast(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+    unsafe_expr: &BlockExpr,
+) -> Option<String> {
+    let segment = ast::PathExpr::cast(mcall.expr()?.syntax().clone())?.path()?.segment()?;
+
+    // Spelled out identical types, lifetimes included, need no transmute at all
+    if let Some((src_ty, dst_ty)) = collect_transmute_type_args(&segment) {
+        if src_ty.to_string() == dst_ty.to_string() {
+            return Some(mcall.arg_list()?.args().exactly_one().ok()?.to_string());
+        }
+    }
+
+    let TransmuteTypeInfo { arg, src, dst } = collect_transmute_types(sema, mcall, unsafe_expr)?;
 
     let mut buf = String::new();
 
-    if let_sign {
-        format_to!(buf, "let {} = CString::new({}).unwrap();", pat, input_argument);
-    } else {
-        format_to!(buf, "{} = CString::new({}).unwrap();", pat, input_argument);
+    // Lifetimes are invisible in the inferred types, a reference may still be extended
+    if src == dst && !src.is_reference() {
+        format_to!(buf, "{}", arg);
+        return Some(buf);
     }
 
-    buf.push('\n');
+    let (src_builtin, dst_builtin) = (src.as_builtin()?, dst.as_builtin()?);
 
-    return buf;
+    if collect_builtin_width(&src_builtin)? != collect_builtin_width(&dst_builtin)? {
+        return None;
+    }
 
-}
+    let src_int = src_builtin.is_int() || src_builtin.is_uint();
 
-pub fn generate_cstring_new_format(pat: String, mcall: &CallExpr, let_sign: bool) -> Option<String> {    
+    let dst_int = dst_builtin.is_int() || dst_builtin.is_uint();
 
-    let input_argument = mcall.arg_list()?.args().nth(0)?.to_string();
+    if !src_int || !dst_int {
+        return None;
+    }
 
-    let buf = generate_cstring_new_string(pat, input_argument, let_sign);
+    format_to!(buf, "{} as {}", make_cast_operand(&arg), dst_builtin.name());
 
     return Some(buf);
 }
 
-fn check_single_bin_expr(target_expr: &BinExpr) -> Option<bool> {
+fn collect_float_bits_pair(
+    src: &hir::Type,
+    dst: &hir::Type,
+) -> Option<(hir::BuiltinType, hir::BuiltinType)> {
+    let (src_builtin, dst_builtin) = (src.as_builtin()?, dst.as_builtin()?);
 
-    // Check if the unsafe bloack only contains one expr
-    if target_expr.syntax().parent()?.prev_sibling().is_none() && target_expr.syntax().parent()?.next_sibling().is_none() {
-        return Some(true);
+    // Only f32 <-> u32 and f64 <-> u64, "from_bits" never takes a signed integer
+    if collect_builtin_width(&src_builtin)? != collect_builtin_width(&dst_builtin)? {
+        return None;
     }
-    return Some(false);
+
+    return Some((src_builtin, dst_builtin));
 }
 
-fn check_single_expr_stmt(target_expr: &ExprStmt) -> Option<bool> {
+fn generate_transmute_to_bits(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+    unsafe_expr: &BlockExpr,
+) -> Option<String> {
+    let TransmuteTypeInfo { arg, src, dst } = collect_transmute_types(sema, mcall, unsafe_expr)?;
 
-    // Check if the unsafe bloack only contains one expr
-    if target_expr.syntax().parent()?.prev_sibling().is_none() && target_expr.syntax().parent()?.next_sibling().is_none() {
-        return Some(true);
+    let (src_builtin, dst_builtin) = collect_float_bits_pair(&src, &dst)?;
+
+    if !src_builtin.is_float() || !dst_builtin.is_uint() {
+        return None;
     }
-    return Some(false);
-}
 
-fn check_single_call_expr(target_expr: &CallExpr) -> Option<bool> {
+    let mut buf = String::new();
 
-    // Check if the unsafe bloack only contains one expr
-    if target_expr.syntax().prev_sibling().is_none() && target_expr.syntax().next_sibling().is_none() {
-        return Some(true);
+    format_to!(buf, "{}.to_bits()", make_cast_operand(&arg));
+
+    return Some(buf);
+}
+
+fn generate_transmute_from_bits(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+    unsafe_expr: &BlockExpr,
+) -> Option<String> {
+    let TransmuteTypeInfo { arg, src, dst } = collect_transmute_types(sema, mcall, unsafe_expr)?;
+
+    let (src_builtin, dst_builtin) = collect_float_bits_pair(&src, &dst)?;
+
+    if !src_builtin.is_uint() || !dst_builtin.is_float() {
+        return None;
     }
-    return Some(false);
+
+    let mut buf = String::new();
+
+    format_to!(buf, "{}::from_bits({})", dst_builtin.name(), arg);
+
+    return Some(buf);
 }
 
-fn check_single_methodcall_expr(target_expr: &MethodCallExpr) -> Option<bool> {
+fn generate_transmute_bits(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+    unsafe_expr: &BlockExpr,
+) -> Option<String> {
+    if let Some(buf) = generate_transmute_to_bits(sema, mcall, unsafe_expr) {
+        return Some(buf);
+    }
 
-    // Check if the unsafe bloack only contains one expr
-    if target_expr.syntax().prev_sibling().is_none() && target_expr.syntax().next_sibling().is_none() {
-        return Some(true);
+    return generate_transmute_from_bits(sema, mcall, unsafe_expr);
+}
+
+fn generate_transmute_rewrite(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+    unsafe_expr: &BlockExpr,
+    bits_sign: bool,
+) -> Option<String> {
+    if bits_sign {
+        return generate_transmute_bits(sema, mcall, unsafe_expr);
     }
-    return Some(false);
+
+    if let Some(buf) = generate_transmute_cast(sema, mcall, unsafe_expr) {
+        return Some(buf);
+    }
+
+    return generate_transmute_discriminant(sema, mcall, unsafe_expr);
 }
 
-fn convert_to_cstring_new(acc: &mut Assists, target_expr: &SyntaxNode, unsafe_range: TextRange, unsafe_expr: &BlockExpr) -> Option<()> {
+fn generate_let_transmute_cast(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+    let_expr: &LetStmt,
+    unsafe_expr: &BlockExpr,
+    bits_sign: bool,
+) -> Option<String> {
+    let pat = let_expr.pat()?;
+
+    let mut buf = String::new();
 
-    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+    match let_expr.ty() {
+        Some(ty) => format_to!(
+            buf,
+            "let {}: {} = {};",
+            pat,
+            ty,
+            generate_transmute_rewrite(sema, mcall, unsafe_expr, bits_sign)?
+        ),
+        None => format_to!(
+            buf,
+            "let {} = {};",
+            pat,
+            generate_transmute_rewrite(sema, mcall, unsafe_expr, bits_sign)?
+        ),
+    }
 
-    if mcall.syntax().parent()?.kind() == BIN_EXPR {
-        let target_expr = mcall.syntax().parent().and_then(ast::BinExpr::cast)?;
+    return Some(buf);
+}
 
-        let mut target_range = target_expr.syntax().parent()?.text_range();
+fn convert_to_transmute_cast(
+    acc: &mut Assists,
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+    bits_sign: bool,
+) -> Option<()> {
+    let (mcall, _) = collect_transmute_call(target_expr)?;
 
-        let buf = generate_cstring_new_format(target_expr.lhs()?.to_string(), &mcall, false)?;
-        
-        if check_single_bin_expr(&target_expr)? == true {
-            target_range = unsafe_range;
-            replace_source_code(acc, target_range, &buf);
+    // "let y = unsafe { mem::transmute::<u32, i32>(x) };" or a function's tail expression
+    if mcall.syntax().parent()?.kind() == STMT_LIST {
+        if !check_single_call_expr(&mcall)? {
             return None;
         }
-        return reindent_expr(unsafe_expr, acc, target_range, &buf);
+
+        let buf = generate_transmute_rewrite(sema, &mcall, unsafe_expr, bits_sign)?;
+        replace_source_code(acc, unsafe_expr.syntax().text_range(), &buf);
+        return None;
     }
-        
-    let target_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
 
-    let mut target_range = target_expr.syntax().text_range();
+    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
 
-    let buf = generate_cstring_new_format(target_expr.pat()?.to_string(), &mcall, true)?;
+    let mut buf = generate_let_transmute_cast(sema, &mcall, &let_expr, unsafe_expr, bits_sign)?;
 
-    if check_single_let_expr(&target_expr) {
-        target_range = unsafe_range;
-        replace_source_code(acc, target_range, &buf);
+    if check_single_let_expr(&let_expr) {
+        replace_source_code(acc, unsafe_range, &buf);
         return None;
     }
 
-    return reindent_expr(unsafe_expr, acc, target_range, &buf);
+    buf.push('\n');
+
+    return reindent_expr(unsafe_expr, acc, let_expr.syntax().text_range(), &buf);
 }
 
-pub fn generate_bytes_len_string(pat: String, input_argument: String, let_sign: bool) -> String {
+fn generate_transmute_discriminant(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+    unsafe_expr: &BlockExpr,
+) -> Option<String> {
+    let TransmuteTypeInfo { arg, src, dst } = collect_transmute_types(sema, mcall, unsafe_expr)?;
+
+    let enum_def = match src.as_adt()? {
+        hir::Adt::Enum(enum_def) => enum_def,
+        _ => return None,
+    };
+
+    // Only a field-less enum casts with "as", and only to the integer its repr spells out
+    if enum_def.is_data_carrying(sema.db) {
+        return None;
+    }
+
+    let dst_builtin = dst.as_builtin()?;
+
+    if enum_def.repr_int(sema.db)? != dst_builtin {
+        return None;
+    }
 
     let mut buf = String::new();
 
-    if let_sign {
-        format_to!(buf, "let {} = {}.as_bytes().len();", pat, input_argument);
-    } else {
-        format_to!(buf, "{} = {}.as_bytes().len();", pat, input_argument);
+    format_to!(buf, "{} as {}", make_cast_operand(&arg), dst_builtin.name());
+
+    return Some(buf);
+}
+
+struct TransmuteEnumInfo {
+    pub arg: ast::Expr,
+    pub int_ty: String,
+    pub enum_name: String,
+    pub enum_def: hir::Enum,
+}
+
+fn collect_transmute_enum(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+    unsafe_expr: &BlockExpr,
+) -> Option<TransmuteEnumInfo> {
+    let TransmuteTypeInfo { arg, src, dst } = collect_transmute_types(sema, mcall, unsafe_expr)?;
+
+    let src_builtin = src.as_builtin()?;
+
+    if !src_builtin.is_int() && !src_builtin.is_uint() {
+        return None;
     }
 
-    buf.push('\n');
+    let enum_def = match dst.as_adt()? {
+        hir::Adt::Enum(enum_def) => enum_def,
+        _ => return None,
+    };
+
+    // A variant with fields has no integer that could stand for it
+    if enum_def.is_data_carrying(sema.db) {
+        return None;
+    }
+
+    let int_ty = src_builtin.name().to_string();
+
+    let enum_name = enum_def.name(sema.db).to_string();
+
+    return Some(TransmuteEnumInfo { arg, int_ty, enum_name, enum_def });
+}
+
+fn generate_enum_try_from(info: &TransmuteEnumInfo) -> String {
+    let mut buf = String::new();
+
+    format_to!(buf, "{}::try_from({}).unwrap()", info.enum_name, info.arg);
 
     return buf;
+}
+
+fn generate_let_enum_try_from(info: &TransmuteEnumInfo, let_expr: &LetStmt) -> Option<String> {
+    let pat = let_expr.pat()?;
 
+    let mut buf = String::new();
+
+    match let_expr.ty() {
+        Some(ty) => format_to!(buf, "let {}: {} = {};", pat, ty, generate_enum_try_from(info)),
+        None => format_to!(buf, "let {} = {};", pat, generate_enum_try_from(info)),
+    }
+
+    return Some(buf);
+}
+
+fn collect_enum_discriminants(enum_src: &ast::Enum) -> Option<Vec<(String, String)>> {
+    let mut discriminants = Vec::new();
+
+    for variant in enum_src.variant_list()?.variants() {
+        if variant.field_list().is_some() {
+            return None;
+        }
+
+        // Only literal discriminants can be used as match patterns
+        let discriminant = match variant.expr()? {
+            ast::Expr::Literal(literal) => literal.to_string(),
+            ast::Expr::PrefixExpr(prefix) if prefix.op_kind() == Some(ast::UnaryOp::Neg) => {
+                match prefix.expr()? {
+                    ast::Expr::Literal(literal) => format!("-{}", literal),
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+
+        discriminants.push((discriminant, variant.name()?.to_string()));
+    }
+
+    return Some(discriminants);
 }
 
-pub fn generate_bytes_len_format(pat: String, mcall: &CallExpr, let_sign: bool) -> Option<String> {    
+fn generate_enum_try_from_impl(enum_src: &ast::Enum, int_ty: &str) -> Option<String> {
+    let discriminants = collect_enum_discriminants(enum_src)?;
+
+    let enum_name = enum_src.name()?.to_string();
+
+    let indent = enum_src.indent_level();
+
+    let mut arms = String::new();
 
-    let input_argument = mcall.arg_list()?.args().nth(0)?.syntax().first_child()?.to_string();
+    for (discriminant, variant) in discriminants {
+        format_to!(
+            arms,
+            "{}            {} => Ok({}::{}),\n",
+            indent,
+            discriminant,
+            enum_name,
+            variant
+        );
+    }
+
+    let mut buf = String::new();
 
-    let buf = generate_bytes_len_string(pat, input_argument, let_sign);
+    format_to!(buf, "\n\n{}impl TryFrom<{}> for {} {{\n", indent, int_ty, enum_name);
+    format_to!(buf, "{}    type Error = {};\n\n", indent, int_ty);
+    format_to!(
+        buf,
+        "{}    fn try_from(value: {}) -> Result<Self, Self::Error> {{\n",
+        indent,
+        int_ty
+    );
+    format_to!(buf, "{}        match value {{\n", indent);
+    buf.push_str(&arms);
+    format_to!(buf, "{}            _ => Err(value),\n", indent);
+    format_to!(buf, "{}        }}\n", indent);
+    format_to!(buf, "{}    }}\n", indent);
+    format_to!(buf, "{}}}", indent);
 
     return Some(buf);
 }
 
+fn check_enum_try_from_impl(file: &SyntaxNode, enum_name: &str, int_ty: &str) -> bool {
+    let trait_name = format!("TryFrom<{}>", int_ty);
 
-fn convert_to_cstring_bytes_len(acc: &mut Assists, target_expr: &SyntaxNode, unsafe_range: TextRange, unsafe_expr: &BlockExpr) -> Option<()> {
-    
+    return file.descendants().filter_map(ast::Impl::cast).any(|imp| {
+        imp.trait_().map_or(false, |trait_| trait_.to_string().ends_with(&trait_name))
+            && imp.self_ty().map_or(false, |self_ty| self_ty.to_string() == enum_name)
+    });
+}
+
+fn convert_to_enum_try_from(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let (mcall, _) = collect_transmute_call(target_expr)?;
+
+    let info = collect_transmute_enum(&ctx.sema, &mcall, unsafe_expr)?;
+
+    // "let e = unsafe { mem::transmute::<u8, MyEnum>(n) };" or the "let" alone in the block
+    let (target_range, buf) = if mcall.syntax().parent()?.kind() == STMT_LIST {
+        if !check_single_call_expr(&mcall)? {
+            return None;
+        }
+
+        (unsafe_expr.syntax().text_range(), generate_enum_try_from(&info))
+    } else {
+        let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
+
+        if !check_single_let_expr(&let_expr) {
+            return None;
+        }
+
+        (unsafe_range, generate_let_enum_try_from(&info, &let_expr)?)
+    };
+
+    let enum_src = info.enum_def.source(ctx.db())?;
+
+    // The impl goes right after the enum, which has to be written in this file
+    if enum_src.file_id.is_macro() || enum_src.file_id.original_file(ctx.db()) != ctx.file_id() {
+        return None;
+    }
+
+    let file = enum_src.value.syntax().ancestors().last()?;
+
+    let impl_buf = match check_enum_try_from_impl(&file, &info.enum_name, &info.int_ty) {
+        true => None,
+        false => Some(generate_enum_try_from_impl(&enum_src.value, &info.int_ty)?),
+    };
+
+    let impl_position = enum_src.value.syntax().text_range().end();
+
+    acc.add(
+        AssistId("convert_unsafe_to_safe", AssistKind::RefactorRewrite),
+        "Convert Unsafe to Safe",
+        target_range,
+        |edit| {
+            edit.replace(target_range, buf);
+            if let Some(impl_buf) = impl_buf {
+                edit.insert(impl_position, impl_buf);
+            }
+        },
+    );
+
+    return None;
+}
+
+struct UnalignedAccessInfo {
+    ptr: CpyAddOffsetInfo,
+    int_ty: String,
+    width: usize,
+}
+
+fn collect_int_byte_width(int_ty: &str) -> Option<usize> {
+    // "usize" and "isize" depend on the target, their byte arrays have no fixed length
+    match int_ty {
+        "u8" | "i8" => return Some(1),
+        "u16" | "i16" => return Some(2),
+        "u32" | "i32" | "f32" => return Some(4),
+        "u64" | "i64" | "f64" => return Some(8),
+        "u128" | "i128" => return Some(16),
+        _ => return None,
+    }
+}
+
+fn collect_unaligned_access(mcall: &CallExpr) -> Option<UnalignedAccessInfo> {
+    // "buf.as_ptr().add(off) as *const u32", the cast names the integer being read
+    let cast_expr = match mcall.arg_list()?.args().next()? {
+        ast::Expr::CastExpr(cast_expr) => cast_expr,
+        _ => return None,
+    };
+
+    let int_ty = match cast_expr.ty()? {
+        ast::Type::PtrType(ptr_ty) => ptr_ty.ty()?.to_string(),
+        _ => return None,
+    };
+
+    let width = collect_int_byte_width(&int_ty)?;
+
+    // The pointer has to lead back to a buffer in this function
+    let ptr = collect_traced_ptr(&cast_expr.expr()?, mcall.syntax())?;
+
+    return Some(UnalignedAccessInfo { ptr, int_ty, width });
+}
+
+fn make_unaligned_range(info: &UnalignedAccessInfo) -> ast::Expr {
+    let width = make::expr_literal(&info.width.to_string()).into();
+
+    return make::expr_index(
+        make_postfix_operand(info.ptr.base_expr.clone()),
+        make_offset_range(info.ptr.offset.clone(), width),
+    );
+}
+
+fn generate_unaligned_read(mcall: &CallExpr) -> Option<String> {
+    let info = collect_unaligned_access(mcall)?;
+
+    let mut buf = String::new();
+
+    format_to!(
+        buf,
+        "{}::from_ne_bytes({}.try_into().unwrap())",
+        info.int_ty,
+        make_unaligned_range(&info)
+    );
+
+    return Some(buf);
+}
+
+pub fn generate_bytes_to_convert(
+    mcall: &CallExpr,
+    unsafe_expr: &BlockExpr,
+    let_sign: bool,
+) -> Option<String> {
+    let mut buf = String::new();
+
+    let read = generate_unaligned_read(mcall);
+
+    // "let x = unsafe { ptr::read_unaligned(..) };"
+    if let Some(let_expr) = unsafe_expr.syntax().parent().and_then(ast::LetStmt::cast) {
+        match let_expr.ty() {
+            Some(ty) => format_to!(buf, "let {}: {} = {};", let_expr.pat()?, ty, read?),
+            None => format_to!(buf, "let {} = {};", let_expr.pat()?, read?),
+        }
+        return Some(buf);
+    }
+
+    if !let_sign {
+        let target_expr = mcall.syntax().parent().and_then(ast::BinExpr::cast)?;
+        format_to!(buf, "{} = {};", target_expr.lhs()?, read?);
+    } else {
+        let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
+        format_to!(buf, "let {} = {};", let_expr.pat()?, read?);
+    }
+
+    buf.push('\n');
+
+    return Some(buf);
+}
+
+fn generate_unaligned_write(mcall: &CallExpr) -> Option<String> {
+    let info = collect_unaligned_access(mcall)?;
+
+    let value = mcall.arg_list()?.args().nth(1)?;
+
+    let mut buf = String::new();
+
+    format_to!(
+        buf,
+        "{}.copy_from_slice(&{}.to_ne_bytes());",
+        make_unaligned_range(&info),
+        make_cast_operand(&value)
+    );
+
+    return Some(buf);
+}
+
+fn convert_to_copy_ne_bytes(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    let expr_stmt = mcall.syntax().parent().and_then(ast::ExprStmt::cast)?;
+
+    let mut buf = generate_unaligned_write(&mcall)?;
+
+    if check_single_expr(&expr_stmt) {
+        replace_source_code(acc, unsafe_range, &buf);
+        return None;
+    }
+
+    buf.push('\n');
+
+    return reindent_expr(unsafe_expr, acc, expr_stmt.syntax().text_range(), &buf);
+}
+
+fn convert_to_from_ne_bytes(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
     let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
 
+    if let Some(let_expr) = unsafe_expr.syntax().parent().and_then(ast::LetStmt::cast) {
+        if mcall.syntax().parent()?.kind() != STMT_LIST || !check_single_call_expr(&mcall)? {
+            return None;
+        }
+
+        let buf = generate_bytes_to_convert(&mcall, unsafe_expr, true)?;
+        replace_source_code(acc, let_expr.syntax().text_range(), &buf);
+        return None;
+    }
+
     if mcall.syntax().parent()?.kind() == BIN_EXPR {
+
         let target_expr = mcall.syntax().parent().and_then(ast::BinExpr::cast)?;
 
         let mut target_range = target_expr.syntax().parent()?.text_range();
 
-        let buf = generate_bytes_len_format(target_expr.lhs()?.to_string(), &mcall, false)?;
+        let buf = generate_bytes_to_convert(&mcall, unsafe_expr, false)?;
         
         if check_single_bin_expr(&target_expr)? == true {
             target_range = unsafe_range;
@@ -1261,14 +3497,13 @@ fn convert_to_cstring_bytes_len(acc: &mut Assists, target_expr: &SyntaxNode, uns
         }
         return reindent_expr(unsafe_expr, acc, target_range, &buf);
     }
-        
-    let target_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
 
-    let mut target_range = target_expr.syntax().text_range();
+    let buf = generate_bytes_to_convert(&mcall, unsafe_expr, true)?;
 
-    let buf = generate_bytes_len_format(target_expr.pat()?.to_string(), &mcall, true)?;
+    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
 
-    if check_single_let_expr(&target_expr) {
+    let mut target_range = let_expr.syntax().text_range();
+    if check_single_let_expr(&let_expr) {
         target_range = unsafe_range;
         replace_source_code(acc, target_range, &buf);
         return None;
@@ -1277,1054 +3512,12815 @@ fn convert_to_cstring_bytes_len(acc: &mut Assists, target_expr: &SyntaxNode, uns
     return reindent_expr(unsafe_expr, acc, target_range, &buf);
 }
 
-fn uninitialized_vec_analysis(target_expr: &SyntaxNode, unsafe_expr: &BlockExpr) -> Option<bool> {
-    // static analysis on unsafe expr's ancestors() and descendants()
-    for backward_slice in unsafe_expr.syntax().parent()?.siblings(Direction::Prev) {
-        if backward_slice.to_string().contains(&UnsafePattern::SetVecCapacity.to_string()) ||
-            backward_slice.to_string().contains(&UnsafePattern::ReserveVec.to_string()) {
+pub fn generate_from_u32(mcall: &CallExpr, expr: &BinExpr) -> Option<String> {
+
+    // Obtain the variable Expr that presents the string
+    let receiver = mcall.arg_list()?.args().nth(0)?;
 
-                let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+    let pat = expr.lhs()?;
 
-                let receiver = mcall.receiver()?;
+    let mut buf = String::new();
 
-                for forward_slice in unsafe_expr.syntax().parent()?.siblings(Direction::Next) {
-                    if forward_slice.to_string().contains(&receiver.to_string()) 
-                        && forward_slice.to_string().contains(&UnsafePattern::WriteVec.to_string()) {
-                        return  Some(false);
-                    }
+    format_to!(buf, "{} = char::from_u32({}).unwrap();", pat, receiver);
 
-                }
-            return Some(true);
-        }
+    buf.push('\n');
+
+    return Some(buf);
+}
+
+pub fn generate_from_u32_expr_stmt(mcall: &CallExpr) -> Option<String> {
+
+    // Obtain the variable Expr that presents the string
+    let receiver = mcall.arg_list()?.args().nth(0)?;
+
+    let mut buf = String::new();
+
+    format_to!(buf, "std::char::from_u32({}).unwrap()", receiver);
+
+    return Some(buf);
+}
+
+pub fn generate_let_from_u32(mcall: &CallExpr, let_expr: &LetStmt) -> Option<String> {
+
+    // Obtain the variable Expr that presents the string
+    let receiver = mcall.arg_list()?.args().nth(0)?;
+
+    let pat = let_expr.pat()?;
+
+    let mut buf = String::new();
+
+    format_to!(buf, "let {} = char::from_u32({}).unwrap();", pat, receiver);
+
+    buf.push('\n');
+
+    return Some(buf);
+}
+
+fn convert_to_from_u32(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    if mcall.syntax().parent()?.kind() == STMT_LIST{
+        let target_expr = &mcall;
+
+        let target_range = target_expr.syntax().parent()?.parent()?.text_range();
+
+        let buf = generate_from_u32_expr_stmt(&mcall)?;
+        
+        if check_single_call_expr(&target_expr)? == true {
+            // target_range = unsafe_range;
+            replace_source_code(acc, target_range, &buf);
+            return None;
+        }
+        return reindent_expr(unsafe_expr, acc, target_range, &buf);
+    }
+
+    if mcall.syntax().parent()?.kind() == EXPR_STMT {
+        let target_expr = mcall.syntax().parent().and_then(ast::ExprStmt::cast)?;
+
+        let target_range = target_expr.syntax().parent()?.parent()?.text_range();
+
+        let buf = generate_from_u32_expr_stmt(&mcall)?;
+        
+        if check_single_expr_stmt(&target_expr)? == true {
+            // target_range = unsafe_range;
+            replace_source_code(acc, target_range, &buf);
+            return None;
+        }
+        return reindent_expr(unsafe_expr, acc, target_range, &buf);
+    }
+
+    if mcall.syntax().parent()?.kind() == BIN_EXPR {
+        let target_expr = mcall.syntax().parent().and_then(ast::BinExpr::cast)?;
+
+        let mut target_range = target_expr.syntax().parent()?.text_range();
+
+        let buf = generate_from_u32(&mcall, &target_expr)?;
+        
+        if check_single_bin_expr(&target_expr)? == true {
+            target_range = unsafe_range;
+            replace_source_code(acc, target_range, &buf);
+            return None;
+        }
+        return reindent_expr(unsafe_expr, acc, target_range, &buf);
+    }
+
+    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
+
+    let buf = generate_let_from_u32(&mcall, &let_expr)?;
+
+    let mut target_range = let_expr.syntax().text_range();
+    if check_single_let_expr(&let_expr) {
+        target_range = unsafe_range;
+        replace_source_code(acc, target_range, &buf);
+        return None;
+    }
+
+    return reindent_expr(unsafe_expr, acc, target_range, &buf);
+}
+
+fn generate_zeroed_scalar(
+    sema: &Semantics<'_, RootDatabase>,
+    ty: &hir::Type,
+    suffix_sign: bool,
+    default_sign: bool,
+    scope: &SyntaxNode,
+) -> Option<String> {
+    let mut buf = String::new();
+
+    if let Some(builtin) = ty.as_builtin() {
+        // Array elements carry the type suffix since the ascription is dropped
+        let suffix = if suffix_sign { builtin.name().to_string() } else { String::new() };
+
+        if builtin.is_int() || builtin.is_uint() {
+            format_to!(buf, "0{}", suffix);
+        } else if builtin.is_float() {
+            format_to!(buf, "0.0{}", suffix);
+        } else if builtin.is_bool() {
+            buf.push_str("false");
+        } else if builtin.is_char() {
+            buf.push_str("'\\0'");
+        } else {
+            return None;
+        }
+
+        return Some(buf);
+    }
+
+    // Only plain scalars can be repeated in an array initializer
+    if suffix_sign || !default_sign {
+        return None;
+    }
+
+    let adt = ty.as_adt()?;
+
+    // A zeroed NonZero value is undefined behavior and has no safe equivalent
+    if adt.name(sema.db).to_string().starts_with("NonZero") {
+        return None;
+    }
+
+    let krate = sema.scope(scope)?.krate();
+
+    let default_trait = FamousDefs(sema, krate).core_default_Default()?;
+
+    if ty.impls_trait(sema.db, default_trait, &[]) {
+        buf.push_str("Default::default()");
+        return Some(buf);
+    }
+
+    return None;
+}
+
+fn generate_zeroed_value(
+    sema: &Semantics<'_, RootDatabase>,
+    ty: &ast::Type,
+    suffix_sign: bool,
+    default_sign: bool,
+) -> Option<String> {
+    let mut buf = String::new();
+
+    if let ast::Type::ArrayType(array_ty) = ty {
+        let elem = generate_zeroed_value(sema, &array_ty.ty()?, true, default_sign)?;
+        format_to!(buf, "[{}; {}]", elem, array_ty.expr()?);
+        return Some(buf);
+    }
+
+    let hir_ty = sema.resolve_type(ty)?;
+
+    return generate_zeroed_scalar(sema, &hir_ty, suffix_sign, default_sign, ty.syntax());
+}
+
+fn collect_init_let(call: &SyntaxNode, unsafe_expr: &BlockExpr) -> Option<LetStmt> {
+    // "let x: T = mem::zeroed();" inside of the "unsafe" block
+    if let Some(let_expr) = call.parent().and_then(ast::LetStmt::cast) {
+        return Some(let_expr);
+    }
+
+    // "let x: T = unsafe { mem::zeroed() };" where the call is the whole block
+    let stmt_list = unsafe_expr.stmt_list()?;
+
+    if stmt_list.statements().next().is_some() || stmt_list.tail_expr()?.syntax() != call {
+        return None;
+    }
+
+    return unsafe_expr.syntax().parent().and_then(ast::LetStmt::cast);
+}
+
+fn collect_zeroed_let(mcall: &CallExpr, unsafe_expr: &BlockExpr) -> Option<LetStmt> {
+    return collect_init_let(mcall.syntax(), unsafe_expr);
+}
+
+fn collect_assume_init_let(mcall: &MethodCallExpr, unsafe_expr: &BlockExpr) -> Option<LetStmt> {
+    return collect_init_let(mcall.syntax(), unsafe_expr);
+}
+
+fn generate_let_zeroed(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+    let_expr: &LetStmt,
+) -> Option<String> {
+    return generate_let_zero_init(sema, mcall, let_expr, true);
+}
+
+fn generate_let_uninitialized(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+    let_expr: &LetStmt,
+) -> Option<String> {
+    // Types beyond plain scalars and arrays need "MaybeUninit", which is not auto-applied
+    return generate_let_zero_init(sema, mcall, let_expr, false);
+}
+
+fn generate_let_zero_init(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+    let_expr: &LetStmt,
+    default_sign: bool,
+) -> Option<String> {
+    if mcall.arg_list()?.args().next().is_some() {
+        return None;
+    }
+
+    let pat = let_expr.pat()?;
+
+    let mut buf = String::new();
+
+    match let_expr.ty() {
+        Some(ast::Type::ArrayType(array_ty)) => {
+            let value =
+                generate_zeroed_value(sema, &ast::Type::ArrayType(array_ty), false, default_sign)?;
+            format_to!(buf, "let {} = {};", pat, value);
+        }
+        Some(ty) => {
+            let value = generate_zeroed_value(sema, &ty, false, default_sign)?;
+            format_to!(buf, "let {}: {} = {};", pat, ty, value);
+        }
+        None => {
+            // Fall back to the inferred type when there is no ascription
+            let ty = sema.type_of_expr(&ast::Expr::CallExpr(mcall.clone()))?.original;
+            let value = generate_zeroed_scalar(sema, &ty, false, default_sign, mcall.syntax())?;
+            format_to!(buf, "let {}: {} = {};", pat, ty.display(sema.db), value);
+        }
+    }
+
+    return Some(buf);
+}
+
+fn convert_to_zeroed_value(
+    acc: &mut Assists,
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    let let_expr = collect_zeroed_let(&mcall, unsafe_expr)?;
+
+    let mut buf = generate_let_zeroed(sema, &mcall, &let_expr)?;
+
+    // The whole let statement is the initializer's owner, replace it in place
+    if unsafe_expr.syntax().parent()?.kind() == LET_STMT {
+        replace_source_code(acc, unsafe_range, &buf);
+        return None;
+    }
+
+    buf.push('\n');
+
+    let mut target_range = let_expr.syntax().text_range();
+    if check_single_let_expr(&let_expr) {
+        target_range = unsafe_range;
+        replace_source_code(acc, target_range, &buf);
+        return None;
+    }
+
+    return reindent_expr(unsafe_expr, acc, target_range, &buf);
+}
+
+fn convert_to_initialized_value(
+    acc: &mut Assists,
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let label = "Replace deprecated mem::uninitialized";
+
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    let let_expr = collect_zeroed_let(&mcall, unsafe_expr)?;
+
+    let mut buf = generate_let_uninitialized(sema, &mcall, &let_expr)?;
+
+    if unsafe_expr.syntax().parent()?.kind() == LET_STMT {
+        replace_source_code_with_label(acc, label, unsafe_range, &buf);
+        return None;
+    }
+
+    buf.push('\n');
+
+    let mut target_range = let_expr.syntax().text_range();
+    if check_single_let_expr(&let_expr) {
+        target_range = unsafe_range;
+        replace_source_code_with_label(acc, label, target_range, &buf);
+        return None;
+    }
+
+    return reindent_expr_with_label(unsafe_expr, acc, label, target_range, &buf);
+}
+
+fn check_uninit_call(receiver: &ast::Expr) -> Option<bool> {
+    let call = match receiver {
+        ast::Expr::CallExpr(call) => call,
+        _ => return Some(false),
+    };
+
+    if call.arg_list()?.args().next().is_some() {
+        return Some(false);
+    }
+
+    let path = match call.expr()? {
+        ast::Expr::PathExpr(path_expr) => path_expr.path()?,
+        _ => return Some(false),
+    };
+
+    return Some(path.segment()?.name_ref()?.text() == "uninit");
+}
+
+fn generate_let_assume_init(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &MethodCallExpr,
+    let_expr: &LetStmt,
+) -> Option<String> {
+    if mcall.arg_list()?.args().next().is_some() || !check_uninit_call(&mcall.receiver()?)? {
+        return None;
+    }
+
+    // Only arrays get a direct initializer, other types still need "MaybeUninit"
+    let array_ty = match let_expr.ty()? {
+        ast::Type::ArrayType(array_ty) => array_ty,
+        _ => return None,
+    };
+
+    // Zero is a valid value for integers and floats only
+    let elem_ty = sema.resolve_type(&array_ty.ty()?)?;
+    let builtin = elem_ty.as_builtin()?;
+
+    if !(builtin.is_int() || builtin.is_uint() || builtin.is_float()) {
+        return None;
+    }
+
+    let pat = let_expr.pat()?;
+
+    let mut buf = String::new();
+
+    let value = generate_zeroed_value(sema, &ast::Type::ArrayType(array_ty), false, false)?;
+    format_to!(buf, "let {} = {};", pat, value);
+
+    return Some(buf);
+}
+
+fn convert_to_array_initialization(
+    acc: &mut Assists,
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+
+    let let_expr = collect_assume_init_let(&mcall, unsafe_expr)?;
+
+    let mut buf = generate_let_assume_init(sema, &mcall, &let_expr)?;
+
+    if unsafe_expr.syntax().parent()?.kind() == LET_STMT {
+        replace_source_code(acc, unsafe_range, &buf);
+        return None;
+    }
+
+    buf.push('\n');
+
+    let mut target_range = let_expr.syntax().text_range();
+    if check_single_let_expr(&let_expr) {
+        target_range = unsafe_range;
+        replace_source_code(acc, target_range, &buf);
+        return None;
+    }
+
+    return reindent_expr(unsafe_expr, acc, target_range, &buf);
+}
+
+fn check_simple_write_arg(arg: &ast::Expr) -> bool {
+    return matches!(arg, ast::Expr::Literal(_) | ast::Expr::PathExpr(_));
+}
+
+fn check_ident_mentioned(node: &SyntaxNode, name: &str) -> bool {
+    return node
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .any(|token| token.kind() == IDENT && token.text() == name);
+}
+
+fn collect_maybe_uninit_write(mcall: &MethodCallExpr, let_expr: &LetStmt) -> Option<ExprStmt> {
+    // Only a plain local can be proven initialized by the backward scan
+    let receiver = match mcall.receiver()? {
+        ast::Expr::PathExpr(path_expr) => path_expr,
+        _ => return None,
+    };
+
+    let mut skipped = Vec::new();
+
+    for backward_slice in let_expr.syntax().siblings(Direction::Prev).skip(1) {
+        if !check_ident_mentioned(&backward_slice, &receiver.to_string()) {
+            skipped.push(backward_slice);
+            continue;
+        }
+
+        // The closest statement touching the receiver has to be the "write" itself
+        let write_stmt = ast::ExprStmt::cast(backward_slice)?;
+
+        let write_call = match write_stmt.expr()? {
+            ast::Expr::MethodCallExpr(write_call) => write_call,
+            _ => return None,
+        };
+
+        if !UnsafePattern::WriteVec.matches(&write_call.name_ref()?.text())
+            || write_call.receiver()?.to_string() != receiver.to_string()
+        {
+            return None;
+        }
+
+        let arg = write_call.arg_list()?.args().exactly_one().ok()?;
+
+        if !check_simple_write_arg(&arg) {
+            return None;
+        }
+
+        // Moving the "write" down must not skip over a change to the written value
+        if skipped.iter().any(|stmt| check_ident_mentioned(stmt, &arg.to_string())) {
+            return None;
+        }
+
+        return Some(write_stmt);
+    }
+
+    return None;
+}
+
+fn generate_let_assume_init_ref(
+    mcall: &MethodCallExpr,
+    let_expr: &LetStmt,
+    write_stmt: &ExprStmt,
+    mut_sign: bool,
+) -> Option<String> {
+    if mcall.arg_list()?.args().next().is_some() {
+        return None;
+    }
+
+    // "MaybeUninit::write" already hands back a reference to the written value
+    let write_call = write_stmt.expr()?;
+
+    let pat = let_expr.pat()?;
+
+    let mut buf = String::new();
+
+    let ref_sign = if mut_sign { "" } else { "&*" };
+
+    match let_expr.ty() {
+        Some(ty) => format_to!(buf, "let {}: {} = {}{};", pat, ty, ref_sign, write_call),
+        None => format_to!(buf, "let {} = {}{};", pat, ref_sign, write_call),
+    }
+
+    return Some(buf);
+}
+
+fn convert_to_written_reference(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+    mut_sign: bool,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+
+    // Only "let r = unsafe { m.assume_init_ref() };" is rewritten
+    if unsafe_expr.syntax().parent()?.kind() != LET_STMT {
+        return None;
+    }
+
+    let let_expr = collect_assume_init_let(&mcall, unsafe_expr)?;
+
+    let write_stmt = collect_maybe_uninit_write(&mcall, &let_expr)?;
+
+    let buf = generate_let_assume_init_ref(&mcall, &let_expr, &write_stmt, mut_sign)?;
+
+    // Drop the indentation in front of the "write" along with the statement
+    let mut write_range = write_stmt.syntax().text_range();
+    if let Some(whitespace) =
+        write_stmt.syntax().prev_sibling_or_token().filter(|it| it.kind() == WHITESPACE)
+    {
+        write_range = TextRange::new(whitespace.text_range().start(), write_range.end());
+    }
+
+    delet_replace_source_code(acc, unsafe_range, write_range, &buf);
+
+    return None;
+}
+
+fn generate_swap(mcall: &MethodCallExpr) -> Option<String> {
+    let receiver = mcall.receiver()?;
+
+    let (first, second) = mcall.arg_list()?.args().collect_tuple()?;
+
+    let mut buf = String::new();
+
+    format_to!(buf, "{}.swap({}, {})", receiver, first, second);
+
+    return Some(buf);
+}
+
+fn convert_to_swap(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+
+    let mut buf = generate_swap(&mcall)?;
+
+    // "unsafe { s.swap_unchecked(i, j) }" where the call is the tail expression
+    if mcall.syntax().parent()?.kind() == STMT_LIST {
+        if check_single_methodcall_expr(&mcall)? {
+            replace_source_code(acc, unsafe_expr.syntax().text_range(), &buf);
+            return None;
+        }
+
+        buf.push_str(";\n");
+        return reindent_expr(unsafe_expr, acc, mcall.syntax().text_range(), &buf);
+    }
+
+    let expr_stmt = mcall.syntax().parent().and_then(ast::ExprStmt::cast)?;
+
+    buf.push(';');
+
+    if check_single_expr(&expr_stmt) {
+        replace_source_code(acc, unsafe_range, &buf);
+        return None;
+    }
+
+    buf.push('\n');
+
+    return reindent_expr(unsafe_expr, acc, expr_stmt.syntax().text_range(), &buf);
+}
+
+fn generate_cstr_from_bytes(mcall: &CallExpr) -> Option<String> {
+    let bytes = mcall.arg_list()?.args().exactly_one().ok()?;
+
+    let mut buf = String::new();
+
+    // The checked constructor also rejects interior nul bytes, not only a missing terminator
+    format_to!(buf, "CStr::from_bytes_with_nul({}).expect(\"missing nul terminator\")", bytes);
+
+    return Some(buf);
+}
+
+fn generate_let_cstr_from_bytes(mcall: &CallExpr, let_expr: &LetStmt) -> Option<String> {
+    let pat = let_expr.pat()?;
+
+    let mut buf = String::new();
+
+    match let_expr.ty() {
+        Some(ty) => format_to!(buf, "let {}: {} = {};", pat, ty, generate_cstr_from_bytes(mcall)?),
+        None => format_to!(buf, "let {} = {};", pat, generate_cstr_from_bytes(mcall)?),
+    }
+
+    return Some(buf);
+}
+
+fn convert_to_cstr_from_bytes(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    // "let s = unsafe { CStr::from_bytes_with_nul_unchecked(bytes) };"
+    if mcall.syntax().parent()?.kind() == STMT_LIST {
+        if !check_single_call_expr(&mcall)? {
+            return None;
+        }
+
+        let buf = generate_cstr_from_bytes(&mcall)?;
+        replace_source_code(acc, unsafe_expr.syntax().text_range(), &buf);
+        return None;
+    }
+
+    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
+
+    let mut buf = generate_let_cstr_from_bytes(&mcall, &let_expr)?;
+
+    if check_single_let_expr(&let_expr) {
+        replace_source_code(acc, unsafe_range, &buf);
+        return None;
+    }
+
+    buf.push('\n');
+
+    return reindent_expr(unsafe_expr, acc, let_expr.syntax().text_range(), &buf);
+}
+
+fn generate_slice_from_ref(mcall: &CallExpr) -> Option<String> {
+    let path = match mcall.expr()? {
+        ast::Expr::PathExpr(path_expr) => path_expr.path()?,
+        _ => return None,
+    };
+
+    let mut_sign = match path.segment()?.name_ref()?.text().as_str() {
+        "from_raw_parts" => false,
+        "from_raw_parts_mut" => true,
+        _ => return None,
+    };
+
+    if path.qualifier().map_or(false, |qualifier| {
+        qualifier
+            .segment()
+            .and_then(|segment| segment.name_ref())
+            .map_or(true, |name| name.text() != "slice")
+    }) {
+        return None;
+    }
+
+    let (ptr, len) = mcall.arg_list()?.args().collect_tuple()?;
+
+    // Only a single element is a "from_ref"/"from_mut", any other length needs the real buffer
+    if len.to_string() != "1" {
+        return None;
+    }
+
+    // "&x as *const T" is the same pointer as the coerced "&x"
+    let ptr = match ptr {
+        ast::Expr::CastExpr(cast_expr) => cast_expr.expr()?,
+        ptr => ptr,
+    };
+
+    let place = match ptr {
+        ast::Expr::RefExpr(ref_expr) if ref_expr.mut_token().is_some() == mut_sign => {
+            ref_expr.to_string()
+        }
+        // "v.as_ptr()" points at the first element, which indexing borrows and bounds-checks
+        ast::Expr::MethodCallExpr(ptr_call) if ptr_call.arg_list()?.args().next().is_none() => {
+            match (ptr_call.name_ref()?.text().as_str(), mut_sign) {
+                ("as_ptr", false) => format!("&{}[0]", make_postfix_operand(ptr_call.receiver()?)),
+                ("as_mut_ptr", true) => {
+                    format!("&mut {}[0]", make_postfix_operand(ptr_call.receiver()?))
+                }
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+
+    let name = if mut_sign { "from_mut" } else { "from_ref" };
+
+    let mut buf = String::new();
+
+    // The qualifier is kept as written, a bare imported "from_raw_parts" gets the full path
+    match path.qualifier() {
+        Some(qualifier) => format_to!(buf, "{}::{}({})", qualifier, name, place),
+        None => format_to!(buf, "std::slice::{}({})", name, place),
+    }
+
+    return Some(buf);
+}
+
+fn generate_let_slice_from_ref(mcall: &CallExpr, let_expr: &LetStmt) -> Option<String> {
+    let pat = let_expr.pat()?;
+
+    let mut buf = String::new();
+
+    match let_expr.ty() {
+        Some(ty) => format_to!(buf, "let {}: {} = {};", pat, ty, generate_slice_from_ref(mcall)?),
+        None => format_to!(buf, "let {} = {};", pat, generate_slice_from_ref(mcall)?),
+    }
+
+    return Some(buf);
+}
+
+fn convert_to_slice_from_ref(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    // "let s = unsafe { slice::from_raw_parts(&x, 1) };"
+    if mcall.syntax().parent()?.kind() == STMT_LIST {
+        if !check_single_call_expr(&mcall)? {
+            return None;
+        }
+
+        let buf = generate_slice_from_ref(&mcall)?;
+        replace_source_code(acc, unsafe_expr.syntax().text_range(), &buf);
+        return None;
+    }
+
+    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
+
+    let mut buf = generate_let_slice_from_ref(&mcall, &let_expr)?;
+
+    if check_single_let_expr(&let_expr) {
+        replace_source_code(acc, unsafe_range, &buf);
+        return None;
+    }
+
+    buf.push('\n');
+
+    return reindent_expr(unsafe_expr, acc, let_expr.syntax().text_range(), &buf);
+}
+
+fn collect_addr_of_place(expr: &ast::Expr) -> Option<(ast::Expr, bool)> {
+    let macro_call = match expr {
+        ast::Expr::MacroExpr(macro_expr) => macro_expr.macro_call()?,
+        _ => return None,
+    };
+
+    let path = macro_call.path()?;
+
+    // "addr_of!(x)", "ptr::addr_of!(x)" or "std::ptr::addr_of_mut!(x)"
+    let mutable = match path.segment()?.name_ref()?.text().as_str() {
+        "addr_of" => false,
+        "addr_of_mut" => true,
+        _ => return None,
+    };
+
+    if path.qualifier().map_or(false, |qualifier| {
+        qualifier
+            .segment()
+            .and_then(|segment| segment.name_ref())
+            .map_or(true, |name| name.text() != "ptr")
+    }) {
+        return None;
+    }
+
+    // The place is read back from the token tree, the expansion would not point into this file
+    let tt = macro_call.token_tree()?;
+
+    let r_delim = NodeOrToken::Token(tt.right_delimiter_token()?);
+
+    let place = tt.syntax().children_with_tokens().skip(1).take_while(|it| *it != r_delim).join("");
+
+    return Some((syntax::hacks::parse_expr_from_str(&place)?, mutable));
+}
+
+fn collect_pointee_place(arg: ast::Expr) -> Option<(ast::Expr, bool)> {
+    let mut arg = arg;
+
+    // "&x as *const T", possibly going through more than one cast
+    while let ast::Expr::CastExpr(cast_expr) = arg {
+        arg = cast_expr.expr()?;
+    }
+
+    match arg {
+        ast::Expr::RefExpr(ref_expr) => {
+            return Some((ref_expr.expr()?, ref_expr.mut_token().is_some()))
+        }
+        arg => return collect_addr_of_place(&arg),
+    }
+}
+
+fn collect_swap_place(arg: ast::Expr) -> Option<ast::Expr> {
+    // Only "&mut place", a raw pointer (possibly offset) may alias the other argument
+    let place = match collect_pointee_place(arg)? {
+        (place, true) => place,
+        _ => return None,
+    };
+
+    // Two places inside one indexed buffer can't be borrowed mutably at once
+    match place {
+        ast::Expr::PathExpr(_) | ast::Expr::FieldExpr(_) => return Some(place),
+        _ => return None,
+    }
+}
+
+fn generate_mem_swap_format(mcall: &CallExpr) -> Option<String> {
+    let (first, second) = mcall.arg_list()?.args().collect_tuple()?;
+
+    let first = collect_swap_place(first)?;
+
+    let second = collect_swap_place(second)?;
+
+    if first.to_string() == second.to_string() {
+        return None;
+    }
+
+    let mut buf = String::new();
+
+    format_to!(buf, "mem::swap(&mut {}, &mut {})", first, second);
+
+    return Some(buf);
+}
+
+fn convert_to_mem_swap(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    let mut buf = generate_mem_swap_format(&mcall)?;
+
+    // "unsafe { ptr::swap(&mut a, &mut b) }" where the call is the tail expression
+    if mcall.syntax().parent()?.kind() == STMT_LIST {
+        if check_single_call_expr(&mcall)? {
+            replace_source_code(acc, unsafe_expr.syntax().text_range(), &buf);
+            return None;
+        }
+
+        buf.push_str(";\n");
+        return reindent_expr(unsafe_expr, acc, mcall.syntax().text_range(), &buf);
+    }
+
+    let expr_stmt = mcall.syntax().parent().and_then(ast::ExprStmt::cast)?;
+
+    buf.push(';');
+
+    if check_single_expr(&expr_stmt) {
+        replace_source_code(acc, unsafe_range, &buf);
+        return None;
+    }
+
+    buf.push('\n');
+
+    return reindent_expr(unsafe_expr, acc, expr_stmt.syntax().text_range(), &buf);
+}
+
+fn collect_replace_place(arg: ast::Expr) -> Option<ast::Expr> {
+    let place = match collect_pointee_place(arg)? {
+        (place, true) => place,
+        _ => return None,
+    };
+
+    // "&mut *p" still goes through a raw pointer
+    match place {
+        ast::Expr::PathExpr(_) | ast::Expr::FieldExpr(_) | ast::Expr::IndexExpr(_) => {
+            return Some(place)
+        }
+        _ => return None,
+    }
+}
+
+fn generate_mem_replace_format(mcall: &CallExpr) -> Option<String> {
+    let (dst, src) = mcall.arg_list()?.args().collect_tuple()?;
+
+    let dst = collect_replace_place(dst)?;
+
+    let mut buf = String::new();
+
+    format_to!(buf, "mem::replace(&mut {}, {})", dst, src);
+
+    return Some(buf);
+}
+
+fn generate_let_mem_replace(mcall: &CallExpr, let_expr: &LetStmt) -> Option<String> {
+    let pat = let_expr.pat()?;
+
+    let mut buf = String::new();
+
+    match let_expr.ty() {
+        Some(ty) => {
+            format_to!(buf, "let {}: {} = {};", pat, ty, generate_mem_replace_format(mcall)?)
+        }
+        None => format_to!(buf, "let {} = {};", pat, generate_mem_replace_format(mcall)?),
+    }
+
+    return Some(buf);
+}
+
+fn convert_to_mem_replace(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    // "let old = unsafe { ptr::replace(&mut x, v) };"
+    if mcall.syntax().parent()?.kind() == STMT_LIST {
+        if !check_single_call_expr(&mcall)? {
+            return None;
+        }
+
+        let buf = generate_mem_replace_format(&mcall)?;
+        replace_source_code(acc, unsafe_expr.syntax().text_range(), &buf);
+        return None;
+    }
+
+    // The old value is dropped, "unsafe { ptr::replace(&mut x, v); }"
+    if let Some(expr_stmt) = mcall.syntax().parent().and_then(ast::ExprStmt::cast) {
+        let mut buf = generate_mem_replace_format(&mcall)?;
+
+        buf.push(';');
+
+        if check_single_expr(&expr_stmt) {
+            replace_source_code(acc, unsafe_range, &buf);
+            return None;
+        }
+
+        buf.push('\n');
+
+        return reindent_expr(unsafe_expr, acc, expr_stmt.syntax().text_range(), &buf);
+    }
+
+    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
+
+    let mut buf = generate_let_mem_replace(&mcall, &let_expr)?;
+
+    if check_single_let_expr(&let_expr) {
+        replace_source_code(acc, unsafe_range, &buf);
+        return None;
+    }
+
+    buf.push('\n');
+
+    return reindent_expr(unsafe_expr, acc, let_expr.syntax().text_range(), &buf);
+}
+
+fn collect_as_mut_vec_call(target_expr: &SyntaxNode) -> Option<MethodCallExpr> {
+    let vec_call = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+
+    // The byte operation is the method called on the "as_mut_vec()" result
+    let mcall = vec_call.syntax().parent().and_then(ast::MethodCallExpr::cast)?;
+
+    if mcall.receiver()?.syntax() != vec_call.syntax() {
+        return None;
+    }
+
+    return Some(mcall);
+}
+
+fn collect_ascii_char(arg: &ast::Expr) -> Option<String> {
+    let literal = match arg {
+        ast::Expr::Literal(literal) => literal,
+        _ => return None,
+    };
+
+    let byte = match literal.kind() {
+        ast::LiteralKind::Byte(byte) => byte,
+        _ => return None,
+    };
+
+    // A non-ASCII byte on its own is never valid UTF-8
+    if !byte.value()?.is_ascii() {
+        return None;
+    }
+
+    // "b'x'" and "'x'" share the escapes for every ASCII byte
+    return byte.text().strip_prefix('b').map(|text| text.to_string());
+}
+
+fn collect_str_as_bytes(arg: &ast::Expr) -> Option<ast::Expr> {
+    let bytes_call = match arg {
+        ast::Expr::MethodCallExpr(bytes_call) => bytes_call,
+        _ => return None,
+    };
+
+    if bytes_call.name_ref()?.to_string() != "as_bytes"
+        || bytes_call.arg_list()?.args().next().is_some()
+    {
+        return None;
+    }
+
+    return bytes_call.receiver();
+}
+
+fn generate_string_push_format(mcall: &MethodCallExpr) -> Option<String> {
+    let string = ast::MethodCallExpr::cast(mcall.receiver()?.syntax().clone())?.receiver()?;
+
+    let arg = mcall.arg_list()?.args().exactly_one().ok()?;
+
+    let mut buf = String::new();
+
+    match mcall.name_ref()?.to_string().as_str() {
+        "push" => format_to!(buf, "{}.push({})", string, collect_ascii_char(&arg)?),
+        "extend_from_slice" => {
+            format_to!(buf, "{}.push_str({})", string, collect_str_as_bytes(&arg)?)
+        }
+        _ => return None,
+    }
+
+    return Some(buf);
+}
+
+fn convert_to_string_push(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = collect_as_mut_vec_call(target_expr)?;
+
+    let mut buf = generate_string_push_format(&mcall)?;
+
+    // "unsafe { s.as_mut_vec().push(b'x') }" as the tail expression
+    if mcall.syntax().parent()?.kind() == STMT_LIST {
+        if !check_single_methodcall_expr(&mcall)? {
+            return None;
+        }
+
+        replace_source_code(acc, unsafe_expr.syntax().text_range(), &buf);
+        return None;
+    }
+
+    let expr_stmt = mcall.syntax().parent().and_then(ast::ExprStmt::cast)?;
+
+    buf.push(';');
+
+    if check_single_expr(&expr_stmt) {
+        replace_source_code(acc, unsafe_range, &buf);
+        return None;
+    }
+
+    buf.push('\n');
+
+    return reindent_expr(unsafe_expr, acc, expr_stmt.syntax().text_range(), &buf);
+}
+
+struct PinBoxInfo {
+    pub value: ast::Expr,
+    pub box_stmt: Option<LetStmt>,
+}
+
+fn collect_box_new_value(expr: &ast::Expr) -> Option<ast::Expr> {
+    let box_call = match expr {
+        ast::Expr::CallExpr(box_call) => box_call,
+        _ => return None,
+    };
+
+    if box_call.expr()?.to_string() != "Box::new" {
+        return None;
+    }
+
+    let value = box_call.arg_list()?.args().exactly_one().ok()?;
+
+    // "Box::pin(&x)" pins the reference, not the value behind it
+    if value.syntax().kind() == REF_EXPR {
+        return None;
+    }
+
+    return Some(value);
+}
+
+fn collect_box_let_stmt(boxed: &ast::PathExpr, unsafe_expr: &BlockExpr) -> Option<LetStmt> {
+    // The statement that holds the "unsafe" block, the Box has to come right before it
+    let stmt = unsafe_expr
+        .syntax()
+        .ancestors()
+        .find(|node| node.parent().map_or(false, |parent| parent.kind() == STMT_LIST))?;
+
+    let let_expr = stmt.prev_sibling().and_then(ast::LetStmt::cast)?;
+
+    let name = match let_expr.pat()? {
+        ast::Pat::IdentPat(ident) => ident.name()?.to_string(),
+        _ => return None,
+    };
+
+    if name != boxed.to_string() {
+        return None;
+    }
+
+    // The Box is moved into the Pin, any other use keeps the separate statement alive
+    let uses = stmt
+        .parent()?
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| token.kind() == IDENT && token.text() == name)
+        .filter(|token| token.text_range().start() >= let_expr.syntax().text_range().end())
+        .count();
+
+    if uses != 1 {
+        return None;
+    }
+
+    return Some(let_expr);
+}
+
+fn collect_pin_box_info(mcall: &CallExpr, unsafe_expr: &BlockExpr) -> Option<PinBoxInfo> {
+    let arg = mcall.arg_list()?.args().exactly_one().ok()?;
+
+    // "Pin::new_unchecked(Box::new(x))"
+    if let Some(value) = collect_box_new_value(&arg) {
+        return Some(PinBoxInfo { value, box_stmt: None });
+    }
+
+    // "let boxed = Box::new(x);" followed by "Pin::new_unchecked(boxed)"
+    let boxed = match &arg {
+        ast::Expr::PathExpr(boxed) => boxed,
+        _ => return None,
+    };
+
+    let box_stmt = collect_box_let_stmt(boxed, unsafe_expr)?;
+
+    let value = collect_box_new_value(&box_stmt.initializer()?)?;
+
+    return Some(PinBoxInfo { value, box_stmt: Some(box_stmt) });
+}
+
+fn generate_box_pin(info: &PinBoxInfo) -> String {
+    let mut buf = String::new();
+
+    format_to!(buf, "Box::pin({})", info.value);
+
+    return buf;
+}
+
+fn generate_let_box_pin(info: &PinBoxInfo, let_expr: &LetStmt) -> Option<String> {
+    let pat = let_expr.pat()?;
+
+    let mut buf = String::new();
+
+    match let_expr.ty() {
+        Some(ty) => format_to!(buf, "let {}: {} = {};", pat, ty, generate_box_pin(info)),
+        None => format_to!(buf, "let {} = {};", pat, generate_box_pin(info)),
+    }
+
+    return Some(buf);
+}
+
+fn convert_to_box_pin(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    let info = collect_pin_box_info(&mcall, unsafe_expr)?;
+
+    // "let p = unsafe { Pin::new_unchecked(..) };" or the "let" alone in the block
+    let (target_range, buf) = if mcall.syntax().parent()?.kind() == STMT_LIST {
+        if !check_single_call_expr(&mcall)? {
+            return None;
+        }
+
+        (unsafe_expr.syntax().text_range(), generate_box_pin(&info))
+    } else {
+        let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
+
+        if !check_single_let_expr(&let_expr) {
+            return None;
+        }
+
+        (unsafe_range, generate_let_box_pin(&info, &let_expr)?)
+    };
+
+    let box_stmt = match &info.box_stmt {
+        Some(box_stmt) => box_stmt,
+        None => {
+            replace_source_code(acc, target_range, &buf);
+            return None;
+        }
+    };
+
+    // Drop the "Box::new" statement together with the line break that follows it
+    let box_end = box_stmt
+        .syntax()
+        .next_sibling_or_token()
+        .filter(|it| it.kind() == WHITESPACE)
+        .map_or(box_stmt.syntax().text_range().end(), |it| it.text_range().end());
+
+    let box_range = TextRange::new(box_stmt.syntax().text_range().start(), box_end);
+
+    delet_replace_source_code(acc, target_range, box_range, &buf);
+
+    return None;
+}
+
+fn generate_split_at_expr(mcall: &MethodCallExpr, mut_sign: bool) -> Option<String> {
+    let receiver = mcall.receiver()?;
+
+    let mid = mcall.arg_list()?.args().exactly_one().ok()?;
+
+    let mut buf = String::new();
+
+    if mut_sign {
+        format_to!(buf, "{}.split_at_mut({})", receiver, mid);
+    } else {
+        format_to!(buf, "{}.split_at({})", receiver, mid);
+    }
+
+    return Some(buf);
+}
+
+fn generate_let_split_at(
+    mcall: &MethodCallExpr,
+    let_expr: &LetStmt,
+    mut_sign: bool,
+) -> Option<String> {
+    // Keep the destructuring pattern of the enclosing "let"
+    let pat = let_expr.pat()?;
+
+    let mut buf = String::new();
+
+    match let_expr.ty() {
+        Some(ty) => {
+            format_to!(buf, "let {}: {} = {};", pat, ty, generate_split_at_expr(mcall, mut_sign)?)
+        }
+        None => format_to!(buf, "let {} = {};", pat, generate_split_at_expr(mcall, mut_sign)?),
+    }
+
+    return Some(buf);
+}
+
+fn convert_to_split_at(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+    mut_sign: bool,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+
+    // "let (a, b) = unsafe { s.split_at_unchecked(mid) };" or the tuple used inline
+    if mcall.syntax().parent()?.kind() == STMT_LIST {
+        if !check_single_methodcall_expr(&mcall)? {
+            return None;
+        }
+
+        let buf = generate_split_at_expr(&mcall, mut_sign)?;
+        replace_source_code(acc, unsafe_expr.syntax().text_range(), &buf);
+        return None;
+    }
+
+    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
+
+    let mut buf = generate_let_split_at(&mcall, &let_expr, mut_sign)?;
+
+    if check_single_let_expr(&let_expr) {
+        replace_source_code(acc, unsafe_range, &buf);
+        return None;
+    }
+
+    buf.push('\n');
+
+    return reindent_expr(unsafe_expr, acc, let_expr.syntax().text_range(), &buf);
+}
+
+fn generate_as_chunks_expr(mcall: &MethodCallExpr) -> Option<String> {
+    let receiver = mcall.receiver()?;
+
+    // Keep the "::<N>" turbofish, without it "N" is still inferred from how the chunks are used
+    let turbofish =
+        mcall.generic_arg_list().map_or(String::new(), |generic_args| generic_args.to_string());
+
+    let mut buf = String::new();
+
+    format_to!(buf, "{}.as_chunks{}().0", receiver, turbofish);
+
+    return Some(buf);
+}
+
+fn generate_chunks_exact_expr(mcall: &MethodCallExpr) -> Option<String> {
+    let receiver = mcall.receiver()?;
+
+    // "chunks_exact" takes the chunk size as a value, so it has to be spelled out
+    let chunk_size = match mcall.generic_arg_list()?.generic_args().exactly_one().ok()? {
+        ast::GenericArg::ConstArg(const_arg) => const_arg.expr()?,
+        _ => return None,
+    };
+
+    let mut buf = String::new();
+
+    format_to!(buf, "{}.chunks_exact({})", receiver, chunk_size);
+
+    return Some(buf);
+}
+
+fn generate_let_as_chunks(mcall: &MethodCallExpr, let_expr: &LetStmt) -> Option<String> {
+    let pat = let_expr.pat()?;
+
+    let mut buf = String::new();
+
+    match let_expr.ty() {
+        Some(ty) => format_to!(buf, "let {}: {} = {};", pat, ty, generate_as_chunks_expr(mcall)?),
+        None => format_to!(buf, "let {} = {};", pat, generate_as_chunks_expr(mcall)?),
+    }
+
+    return Some(buf);
+}
+
+fn collect_iterated_chunks(unsafe_expr: &BlockExpr) -> Option<SyntaxNode> {
+    let parent = unsafe_expr.syntax().parent()?;
+
+    // "for chunk in unsafe { data.as_chunks_unchecked::<4>() } { ... }"
+    if let Some(for_expr) = ast::ForExpr::cast(parent.clone()) {
+        if for_expr.iterable()?.syntax() == unsafe_expr.syntax() {
+            return Some(unsafe_expr.syntax().clone());
+        }
+        return None;
+    }
+
+    // "unsafe { data.as_chunks_unchecked::<4>() }.iter()" is folded into the iterator as well
+    let iter_call = ast::MethodCallExpr::cast(parent)?;
+
+    if iter_call.name_ref()?.to_string() != "iter"
+        || iter_call.receiver()?.syntax() != unsafe_expr.syntax()
+    {
+        return None;
+    }
+
+    return Some(iter_call.syntax().clone());
+}
+
+fn convert_to_as_chunks(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+
+    // "let chunks = unsafe { data.as_chunks_unchecked::<4>() };" or the chunks used inline
+    if mcall.syntax().parent()?.kind() == STMT_LIST {
+        if !check_single_methodcall_expr(&mcall)? {
+            return None;
+        }
+
+        // Iterated right away, the chunks can come from "chunks_exact" without building the slice of arrays
+        if let (Some(iterated), Some(buf)) =
+            (collect_iterated_chunks(unsafe_expr), generate_chunks_exact_expr(&mcall))
+        {
+            replace_source_code(acc, iterated.text_range(), &buf);
+            return None;
+        }
+
+        let buf = generate_as_chunks_expr(&mcall)?;
+        replace_source_code(acc, unsafe_expr.syntax().text_range(), &buf);
+        return None;
+    }
+
+    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
+
+    let mut buf = generate_let_as_chunks(&mcall, &let_expr)?;
+
+    if check_single_let_expr(&let_expr) {
+        replace_source_code(acc, unsafe_range, &buf);
+        return None;
+    }
+
+    buf.push('\n');
+
+    return reindent_expr(unsafe_expr, acc, let_expr.syntax().text_range(), &buf);
+}
+
+fn generate_checked_arith(mcall: &MethodCallExpr, op: &str, wrapping: bool) -> Option<String> {
+    let receiver = mcall.receiver()?;
+
+    let rhs = mcall.arg_list()?.args().exactly_one().ok()?;
+
+    let mut buf = String::new();
+
+    // The checked form keeps the "no overflow" contract by panicking instead
+    if wrapping {
+        format_to!(buf, "{}.wrapping_{}({})", receiver, op, rhs);
+    } else {
+        format_to!(buf, "{}.checked_{}({}).expect(\"overflow\")", receiver, op, rhs);
+    }
+
+    return Some(buf);
+}
+
+fn generate_let_checked_arith(
+    mcall: &MethodCallExpr,
+    let_expr: &LetStmt,
+    op: &str,
+    wrapping: bool,
+) -> Option<String> {
+    let pat = let_expr.pat()?;
+
+    let mut buf = String::new();
+
+    match let_expr.ty() {
+        Some(ty) => format_to!(
+            buf,
+            "let {}: {} = {};",
+            pat,
+            ty,
+            generate_checked_arith(mcall, op, wrapping)?
+        ),
+        None => format_to!(buf, "let {} = {};", pat, generate_checked_arith(mcall, op, wrapping)?),
+    }
+
+    return Some(buf);
+}
+
+fn replace_unchecked_arith(
+    acc: &mut Assists,
+    label: &str,
+    mcall: &MethodCallExpr,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+    op: &str,
+    wrapping: bool,
+) -> Option<()> {
+    // "let s = unsafe { a.unchecked_add(b) };" or the result used inline
+    if mcall.syntax().parent()?.kind() == STMT_LIST {
+        if !check_single_methodcall_expr(mcall)? {
+            return None;
+        }
+
+        let buf = generate_checked_arith(mcall, op, wrapping)?;
+        replace_source_code_with_label(acc, label, unsafe_expr.syntax().text_range(), &buf);
+        return None;
+    }
+
+    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
+
+    let mut buf = generate_let_checked_arith(mcall, &let_expr, op, wrapping)?;
+
+    if check_single_let_expr(&let_expr) {
+        replace_source_code_with_label(acc, label, unsafe_range, &buf);
+        return None;
+    }
+
+    buf.push('\n');
+
+    return reindent_expr_with_label(unsafe_expr, acc, label, let_expr.syntax().text_range(), &buf);
+}
+
+fn convert_to_checked_arith(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+    op: &str,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+
+    // Both rewrites are offered, the checked one is added last as the primary conversion
+    replace_unchecked_arith(
+        acc,
+        "Convert to Wrapping Arithmetic",
+        &mcall,
+        unsafe_range,
+        unsafe_expr,
+        op,
+        true,
+    );
+
+    return replace_unchecked_arith(
+        acc,
+        "Convert Unsafe to Safe",
+        &mcall,
+        unsafe_range,
+        unsafe_expr,
+        op,
+        false,
+    );
+}
+
+fn check_integer_receiver(
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+) -> Option<bool> {
+    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+
+    // User types may define their own "unchecked_*" methods with a different contract
+    let receiver_ty = sema.type_of_expr(&mcall.receiver()?)?.original.strip_references();
+
+    return Some(
+        receiver_ty.as_builtin().map_or(false, |builtin| builtin.is_int() || builtin.is_uint()),
+    );
+}
+
+fn collect_int_cast_type(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &MethodCallExpr,
+    unsafe_expr: &BlockExpr,
+) -> Option<String> {
+    // "x.to_int_unchecked::<i32>()"
+    if let Some(generic_args) = mcall.generic_arg_list() {
+        return match generic_args.generic_args().exactly_one().ok()? {
+            ast::GenericArg::TypeArg(type_arg) => Some(type_arg.ty()?.to_string()),
+            _ => None,
+        };
+    }
+
+    // "let n: i32 = unsafe { x.to_int_unchecked() };"
+    let let_expr = mcall
+        .syntax()
+        .parent()
+        .and_then(ast::LetStmt::cast)
+        .or_else(|| unsafe_expr.syntax().parent().and_then(ast::LetStmt::cast));
+
+    if let Some(ty) = let_expr.and_then(|let_expr| let_expr.ty()) {
+        return Some(ty.to_string());
+    }
+
+    // Otherwise fall back to whatever the surrounding expression expects
+    let ty = sema.type_of_expr(&ast::Expr::MethodCallExpr(mcall.clone()))?.original;
+
+    let builtin = ty.as_builtin()?;
+
+    if !builtin.is_int() && !builtin.is_uint() {
+        return None;
+    }
+
+    return Some(ty.display(sema.db).to_string());
+}
+
+fn generate_int_cast(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &MethodCallExpr,
+    unsafe_expr: &BlockExpr,
+) -> Option<String> {
+    let receiver = mcall.receiver()?;
+
+    let ty = collect_int_cast_type(sema, mcall, unsafe_expr)?;
+
+    let mut buf = String::new();
+
+    format_to!(buf, "{} as {}", receiver, ty);
+
+    return Some(buf);
+}
+
+fn generate_let_int_cast(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &MethodCallExpr,
+    let_expr: &LetStmt,
+    unsafe_expr: &BlockExpr,
+) -> Option<String> {
+    let pat = let_expr.pat()?;
+
+    let mut buf = String::new();
+
+    match let_expr.ty() {
+        Some(ty) => format_to!(
+            buf,
+            "let {}: {} = {};",
+            pat,
+            ty,
+            generate_int_cast(sema, mcall, unsafe_expr)?
+        ),
+        None => format_to!(buf, "let {} = {};", pat, generate_int_cast(sema, mcall, unsafe_expr)?),
+    }
+
+    return Some(buf);
+}
+
+fn convert_to_int_cast(
+    acc: &mut Assists,
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+
+    // The "unsafe" block only wraps the conversion, drop it together with the call
+    if mcall.syntax().parent()?.kind() == STMT_LIST {
+        if !check_single_methodcall_expr(&mcall)? {
+            return None;
+        }
+
+        let buf = generate_int_cast(sema, &mcall, unsafe_expr)?;
+        replace_source_code(acc, unsafe_expr.syntax().text_range(), &buf);
+        return None;
+    }
+
+    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
+
+    let mut buf = generate_let_int_cast(sema, &mcall, &let_expr, unsafe_expr)?;
+
+    if check_single_let_expr(&let_expr) {
+        replace_source_code(acc, unsafe_range, &buf);
+        return None;
+    }
+
+    buf.push('\n');
+
+    return reindent_expr(unsafe_expr, acc, let_expr.syntax().text_range(), &buf);
+}
+
+fn check_float_receiver(
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+) -> Option<bool> {
+    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+
+    let receiver_ty = sema.type_of_expr(&mcall.receiver()?)?.original.strip_references();
+
+    return Some(receiver_ty.as_builtin().map_or(false, |builtin| builtin.is_float()));
+}
+
+struct RawPartsInfo {
+    pub vec_name: String,
+    pub rebuilt: ast::IdentPat,
+    pub stmts: Vec<SyntaxNode>,
+    pub reassembly: SyntaxNode,
+}
+
+fn collect_raw_part_arg(expr: ast::Expr) -> Option<String> {
+    match expr {
+        ast::Expr::PathExpr(path_expr) => {
+            return Some(path_expr.path()?.as_single_name_ref()?.to_string())
+        }
+        _ => return None,
+    }
+}
+
+fn collect_raw_part_let(stmt: &SyntaxNode, name: &str, method: &str) -> Option<String> {
+    let let_expr = ast::LetStmt::cast(stmt.clone())?;
+
+    match let_expr.pat()? {
+        ast::Pat::IdentPat(ident_pat) if ident_pat.name()?.to_string() == name => (),
+        _ => return None,
+    }
+
+    let mcall = match let_expr.initializer()? {
+        ast::Expr::MethodCallExpr(mcall) => mcall,
+        _ => return None,
+    };
+
+    if mcall.name_ref()?.to_string() != method || mcall.arg_list()?.args().next().is_some() {
+        return None;
+    }
+
+    return collect_raw_part_arg(mcall.receiver()?);
+}
+
+fn collect_forget_arg(stmt: &SyntaxNode) -> Option<String> {
+    let mcall = match ast::ExprStmt::cast(stmt.clone())?.expr()? {
+        ast::Expr::CallExpr(mcall) => mcall,
+        _ => return None,
+    };
+
+    let path = match mcall.expr()? {
+        ast::Expr::PathExpr(path_expr) => path_expr.path()?,
+        _ => return None,
+    };
+
+    if path.segment()?.to_string() != "forget" {
+        return None;
+    }
+
+    return collect_raw_part_arg(mcall.arg_list()?.args().exactly_one().ok()?);
+}
+
+fn collect_reassembly_let(
+    mcall: &CallExpr,
+    unsafe_expr: &BlockExpr,
+) -> Option<(LetStmt, SyntaxNode)> {
+    // "let v = unsafe { from_raw(..) };" or the "let" alone inside the block
+    let (let_expr, reassembly) = match unsafe_expr.syntax().parent().and_then(ast::LetStmt::cast) {
+        Some(let_expr) => {
+            if mcall.syntax().parent()?.kind() != STMT_LIST || !check_single_call_expr(mcall)? {
+                return None;
+            }
+
+            let reassembly = let_expr.syntax().clone();
+
+            (let_expr, reassembly)
+        }
+        None => {
+            let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
+
+            if !check_single_let_expr(&let_expr) {
+                return None;
+            }
+
+            let reassembly = match unsafe_expr.syntax().parent()?.kind() {
+                STMT_LIST => unsafe_expr.syntax().clone(),
+                _ => unsafe_expr.syntax().parent()?,
+            };
+
+            (let_expr, reassembly)
+        }
+    };
+
+    return Some((let_expr, reassembly));
+}
+
+fn collect_raw_parts_info(mcall: &CallExpr, unsafe_expr: &BlockExpr) -> Option<RawPartsInfo> {
+    let (ptr, len, cap) = mcall.arg_list()?.args().map(collect_raw_part_arg).collect_tuple()?;
+
+    let (ptr, len, cap) = (ptr?, len?, cap?);
+
+    let (let_expr, reassembly) = collect_reassembly_let(mcall, unsafe_expr)?;
+
+    let rebuilt = match let_expr.pat()? {
+        ast::Pat::IdentPat(ident_pat) => ident_pat,
+        _ => return None,
+    };
+
+    let parts = [ptr.as_str(), len.as_str(), cap.as_str()];
+
+    let mut vec_name = None;
+
+    let mut ptr_stmt = None;
+    let mut len_stmt = None;
+    let mut cap_stmt = None;
+    let mut forget_stmt = None;
+
+    // "mem::forget" right after the reassembly leaks the original the same way
+    if let Some(next) = reassembly.siblings(Direction::Next).nth(1) {
+        if let Some(receiver) = collect_forget_arg(&next) {
+            vec_name = Some(receiver);
+            forget_stmt = Some(next);
+        }
+    }
+
+    for iter in reassembly.siblings(Direction::Prev).skip(1) {
+        let decomposed = [
+            (&mut ptr_stmt, collect_raw_part_let(&iter, &ptr, "as_mut_ptr")),
+            (&mut len_stmt, collect_raw_part_let(&iter, &len, "len")),
+            (&mut cap_stmt, collect_raw_part_let(&iter, &cap, "capacity")),
+        ];
+
+        let mut matched = false;
+
+        for (stmt, receiver) in decomposed {
+            if let Some(receiver) = receiver {
+                // Every piece has to come from the same Vec, and the "forget" has to follow them
+                if forget_stmt.is_none() || stmt.is_some() {
+                    return None;
+                }
+
+                if *vec_name.get_or_insert_with(|| receiver.clone()) != receiver {
+                    return None;
+                }
+
+                *stmt = Some(iter.clone());
+                matched = true;
+            }
+        }
+
+        if matched {
+            if ptr_stmt.is_some() && len_stmt.is_some() && cap_stmt.is_some() {
+                break;
+            }
+            continue;
+        }
+
+        if forget_stmt.is_none() {
+            if let Some(receiver) = collect_forget_arg(&iter) {
+                vec_name = Some(receiver);
+                forget_stmt = Some(iter.clone());
+                continue;
+            }
+        }
+
+        // Raw parts used by anything else, e.g. handed over to FFI, can't be dropped
+        if parts.iter().any(|part| check_ident_mentioned(&iter, part)) {
+            return None;
+        }
+
+        if vec_name.as_ref().map_or(false, |name| check_ident_mentioned(&iter, name)) {
+            return None;
+        }
+    }
+
+    // The raw parts must not outlive the reassembly either
+    if reassembly
+        .siblings(Direction::Next)
+        .skip(1)
+        .any(|iter| parts.iter().any(|part| check_ident_mentioned(&iter, part)))
+    {
+        return None;
+    }
+
+    let vec_name = vec_name?;
+
+    let stmts = vec![ptr_stmt?, len_stmt?, cap_stmt?, forget_stmt?];
+
+    return Some(RawPartsInfo { vec_name, rebuilt, stmts, reassembly });
+}
+
+fn collect_stmt_range(stmt: &SyntaxNode) -> TextRange {
+    // Drop the indentation in front of the statement along with it
+    let mut range = stmt.text_range();
+    if let Some(whitespace) = stmt.prev_sibling_or_token().filter(|it| it.kind() == WHITESPACE) {
+        range = TextRange::new(whitespace.text_range().start(), range.end());
+    }
+
+    return range;
+}
+
+fn collect_rename_ranges(reassembly: &SyntaxNode, rebuilt_name: &str) -> Vec<TextRange> {
+    // Later uses of the rebuilt value refer to the original one instead, macro arguments included
+    return reassembly
+        .siblings(Direction::Next)
+        .skip(1)
+        .flat_map(|iter| {
+            iter.descendants_with_tokens()
+                .filter_map(|element| element.into_token())
+                .collect::<Vec<_>>()
+        })
+        .filter(|token| token.kind() == IDENT && token.text() == rebuilt_name)
+        .map(|token| token.text_range())
+        .collect();
+}
+
+fn convert_to_original_vec(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    let info = collect_raw_parts_info(&mcall, unsafe_expr)?;
+
+    let rebuilt_name = info.rebuilt.name()?.to_string();
+
+    let mut delete_ranges: Vec<TextRange> = info.stmts.iter().map(collect_stmt_range).collect();
+    delete_ranges.push(collect_stmt_range(&info.reassembly));
+
+    let rename_ranges = collect_rename_ranges(&info.reassembly, &rebuilt_name);
+
+    let target_range = info.reassembly.text_range();
+
+    acc.add(
+        AssistId("convert_unsafe_to_safe", AssistKind::RefactorRewrite),
+        "Keep the original Vec instead of rebuilding it",
+        target_range,
+        |edit| {
+            for range in delete_ranges {
+                edit.delete(range);
+            }
+            for range in rename_ranges {
+                edit.replace(range, info.vec_name.clone());
+            }
+        },
+    );
+
+    return None;
+}
+
+struct RcRoundTripInfo {
+    pub rc_name: String,
+    pub rebuilt: ast::IdentPat,
+    pub into_raw: SyntaxNode,
+    pub reassembly: SyntaxNode,
+}
+
+fn collect_into_raw_arg(stmt: &SyntaxNode, ptr: &str, kind: &str) -> Option<String> {
+    let let_expr = ast::LetStmt::cast(stmt.clone())?;
+
+    match let_expr.pat()? {
+        ast::Pat::IdentPat(ident_pat) if ident_pat.name()?.to_string() == ptr => (),
+        _ => return None,
+    }
+
+    let mcall = match let_expr.initializer()? {
+        ast::Expr::CallExpr(mcall) => mcall,
+        _ => return None,
+    };
+
+    if mcall.expr()?.to_string() != format!("{}::into_raw", kind) {
+        return None;
+    }
+
+    return collect_raw_part_arg(mcall.arg_list()?.args().exactly_one().ok()?);
+}
+
+fn collect_rc_round_trip(
+    mcall: &CallExpr,
+    unsafe_expr: &BlockExpr,
+    kind: &str,
+) -> Option<RcRoundTripInfo> {
+    let ptr = collect_raw_part_arg(mcall.arg_list()?.args().exactly_one().ok()?)?;
+
+    let (let_expr, reassembly) = collect_reassembly_let(mcall, unsafe_expr)?;
+
+    let rebuilt = match let_expr.pat()? {
+        ast::Pat::IdentPat(ident_pat) => ident_pat,
+        _ => return None,
+    };
+
+    let mut into_raw = None;
+
+    for iter in reassembly.siblings(Direction::Prev).skip(1) {
+        if let Some(rc_name) = collect_into_raw_arg(&iter, &ptr, kind) {
+            into_raw = Some((rc_name, iter));
+            break;
+        }
+
+        // A copy of the pointer, or one handed to FFI, may still be turned back into a
+        // strong reference elsewhere, so the counts would no longer balance
+        if check_ident_mentioned(&iter, &ptr) {
+            return None;
+        }
+    }
+
+    // Exactly one "from_raw" may consume the pointer
+    if reassembly.siblings(Direction::Next).skip(1).any(|iter| check_ident_mentioned(&iter, &ptr)) {
+        return None;
+    }
+
+    let (rc_name, into_raw) = into_raw?;
+
+    return Some(RcRoundTripInfo { rc_name, rebuilt, into_raw, reassembly });
+}
+
+fn convert_to_original_rc(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_expr: &BlockExpr,
+    kind: &str,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    let info = collect_rc_round_trip(&mcall, unsafe_expr, kind)?;
+
+    let rebuilt_name = info.rebuilt.name()?.to_string();
+
+    let delete_ranges = [collect_stmt_range(&info.into_raw), collect_stmt_range(&info.reassembly)];
+
+    let rename_ranges = collect_rename_ranges(&info.reassembly, &rebuilt_name);
+
+    let target_range = info.reassembly.text_range();
+
+    acc.add(
+        AssistId("convert_unsafe_to_safe", AssistKind::RefactorRewrite),
+        format!("Remove {} into_raw/from_raw round trip", kind),
+        target_range,
+        |edit| {
+            for range in delete_ranges {
+                edit.delete(range);
+            }
+            for range in rename_ranges {
+                edit.replace(range, info.rc_name.clone());
+            }
+        },
+    );
+
+    return None;
+}
+
+fn collect_ptr_read_place(mcall: &CallExpr) -> Option<ast::Expr> {
+    // Only "ptr::read(&X)" reads out of a place that "mem::take" can borrow mutably
+    match collect_pointee_place(mcall.arg_list()?.args().exactly_one().ok()?)? {
+        (place, false) => return Some(place),
+        _ => return None,
+    }
+}
+
+fn generate_mem_take(mcall: &CallExpr) -> Option<String> {
+    let place = collect_ptr_read_place(mcall)?;
+
+    let mut buf = String::new();
+
+    format_to!(buf, "mem::take(&mut {})", place);
+
+    return Some(buf);
+}
+
+fn generate_let_mem_take(mcall: &CallExpr, let_expr: &LetStmt) -> Option<String> {
+    let pat = let_expr.pat()?;
+
+    let mut buf = String::new();
+
+    match let_expr.ty() {
+        Some(ty) => format_to!(buf, "let {}: {} = {};", pat, ty, generate_mem_take(mcall)?),
+        None => format_to!(buf, "let {} = {};", pat, generate_mem_take(mcall)?),
+    }
+
+    return Some(buf);
+}
+
+fn convert_to_mem_take(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    // "let f = unsafe { ptr::read(&md.field) };"
+    if mcall.syntax().parent()?.kind() == STMT_LIST {
+        if !check_single_call_expr(&mcall)? {
+            return None;
+        }
+
+        let buf = generate_mem_take(&mcall)?;
+        replace_source_code(acc, unsafe_expr.syntax().text_range(), &buf);
+        return None;
+    }
+
+    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
+
+    let mut buf = generate_let_mem_take(&mcall, &let_expr)?;
+
+    if check_single_let_expr(&let_expr) {
+        replace_source_code(acc, unsafe_range, &buf);
+        return None;
+    }
+
+    buf.push('\n');
+
+    return reindent_expr(unsafe_expr, acc, let_expr.syntax().text_range(), &buf);
+}
+
+fn check_ptr_read_default(
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+) -> Option<bool> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    let place = collect_ptr_read_place(&mcall)?;
+
+    let famous_defs = FamousDefs(sema, sema.scope(mcall.syntax())?.krate());
+
+    // Reading out of "self" in a Drop impl is a deliberate move, leave it alone
+    let drop_trait = famous_defs.core_ops_Drop();
+
+    let in_drop_impl = mcall
+        .syntax()
+        .ancestors()
+        .filter_map(ast::Impl::cast)
+        .filter_map(|imp| sema.to_def(&imp))
+        .any(|imp| drop_trait.is_some() && imp.trait_(sema.db) == drop_trait);
+
+    if in_drop_impl {
+        return Some(false);
+    }
+
+    // "mem::take" needs a value to leave behind, the read value has the type of the place
+    let default_trait = famous_defs.core_default_Default()?;
+
+    let ty = sema.type_of_expr(&place)?.original;
+
+    return Some(ty.impls_trait(sema.db, default_trait, &[]));
+}
+
+fn collect_dropped_local(mcall: &CallExpr) -> Option<ast::PathExpr> {
+    // "&mut x as *mut T", "addr_of_mut!(x)" or the reference coerced in place
+    match collect_pointee_place(mcall.arg_list()?.args().exactly_one().ok()?)? {
+        (ast::Expr::PathExpr(path_expr), true) => return Some(path_expr),
+        _ => return None,
+    }
+}
+
+fn generate_drop(mcall: &CallExpr) -> Option<String> {
+    let local = collect_dropped_local(mcall)?;
+
+    let mut buf = String::new();
+
+    format_to!(buf, "drop({});", local);
+
+    return Some(buf);
+}
+
+fn convert_to_drop(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    let expr_stmt = mcall.syntax().parent().and_then(ast::ExprStmt::cast)?;
+
+    let mut buf = generate_drop(&mcall)?;
+
+    if check_single_expr(&expr_stmt) {
+        replace_source_code(acc, unsafe_range, &buf);
+        return None;
+    }
+
+    buf.push('\n');
+
+    return reindent_expr(unsafe_expr, acc, expr_stmt.syntax().text_range(), &buf);
+}
+
+fn check_drop_in_place_local(
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+    unsafe_expr: &BlockExpr,
+) -> Option<bool> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    let path_expr = collect_dropped_local(&mcall)?;
+
+    let local = match sema.resolve_path(&path_expr.path()?)? {
+        hir::PathResolution::Local(local) => local,
+        _ => return Some(false),
+    };
+
+    // Touching the value after "drop_in_place" is already UB, there is nothing safe to offer then
+    let unsafe_end = unsafe_expr.syntax().text_range().end();
+
+    let used_after = Definition::Local(local)
+        .usages(sema)
+        .all()
+        .iter()
+        .flat_map(|(_, references)| references.iter())
+        .any(|reference| reference.range.start() >= unsafe_end);
+
+    return Some(!used_after);
+}
+
+struct RawPtrIndexInfo {
+    pub base: ast::Expr,
+    pub index: String,
+}
+
+fn collect_raw_ptr_index(prefix: &ast::PrefixExpr) -> Option<RawPtrIndexInfo> {
+    if prefix.op_kind()? != ast::UnaryOp::Deref {
+        return None;
+    }
+
+    let mut ptr_call = match prefix.expr()? {
+        ast::Expr::MethodCallExpr(ptr_call) => ptr_call,
+        _ => return None,
+    };
+
+    // "*v.as_ptr()" without a step reads the first element
+    let mut index = String::from("0");
+
+    let step = ptr_call.name_ref()?.to_string();
+
+    if step == "add" || step == "offset" {
+        let count = ptr_call.arg_list()?.args().exactly_one().ok()?;
+
+        // "offset" takes an isize, only a literal can be used as the index as it is
+        if step == "add" || matches!(count, ast::Expr::Literal(_)) {
+            index = count.to_string();
+        } else if matches!(count, ast::Expr::PathExpr(_)) {
+            index = format!("{} as usize", count);
+        } else {
+            index = format!("({}) as usize", count);
+        }
+
+        ptr_call = match ptr_call.receiver()? {
+            ast::Expr::MethodCallExpr(ptr_call) => ptr_call,
+            _ => return None,
+        };
+    }
+
+    let ptr_name = ptr_call.name_ref()?.to_string();
+
+    if !UnsafePattern::AsPtr.matches(&ptr_name) && ptr_name != "as_mut_ptr" {
+        return None;
+    }
+
+    if ptr_call.arg_list()?.args().next().is_some() {
+        return None;
+    }
+
+    let base = ptr_call.receiver()?;
+
+    return Some(RawPtrIndexInfo { base, index });
+}
+
+fn generate_raw_ptr_index(prefix: &ast::PrefixExpr) -> Option<String> {
+    let RawPtrIndexInfo { base, index } = collect_raw_ptr_index(prefix)?;
+
+    let mut buf = String::new();
+
+    format_to!(buf, "{}[{}]", base, index);
+
+    return Some(buf);
+}
+
+fn collect_raw_ptr_assign(prefix: &ast::PrefixExpr) -> Option<BinExpr> {
+    let bin_expr = prefix.syntax().parent().and_then(ast::BinExpr::cast)?;
+
+    // Only a write through the pointer, the deref has to be the assigned place
+    if !matches!(bin_expr.op_kind()?, ast::BinaryOp::Assignment { .. })
+        || bin_expr.lhs()?.syntax() != prefix.syntax()
+    {
+        return None;
+    }
+
+    return Some(bin_expr);
+}
+
+fn generate_raw_ptr_index_assign(prefix: &ast::PrefixExpr, bin_expr: &BinExpr) -> Option<String> {
+    let mut buf = String::new();
+
+    format_to!(
+        buf,
+        "{} {} {};",
+        generate_raw_ptr_index(prefix)?,
+        bin_expr.op_token()?,
+        bin_expr.rhs()?
+    );
+
+    return Some(buf);
+}
+
+fn generate_let_raw_ptr_index(prefix: &ast::PrefixExpr, let_expr: &LetStmt) -> Option<String> {
+    let pat = let_expr.pat()?;
+
+    let mut buf = String::new();
+
+    match let_expr.ty() {
+        Some(ty) => format_to!(buf, "let {}: {} = {};", pat, ty, generate_raw_ptr_index(prefix)?),
+        None => format_to!(buf, "let {} = {};", pat, generate_raw_ptr_index(prefix)?),
+    }
+
+    return Some(buf);
+}
+
+fn convert_to_index(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let prefix = ast::PrefixExpr::cast(target_expr.clone())?;
+
+    // "*v.as_mut_ptr().add(i) = x;"
+    if let Some(bin_expr) = collect_raw_ptr_assign(&prefix) {
+        let expr_stmt = bin_expr.syntax().parent().and_then(ast::ExprStmt::cast)?;
+
+        let mut buf = generate_raw_ptr_index_assign(&prefix, &bin_expr)?;
+
+        if check_single_expr(&expr_stmt) {
+            replace_source_code(acc, unsafe_range, &buf);
+            return None;
+        }
+
+        buf.push('\n');
+
+        return reindent_expr(unsafe_expr, acc, expr_stmt.syntax().text_range(), &buf);
+    }
+
+    // "let x = *v.as_ptr().add(i);" inside the block
+    if let Some(let_expr) = prefix.syntax().parent().and_then(ast::LetStmt::cast) {
+        let mut buf = generate_let_raw_ptr_index(&prefix, &let_expr)?;
+
+        if check_single_let_expr(&let_expr) {
+            replace_source_code(acc, unsafe_range, &buf);
+            return None;
+        }
+
+        buf.push('\n');
+
+        return reindent_expr(unsafe_expr, acc, let_expr.syntax().text_range(), &buf);
+    }
+
+    let buf = generate_raw_ptr_index(&prefix)?;
+
+    // "let x = unsafe { *v.as_ptr().add(i) };"
+    if prefix.syntax().parent()?.kind() == STMT_LIST && prefix.syntax().prev_sibling().is_none() {
+        replace_source_code(acc, unsafe_expr.syntax().text_range(), &buf);
+        return None;
+    }
+
+    // Nested in a larger expression, only the read itself is rewritten
+    replace_source_code(acc, prefix.syntax().text_range(), &buf);
+
+    return None;
+}
+
+struct SafeWrapperInfo {
+    pub name: String,
+    pub exact_sign: bool,
+}
+
+fn collect_safe_wrapper(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+) -> Option<SafeWrapperInfo> {
+    let path = match mcall.expr()? {
+        ast::Expr::PathExpr(path_expr) => path_expr.path()?,
+        _ => return None,
+    };
+
+    let func = match sema.resolve_path(&path)? {
+        hir::PathResolution::Def(hir::ModuleDef::Function(func)) => func,
+        _ => return None,
+    };
+
+    if !func.is_unsafe_to_call(sema.db) || func.has_self_param(sema.db) {
+        return None;
+    }
+
+    let params = func.assoc_fn_params(sema.db);
+
+    for suffix in ["_safe", "_checked"] {
+        let wrapper_name = format!("{}{}", func.name(sema.db), suffix);
+
+        // Wrappers are looked up next to the "unsafe fn" itself
+        let wrapper =
+            func.module(sema.db).declarations(sema.db).into_iter().find_map(|def| match def {
+                hir::ModuleDef::Function(it) if it.name(sema.db).to_string() == wrapper_name => {
+                    Some(it)
+                }
+                _ => None,
+            });
+
+        let wrapper = match wrapper {
+            Some(wrapper) if !wrapper.is_unsafe_to_call(sema.db) => wrapper,
+            _ => continue,
+        };
+
+        let wrapper_params = wrapper.assoc_fn_params(sema.db);
+
+        // The same arguments have to be accepted for the wrapper to be a candidate at all
+        if wrapper_params.len() != params.len() {
+            continue;
+        }
+
+        let same_params = wrapper_params.iter().zip(params.iter()).all(|(wrapper_param, param)| {
+            wrapper_param.ty().display(sema.db).to_string()
+                == param.ty().display(sema.db).to_string()
+        });
+
+        let same_ret = wrapper.ret_type(sema.db).display(sema.db).to_string()
+            == func.ret_type(sema.db).display(sema.db).to_string();
+
+        return Some(SafeWrapperInfo { name: wrapper_name, exact_sign: same_params && same_ret });
+    }
+
+    return None;
+}
+
+fn check_safe_wrapper_call(
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+) -> Option<UnsafePattern> {
+    if target_expr.kind() != PATH_EXPR {
+        return None;
+    }
+
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    collect_safe_wrapper(sema, &mcall)?;
+
+    return Some(UnsafePattern::SafeWrapperCall);
+}
+
+struct AtomicStaticInfo {
+    pub static_src: ast::Static,
+    pub edits: Vec<(TextRange, String)>,
+}
+
+fn collect_atomic_type(builtin: &hir::BuiltinType) -> Option<String> {
+    if builtin.is_bool() {
+        return Some("AtomicBool".to_string());
+    }
+
+    if !builtin.is_int() && !builtin.is_uint() {
+        return None;
+    }
+
+    let name = builtin.name().to_string();
+
+    // There is no stable 128-bit atomic
+    if name.ends_with("128") {
+        return None;
+    }
+
+    let mut chars = name.chars();
+
+    let first = chars.next()?.to_ascii_uppercase();
+
+    return Some(format!("Atomic{}{}", first, chars.as_str()));
+}
+
+fn generate_atomic_access(
+    path_expr: &ast::PathExpr,
+    bool_sign: bool,
+) -> Option<(SyntaxNode, String)> {
+    let parent = path_expr.syntax().parent()?;
+
+    let mut buf = String::new();
+
+    // "COUNTER = v" / "COUNTER += v", the static is the place being written
+    if let Some(bin_expr) = ast::BinExpr::cast(parent.clone()) {
+        if bin_expr.lhs()?.syntax() == path_expr.syntax() {
+            let method = match bin_expr.op_kind()? {
+                ast::BinaryOp::Assignment { op: None } => "store",
+                ast::BinaryOp::Assignment { op: Some(ast::ArithOp::Add) } if !bool_sign => {
+                    "fetch_add"
+                }
+                ast::BinaryOp::Assignment { op: Some(ast::ArithOp::Sub) } if !bool_sign => {
+                    "fetch_sub"
+                }
+                ast::BinaryOp::Assignment { op: Some(ast::ArithOp::BitOr) } => "fetch_or",
+                ast::BinaryOp::Assignment { op: Some(ast::ArithOp::BitAnd) } => "fetch_and",
+                ast::BinaryOp::Assignment { op: Some(ast::ArithOp::BitXor) } => "fetch_xor",
+                ast::BinaryOp::Assignment { .. } => return None,
+                _ => {
+                    format_to!(buf, "{}.load(Ordering::Relaxed)", path_expr);
+                    return Some((path_expr.syntax().clone(), buf));
+                }
+            };
+
+            format_to!(buf, "{}.{}({}, Ordering::Relaxed)", path_expr, method, bin_expr.rhs()?);
+            return Some((bin_expr.syntax().clone(), buf));
+        }
+    }
+
+    // A borrow would point at the atomic instead of the integer
+    if parent.kind() == REF_EXPR {
+        return None;
+    }
+
+    format_to!(buf, "{}.load(Ordering::Relaxed)", path_expr);
+
+    return Some((path_expr.syntax().clone(), buf));
+}
+
+fn collect_atomic_edit_range(node: &SyntaxNode, buf: String) -> Option<(TextRange, String)> {
+    // The statement form carries the semicolon over to the replacement
+    let (stmt, stmt_buf) = match node.parent().and_then(ast::ExprStmt::cast) {
+        Some(expr_stmt) => (expr_stmt.syntax().clone(), format!("{};", buf)),
+        None => (node.clone(), buf.clone()),
+    };
+
+    let block = stmt
+        .parent()
+        .and_then(ast::StmtList::cast)
+        .and_then(|stmt_list| stmt_list.syntax().parent())
+        .and_then(ast::BlockExpr::cast)
+        .filter(|block| block.unsafe_token().is_some());
+
+    // "unsafe { COUNTER += 1; }" loses its only statement, so the block goes with it
+    if let Some(block) = block {
+        let stmt_list = block.stmt_list()?;
+
+        if stmt_list.statements().count() + stmt_list.tail_expr().iter().count() == 1 {
+            return Some((block.syntax().text_range(), stmt_buf));
+        }
+    }
+
+    return Some((node.text_range(), buf));
+}
+
+fn check_atomic_import(file: &SyntaxNode, name: &str) -> bool {
+    return file.descendants().filter_map(ast::Use::cast).any(|use_item| {
+        use_item
+            .syntax()
+            .descendants()
+            .filter_map(ast::NameRef::cast)
+            .any(|name_ref| name_ref.text() == name)
+    });
+}
+
+fn generate_atomic_static_edits(
+    static_src: &ast::Static,
+    atomic_ty: &str,
+) -> Option<Vec<(TextRange, String)>> {
+    let mut edits = Vec::new();
+
+    let mut_token = static_src.mut_token()?;
+
+    let mut_end = mut_token
+        .next_token()
+        .filter(|token| token.kind() == WHITESPACE)
+        .map_or(mut_token.text_range().end(), |token| token.text_range().end());
+
+    edits.push((TextRange::new(mut_token.text_range().start(), mut_end), String::new()));
+
+    edits.push((static_src.ty()?.syntax().text_range(), atomic_ty.to_string()));
+
+    let body = static_src.body()?;
+
+    edits.push((body.syntax().text_range(), format!("{}::new({})", atomic_ty, body)));
+
+    // Only bring in the names that the file doesn't import yet
+    let file = static_src.syntax().ancestors().last()?;
+
+    let imports: Vec<&str> = [atomic_ty, "Ordering"]
+        .into_iter()
+        .filter(|name| !check_atomic_import(&file, name))
+        .collect();
+
+    if !imports.is_empty() {
+        let mut buf = String::new();
+
+        match imports.len() {
+            1 => format_to!(
+                buf,
+                "use std::sync::atomic::{};\n\n{}",
+                imports[0],
+                static_src.indent_level()
+            ),
+            _ => format_to!(
+                buf,
+                "use std::sync::atomic::{{{}}};\n\n{}",
+                imports.join(", "),
+                static_src.indent_level()
+            ),
+        }
+
+        edits.push((TextRange::empty(static_src.syntax().text_range().start()), buf));
+    }
+
+    return Some(edits);
+}
+
+fn collect_atomic_static(
+    sema: &Semantics<'_, RootDatabase>,
+    path_expr: &ast::PathExpr,
+) -> Option<AtomicStaticInfo> {
+    let static_def = match sema.resolve_path(&path_expr.path()?)? {
+        hir::PathResolution::Def(hir::ModuleDef::Static(it)) if it.is_mut(sema.db) => it,
+        _ => return None,
+    };
+
+    let builtin = static_def.ty(sema.db).as_builtin()?;
+
+    let atomic_ty = collect_atomic_type(&builtin)?;
+
+    let static_src = static_def.source(sema.db)?;
+
+    if static_src.file_id.is_macro() {
+        return None;
+    }
+
+    let file_id = static_src.file_id.original_file(sema.db);
+
+    let mut edits = generate_atomic_static_edits(&static_src.value, &atomic_ty)?;
+
+    let mut access_ranges: Vec<TextRange> = Vec::new();
+
+    // Every use has to become an atomic operation, or the static can't change its type
+    for (usage_file, references) in Definition::Static(static_def).usages(sema).all() {
+        if usage_file != file_id {
+            return None;
+        }
+
+        for reference in references {
+            let name_ref = match reference.name {
+                ast::NameLike::NameRef(name_ref) => name_ref,
+                _ => return None,
+            };
+
+            let usage = name_ref.syntax().ancestors().find_map(ast::PathExpr::cast)?;
+
+            let (node, buf) = generate_atomic_access(&usage, builtin.is_bool())?;
+
+            // "COUNTER = COUNTER + 1" would need one rewrite nested in another
+            let range = node.text_range();
+
+            if access_ranges
+                .iter()
+                .any(|other| other.intersect(range).map_or(false, |common| !common.is_empty()))
+            {
+                return None;
+            }
+
+            access_ranges.push(range);
+
+            edits.push(collect_atomic_edit_range(&node, buf)?);
+        }
+    }
+
+    return Some(AtomicStaticInfo { static_src: static_src.value, edits });
+}
+
+fn convert_to_atomic_static(
+    acc: &mut Assists,
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+) -> Option<()> {
+    let path_expr = ast::PathExpr::cast(target_expr.clone())?;
+
+    let AtomicStaticInfo { static_src, edits, .. } = collect_atomic_static(sema, &path_expr)?;
+
+    // The declaration and every access change together, or nothing changes
+    acc.add(
+        AssistId("convert_unsafe_to_safe", AssistKind::RefactorRewrite),
+        "Convert Unsafe to Safe",
+        static_src.syntax().text_range(),
+        |edit| {
+            for (range, buf) in edits {
+                edit.replace(range, buf);
+            }
+        },
+    );
+
+    return None;
+}
+
+struct BoxLeakInfo {
+    pub leaked: String,
+    pub boxed: ast::Expr,
+    pub leak_stmt: LetStmt,
+}
+
+fn collect_leaked_ptr_arg(expr: ast::Expr) -> Option<String> {
+    // "r", "r as *mut T" or "r as *const T as *mut T"
+    match expr {
+        ast::Expr::CastExpr(cast_expr) => return collect_leaked_ptr_arg(cast_expr.expr()?),
+        expr => return collect_raw_part_arg(expr),
+    }
+}
+
+fn collect_box_leak_let(stmt: &SyntaxNode, name: &str) -> Option<(LetStmt, ast::Expr)> {
+    let let_expr = ast::LetStmt::cast(stmt.clone())?;
+
+    match let_expr.pat()? {
+        ast::Pat::IdentPat(ident_pat) if ident_pat.name()?.to_string() == name => (),
+        _ => return None,
+    }
+
+    let mcall = match let_expr.initializer()? {
+        ast::Expr::CallExpr(mcall) => mcall,
+        _ => return None,
+    };
+
+    if mcall.expr()?.to_string() != "Box::leak" {
+        return None;
+    }
+
+    let boxed = mcall.arg_list()?.args().exactly_one().ok()?;
+
+    return Some((let_expr, boxed));
+}
+
+fn check_leaked_ref_escapes(stmt: &SyntaxNode, name: &str) -> bool {
+    // Handing the reference to a call or returning it may stash it somewhere the function can't see
+    return stmt
+        .descendants()
+        .filter_map(ast::PathExpr::cast)
+        .filter(|path_expr| path_expr.to_string() == name)
+        .any(|path_expr| {
+            path_expr.syntax().parent().and_then(ast::ArgList::cast).is_some()
+                || path_expr.syntax().ancestors().any(|node| ast::ReturnExpr::can_cast(node.kind()))
+        });
+}
+
+fn collect_box_leak_round_trip(mcall: &CallExpr, unsafe_expr: &BlockExpr) -> Option<BoxLeakInfo> {
+    let leaked = collect_leaked_ptr_arg(mcall.arg_list()?.args().exactly_one().ok()?)?;
+
+    // The statement that holds the "unsafe" block, the "Box::leak" has to sit in the same body
+    let reassembly = unsafe_expr
+        .syntax()
+        .ancestors()
+        .find(|node| node.parent().map_or(false, |parent| parent.kind() == STMT_LIST))?;
+
+    let mut leak = None;
+
+    for iter in reassembly.siblings(Direction::Prev).skip(1) {
+        if let Some((leak_stmt, boxed)) = collect_box_leak_let(&iter, &leaked) {
+            leak = Some((leak_stmt, boxed));
+            break;
+        }
+
+        if check_leaked_ref_escapes(&iter, &leaked) {
+            return None;
+        }
+    }
+
+    // Nothing may touch the reference once the box is rebuilt from it
+    if reassembly
+        .siblings(Direction::Next)
+        .skip(1)
+        .any(|iter| check_ident_mentioned(&iter, &leaked))
+    {
+        return None;
+    }
+
+    let (leak_stmt, boxed) = leak?;
+
+    return Some(BoxLeakInfo { leaked, boxed, leak_stmt });
+}
+
+fn collect_boxed_let(info: &BoxLeakInfo, box_name: &str) -> Option<ast::IdentPat> {
+    // The box has to be a local that stays alive once it is no longer leaked
+    for iter in info.leak_stmt.syntax().siblings(Direction::Prev).skip(1) {
+        match ast::LetStmt::cast(iter).and_then(|let_expr| let_expr.pat()) {
+            Some(ast::Pat::IdentPat(ident_pat)) if ident_pat.name()?.to_string() == box_name => {
+                return Some(ident_pat)
+            }
+            _ => continue,
+        }
+    }
+
+    return None;
+}
+
+fn collect_leaked_ref_uses(
+    info: &BoxLeakInfo,
+    reassembly: &SyntaxNode,
+    box_name: &str,
+) -> Option<Vec<(TextRange, String)>> {
+    let mut edits = Vec::new();
+
+    for iter in info
+        .leak_stmt
+        .syntax()
+        .siblings(Direction::Next)
+        .skip(1)
+        .take_while(|iter| iter != reassembly)
+    {
+        let uses: Vec<_> = iter
+            .descendants()
+            .filter_map(ast::PathExpr::cast)
+            .filter(|path_expr| path_expr.to_string() == info.leaked)
+            .collect();
+
+        // A mention that is no plain use, in a macro or a new binding, can't be followed
+        if iter
+            .descendants_with_tokens()
+            .filter_map(|element| element.into_token())
+            .filter(|token| token.kind() == IDENT && token.text() == info.leaked)
+            .count()
+            != uses.len()
+        {
+            return None;
+        }
+
+        for path_expr in uses {
+            let parent = path_expr.syntax().parent()?;
+
+            // "*r", "r.x" and "r.f()" reach through the box the same way, anything else borrows it
+            let auto_deref = ast::PrefixExpr::cast(parent.clone())
+                .map_or(false, |prefix| prefix.op_kind() == Some(ast::UnaryOp::Deref))
+                || ast::MethodCallExpr::cast(parent.clone())
+                    .and_then(|method| method.receiver())
+                    .map_or(false, |receiver| receiver.syntax() == path_expr.syntax())
+                || ast::FieldExpr::can_cast(parent.kind());
+
+            match auto_deref {
+                true => edits.push((path_expr.syntax().text_range(), box_name.to_string())),
+                false => {
+                    edits.push((path_expr.syntax().text_range(), format!("&mut *{}", box_name)))
+                }
+            }
+        }
+    }
+
+    return Some(edits);
+}
+
+fn convert_to_owned_box(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    let info = collect_box_leak_round_trip(&mcall, unsafe_expr)?;
+
+    let (let_expr, reassembly) = collect_reassembly_let(&mcall, unsafe_expr)?;
+
+    let rebuilt_name = match let_expr.pat()? {
+        ast::Pat::IdentPat(ident_pat) => ident_pat.name()?.to_string(),
+        _ => return None,
+    };
+
+    let box_name = match &info.boxed {
+        ast::Expr::PathExpr(path_expr) => path_expr.path()?.as_single_name_ref()?.to_string(),
+        _ => return None,
+    };
+
+    let box_pat = collect_boxed_let(&info, &box_name)?;
+
+    let uses = collect_leaked_ref_uses(&info, &reassembly, &box_name)?;
+
+    let delete_ranges =
+        [collect_stmt_range(info.leak_stmt.syntax()), collect_stmt_range(&reassembly)];
+
+    let rename_ranges = collect_rename_ranges(&reassembly, &rebuilt_name);
+
+    // The leaked reference was "&mut", borrowing the box the same way needs a "mut" binding
+    let mut_position = match box_pat.mut_token() {
+        Some(_) => None,
+        None => Some(box_pat.name()?.syntax().text_range().start()),
+    };
+
+    let target_range = reassembly.text_range();
+
+    acc.add(
+        AssistId("convert_unsafe_to_safe", AssistKind::RefactorRewrite),
+        "Keep the Box instead of leaking it",
+        target_range,
+        |edit| {
+            if let Some(position) = mut_position {
+                edit.insert(position, "mut ");
+            }
+            for range in delete_ranges {
+                edit.delete(range);
+            }
+            for (range, buf) in uses {
+                edit.replace(range, buf);
+            }
+            for range in rename_ranges {
+                edit.replace(range, box_name.clone());
+            }
+        },
+    );
+
+    return None;
+}
+
+struct AllocVecInfo {
+    pub decl: String,
+    pub alloc_stmt: LetStmt,
+    pub layout_stmt: Option<LetStmt>,
+    pub rewrites: Vec<(SyntaxNode, String)>,
+}
+
+enum AllocPtrOp {
+    Write(u128, ast::Expr),
+    Read(u128),
+    Dealloc,
+}
+
+fn collect_layout_array(expr: &ast::Expr) -> Option<(ast::Type, ast::Expr)> {
+    // "Layout::array::<T>(n).unwrap()", the only layout that tells the element type and count
+    let mcall = match expr {
+        ast::Expr::MethodCallExpr(mcall) => mcall,
+        _ => return None,
+    };
+
+    if !["unwrap", "expect"].contains(&mcall.name_ref()?.to_string().as_str()) {
+        return None;
+    }
+
+    let array_call = match mcall.receiver()? {
+        ast::Expr::CallExpr(array_call) => array_call,
+        _ => return None,
+    };
+
+    let path = match array_call.expr()? {
+        ast::Expr::PathExpr(path_expr) => path_expr.path()?,
+        _ => return None,
+    };
+
+    let segment = path.segment()?;
+
+    if segment.name_ref()?.to_string() != "array"
+        || path.qualifier()?.segment()?.to_string() != "Layout"
+    {
+        return None;
+    }
+
+    let elem_ty = match segment.generic_arg_list()?.generic_args().exactly_one().ok()? {
+        ast::GenericArg::TypeArg(type_arg) => type_arg.ty()?,
+        _ => return None,
+    };
+
+    let count = array_call.arg_list()?.args().exactly_one().ok()?;
+
+    return Some((elem_ty, count));
+}
+
+fn collect_alloc_layout(
+    arg: &ast::Expr,
+    alloc_stmt: &SyntaxNode,
+) -> Option<(ast::Type, ast::Expr, Option<LetStmt>)> {
+    if let Some((elem_ty, count)) = collect_layout_array(arg) {
+        return Some((elem_ty, count, None));
+    }
+
+    // Otherwise a layout bound by the closest "let" above the allocation
+    let name = collect_raw_part_arg(arg.clone())?;
+
+    for iter in alloc_stmt.siblings(Direction::Prev).skip(1) {
+        let let_expr = match ast::LetStmt::cast(iter) {
+            Some(let_expr) => let_expr,
+            None => continue,
+        };
+
+        let ident_pat = match let_expr.pat()? {
+            ast::Pat::IdentPat(ident_pat) if ident_pat.name()?.to_string() == name => ident_pat,
+            _ => continue,
+        };
+
+        if ident_pat.mut_token().is_some() {
+            return None;
+        }
+
+        let (elem_ty, count) = collect_layout_array(&let_expr.initializer()?)?;
+
+        return Some((elem_ty, count, Some(let_expr)));
+    }
+
+    return None;
+}
+
+fn collect_alloc_ptr_index(expr: &ast::Expr, ptr: &str) -> Option<u128> {
+    // "p" itself or "p.add(i)" with a literal "i", anything else can't be checked against the count
+    match expr {
+        ast::Expr::PathExpr(_) if expr.to_string() == ptr => return Some(0),
+        ast::Expr::MethodCallExpr(mcall) => {
+            if mcall.name_ref()?.to_string() != "add" || mcall.receiver()?.to_string() != ptr {
+                return None;
+            }
+
+            return collect_offset_literal(&Some(mcall.arg_list()?.args().exactly_one().ok()?));
+        }
+        _ => return None,
+    }
+}
+
+fn collect_alloc_ptr_op(expr: &ast::Expr, ptr: &str, layout: &str) -> Option<AllocPtrOp> {
+    match expr {
+        ast::Expr::MethodCallExpr(mcall) => {
+            let method = mcall.name_ref()?.to_string();
+
+            let index = collect_alloc_ptr_index(&mcall.receiver()?, ptr)?;
+
+            let args: Vec<ast::Expr> = mcall.arg_list()?.args().collect();
+
+            match (method.as_str(), args.as_slice()) {
+                // The written value must not reach back into the buffer itself
+                ("write", [value]) if !check_ident_mentioned(value.syntax(), ptr) => {
+                    return Some(AllocPtrOp::Write(index, value.clone()))
+                }
+                ("read", []) => return Some(AllocPtrOp::Read(index)),
+                _ => return None,
+            }
+        }
+        ast::Expr::CallExpr(mcall) => {
+            if !["dealloc", "alloc::dealloc", "std::alloc::dealloc"]
+                .contains(&mcall.expr()?.to_string().as_str())
+            {
+                return None;
+            }
+
+            let (freed, freed_layout) = mcall.arg_list()?.args().collect_tuple()?;
+
+            // Given back through the "*mut u8" it came from, with the layout it was allocated with
+            let freed = match freed {
+                ast::Expr::CastExpr(cast_expr) if cast_expr.ty()?.to_string() == "*mut u8" => {
+                    cast_expr.expr()?
+                }
+                _ => return None,
+            };
+
+            if freed.to_string() != ptr || freed_layout.to_string() != layout {
+                return None;
+            }
+
+            return Some(AllocPtrOp::Dealloc);
+        }
+        _ => return None,
+    }
+}
+
+fn collect_alloc_stmt_ops(stmt: &SyntaxNode, ptr: &str, layout: &str) -> Option<Vec<AllocPtrOp>> {
+    // "let x = unsafe { p.add(i).read() };"
+    if let Some(let_expr) = ast::LetStmt::cast(stmt.clone()) {
+        if check_ident_mentioned(let_expr.pat()?.syntax(), ptr) {
+            return None;
+        }
+
+        let stmt_list = match let_expr.initializer()? {
+            ast::Expr::BlockExpr(block) if block.unsafe_token().is_some() => block.stmt_list()?,
+            _ => return None,
+        };
+
+        if stmt_list.statements().next().is_some() {
+            return None;
+        }
+
+        return match collect_alloc_ptr_op(&stmt_list.tail_expr()?, ptr, layout)? {
+            AllocPtrOp::Read(index) => Some(vec![AllocPtrOp::Read(index)]),
+            _ => None,
+        };
+    }
+
+    // "unsafe { p.add(i).write(x); ... dealloc(p as *mut u8, layout); }" with nothing else in it,
+    // as a statement or as the tail of the enclosing block
+    let expr = match ast::ExprStmt::cast(stmt.clone()) {
+        Some(expr_stmt) => expr_stmt.expr()?,
+        None => ast::Expr::cast(stmt.clone())?,
+    };
+
+    let stmt_list = match expr {
+        ast::Expr::BlockExpr(block) if block.unsafe_token().is_some() => block.stmt_list()?,
+        _ => return None,
+    };
+
+    let mut exprs = Vec::new();
+
+    for iter in stmt_list.statements() {
+        match iter {
+            ast::Stmt::ExprStmt(expr_stmt) => exprs.push(expr_stmt.expr()?),
+            _ => return None,
+        }
+    }
+
+    exprs.extend(stmt_list.tail_expr());
+
+    let ops: Vec<AllocPtrOp> =
+        exprs.iter().map(|expr| collect_alloc_ptr_op(expr, ptr, layout)).collect::<Option<_>>()?;
+
+    // A bare read as a statement throws the value away, leave that one alone
+    if ops.iter().any(|op| matches!(op, AllocPtrOp::Read(_))) {
+        return None;
+    }
+
+    return Some(ops);
+}
+
+fn check_alloc_push_order(ops: &[&AllocPtrOp]) -> bool {
+    // Writes filling the buffer from the front, and reads only of what has been written so far
+    let mut written = 0;
+
+    for op in ops {
+        match op {
+            AllocPtrOp::Write(index, _) if *index == written => written += 1,
+            AllocPtrOp::Write(..) => return false,
+            AllocPtrOp::Read(index) if *index >= written => return false,
+            _ => (),
+        }
+    }
+
+    return true;
+}
+
+fn collect_zero_literal(elem_ty: &ast::Type) -> Option<&'static str> {
+    match elem_ty.to_string().as_str() {
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64" | "i128"
+        | "isize" => return Some("0"),
+        "f32" | "f64" => return Some("0.0"),
+        _ => return None,
+    }
+}
+
+fn check_layout_kept(
+    layout_stmt: &LetStmt,
+    alloc_stmt: &LetStmt,
+    stmts: &[(SyntaxNode, Vec<AllocPtrOp>)],
+    layout: &str,
+) -> bool {
+    // The layout can go with the allocation only when "alloc" and "dealloc" were its sole users
+    return layout_stmt.syntax().siblings(Direction::Next).skip(1)
+        .filter(|iter| iter != alloc_stmt.syntax() && check_ident_mentioned(iter, layout))
+        .any(|iter| match stmts.iter().find(|(stmt, _)| *stmt == iter) {
+            Some((stmt, ops)) => ast::LetStmt::can_cast(stmt.kind()) || ops.iter().any(|op| {
+                matches!(op, AllocPtrOp::Write(_, value) if check_ident_mentioned(value.syntax(), layout))
+            }),
+            None => true,
+        });
+}
+
+fn generate_alloc_stmt_rewrite(
+    stmt: &SyntaxNode,
+    ops: &[AllocPtrOp],
+    ptr: &str,
+    push_sign: bool,
+) -> Option<String> {
+    if let Some(let_expr) = ast::LetStmt::cast(stmt.clone()) {
+        let index = match ops {
+            [AllocPtrOp::Read(index)] => index,
+            _ => return None,
+        };
+
+        let range: std::ops::Range<usize> =
+            (let_expr.initializer()?.syntax().text_range() - stmt.text_range().start()).into();
+
+        let mut buf = stmt.to_string();
+
+        buf.replace_range(range, &format!("{}[{}]", ptr, index));
+
+        return Some(buf);
+    }
+
+    let indent_level = IndentLevel::from_node(stmt);
+
+    // The "dealloc" goes away, the Vec frees itself once it is dropped
+    let lines: Vec<String> = ops
+        .iter()
+        .filter_map(|op| match op {
+            AllocPtrOp::Write(_, value) if push_sign => Some(format!("{}.push({});", ptr, value)),
+            AllocPtrOp::Write(index, value) => Some(format!("{}[{}] = {};", ptr, index, value)),
+            _ => None,
+        })
+        .collect();
+
+    return Some(lines.join(&format!("\n{}", indent_level)));
+}
+
+fn collect_alloc_to_vec(mcall: &CallExpr, unsafe_expr: &BlockExpr) -> Option<AllocVecInfo> {
+    // "let p = unsafe { alloc(layout) as *mut T };" with the cast alone in the block
+    let cast_expr = mcall.syntax().parent().and_then(ast::CastExpr::cast)?;
+
+    let stmt_list = unsafe_expr.stmt_list()?;
+
+    if stmt_list.statements().next().is_some()
+        || stmt_list.tail_expr()?.syntax() != cast_expr.syntax()
+    {
+        return None;
+    }
+
+    let alloc_stmt = unsafe_expr.syntax().parent().and_then(ast::LetStmt::cast)?;
+
+    let ptr_name = match alloc_stmt.pat()? {
+        ast::Pat::IdentPat(ident_pat) => ident_pat.name()?.to_string(),
+        _ => return None,
+    };
+
+    let layout_arg = mcall.arg_list()?.args().exactly_one().ok()?;
+
+    let (elem_ty, count, layout_stmt) = collect_alloc_layout(&layout_arg, alloc_stmt.syntax())?;
+
+    match cast_expr.ty()? {
+        ast::Type::PtrType(ptr_ty)
+            if ptr_ty.mut_token().is_some() && ptr_ty.ty()?.to_string() == elem_ty.to_string() => {}
+        _ => return None,
+    }
+
+    let layout = layout_arg.to_string();
+
+    let mut stmts: Vec<(SyntaxNode, Vec<AllocPtrOp>)> = Vec::new();
+
+    for iter in alloc_stmt.syntax().siblings(Direction::Next).skip(1) {
+        if !check_ident_mentioned(&iter, &ptr_name) {
+            continue;
+        }
+
+        // Anything else, e.g. handing the pointer to a call, lets it escape
+        let ops = collect_alloc_stmt_ops(&iter, &ptr_name, &layout)?;
+
+        stmts.push((iter, ops));
+    }
+
+    let ops: Vec<&AllocPtrOp> = stmts.iter().flat_map(|(_, ops)| ops).collect();
+
+    // Exactly one "dealloc", and nothing touches the buffer once it is given back
+    if !matches!(ops.last(), Some(AllocPtrOp::Dealloc))
+        || ops.iter().filter(|op| matches!(op, AllocPtrOp::Dealloc)).count() != 1
+    {
+        return None;
+    }
+
+    if let Some(len) = collect_offset_literal(&Some(count.clone())) {
+        let in_bounds = ops.iter().all(|op| match op {
+            AllocPtrOp::Write(index, _) | AllocPtrOp::Read(index) => *index < len,
+            AllocPtrOp::Dealloc => true,
+        });
+
+        if !in_bounds {
+            return None;
+        }
+    }
+
+    let push_sign = check_alloc_push_order(&ops);
+
+    let init = match push_sign {
+        true => format!("Vec::with_capacity({})", count),
+        // Out of order writes need every slot to exist already
+        false => format!("vec![{}; {}]", collect_zero_literal(&elem_ty)?, count),
+    };
+
+    let mut_sign = match ops.iter().any(|op| matches!(op, AllocPtrOp::Write(..))) {
+        true => "mut ",
+        false => "",
+    };
+
+    let decl = format!("let {}{}: Vec<{}> = {};", mut_sign, ptr_name, elem_ty, init);
+
+    let layout_stmt = layout_stmt
+        .filter(|layout_stmt| !check_layout_kept(layout_stmt, &alloc_stmt, &stmts, &layout));
+
+    let rewrites = stmts
+        .iter()
+        .map(|(stmt, ops)| {
+            Some((stmt.clone(), generate_alloc_stmt_rewrite(stmt, ops, &ptr_name, push_sign)?))
+        })
+        .collect::<Option<_>>()?;
+
+    return Some(AllocVecInfo { decl, alloc_stmt, layout_stmt, rewrites });
+}
+
+fn convert_to_alloc_vec(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    let info = collect_alloc_to_vec(&mcall, unsafe_expr)?;
+
+    let target_range = info.alloc_stmt.syntax().text_range();
+
+    acc.add(
+        AssistId("convert_unsafe_to_safe", AssistKind::RefactorRewrite),
+        "Allocate through a Vec instead",
+        target_range,
+        |edit| {
+            if let Some(layout_stmt) = &info.layout_stmt {
+                edit.delete(collect_stmt_range(layout_stmt.syntax()));
+            }
+
+            edit.replace(target_range, info.decl.clone());
+
+            for (stmt, buf) in &info.rewrites {
+                match buf.is_empty() {
+                    true => edit.delete(collect_stmt_range(stmt)),
+                    false => edit.replace(stmt.text_range(), buf.clone()),
+                }
+            }
+        },
+    );
+
+    return None;
+}
+
+pub fn check_static_mut_access(
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+) -> Option<UnsafePattern> {
+    let path_expr = ast::PathExpr::cast(target_expr.clone())?;
+
+    // Only reads and writes of a "static mut" need the "unsafe" block, plain statics don't
+    match sema.resolve_path(&path_expr.path()?)? {
+        hir::PathResolution::Def(hir::ModuleDef::Static(it)) if it.is_mut(sema.db) => {
+            return Some(UnsafePattern::StaticMutAccess)
+        }
+        _ => return None,
+    }
+}
+
+fn collect_manually_drop_let(stmt: &SyntaxNode, name: &str) -> Option<ast::Expr> {
+    let let_expr = ast::LetStmt::cast(stmt.clone())?;
+
+    match let_expr.pat()? {
+        ast::Pat::IdentPat(ident_pat) if ident_pat.name()?.to_string() == name => (),
+        _ => return None,
+    }
+
+    let mcall = match let_expr.initializer()? {
+        ast::Expr::CallExpr(mcall) => mcall,
+        _ => return None,
+    };
+
+    if ![
+        "ManuallyDrop::new",
+        "mem::ManuallyDrop::new",
+        "std::mem::ManuallyDrop::new",
+        "core::mem::ManuallyDrop::new",
+    ]
+    .contains(&mcall.expr()?.to_string().as_str())
+    {
+        return None;
+    }
+
+    return mcall.arg_list()?.args().exactly_one().ok();
+}
+
+fn check_wrapper_use(node: &SyntaxNode, name: &str) -> bool {
+    let uses: Vec<_> = node
+        .descendants()
+        .filter_map(ast::PathExpr::cast)
+        .filter(|path_expr| path_expr.to_string() == name)
+        .collect();
+
+    // A mention that is no plain use, in a macro or a new binding, can't be followed
+    if node
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| token.kind() == IDENT && token.text() == name)
+        .count()
+        != uses.len()
+    {
+        return false;
+    }
+
+    // "*m", "m.x" and "m.f()" only reach through the wrapper, anything else may move the value out of it
+    return uses.iter().all(|path_expr| {
+        let parent = match path_expr.syntax().parent() {
+            Some(parent) => parent,
+            None => return false,
+        };
+
+        ast::PrefixExpr::cast(parent.clone())
+            .map_or(false, |prefix| prefix.op_kind() == Some(ast::UnaryOp::Deref))
+            || ast::MethodCallExpr::cast(parent.clone())
+                .and_then(|method| method.receiver())
+                .map_or(false, |receiver| receiver.syntax() == path_expr.syntax())
+            || ast::FieldExpr::can_cast(parent.kind())
+    });
+}
+
+fn collect_manually_drop_origin(mcall: &CallExpr, unsafe_expr: &BlockExpr) -> Option<ast::Expr> {
+    let name = match mcall.arg_list()?.args().exactly_one().ok()? {
+        ast::Expr::RefExpr(ref_expr) if ref_expr.mut_token().is_some() => {
+            collect_raw_part_arg(ref_expr.expr()?)?
+        }
+        _ => return None,
+    };
+
+    // The drop has to be the only mention of the wrapper inside the "unsafe" block
+    let mentions = unsafe_expr
+        .syntax()
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| token.kind() == IDENT && token.text() == name)
+        .count();
+
+    if mentions != 1 {
+        return None;
+    }
+
+    let reassembly = unsafe_expr
+        .syntax()
+        .ancestors()
+        .find(|node| node.parent().map_or(false, |parent| parent.kind() == STMT_LIST))?;
+
+    let mut origin = None;
+
+    for iter in reassembly.siblings(Direction::Prev).skip(1) {
+        if let Some(wrapped) = collect_manually_drop_let(&iter, &name) {
+            origin = Some(wrapped);
+            break;
+        }
+
+        if !check_wrapper_use(&iter, &name) {
+            return None;
+        }
+    }
+
+    // Once dropped the wrapper must not be touched again
+    if reassembly.siblings(Direction::Next).skip(1).any(|iter| check_ident_mentioned(&iter, &name))
+    {
+        return None;
+    }
+
+    return origin;
+}
+
+pub fn check_manually_drop(
+    target_expr: &SyntaxNode,
+    unsafe_expr: &BlockExpr,
+) -> Option<UnsafePattern> {
+    if target_expr.kind() != PATH_EXPR
+        || !UnsafePattern::ManuallyDropDrop.matches(&target_expr.to_string())
+    {
+        return None;
+    }
+
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    collect_manually_drop_origin(&mcall, unsafe_expr)?;
+
+    return Some(UnsafePattern::ManuallyDropDrop);
+}
+
+fn generate_safe_wrapper_call(mcall: &CallExpr, info: &SafeWrapperInfo) -> Option<String> {
+    let path = match mcall.expr()? {
+        ast::Expr::PathExpr(path_expr) => path_expr.path()?,
+        _ => return None,
+    };
+
+    let mut buf = String::new();
+
+    match path.qualifier() {
+        Some(qualifier) => format_to!(buf, "{}::{}{}", qualifier, info.name, mcall.arg_list()?),
+        None => format_to!(buf, "{}{}", info.name, mcall.arg_list()?),
+    }
+
+    return Some(buf);
+}
+
+fn convert_to_safe_wrapper(
+    acc: &mut Assists,
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    let info = collect_safe_wrapper(sema, &mcall)?;
+
+    // A wrapper with a different signature is only pointed out in the hover
+    if !info.exact_sign {
+        return None;
+    }
+
+    let mut buf = generate_safe_wrapper_call(&mcall, &info)?;
+
+    if mcall.syntax().parent()?.kind() == STMT_LIST {
+        if !check_single_call_expr(&mcall)? {
+            return None;
+        }
+
+        replace_source_code(acc, unsafe_expr.syntax().text_range(), &buf);
+        return None;
+    }
+
+    let expr_stmt = mcall.syntax().parent().and_then(ast::ExprStmt::cast)?;
+
+    buf.push(';');
+
+    if check_single_expr(&expr_stmt) {
+        replace_source_code(acc, unsafe_range, &buf);
+        return None;
+    }
+
+    buf.push('\n');
+
+    return reindent_expr(unsafe_expr, acc, expr_stmt.syntax().text_range(), &buf);
+}
+
+struct CpyNonOverlapInfo {
+    src_expr: IndexExpr,
+    dst_expr: IndexExpr,
+    count: ast::Expr,
+}
+
+fn extract_index_expr(
+    backward_list: impl Iterator<Item = SyntaxNode>,
+    expr_variable: &String,
+) -> Option<IndexExpr> {
+    for iter in backward_list {
+        if iter.kind() == LET_STMT && iter.to_string().contains(expr_variable) {
+            let let_expr = ast::LetStmt::cast(iter)?;
+            let expr = ast::IndexExpr::cast(let_expr.syntax().last_child()?.first_child()?)?;
+            return Some(expr);
+        }
+    }
+    return None;
+}
+
+fn collect_cpy_nonoverlap_info(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+    unsafe_expr: &BlockExpr,
+) -> Option<CpyNonOverlapInfo> {
+    let src_expr;
+
+    let dst_expr;
+
+    let mut backward_list_src = unsafe_expr.syntax().siblings(Direction::Prev);
+
+    let mut backward_list_dst = unsafe_expr.syntax().siblings(Direction::Prev);
+
+    if unsafe_expr.syntax().parent()?.kind() != STMT_LIST {
+        backward_list_src = unsafe_expr.syntax().parent()?.siblings(Direction::Prev);
+        backward_list_dst = unsafe_expr.syntax().parent()?.siblings(Direction::Prev);
+    }
+
+    if mcall.arg_list()?.args().nth(0)?.syntax().kind() == PATH_EXPR {
+        src_expr = extract_index_expr(
+            &mut backward_list_src,
+            &mcall.arg_list()?.args().nth(0)?.to_string(),
+        )?;
+    } else {
+        src_expr = ast::IndexExpr::cast(mcall.arg_list()?.args().nth(0)?.syntax().children().nth(0)?)?;
+    }
+
+    if mcall.arg_list()?.args().nth(1)?.syntax().kind() == PATH_EXPR {
+        dst_expr = extract_index_expr(
+            &mut backward_list_dst,
+            &mcall.arg_list()?.args().nth(1)?.to_string(),
+        )?;
+    } else {
+        dst_expr = ast::IndexExpr::cast(mcall.arg_list()?.args().nth(1)?.syntax().children().nth(0)?)?;
+    }
+
+    if !check_copy_base_ty(sema, &src_expr.base()?, mcall.syntax())
+        || !check_copy_base_ty(sema, &dst_expr.base()?, mcall.syntax())
+    {
+        return None;
+    }
+
+    let count = mcall.arg_list()?.args().nth(2)?;
+
+    return Some(CpyNonOverlapInfo { src_expr, dst_expr, count });
+}
+
+fn make_index_expr(expr_index: &IndexExpr, count: &ast::Expr) -> Option<ast::Expr> {
+    let range = make_offset_range(collect_index_range(expr_index)?.start(), count.clone());
+
+    return Some(make::expr_index(make_postfix_operand(expr_index.base()?), range));
+}
+
+pub fn generate_copy_from_slice_string(
+    src_expr: IndexExpr,
+    dst_expr: IndexExpr,
+    count: ast::Expr,
+) -> Option<String> {
+    let src = make::expr_ref(make_index_expr(&src_expr, &count)?, false);
+
+    let copy = make::expr_method_call(
+        make_index_expr(&dst_expr, &count)?,
+        make::name_ref("copy_from_slice"),
+        make::arg_list([src]),
+    );
+
+    let mut buf = String::new();
+
+    format_to!(buf, "{};", copy);
+
+    buf.push('\n');
+
+    return Some(buf);
+
+}
+
+struct CpyAddOffsetInfo {
+    base_expr: ast::Expr,
+    offset: Option<ast::Expr>,
+}
+
+fn collect_cpy_add_offset_info(arg: &ast::Expr) -> Option<CpyAddOffsetInfo> {
+    // Match the "base.as_ptr().add(i)" / "base.as_mut_ptr().add(i)" form
+    let add_call = match arg {
+        ast::Expr::MethodCallExpr(add_call) => add_call,
+        _ => return None,
+    };
+
+    if add_call.name_ref()?.to_string() != "add" {
+        return None;
+    }
+
+    let ptr_call = match add_call.receiver()? {
+        ast::Expr::MethodCallExpr(ptr_call) => ptr_call,
+        _ => return None,
+    };
+
+    let ptr_name = ptr_call.name_ref()?.to_string();
+
+    if !UnsafePattern::AsPtr.matches(&ptr_name) && ptr_name != "as_mut_ptr" {
+        return None;
+    }
+
+    let base_expr = ptr_call.receiver()?;
+
+    let offset = Some(add_call.arg_list()?.args().exactly_one().ok()?);
+
+    return Some(CpyAddOffsetInfo { base_expr, offset });
+}
+
+fn check_simple_count_expr(count: &ast::Expr) -> bool {
+    // The count is appended to the offsets, so it must not split on operator precedence
+    return matches!(
+        count,
+        ast::Expr::Literal(_)
+            | ast::Expr::PathExpr(_)
+            | ast::Expr::MethodCallExpr(_)
+            | ast::Expr::FieldExpr(_)
+            | ast::Expr::CallExpr(_)
+            | ast::Expr::ParenExpr(_)
+            | ast::Expr::IndexExpr(_)
+    );
+}
+
+fn generate_copy_from_slice_add_format(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+) -> Option<String> {
+    let src = collect_cpy_add_offset_info(&mcall.arg_list()?.args().next()?)?;
+
+    let dst = collect_cpy_add_offset_info(&mcall.arg_list()?.args().nth(1)?)?;
+
+    if !check_copy_base_ty(sema, &src.base_expr, mcall.syntax())
+        || !check_copy_base_ty(sema, &dst.base_expr, mcall.syntax())
+    {
+        return None;
+    }
+
+    // The count is parenthesized where needed when it is appended to the offsets
+    let count = mcall.arg_list()?.args().nth(2)?;
+
+    return Some(generate_copy_from_slice_offset_string(&src, &dst, &count));
+}
+
+fn make_offset_index(info: &CpyAddOffsetInfo, count: &ast::Expr) -> ast::Expr {
+    return make::expr_index(
+        make_postfix_operand(info.base_expr.clone()),
+        make_offset_range(info.offset.clone(), count.clone()),
+    );
+}
+
+fn generate_copy_from_slice_offset_string(
+    src: &CpyAddOffsetInfo,
+    dst: &CpyAddOffsetInfo,
+    count: &ast::Expr,
+) -> String {
+    let src = make::expr_ref(make_offset_index(src, count), false);
+
+    let copy = make::expr_method_call(
+        make_offset_index(dst, count),
+        make::name_ref("copy_from_slice"),
+        make::arg_list([src]),
+    );
+
+    let mut buf = String::new();
+
+    format_to!(buf, "{};", copy);
+
+    buf.push('\n');
+
+    return buf;
+}
+
+struct LibcCopyArg {
+    info: CpyAddOffsetInfo,
+    ptr_call: MethodCallExpr,
+}
+
+fn collect_ptr_shape(expr: &ast::Expr) -> Option<CpyAddOffsetInfo> {
+    if let Some(info) = collect_cpy_add_offset_info(expr) {
+        return Some(info);
+    }
+
+    // "addr_of!(base[i])" points at index "i" like "base.as_ptr().add(i)"
+    if let Some((ast::Expr::IndexExpr(index_expr), _)) = collect_addr_of_place(expr) {
+        let index = index_expr.index()?;
+
+        if matches!(index, ast::Expr::RangeExpr(_)) {
+            return None;
+        }
+
+        return Some(CpyAddOffsetInfo { base_expr: index_expr.base()?, offset: Some(index) });
+    }
+
+    // "base[a..].as_ptr()" points at index "a", a whole "base.as_ptr()" at index 0
+    let ptr_call = match expr {
+        ast::Expr::MethodCallExpr(ptr_call) => ptr_call,
+        _ => return None,
+    };
+
+    let ptr_name = ptr_call.name_ref()?.to_string();
+
+    if !UnsafePattern::AsPtr.matches(&ptr_name) && ptr_name != "as_mut_ptr" {
+        return None;
+    }
+
+    if let Some(ast::Expr::IndexExpr(index_expr)) = ptr_call.receiver() {
+        if let Some(range) = collect_index_range(&index_expr) {
+            return Some(CpyAddOffsetInfo { base_expr: index_expr.base()?, offset: range.start() });
+        }
+    }
+
+    return Some(CpyAddOffsetInfo { base_expr: ptr_call.receiver()?, offset: None });
+}
+
+fn collect_traced_ptr(expr: &ast::Expr, scope: &SyntaxNode) -> Option<CpyAddOffsetInfo> {
+    let name = match expr {
+        ast::Expr::PathExpr(path_expr) => path_expr.path()?.as_single_name_ref()?.to_string(),
+        _ => return collect_ptr_shape(expr),
+    };
+
+    return collect_ptr_shape(&collect_ptr_let_init(&name, scope)?);
+}
+
+fn collect_ptr_let_init(name: &str, scope: &SyntaxNode) -> Option<ast::Expr> {
+    // Follow the name back to its "let", a pointer without one came from outside the function
+    for stmt in scope
+        .ancestors()
+        .filter(|node| node.parent().map_or(false, |parent| parent.kind() == STMT_LIST))
+    {
+        for backward_slice in stmt.siblings(Direction::Prev).skip(1) {
+            let let_expr = match ast::LetStmt::cast(backward_slice) {
+                Some(let_expr) => let_expr,
+                None => continue,
+            };
+
+            match let_expr.pat()? {
+                // A "mut" binding may be moved to another buffer before the call
+                ast::Pat::IdentPat(ident_pat) if ident_pat.name()?.to_string() == name => {
+                    if ident_pat.mut_token().is_some() {
+                        return None;
+                    }
+
+                    return let_expr.initializer();
+                }
+                _ => continue,
+            }
+        }
+    }
+
+    return None;
+}
+
+fn collect_offset_literal(offset: &Option<ast::Expr>) -> Option<u128> {
+    match offset {
+        None => return Some(0),
+        Some(ast::Expr::Literal(literal)) => {
+            return literal.to_string().trim_end_matches("usize").replace('_', "").parse().ok()
+        }
+        _ => return None,
+    }
+}
+
+fn generate_offset_from_format(mcall: &MethodCallExpr) -> Option<String> {
+    // "end.offset_from(start)" is the distance from "start" to "end"
+    let end = collect_traced_ptr(&mcall.receiver()?, mcall.syntax())?;
+
+    let start = collect_traced_ptr(&mcall.arg_list()?.args().exactly_one().ok()?, mcall.syntax())?;
+
+    if end.base_expr.to_string() != start.base_expr.to_string() {
+        return None;
+    }
+
+    if end.offset.is_none() && start.offset.is_none() {
+        return None;
+    }
+
+    let mut buf = String::new();
+
+    // With literal offsets in order the range is known, slicing keeps the bounds check
+    if let (Some(end_pos), Some(start_pos)) =
+        (collect_offset_literal(&end.offset), collect_offset_literal(&start.offset))
+    {
+        if start_pos <= end_pos {
+            let start_text =
+                start.offset.as_ref().map_or(String::new(), |offset| offset.to_string());
+            let end_text =
+                end.offset.as_ref().map_or(String::from("0"), |offset| offset.to_string());
+            format_to!(
+                buf,
+                "{}[{}..{}].len() as isize",
+                make_postfix_operand(end.base_expr.clone()),
+                start_text,
+                end_text
+            );
+            return Some(buf);
+        }
+    }
+
+    match (end.offset, start.offset) {
+        (Some(end_offset), Some(start_offset)) => format_to!(
+            buf,
+            "{} as isize - {} as isize",
+            make_cast_operand(&end_offset),
+            make_cast_operand(&start_offset)
+        ),
+        (Some(end_offset), None) => format_to!(buf, "{} as isize", make_cast_operand(&end_offset)),
+        (None, Some(start_offset)) => {
+            format_to!(buf, "-({} as isize)", make_cast_operand(&start_offset))
+        }
+        (None, None) => return None,
+    }
+
+    return Some(buf);
+}
+
+fn generate_let_offset_from(mcall: &MethodCallExpr, let_expr: &LetStmt) -> Option<String> {
+    let pat = let_expr.pat()?;
+
+    let mut buf = String::new();
+
+    match let_expr.ty() {
+        Some(ty) => {
+            format_to!(buf, "let {}: {} = {};", pat, ty, generate_offset_from_format(mcall)?)
+        }
+        None => format_to!(buf, "let {} = {};", pat, generate_offset_from_format(mcall)?),
+    }
+
+    return Some(buf);
+}
+
+fn convert_to_offset_arith(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+
+    // "let d = unsafe { end.offset_from(start) };" or the distance used inline
+    if mcall.syntax().parent()?.kind() == STMT_LIST {
+        if !check_single_methodcall_expr(&mcall)? {
+            return None;
+        }
+
+        let mut buf = generate_offset_from_format(&mcall)?;
+
+        // The subtraction would bind to whatever operator surrounds the block
+        if !matches!(unsafe_expr.syntax().parent()?.kind(), LET_STMT | EXPR_STMT | STMT_LIST) {
+            buf = format!("({})", buf);
+        }
+
+        replace_source_code(acc, unsafe_expr.syntax().text_range(), &buf);
+        return None;
+    }
+
+    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
+
+    let mut buf = generate_let_offset_from(&mcall, &let_expr)?;
+
+    if check_single_let_expr(&let_expr) {
+        replace_source_code(acc, unsafe_range, &buf);
+        return None;
+    }
+
+    buf.push('\n');
+
+    return reindent_expr(unsafe_expr, acc, let_expr.syntax().text_range(), &buf);
+}
+
+fn check_same_pointee(
+    sema: &Semantics<'_, RootDatabase>,
+    cast_expr: &ast::CastExpr,
+    ref_expr: &ast::RefExpr,
+) -> bool {
+    let ptr_ty =
+        sema.type_of_expr(&ast::Expr::CastExpr(cast_expr.clone())).map(|info| info.original);
+
+    let ref_ty = sema
+        .type_of_expr(&ast::Expr::RefExpr(ref_expr.clone()))
+        .and_then(|info| info.original.remove_ref());
+
+    let (ptr_ty, ref_ty) = match (ptr_ty, ref_ty) {
+        (Some(ptr_ty), Some(ref_ty)) if !ptr_ty.is_unknown() && !ref_ty.is_unknown() => {
+            (ptr_ty, ref_ty)
+        }
+        _ => return true,
+    };
+
+    let ptr_ty = ptr_ty.display(sema.db).to_string();
+
+    let pointee = ptr_ty.strip_prefix("*const ").or_else(|| ptr_ty.strip_prefix("*mut "));
+
+    return pointee == Some(ref_ty.display(sema.db).to_string().as_str());
+}
+
+fn collect_traced_ref_place(
+    sema: &Semantics<'_, RootDatabase>,
+    ptr: &ast::Expr,
+    scope: &SyntaxNode,
+) -> Option<(ast::Expr, bool)> {
+    let ptr = match ptr {
+        ast::Expr::ParenExpr(paren_expr) => paren_expr.expr()?,
+        ast::Expr::PathExpr(path_expr) => {
+            collect_ptr_let_init(&path_expr.path()?.as_single_name_ref()?.to_string(), scope)?
+        }
+        _ => ptr.clone(),
+    };
+
+    // A pointer from arithmetic or an FFI call has no reference to go back to
+    match ptr {
+        // A cast of the array "&[T; N] as *const T" points at the first element, not the array
+        ast::Expr::CastExpr(cast_expr) => match cast_expr.expr()? {
+            ast::Expr::RefExpr(ref_expr) if check_same_pointee(sema, &cast_expr, &ref_expr) => {
+                return Some((ref_expr.expr()?, ref_expr.mut_token().is_some()))
+            }
+            _ => return None,
+        },
+        ptr => return collect_addr_of_place(&ptr),
+    }
+}
+
+fn generate_ptr_as_ref_format(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &MethodCallExpr,
+) -> Option<String> {
+    let mutable = match mcall.name_ref()?.text().as_str() {
+        "as_ref" => false,
+        "as_mut" => true,
+        _ => return None,
+    };
+
+    if mcall.arg_list()?.args().next().is_some() {
+        return None;
+    }
+
+    let receiver = mcall.receiver()?;
+
+    // "Option::as_ref" and "AsRef::as_ref" share the name, only the raw pointer one is unsafe
+    if sema
+        .type_of_expr(&receiver)
+        .map_or(false, |info| !info.original.is_unknown() && !info.original.is_raw_ptr())
+    {
+        return None;
+    }
+
+    let (place, place_mut) = collect_traced_ref_place(sema, &receiver, mcall.syntax())?;
+
+    // A pointer made from "&x" can't be turned into "&mut x"
+    if mutable && !place_mut {
+        return None;
+    }
+
+    match mutable {
+        true => return Some(format!("Some(&mut {})", place)),
+        false => return Some(format!("Some(&{})", place)),
+    }
+}
+
+fn generate_let_ptr_as_ref(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &MethodCallExpr,
+    let_expr: &LetStmt,
+) -> Option<String> {
+    let pat = let_expr.pat()?;
+
+    let mut buf = String::new();
+
+    match let_expr.ty() {
+        Some(ty) => {
+            format_to!(buf, "let {}: {} = {};", pat, ty, generate_ptr_as_ref_format(sema, mcall)?)
+        }
+        None => format_to!(buf, "let {} = {};", pat, generate_ptr_as_ref_format(sema, mcall)?),
+    }
+
+    return Some(buf);
+}
+
+fn convert_to_ptr_reference(
+    acc: &mut Assists,
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+
+    // "let r = unsafe { p.as_ref() };" or the reference used inline
+    if mcall.syntax().parent()?.kind() == STMT_LIST {
+        if !check_single_methodcall_expr(&mcall)? {
+            return None;
+        }
+
+        let buf = generate_ptr_as_ref_format(sema, &mcall)?;
+
+        replace_source_code(acc, unsafe_expr.syntax().text_range(), &buf);
+        return None;
+    }
+
+    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
+
+    let mut buf = generate_let_ptr_as_ref(sema, &mcall, &let_expr)?;
+
+    if check_single_let_expr(&let_expr) {
+        replace_source_code(acc, unsafe_range, &buf);
+        return None;
+    }
+
+    buf.push('\n');
+
+    return reindent_expr(unsafe_expr, acc, let_expr.syntax().text_range(), &buf);
+}
+
+fn collect_libc_copy_arg(arg: ast::Expr) -> Option<LibcCopyArg> {
+    let mut arg = arg;
+
+    // Peel "v.as_ptr() as *const c_void", possibly going through more than one cast
+    while let ast::Expr::CastExpr(cast_expr) = arg {
+        arg = cast_expr.expr()?;
+    }
+
+    if let Some(info) = collect_cpy_add_offset_info(&arg) {
+        let ptr_call = match arg {
+            ast::Expr::MethodCallExpr(add_call) => match add_call.receiver()? {
+                ast::Expr::MethodCallExpr(ptr_call) => ptr_call,
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        return Some(LibcCopyArg { info, ptr_call });
+    }
+
+    let ptr_call = match arg {
+        ast::Expr::MethodCallExpr(ptr_call) => ptr_call,
+        _ => return None,
+    };
+
+    let ptr_name = ptr_call.name_ref()?.to_string();
+
+    if !UnsafePattern::AsPtr.matches(&ptr_name) && ptr_name != "as_mut_ptr" {
+        return None;
+    }
+
+    let info = CpyAddOffsetInfo { base_expr: ptr_call.receiver()?, offset: None };
+
+    return Some(LibcCopyArg { info, ptr_call });
+}
+
+fn collect_ptr_elem_ty(
+    sema: &Semantics<'_, RootDatabase>,
+    ptr_call: &MethodCallExpr,
+) -> Option<String> {
+    let ty = sema.type_of_expr(&ast::Expr::MethodCallExpr(ptr_call.clone()))?.original;
+
+    if !ty.is_raw_ptr() {
+        return None;
+    }
+
+    let ty = ty.display(sema.db).to_string();
+
+    let elem_ty = ty.strip_prefix("*const ").or_else(|| ty.strip_prefix("*mut "))?;
+
+    return Some(elem_ty.to_string());
+}
+
+fn check_size_of_call(expr: &ast::Expr, elem_ty: &str) -> Option<bool> {
+    let call = match expr {
+        ast::Expr::CallExpr(call) => call,
+        _ => return Some(false),
+    };
+
+    let segment = match call.expr()? {
+        ast::Expr::PathExpr(path_expr) => path_expr.path()?.segment()?,
+        _ => return Some(false),
+    };
+
+    if segment.name_ref()?.to_string() != "size_of" || call.arg_list()?.args().next().is_some() {
+        return Some(false);
+    }
+
+    let ty = match segment.generic_arg_list()?.generic_args().exactly_one().ok()? {
+        ast::GenericArg::TypeArg(type_arg) => type_arg.ty()?,
+        _ => return Some(false),
+    };
+
+    return Some(ty.to_string() == elem_ty);
+}
+
+fn collect_libc_elem_count(count: &ast::Expr, elem_ty: &str) -> Option<ast::Expr> {
+    // "n * size_of::<T>()" in either order, the other operand is the element count
+    if let ast::Expr::BinExpr(bin_expr) = count {
+        if bin_expr.op_kind()? == ast::BinaryOp::ArithOp(ast::ArithOp::Mul) {
+            let (lhs, rhs) = (bin_expr.lhs()?, bin_expr.rhs()?);
+
+            if check_size_of_call(&rhs, elem_ty)? && check_simple_count_expr(&lhs) {
+                return Some(lhs);
+            }
+
+            if check_size_of_call(&lhs, elem_ty)? && check_simple_count_expr(&rhs) {
+                return Some(rhs);
+            }
+        }
+    }
+
+    // A bare byte count only equals the element count for bytes
+    if elem_ty == "u8" && check_simple_count_expr(count) {
+        return Some(count.clone());
+    }
+
+    return None;
+}
+
+fn generate_libc_copy_format(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+) -> Option<String> {
+    // "memcpy(dst, src, n)" takes the destination first, unlike "ptr::copy_nonoverlapping"
+    let (dst, src, count) = mcall.arg_list()?.args().collect_tuple()?;
+
+    let dst = collect_libc_copy_arg(dst)?;
+
+    let src = collect_libc_copy_arg(src)?;
+
+    let elem_ty = collect_ptr_elem_ty(sema, &dst.ptr_call)?;
+
+    if collect_ptr_elem_ty(sema, &src.ptr_call)? != elem_ty {
+        return None;
+    }
+
+    let count = collect_libc_elem_count(&count, &elem_ty)?;
+
+    // Both pointers come from one buffer, only "copy_within" can borrow it once
+    if dst.info.base_expr.to_string() == src.info.base_expr.to_string() {
+        return Some(generate_copywithin_node(
+            src.info.base_expr,
+            src.info.offset,
+            dst.info.offset,
+            count,
+        ));
+    }
+
+    return Some(generate_copy_from_slice_offset_string(&src.info, &dst.info, &count));
+}
+
+fn check_rust_buffer(
+    sema: &Semantics<'_, RootDatabase>,
+    ptr_call: &MethodCallExpr,
+) -> Option<bool> {
+    // A pointer from "libc::malloc" has no slice to fill, only Rust-owned buffers qualify
+    let ty = sema.type_of_expr(&ptr_call.receiver()?)?.original.strip_references();
+
+    if ty.is_slice() || ty.is_array() {
+        return Some(true);
+    }
+
+    return Some(ty.as_adt().map_or(false, |adt| adt.name(sema.db).to_string() == "Vec"));
+}
+
+fn collect_fill_byte(value: &ast::Expr) -> Option<String> {
+    let literal = match value {
+        ast::Expr::Literal(literal) => literal,
+        _ => return None,
+    };
+
+    // libc takes the fill value as a c_int but only writes its low byte
+    let byte = match literal.kind() {
+        ast::LiteralKind::IntNumber(int) => u8::try_from(int.value()?).ok()?,
+        _ => return None,
+    };
+
+    return Some(byte.to_string());
+}
+
+fn generate_libc_fill_format(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+) -> Option<String> {
+    let (dst, value, count) = mcall.arg_list()?.args().collect_tuple()?;
+
+    let dst = collect_libc_copy_arg(dst)?;
+
+    if !check_rust_buffer(sema, &dst.ptr_call)? || collect_ptr_elem_ty(sema, &dst.ptr_call)? != "u8"
+    {
+        return None;
+    }
+
+    let byte = collect_fill_byte(&value)?;
+
+    let mut buf = String::new();
+
+    // "memset(buf.as_mut_ptr(), 0, buf.len())" covers the whole buffer
+    let mut full_len = String::new();
+
+    format_to!(full_len, "{}.len()", dst.info.base_expr);
+
+    if dst.info.offset.is_none() && count.to_string() == full_len {
+        format_to!(buf, "{}.fill({});", dst.info.base_expr, byte);
+    } else {
+        let count = collect_libc_elem_count(&count, "u8")?;
+
+        format_to!(buf, "{}.fill({});", make_offset_index(&dst.info, &count), byte);
+    }
+
+    buf.push('\n');
+
+    return Some(buf);
+}
+
+fn convert_to_libc_fill(
+    acc: &mut Assists,
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    let target_expr = mcall.syntax().parent().and_then(ast::ExprStmt::cast)?;
+
+    let buf = generate_libc_fill_format(sema, &mcall)?;
+
+    if check_single_expr(&target_expr) {
+        replace_source_code(acc, unsafe_range, &buf);
+        return None;
+    }
+
+    return reindent_expr(unsafe_expr, acc, target_expr.syntax().text_range(), &buf);
+}
+
+fn convert_to_libc_copy(
+    acc: &mut Assists,
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    let target_expr = mcall.syntax().parent().and_then(ast::ExprStmt::cast)?;
+
+    let buf = generate_libc_copy_format(sema, &mcall)?;
+
+    if check_single_expr(&target_expr) {
+        replace_source_code(acc, unsafe_range, &buf);
+        return None;
+    }
+
+    return reindent_expr(unsafe_expr, acc, target_expr.syntax().text_range(), &buf);
+}
+
+pub fn generate_copy_from_slice_format(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+    unsafe_expr: &BlockExpr,
+) -> Option<String> {
+    if let Some(buf) = generate_copy_from_slice_add_format(sema, mcall) {
+        return Some(buf);
+    }
+
+    if let Some(CpyNonOverlapInfo { src_expr, dst_expr, count }) =
+        collect_cpy_nonoverlap_info(sema, mcall, unsafe_expr)
+    {
+        return generate_copy_from_slice_string(src_expr, dst_expr, count);
+    }
+
+    // Whole collections take their range from the count alone
+    let src = collect_traced_ptr(&mcall.arg_list()?.args().next()?, mcall.syntax())?;
+
+    let dst = collect_traced_ptr(&mcall.arg_list()?.args().nth(1)?, mcall.syntax())?;
+
+    // Both halves of one buffer can't be borrowed at once, that is a "copy_within"
+    if src.base_expr.to_string() == dst.base_expr.to_string() {
+        return None;
+    }
+
+    if !check_copy_base_ty(sema, &src.base_expr, mcall.syntax())
+        || !check_copy_base_ty(sema, &dst.base_expr, mcall.syntax())
+    {
+        return None;
+    }
+
+    let count = mcall.arg_list()?.args().nth(2)?;
+
+    return Some(generate_copy_from_slice_offset_string(&src, &dst, &count));
+}
+
+fn collect_copy_from_slice_stmt(
+    sema: &Semantics<'_, RootDatabase>,
+    stmt: &SyntaxNode,
+    unsafe_expr: &BlockExpr,
+) -> Option<(ExprStmt, String)> {
+    let expr_stmt = ast::ExprStmt::cast(stmt.clone())?;
+
+    let mcall = match expr_stmt.expr()? {
+        ast::Expr::CallExpr(mcall) => mcall,
+        _ => return None,
+    };
+
+    if !UnsafePattern::CopyNonOverlap.matches(&mcall.expr()?.to_string()) {
+        return None;
+    }
+
+    let buf = generate_copy_from_slice_format(sema, &mcall, unsafe_expr)?;
+
+    return Some((expr_stmt, buf));
+}
+
+fn collect_copy_from_slice_batch(
+    sema: &Semantics<'_, RootDatabase>,
+    expr_stmt: &ExprStmt,
+    unsafe_expr: &BlockExpr,
+) -> Vec<(ExprStmt, String)> {
+    // Back-to-back copies, stopping at the first statement that doesn't convert
+    return expr_stmt
+        .syntax()
+        .siblings(Direction::Next)
+        .skip(1)
+        .map_while(|stmt| collect_copy_from_slice_stmt(sema, &stmt, unsafe_expr))
+        .collect();
+}
+
+fn convert_to_copy_from_slice(
+    acc: &mut Assists,
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    let target_expr = mcall.syntax().parent().and_then(ast::ExprStmt::cast)?;
+
+    let mut target_range = target_expr.syntax().text_range();
+
+    let mut buf = generate_copy_from_slice_format(sema, &mcall, unsafe_expr)?;
+
+    let batch = collect_copy_from_slice_batch(sema, &target_expr, unsafe_expr);
+
+    // Hoist the whole run in its original order, one line per copy
+    if let Some((last_stmt, _)) = batch.last() {
+        target_range = TextRange::new(target_range.start(), last_stmt.syntax().text_range().end());
+
+        for (_, line) in batch.iter() {
+            format_to!(buf, "{}{}", unsafe_expr.indent_level(), line);
+        }
+
+        return reindent_expr(unsafe_expr, acc, target_range, &buf);
+    }
+
+    return reindent_expr(unsafe_expr, acc, target_range, &buf);
+}
+
+pub fn generate_cstring_new_string(pat: String, input_argument: String, let_sign: bool) -> String {
+
+    let mut buf = String::new();
+
+    if let_sign {
+        format_to!(buf, "let {} = CString::new({}).unwrap();", pat, input_argument);
+    } else {
+        format_to!(buf, "{} = CString::new({}).unwrap();", pat, input_argument);
+    }
+
+    buf.push('\n');
+
+    return buf;
+
+}
+
+pub fn generate_cstring_new_format(
+    pat: String,
+    mcall: &CallExpr,
+    let_sign: bool,
+) -> Option<String> {
+    let input_argument = mcall.arg_list()?.args().nth(0)?.to_string();
+
+    let buf = generate_cstring_new_string(pat, input_argument, let_sign);
+
+    return Some(buf);
+}
+
+fn check_call_assignment(mcall: &CallExpr) -> Option<BinExpr> {
+    // "c_string = CString::from_vec_unchecked(raw)", any other operator or operand is nested
+    let target_expr = mcall.syntax().parent().and_then(ast::BinExpr::cast)?;
+
+    if target_expr.op_kind()? != (ast::BinaryOp::Assignment { op: None })
+        || target_expr.rhs()?.syntax() != mcall.syntax()
+    {
+        return None;
+    }
+
+    return Some(target_expr);
+}
+
+fn generate_cstring_new_nested(mcall: &CallExpr) -> Option<(SyntaxNode, String)> {
+    // The statement, or tail expression, of the block the call sits in
+    let stmt = mcall
+        .syntax()
+        .ancestors()
+        .find(|node| node.parent().map_or(false, |parent| parent.kind() == STMT_LIST))?;
+
+    let call_range = mcall.syntax().text_range().checked_sub(stmt.text_range().start())?;
+
+    let mut buf = stmt.to_string();
+
+    // Only the call changes, whatever surrounds it is kept as written
+    buf.replace_range(
+        std::ops::Range::<usize>::from(call_range),
+        &format!("CString::new({}).unwrap()", mcall.arg_list()?.args().next()?),
+    );
+
+    return Some((stmt, buf));
+}
+
+fn check_single_bin_expr(target_expr: &BinExpr) -> Option<bool> {
+
+    // Check if the unsafe bloack only contains one expr
+    if target_expr.syntax().parent()?.prev_sibling().is_none() && target_expr.syntax().parent()?.next_sibling().is_none() {
+        return Some(true);
+    }
+    return Some(false);
+}
+
+fn check_single_expr_stmt(target_expr: &ExprStmt) -> Option<bool> {
+
+    // Check if the unsafe bloack only contains one expr
+    if target_expr.syntax().parent()?.prev_sibling().is_none() && target_expr.syntax().parent()?.next_sibling().is_none() {
+        return Some(true);
+    }
+    return Some(false);
+}
+
+fn check_single_call_expr(target_expr: &CallExpr) -> Option<bool> {
+
+    // Check if the unsafe bloack only contains one expr
+    if target_expr.syntax().prev_sibling().is_none() && target_expr.syntax().next_sibling().is_none() {
+        return Some(true);
+    }
+    return Some(false);
+}
+
+fn check_single_methodcall_expr(target_expr: &MethodCallExpr) -> Option<bool> {
+
+    // Check if the unsafe bloack only contains one expr
+    if target_expr.syntax().prev_sibling().is_none() && target_expr.syntax().next_sibling().is_none() {
+        return Some(true);
+    }
+    return Some(false);
+}
+
+fn convert_to_cstring_new(
+    acc: &mut Assists,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    if let Some(target_expr) = check_call_assignment(&mcall) {
+        let mut target_range = target_expr.syntax().parent()?.text_range();
+
+        let buf = generate_cstring_new_format(target_expr.lhs()?.to_string(), &mcall, false)?;
+        
+        if check_single_bin_expr(&target_expr)? == true {
+            target_range = unsafe_range;
+            replace_source_code(acc, target_range, &buf);
+            return None;
+        }
+        return reindent_expr(unsafe_expr, acc, target_range, &buf);
+    }
+
+    // "total + CString::from_vec_unchecked(raw).as_bytes().len()", rewrite the call where it is
+    if mcall.syntax().parent()?.kind() != LET_STMT {
+        let (stmt, mut buf) = generate_cstring_new_nested(&mcall)?;
+
+        if stmt.prev_sibling().is_none() && stmt.next_sibling().is_none() {
+            match stmt.kind() {
+                EXPR_STMT => replace_source_code(acc, unsafe_range, &buf),
+                _ => replace_source_code(acc, unsafe_expr.syntax().text_range(), &buf),
+            }
+            return None;
+        }
+
+        buf.push('\n');
+
+        return reindent_expr(unsafe_expr, acc, stmt.text_range(), &buf);
+    }
+
+    let target_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
+
+    let mut target_range = target_expr.syntax().text_range();
+
+    let buf = generate_cstring_new_format(target_expr.pat()?.to_string(), &mcall, true)?;
+
+    if check_single_let_expr(&target_expr) {
+        target_range = unsafe_range;
+        replace_source_code(acc, target_range, &buf);
+        return None;
+    }
+
+    return reindent_expr(unsafe_expr, acc, target_range, &buf);
+}
+
+pub fn generate_bytes_len_string(pat: String, len_expr: String, let_sign: bool) -> String {
+    let mut buf = String::new();
+
+    if let_sign {
+        format_to!(buf, "let {} = {};", pat, len_expr);
+    } else {
+        format_to!(buf, "{} = {};", pat, len_expr);
+    }
+
+    buf.push('\n');
+
+    return buf;
+
+}
+
+fn collect_cstr_bytes_method(
+    sema: &Semantics<'_, RootDatabase>,
+    receiver: &ast::Expr,
+) -> Option<&'static str> {
+    let ty = sema.type_of_expr(receiver)?.original.strip_references();
+
+    // "CString::as_bytes" and "CStr::to_bytes" both leave out the nul terminator
+    match ty.as_adt()?.name(sema.db).to_string().as_str() {
+        "CString" => return Some("as_bytes"),
+        "CStr" => return Some("to_bytes"),
+        _ => return None,
+    }
+}
+
+fn collect_strlen_receiver(mcall: &CallExpr) -> Option<ast::Expr> {
+    let ptr_call = match mcall.arg_list()?.args().exactly_one().ok()? {
+        ast::Expr::MethodCallExpr(ptr_call) => ptr_call,
+        _ => return None,
+    };
+
+    if !UnsafePattern::AsPtr.matches(&ptr_call.name_ref()?.to_string()) {
+        return None;
+    }
+
+    return ptr_call.receiver();
+}
+
+fn generate_strlen_expr(sema: &Semantics<'_, RootDatabase>, mcall: &CallExpr) -> Option<String> {
+    let receiver = collect_strlen_receiver(mcall)?;
+
+    // An unresolved receiver is taken to be a "CString"
+    let method = collect_cstr_bytes_method(sema, &receiver).unwrap_or("as_bytes");
+
+    let mut buf = String::new();
+
+    format_to!(buf, "{}.{}().len()", receiver, method);
+
+    return Some(buf);
+}
+
+pub fn generate_bytes_len_format(
+    sema: &Semantics<'_, RootDatabase>,
+    pat: String,
+    mcall: &CallExpr,
+    let_sign: bool,
+) -> Option<String> {
+    let buf = generate_bytes_len_string(pat, generate_strlen_expr(sema, mcall)?, let_sign);
+
+    return Some(buf);
+}
+
+fn collect_strlen_compare(unsafe_expr: &BlockExpr) -> Option<BinExpr> {
+    let bin_expr = unsafe_expr.syntax().parent().and_then(ast::BinExpr::cast)?;
+
+    // "unsafe { strlen(p) } == 0"
+    if bin_expr.lhs()?.syntax() != unsafe_expr.syntax() || bin_expr.rhs()?.to_string() != "0" {
+        return None;
+    }
+
+    match bin_expr.op_kind()? {
+        ast::BinaryOp::CmpOp(ast::CmpOp::Eq { .. }) => return Some(bin_expr),
+        _ => return None,
+    }
+}
+
+fn generate_strlen_is_empty(bin_expr: &BinExpr, mcall: &CallExpr) -> Option<String> {
+    let receiver = collect_strlen_receiver(mcall)?;
+
+    let mut buf = String::new();
+
+    // "CString" derefs to "CStr", so "is_empty" fits both receivers
+    match bin_expr.op_kind()? {
+        ast::BinaryOp::CmpOp(ast::CmpOp::Eq { negated: true }) => {
+            format_to!(buf, "!{}.is_empty()", receiver)
+        }
+        _ => format_to!(buf, "{}.is_empty()", receiver),
+    }
+
+    return Some(buf);
+}
+
+fn convert_to_cstring_bytes_len(
+    acc: &mut Assists,
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+
+    if mcall.syntax().parent()?.kind() == STMT_LIST {
+        if !check_single_call_expr(&mcall)? {
+            return None;
+        }
+
+        // "if unsafe { strlen(p) } == 0" compares the whole length away
+        if let Some(bin_expr) = collect_strlen_compare(unsafe_expr) {
+            let buf = generate_strlen_is_empty(&bin_expr, &mcall)?;
+            replace_source_code(acc, bin_expr.syntax().text_range(), &buf);
+            return None;
+        }
+
+        let buf = generate_strlen_expr(sema, &mcall)?;
+        replace_source_code(acc, unsafe_expr.syntax().text_range(), &buf);
+        return None;
+    }
+
+    if mcall.syntax().parent()?.kind() == BIN_EXPR {
+        // Only the length assigned as a whole, "n = total + strlen(p)" would lose "total"
+        let target_expr = check_call_assignment(&mcall)?;
+
+        let mut target_range = target_expr.syntax().parent()?.text_range();
+
+        let buf = generate_bytes_len_format(sema, target_expr.lhs()?.to_string(), &mcall, false)?;
+
+        if check_single_bin_expr(&target_expr)? == true {
+            target_range = unsafe_range;
+            replace_source_code(acc, target_range, &buf);
+            return None;
+        }
+        return reindent_expr(unsafe_expr, acc, target_range, &buf);
+    }
+        
+    let target_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
+
+    let mut target_range = target_expr.syntax().text_range();
+
+    let buf = generate_bytes_len_format(sema, target_expr.pat()?.to_string(), &mcall, true)?;
+
+    if check_single_let_expr(&target_expr) {
+        target_range = unsafe_range;
+        replace_source_code(acc, target_range, &buf);
+        return None;
+    }
+
+    return reindent_expr(unsafe_expr, acc, target_range, &buf);
+}
+
+fn collect_type_tokens(ty: &ast::Type) -> Vec<String> {
+    // Lifetimes are skipped so that only the structure of the type is compared
+    return ty
+        .syntax()
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| !token.kind().is_trivia() && token.kind() != LIFETIME_IDENT)
+        .map(|token| token.text().to_string())
+        .collect();
+}
+
+fn check_transmute_lifetime(target_expr: &SyntaxNode) -> Option<bool> {
+    let path = ast::PathExpr::cast(target_expr.clone())?.path()?;
+
+    let segment = path.segment()?;
+
+    if !UnsafePattern::TransmuteLifetime.matches(&segment.name_ref()?.to_string()) {
+        return Some(false);
+    }
+
+    if let Some(qualifier) = path.qualifier() {
+        let qualifier = qualifier.to_string();
+        if qualifier != "mem" && qualifier != "std::mem" && qualifier != "core::mem" {
+            return Some(false);
+        }
+    }
+
+    // Only the turbofish form spells out both the source and the target type
+    let (src_ty, dst_ty) = match collect_transmute_type_args(&segment) {
+        Some(type_args) => type_args,
+        None => return Some(false),
+    };
+
+    // Identical types are a no-op, the lifetimes have to differ
+    if src_ty.to_string() == dst_ty.to_string() {
+        return Some(false);
+    }
+
+    return Some(collect_type_tokens(&src_ty) == collect_type_tokens(&dst_ty));
+}
+
+fn collect_transmute_type_args(segment: &ast::PathSegment) -> Option<(ast::Type, ast::Type)> {
+    return segment
+        .generic_arg_list()?
+        .generic_args()
+        .filter_map(|arg| match arg {
+            ast::GenericArg::TypeArg(type_arg) => type_arg.ty(),
+            _ => None,
+        })
+        .collect_tuple();
+}
+
+fn uninitialized_vec_analysis(target_expr: &SyntaxNode, unsafe_expr: &BlockExpr) -> Option<bool> {
+    // static analysis on unsafe expr's ancestors() and descendants()
+    for backward_slice in unsafe_expr.syntax().parent()?.siblings(Direction::Prev) {
+        if UnsafePattern::SetVecCapacity.found_in(&backward_slice.to_string())
+            || UnsafePattern::ReserveVec.found_in(&backward_slice.to_string())
+        {
+            let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+
+            let receiver = mcall.receiver()?;
+
+            for forward_slice in unsafe_expr.syntax().parent()?.siblings(Direction::Next) {
+                if forward_slice.to_string().contains(&receiver.to_string())
+                    && UnsafePattern::WriteVec.found_in(&forward_slice.to_string())
+                {
+                    return Some(false);
+                }
+            }
+            return Some(true);
+        }
+    }
+    return Some(false);
+}
+
+fn check_maybe_uninit_receiver(
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+) -> Option<bool> {
+    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+
+    // Resolve the receiver so that a user-defined "assume_init" does not match
+    let receiver_ty = sema.type_of_expr(&mcall.receiver()?)?.original.strip_references();
+
+    let krate = sema.scope(mcall.syntax())?.krate();
+
+    let maybe_uninit = FamousDefs(sema, krate).core_mem_MaybeUninit()?;
+
+    return Some(receiver_ty.as_adt()? == hir::Adt::Union(maybe_uninit));
+}
+
+fn check_slice_method(
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+) -> Option<bool> {
+    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+
+    // Only the inherent "[T]" impl in core can define methods on slices
+    let func = sema.resolve_method_call(&mcall)?;
+
+    match func.as_assoc_item(sema.db)?.container(sema.db) {
+        hir::AssocItemContainer::Impl(imp) => return Some(imp.self_ty(sema.db).is_slice()),
+        hir::AssocItemContainer::Trait(_) => return Some(false),
+    }
+}
+
+fn check_std_get_unchecked(sema: &Semantics<'_, RootDatabase>, target_expr: &SyntaxNode) -> bool {
+    let mcall = match target_expr.parent().and_then(ast::MethodCallExpr::cast) {
+        Some(mcall) => mcall,
+        None => return false,
+    };
+
+    // An unresolved call may be anyone's "get_unchecked", there is no telling it has a "get"
+    let func = match sema.resolve_method_call(&mcall) {
+        Some(func) => func,
+        None => return false,
+    };
+
+    // "Vec" reaches the slice method by deref, a user type's own "get_unchecked" has no "get" to fall back on
+    match func.as_assoc_item(sema.db).map(|item| item.container(sema.db)) {
+        Some(hir::AssocItemContainer::Impl(imp)) => {
+            let self_ty = imp.self_ty(sema.db);
+            return self_ty.is_slice()
+                || self_ty.as_builtin().map_or(false, |builtin| builtin.is_str());
+        }
+        _ => return false,
+    }
+}
+
+fn check_ptr_fn_callee(
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+    name: &str,
+) -> bool {
+    let path = match ast::PathExpr::cast(target_expr.clone()).and_then(|it| it.path()) {
+        Some(path) => path,
+        None => return false,
+    };
+
+    if target_expr.parent().and_then(ast::CallExpr::cast).is_none() {
+        return false;
+    }
+
+    // A resolved callee has to be the one from "core::ptr", "std::ptr" re-exports it
+    if let Some(hir::PathResolution::Def(def)) = sema.resolve_path(&path) {
+        let func = match def {
+            hir::ModuleDef::Function(func) => func,
+            _ => return false,
+        };
+        let module = func.module(sema.db);
+
+        return module.krate().is_builtin(sema.db)
+            && module.name(sema.db).map_or(false, |module_name| module_name.to_string() == "ptr");
+    }
+
+    // Without the standard library in view, a "ptr::" qualifier or a "use" of it has to do
+    if path.qualifier().is_some() {
+        return path.to_string().ends_with(&format!("ptr::{}", name));
+    }
+
+    let file = match target_expr.ancestors().last() {
+        Some(file) => file,
+        None => return false,
+    };
+
+    return file.descendants().filter_map(ast::UseTree::cast).any(|use_tree| {
+        let path = match use_tree.path() {
+            Some(path) if use_tree.rename().is_none() => path,
+            _ => return false,
+        };
+
+        let prefix = use_tree
+            .syntax()
+            .parent()
+            .and_then(ast::UseTreeList::cast)
+            .and_then(|list| list.parent_use_tree().path());
+
+        let full_path = match prefix {
+            Some(prefix) => format!("{}::{}", prefix, path),
+            None => path.to_string(),
+        };
+
+        return full_path.ends_with(&format!("ptr::{}", name));
+    });
+}
+
+fn check_str_receiver(
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+) -> Option<bool> {
+    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+
+    // Strings deref to "str", so only the stripped receiver type needs to be checked
+    let receiver_ty = sema.type_of_expr(&mcall.receiver()?)?.original.strip_references();
+
+    if receiver_ty.as_builtin().map_or(false, |builtin| builtin.is_str()) {
+        return Some(true);
+    }
+
+    let adt = receiver_ty.as_adt()?;
+
+    // A user type that happens to be called "String" has no "str::get" behind it
+    let krate = sema.scope(mcall.syntax())?.krate();
+    let string = FamousDefs(sema, krate).alloc_string_String()?;
+
+    return Some(adt == hir::Adt::Struct(string));
+}
+
+pub fn check_convert_type(
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+    unsafe_expr: &BlockExpr,
+) -> Option<UnsafePattern> {
+    if UnsafePattern::UnitializedVec.matches(&target_expr.to_string()) {
+        // The block's own raw writes are the new elements, not a write that would be lost to the fill
+        let growth = collect_raw_write_growth(unsafe_expr).filter(|growth| {
+            target_expr.parent().map_or(false, |parent| growth.set_len.syntax() == &parent)
+        });
+
+        if growth.is_some() || uninitialized_vec_analysis(target_expr, unsafe_expr).unwrap_or(false)
+        {
+            return Some(UnsafePattern::UnitializedVec);
+        }
+    }
+
+    if UnsafePattern::CopyWithin.matches(&target_expr.to_string())
+        && check_ptr_fn_callee(sema, target_expr, "copy")
+    {
+        return Some(UnsafePattern::CopyWithin);
+    }
+
+    if UnsafePattern::StrBytesGetUnchecked.matches(&target_expr.to_string()) {
+        let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast);
+
+        if mcall.and_then(|mcall| check_as_bytes_receiver(&mcall)).unwrap_or(false) {
+            return Some(UnsafePattern::StrBytesGetUnchecked);
+        }
+    }
+
+    if UnsafePattern::StrGetUnchecked.matches(&target_expr.to_string())
+        && check_str_receiver(sema, target_expr).unwrap_or(false)
+    {
+        return Some(UnsafePattern::StrGetUnchecked);
+    }
+
+    if UnsafePattern::GetUncheck.matches(&target_expr.to_string())
+        && check_std_get_unchecked(sema, target_expr)
+    {
+        return Some(UnsafePattern::GetUncheck);
+    }
+
+    // Two "get_unchecked_mut" on one buffer can't become two "get_mut", the borrows would overlap
+    if UnsafePattern::GetUncheckMutPair.matches(&target_expr.to_string())
+        && check_std_get_unchecked(sema, target_expr)
+    {
+        let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast);
+
+        if mcall.and_then(|mcall| collect_unchecked_mut_pair(&mcall, unsafe_expr)).is_some() {
+            return Some(UnsafePattern::GetUncheckMutPair);
+        }
+    }
+
+    if UnsafePattern::GetUncheckMut.matches(&target_expr.to_string())
+        && check_std_get_unchecked(sema, target_expr)
+    {
+        return Some(UnsafePattern::GetUncheckMut);
+    }
+
+    if UnsafePattern::CopyNonOverlap.matches(&target_expr.to_string())
+        && check_ptr_fn_callee(sema, target_expr, "copy_nonoverlapping")
+    {
+        return Some(UnsafePattern::CopyNonOverlap);
+    }
+
+    if UnsafePattern::LibcMemcpy.matches(&target_expr.to_string())
+        || UnsafePattern::LibcMemmove.matches(&target_expr.to_string())
+    {
+        let mcall = target_expr.parent().and_then(ast::CallExpr::cast);
+
+        if mcall.and_then(|mcall| generate_libc_copy_format(sema, &mcall)).is_some() {
+            if UnsafePattern::LibcMemcpy.matches(&target_expr.to_string()) {
+                return Some(UnsafePattern::LibcMemcpy);
+            }
+            return Some(UnsafePattern::LibcMemmove);
+        }
+    }
+
+    if UnsafePattern::LibcMemset.matches(&target_expr.to_string()) {
+        let mcall = target_expr.parent().and_then(ast::CallExpr::cast);
+
+        if mcall.and_then(|mcall| generate_libc_fill_format(sema, &mcall)).is_some() {
+            return Some(UnsafePattern::LibcMemset);
+        }
+    }
+
+    if UnsafePattern::SliceFromRawParts.matches(&target_expr.to_string()) {
+        let mcall = target_expr.parent().and_then(ast::CallExpr::cast);
+
+        if mcall.and_then(|mcall| generate_slice_from_ref(&mcall)).is_some() {
+            return Some(UnsafePattern::SliceFromRawParts);
+        }
+    }
+
+    if UnsafePattern::PtrSwap.matches(&target_expr.to_string()) {
+        let mcall = target_expr.parent().and_then(ast::CallExpr::cast);
+
+        if mcall.and_then(|mcall| generate_mem_swap_format(&mcall)).is_some() {
+            return Some(UnsafePattern::PtrSwap);
+        }
+    }
+
+    if UnsafePattern::CStrFromBytesWithNul.matches(&target_expr.to_string()) {
+        return Some(UnsafePattern::CStrFromBytesWithNul);
+    }
+
+    if UnsafePattern::PtrReplace.matches(&target_expr.to_string()) {
+        let mcall = target_expr.parent().and_then(ast::CallExpr::cast);
+
+        if mcall.and_then(|mcall| generate_mem_replace_format(&mcall)).is_some() {
+            return Some(UnsafePattern::PtrReplace);
+        }
+    }
+
+    // Any byte operation is reported, only the UTF-8 preserving ones get rewritten
+    if UnsafePattern::StringAsMutVec.matches(&target_expr.to_string())
+        && collect_as_mut_vec_call(target_expr).is_some()
+    {
+        return Some(UnsafePattern::StringAsMutVec);
+    }
+
+    if UnsafePattern::PinNewUnchecked.matches(&target_expr.to_string()) {
+        let mcall = target_expr.parent().and_then(ast::CallExpr::cast);
+
+        if mcall.and_then(|mcall| collect_pin_box_info(&mcall, unsafe_expr)).is_some() {
+            return Some(UnsafePattern::PinNewUnchecked);
+        }
+    }
+
+    if let Some(path_expr) = ast::PathExpr::cast(target_expr.clone()) {
+        if collect_atomic_static(sema, &path_expr).is_some() {
+            return Some(UnsafePattern::StaticMutAtomic);
+        }
+    }
+
+    if UnsafePattern::BoxLeakRoundTrip.matches(&target_expr.to_string()) {
+        let mcall = target_expr.parent().and_then(ast::CallExpr::cast);
+
+        if target_expr.kind() == PATH_EXPR
+            && mcall.and_then(|mcall| collect_box_leak_round_trip(&mcall, unsafe_expr)).is_some()
+        {
+            return Some(UnsafePattern::BoxLeakRoundTrip);
+        }
+    }
+
+    if UnsafePattern::PtrAsRef.matches(&target_expr.to_string())
+        || UnsafePattern::PtrAsMut.matches(&target_expr.to_string())
+    {
+        let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast);
+
+        if let Some(mcall) = mcall.filter(|mcall| generate_ptr_as_ref_format(sema, mcall).is_some())
+        {
+            match mcall.name_ref()?.text().as_str() {
+                "as_mut" => return Some(UnsafePattern::PtrAsMut),
+                _ => return Some(UnsafePattern::PtrAsRef),
+            }
+        }
+    }
+
+    if UnsafePattern::PtrOffsetFrom.matches(&target_expr.to_string()) {
+        let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast);
+
+        if mcall.and_then(|mcall| generate_offset_from_format(&mcall)).is_some() {
+            return Some(UnsafePattern::PtrOffsetFrom);
+        }
+    }
+
+    // Reported on its own when no ASCII-only change follows, the hover explains the invariant
+    if UnsafePattern::AsBytesMut.matches(&target_expr.to_string())
+        && target_expr.parent().and_then(ast::MethodCallExpr::cast).is_some()
+    {
+        return Some(UnsafePattern::AsBytesMut);
+    }
+
+    if UnsafePattern::AllocToVec.matches(&target_expr.to_string()) {
+        let mcall = target_expr.parent().and_then(ast::CallExpr::cast);
+
+        if target_expr.kind() == PATH_EXPR
+            && mcall.and_then(|mcall| collect_alloc_to_vec(&mcall, unsafe_expr)).is_some()
+        {
+            return Some(UnsafePattern::AllocToVec);
+        }
+    }
+
+    if UnsafePattern::CStringFromVec.matches(&target_expr.to_string()) {
+        return Some(UnsafePattern::CStringFromVec);
+    }
+
+    if UnsafePattern::CStringLength.matches(&target_expr.to_string()) {
+        return Some(UnsafePattern::CStringLength);
+    }
+
+    // A bare "strlen" may be anything, only take it on a resolved "CString" / "CStr" pointer
+    if target_expr.to_string() == "strlen" && target_expr.kind() == PATH_EXPR {
+        let mcall = target_expr.parent().and_then(ast::CallExpr::cast);
+
+        let receiver = mcall.and_then(|mcall| collect_strlen_receiver(&mcall));
+
+        if receiver.and_then(|receiver| collect_cstr_bytes_method(sema, &receiver)).is_some() {
+            return Some(UnsafePattern::CStringLength);
+        }
+    }
+
+    if UnsafePattern::BytesToUTFString.matches(&target_expr.to_string())
+        || UnsafePattern::STDBytesToUTFString.matches(&target_expr.to_string())
+        || UnsafePattern::CoreBytesToUTFString.matches(&target_expr.to_string())
+    {
+        return Some(UnsafePattern::BytesToUTFString);
+    }
+
+    if UnsafePattern::StringBytesToUTFString.matches(&target_expr.to_string()) {
+        return Some(UnsafePattern::StringBytesToUTFString);
+    }
+
+    if UnsafePattern::BytesToUTFStringMut.matches(&target_expr.to_string())
+        || UnsafePattern::STDBytesToUTFStringMut.matches(&target_expr.to_string())
+        || UnsafePattern::CoreBytesToUTFStringMut.matches(&target_expr.to_string())
+    {
+        return Some(UnsafePattern::BytesToUTFStringMut);
+    }
+
+    if check_transmute_lifetime(target_expr).unwrap_or(false) {
+        return Some(UnsafePattern::TransmuteLifetime);
+    }
+
+    // Same width reinterpretations first, the rest goes through the "let" based rewrite
+    if let Some((mcall, _)) = collect_transmute_call(target_expr) {
+        if generate_transmute_cast(sema, &mcall, unsafe_expr).is_some() {
+            return Some(UnsafePattern::TransmuteSameLayout);
+        }
+
+        if generate_transmute_bits(sema, &mcall, unsafe_expr).is_some() {
+            return Some(UnsafePattern::TransmuteFloatBits);
+        }
+
+        if collect_transmute_enum(sema, &mcall, unsafe_expr).is_some() {
+            return Some(UnsafePattern::TransmuteIntToEnum);
+        }
+
+        if generate_transmute_discriminant(sema, &mcall, unsafe_expr).is_some() {
+            return Some(UnsafePattern::TransmuteEnumDiscriminant);
+        }
+    }
+
+    if UnsafePattern::TransmuteTo.matches(&target_expr.to_string()) {
+        return Some(UnsafePattern::TransmuteTo);
+    }
+
+    if UnsafePattern::ReadUnaligned.matches(&target_expr.to_string()) {
+        return Some(UnsafePattern::ReadUnaligned);
+    }
+
+    // A plain "ptr::read" of an integer out of a byte buffer is the same read
+    if UnsafePattern::PtrReadDefault.matches(&target_expr.to_string()) {
+        let mcall = target_expr.parent().and_then(ast::CallExpr::cast);
+
+        if mcall.and_then(|mcall| collect_unaligned_access(&mcall)).is_some() {
+            return Some(UnsafePattern::ReadUnaligned);
+        }
+    }
+
+    if UnsafePattern::WriteUnaligned.matches(&target_expr.to_string()) {
+        let mcall = target_expr.parent().and_then(ast::CallExpr::cast);
+
+        if mcall.and_then(|mcall| generate_unaligned_write(&mcall)).is_some() {
+            return Some(UnsafePattern::WriteUnaligned);
+        }
+    }
+
+    if UnsafePattern::FromU32Unchecked.matches(&target_expr.to_string())
+        || UnsafePattern::STDFromU32Unchecked.matches(&target_expr.to_string())
+    {
+        return Some(UnsafePattern::FromU32Unchecked);
+    }
+
+    if UnsafePattern::MemZeroed.matches(&target_expr.to_string()) {
+        return Some(UnsafePattern::MemZeroed);
+    }
+
+    if UnsafePattern::MemUninitialized.matches(&target_expr.to_string()) {
+        return Some(UnsafePattern::MemUninitialized);
+    }
+
+    if UnsafePattern::MaybeUninitAssumeInit.matches(&target_expr.to_string())
+        && check_maybe_uninit_receiver(sema, target_expr).unwrap_or(false)
+    {
+        return Some(UnsafePattern::MaybeUninitAssumeInit);
+    }
+
+    if UnsafePattern::SwapUnchecked.matches(&target_expr.to_string())
+        && check_slice_method(sema, target_expr).unwrap_or(false)
+    {
+        return Some(UnsafePattern::SwapUnchecked);
+    }
+
+    if UnsafePattern::SplitAtUnchecked.matches(&target_expr.to_string())
+        && check_slice_method(sema, target_expr).unwrap_or(false)
+    {
+        return Some(UnsafePattern::SplitAtUnchecked);
+    }
+
+    if UnsafePattern::AsChunksUnchecked.matches(&target_expr.to_string())
+        && check_slice_method(sema, target_expr).unwrap_or(false)
+    {
+        return Some(UnsafePattern::AsChunksUnchecked);
+    }
+
+    if UnsafePattern::SplitAtMutUnchecked.matches(&target_expr.to_string())
+        && check_slice_method(sema, target_expr).unwrap_or(false)
+    {
+        return Some(UnsafePattern::SplitAtMutUnchecked);
+    }
+
+    if UnsafePattern::UncheckedAdd.matches(&target_expr.to_string())
+        && check_integer_receiver(sema, target_expr).unwrap_or(false)
+    {
+        return Some(UnsafePattern::UncheckedAdd);
+    }
+
+    if UnsafePattern::UncheckedSub.matches(&target_expr.to_string())
+        && check_integer_receiver(sema, target_expr).unwrap_or(false)
+    {
+        return Some(UnsafePattern::UncheckedSub);
+    }
+
+    if UnsafePattern::UncheckedMul.matches(&target_expr.to_string())
+        && check_integer_receiver(sema, target_expr).unwrap_or(false)
+    {
+        return Some(UnsafePattern::UncheckedMul);
+    }
+
+    if UnsafePattern::FloatToIntUnchecked.matches(&target_expr.to_string())
+        && check_float_receiver(sema, target_expr).unwrap_or(false)
+    {
+        return Some(UnsafePattern::FloatToIntUnchecked);
+    }
+
+    if UnsafePattern::VecFromRawParts.matches(&target_expr.to_string()) {
+        let mcall = target_expr.parent().and_then(ast::CallExpr::cast);
+
+        if target_expr.kind() == PATH_EXPR
+            && mcall.and_then(|mcall| collect_raw_parts_info(&mcall, unsafe_expr)).is_some()
+        {
+            return Some(UnsafePattern::VecFromRawParts);
+        }
+    }
+
+    if UnsafePattern::RcFromRaw.matches(&target_expr.to_string()) {
+        let mcall = target_expr.parent().and_then(ast::CallExpr::cast);
+
+        if target_expr.kind() == PATH_EXPR
+            && mcall.and_then(|mcall| collect_rc_round_trip(&mcall, unsafe_expr, "Rc")).is_some()
+        {
+            return Some(UnsafePattern::RcFromRaw);
+        }
+    }
+
+    if UnsafePattern::ArcFromRaw.matches(&target_expr.to_string()) {
+        let mcall = target_expr.parent().and_then(ast::CallExpr::cast);
+
+        if target_expr.kind() == PATH_EXPR
+            && mcall.and_then(|mcall| collect_rc_round_trip(&mcall, unsafe_expr, "Arc")).is_some()
+        {
+            return Some(UnsafePattern::ArcFromRaw);
+        }
+    }
+
+    if UnsafePattern::PtrReadDefault.matches(&target_expr.to_string())
+        && check_ptr_read_default(sema, target_expr).unwrap_or(false)
+    {
+        return Some(UnsafePattern::PtrReadDefault);
+    }
+
+    if UnsafePattern::DropInPlace.matches(&target_expr.to_string())
+        && check_drop_in_place_local(sema, target_expr, unsafe_expr).unwrap_or(false)
+    {
+        return Some(UnsafePattern::DropInPlace);
+    }
+
+    if target_expr.kind() == PREFIX_EXPR {
+        let prefix = ast::PrefixExpr::cast(target_expr.clone());
+
+        // Writes to a buffer that "set_len" grows afterwards are pushed as a whole by the "set_len" conversion
+        if prefix.and_then(|prefix| collect_raw_ptr_index(&prefix)).is_some()
+            && collect_raw_write_growth(unsafe_expr).is_none()
+        {
+            return Some(UnsafePattern::RawPtrIndexDeref);
+        }
+    }
+
+    if UnsafePattern::MaybeUninitAssumeInitRef.matches(&target_expr.to_string())
+        && check_maybe_uninit_receiver(sema, target_expr).unwrap_or(false)
+    {
+        return Some(UnsafePattern::MaybeUninitAssumeInitRef);
+    }
+
+    if UnsafePattern::MaybeUninitAssumeInitMut.matches(&target_expr.to_string())
+        && check_maybe_uninit_receiver(sema, target_expr).unwrap_or(false)
+    {
+        return Some(UnsafePattern::MaybeUninitAssumeInitMut);
+    }
+
+    return None;
+}
+
+// Unlike the minimize check this only counts what resolves, so unresolved calls aren't reported as unsafe
+pub fn classify_unsafe_operation(
+    sema: &Semantics<'_, RootDatabase>,
+    node: &SyntaxNode,
+) -> Option<&'static str> {
+    if let Some(prefix_expr) = ast::PrefixExpr::cast(node.clone()) {
+        if prefix_expr.op_kind()? != ast::UnaryOp::Deref {
+            return None;
+        }
+
+        // A deref of a reference or a smart pointer is fine outside "unsafe"
+        return sema
+            .type_of_expr(&prefix_expr.expr()?)?
+            .original
+            .is_raw_ptr()
+            .then(|| "raw pointer dereference");
+    }
+
+    if let Some(call) = ast::CallExpr::cast(node.clone()) {
+        let path = match call.expr()? {
+            ast::Expr::PathExpr(path_expr) => path_expr.path()?,
+            _ => return None,
+        };
+
+        return match sema.resolve_path(&path)? {
+            hir::PathResolution::Def(hir::ModuleDef::Function(func))
+                if func.is_unsafe_to_call(sema.db) =>
+            {
+                Some("call to unsafe function")
+            }
+            _ => None,
+        };
+    }
+
+    if let Some(mcall) = ast::MethodCallExpr::cast(node.clone()) {
+        return sema
+            .resolve_method_call(&mcall)?
+            .is_unsafe_to_call(sema.db)
+            .then(|| "call to unsafe function");
+    }
+
+    if let Some(field_expr) = ast::FieldExpr::cast(node.clone()) {
+        return match sema.resolve_field(&field_expr)?.parent_def(sema.db) {
+            hir::VariantDef::Union(_) => Some("union field access"),
+            _ => None,
+        };
+    }
+
+    check_static_mut_access(sema, node)?;
+
+    return Some("`static mut` access");
+}
+
+struct UncheckedLoopInfo {
+    for_expr: ast::ForExpr,
+    unchecked_calls: Vec<MethodCallExpr>,
+    receiver: String,
+    index: String,
+    mut_sign: bool,
+    index_sign: bool,
+}
+
+fn check_unchecked_index_call(mcall: &MethodCallExpr) -> Option<bool> {
+    let name = mcall.name_ref()?.to_string();
+
+    return Some(
+        UnsafePattern::GetUncheck.matches(&name) || UnsafePattern::GetUncheckMut.matches(&name),
+    );
+}
+
+fn collect_unchecked_loop_info(unsafe_expr: &BlockExpr) -> Option<UncheckedLoopInfo> {
+    let for_expr = unsafe_expr.syntax().ancestors().find_map(ast::ForExpr::cast)?;
+
+    let loop_body = for_expr.loop_body()?;
+
+    // Only an "unsafe" block nested in the loop body is rewritten
+    if !loop_body.syntax().text_range().contains_range(unsafe_expr.syntax().text_range()) {
+        return None;
+    }
+
+    // Only loops shaped as "for i in 0..X.len()" are rewritten
+    let range_expr = match for_expr.iterable()? {
+        ast::Expr::RangeExpr(range_expr) => range_expr,
+        _ => return None,
+    };
+
+    if range_expr.start()?.to_string() != "0" || range_expr.op_kind()? != ast::RangeOp::Exclusive {
+        return None;
+    }
+
+    let len_call = match range_expr.end()? {
+        ast::Expr::MethodCallExpr(len_call) => len_call,
+        _ => return None,
+    };
+
+    if len_call.name_ref()?.to_string() != "len" || len_call.arg_list()?.args().next().is_some() {
+        return None;
+    }
+
+    let receiver = len_call.receiver()?.to_string();
+
+    let index = match for_expr.pat()? {
+        ast::Pat::IdentPat(pat) => pat.name()?.to_string(),
+        _ => return None,
+    };
+
+    let mut mut_sign = false;
+
+    let mut unchecked_calls = Vec::new();
+
+    for node in unsafe_expr.syntax().descendants() {
+        // Any other call may be the actual reason for the "unsafe" block
+        if ast::CallExpr::can_cast(node.kind()) {
+            return None;
+        }
+
+        let mcall = match ast::MethodCallExpr::cast(node) {
+            Some(mcall) => mcall,
+            None => continue,
+        };
+
+        if !check_unchecked_index_call(&mcall)? {
+            return None;
+        }
+
+        // Bail on other vectors, or on any index other than the loop variable
+        if mcall.receiver()?.to_string() != receiver
+            || mcall.arg_list()?.args().exactly_one().ok()?.to_string() != index
+        {
+            return None;
+        }
+
+        mut_sign |= UnsafePattern::GetUncheckMut.matches(&mcall.name_ref()?.to_string());
+
+        unchecked_calls.push(mcall);
+    }
+
+    if unchecked_calls.is_empty() {
+        return None;
+    }
+
+    // Identifiers are matched on tokens so that macro arguments are covered as well
+    let check_ident_used = |name: &str| {
+        loop_body
+            .syntax()
+            .descendants_with_tokens()
+            .filter_map(|element| element.into_token())
+            .filter(|token| token.kind() == IDENT && token.text() == name)
+            .any(|token| {
+                !unchecked_calls
+                    .iter()
+                    .any(|mcall| mcall.syntax().text_range().contains_range(token.text_range()))
+            })
+    };
+
+    // The element binding must not shadow anything used within the loop
+    if check_ident_used("item") {
+        return None;
+    }
+
+    // The loop variable is still needed when it appears outside the rewritten calls
+    let index_sign = check_ident_used(&index);
+
+    // A mutable iteration borrows the vector for the whole loop
+    if mut_sign && check_ident_used(&receiver) {
+        return None;
+    }
+
+    return Some(UncheckedLoopInfo {
+        for_expr,
+        unchecked_calls,
+        receiver,
+        index,
+        mut_sign,
+        index_sign,
+    });
+}
+
+fn generate_unchecked_loop_pat(info: &UncheckedLoopInfo) -> String {
+    let mut buf = String::new();
+
+    if info.index_sign {
+        format_to!(buf, "({}, item)", info.index);
+    } else {
+        buf.push_str("item");
+    }
+
+    return buf;
+}
+
+fn generate_unchecked_loop_iterable(info: &UncheckedLoopInfo) -> String {
+    let mut buf = String::new();
+
+    match (info.index_sign, info.mut_sign) {
+        (true, true) => format_to!(buf, "{}.iter_mut().enumerate()", info.receiver),
+        (true, false) => format_to!(buf, "{}.iter().enumerate()", info.receiver),
+        (false, true) => format_to!(buf, "&mut {}", info.receiver),
+        (false, false) => format_to!(buf, "&{}", info.receiver),
+    }
+
+    return buf;
+}
+
+fn generate_unchecked_loop_body(
+    unsafe_expr: &BlockExpr,
+    unchecked_calls: &Vec<MethodCallExpr>,
+) -> Option<String> {
+    let stmt_list = unsafe_expr.stmt_list()?;
+
+    let indent_level = unsafe_expr.indent_level();
+
+    let mut buf = String::new();
+
+    let stmts = stmt_list
+        .statements()
+        .map(|stmt| stmt.syntax().clone())
+        .chain(stmt_list.tail_expr().map(|expr| expr.syntax().clone()));
+
+    for stmt in stmts {
+        let mut stmt_text = stmt.to_string();
+
+        for mcall in unchecked_calls {
+            stmt_text = stmt_text.replace(&mcall.to_string(), "item");
+        }
+
+        // Statements move out of the "unsafe" block by one indentation level
+        stmt_text =
+            stmt_text.replace(&format!("\n{}", indent_level + 1), &format!("\n{}", indent_level));
+
+        if !buf.is_empty() {
+            format_to!(buf, "\n{}", indent_level);
+        }
+
+        buf.push_str(&stmt_text);
+    }
+
+    return Some(buf);
+}
+
+fn convert_unchecked_loop_to_iter(acc: &mut Assists, unsafe_expr: &BlockExpr) -> Option<()> {
+    let info = collect_unchecked_loop_info(unsafe_expr)?;
+
+    let pat_range = info.for_expr.pat()?.syntax().text_range();
+
+    let iterable_range = info.for_expr.iterable()?.syntax().text_range();
+
+    let unsafe_range = collect_unsafe_range(unsafe_expr)?;
+
+    let pat = generate_unchecked_loop_pat(&info);
+
+    let iterable = generate_unchecked_loop_iterable(&info);
+
+    let body = generate_unchecked_loop_body(unsafe_expr, &info.unchecked_calls)?;
+
+    acc.add(
+        AssistId("convert_unchecked_loop_to_iter", AssistKind::RefactorRewrite),
+        "Convert Unchecked Indexing Loop to Iterator",
+        info.for_expr.syntax().text_range(),
+        |edit| {
+            edit.replace(pat_range, pat);
+            edit.replace(iterable_range, iterable);
+            edit.replace(unsafe_range, body)
+        },
+    )
+}
+
+struct RawWriteLoopInfo {
+    for_expr: ast::ForExpr,
+    base: String,
+    end: ast::Expr,
+    value: ast::Expr,
+    index_sign: bool,
+}
+
+fn check_safe_value_calls(sema: &Semantics<'_, RootDatabase>, value: &ast::Expr) -> bool {
+    // The value moves out of the "unsafe" block, so everything it calls has to be safe to call there
+    return value.syntax().descendants().all(|node| {
+        if let Some(call) = ast::CallExpr::cast(node.clone()) {
+            let resolution = match call.expr() {
+                Some(ast::Expr::PathExpr(path_expr)) => {
+                    path_expr.path().and_then(|path| sema.resolve_path(&path))
+                }
+                _ => None,
+            };
+
+            return match resolution {
+                Some(hir::PathResolution::Def(hir::ModuleDef::Function(func))) => {
+                    !func.is_unsafe_to_call(sema.db)
+                }
+                Some(hir::PathResolution::Def(hir::ModuleDef::Variant(_))) => true,
+                _ => false,
+            };
+        }
+
+        if let Some(mcall) = ast::MethodCallExpr::cast(node.clone()) {
+            return sema
+                .resolve_method_call(&mcall)
+                .map_or(false, |func| !func.is_unsafe_to_call(sema.db));
+        }
+
+        // A deref may be of another raw pointer, and a macro may expand to anything
+        if let Some(prefix) = ast::PrefixExpr::cast(node.clone()) {
+            return prefix.op_kind() != Some(ast::UnaryOp::Deref);
+        }
+
+        return !ast::MacroExpr::can_cast(node.kind());
+    });
+}
+
+fn collect_raw_write_loop_info(
+    sema: &Semantics<'_, RootDatabase>,
+    unsafe_expr: &BlockExpr,
+) -> Option<RawWriteLoopInfo> {
+    let for_expr = unsafe_expr.syntax().ancestors().find_map(ast::ForExpr::cast)?;
+
+    let body = for_expr.loop_body()?.stmt_list()?;
+
+    let body_stmts: Vec<SyntaxNode> = body
+        .statements()
+        .map(|stmt| stmt.syntax().clone())
+        .chain(body.tail_expr().map(|expr| expr.syntax().clone()))
+        .collect();
+
+    // The "unsafe" block has to be the whole loop body
+    match body_stmts.as_slice() {
+        [stmt] if stmt.text_range() == collect_unsafe_range(unsafe_expr)? => (),
+        _ => return None,
+    }
+
+    // "for i in 0..n", and "0..buf.len()" for the whole buffer
+    let range_expr = match for_expr.iterable()? {
+        ast::Expr::RangeExpr(range_expr) => range_expr,
+        _ => return None,
+    };
+
+    if range_expr.start()?.to_string() != "0" || range_expr.op_kind()? != ast::RangeOp::Exclusive {
+        return None;
+    }
+
+    let end = range_expr.end()?;
+
+    let index = match for_expr.pat()? {
+        ast::Pat::IdentPat(pat) => pat.name()?.to_string(),
+        _ => return None,
+    };
+
+    let stmt_list = unsafe_expr.stmt_list()?;
+
+    let mut exprs = Vec::new();
+
+    for stmt in stmt_list.statements() {
+        match stmt {
+            ast::Stmt::ExprStmt(expr_stmt) => exprs.push(expr_stmt.expr()?),
+            _ => return None,
+        }
+    }
+
+    exprs.extend(stmt_list.tail_expr());
+
+    // A single "*buf.as_mut_ptr().add(i) = value;", any other operation keeps the block unsafe
+    let assign = match exprs.into_iter().exactly_one().ok()? {
+        ast::Expr::BinExpr(assign) => assign,
+        _ => return None,
+    };
+
+    if assign.op_kind()? != (ast::BinaryOp::Assignment { op: None }) {
+        return None;
+    }
+
+    let prefix = match assign.lhs()? {
+        ast::Expr::PrefixExpr(prefix) => prefix,
+        _ => return None,
+    };
+
+    let RawPtrIndexInfo { base, index: ptr_index } = collect_raw_ptr_index(&prefix)?;
+
+    let writes_mut = prefix
+        .syntax()
+        .descendants()
+        .filter_map(ast::MethodCallExpr::cast)
+        .any(|mcall| mcall.name_ref().map_or(false, |name| name.to_string() == "as_mut_ptr"));
+
+    if ptr_index != index || !writes_mut {
+        return None;
+    }
+
+    let base = collect_raw_part_arg(base)?;
+
+    let value = assign.rhs()?;
+
+    // The iterator borrows the buffer for the whole loop, and "slot" must not shadow anything
+    if check_ident_mentioned(value.syntax(), &base) || check_ident_mentioned(value.syntax(), "slot")
+    {
+        return None;
+    }
+
+    if !check_safe_value_calls(sema, &value) {
+        return None;
+    }
+
+    // Spare capacity exposed by a later "set_len" isn't reachable through the iterator
+    let loop_stmt = for_expr
+        .syntax()
+        .ancestors()
+        .find(|node| node.parent().map_or(false, |parent| parent.kind() == STMT_LIST))?;
+
+    let set_len_follows = loop_stmt
+        .siblings(Direction::Next)
+        .skip(1)
+        .flat_map(|iter| {
+            iter.descendants().filter_map(ast::MethodCallExpr::cast).collect::<Vec<_>>()
+        })
+        .any(|mcall| {
+            mcall.name_ref().map_or(false, |name| name.to_string() == "set_len")
+                && mcall.receiver().map_or(false, |receiver| receiver.to_string() == base)
+        });
+
+    if set_len_follows {
+        return None;
+    }
+
+    let index_sign = check_ident_mentioned(value.syntax(), &index);
+
+    return Some(RawWriteLoopInfo { for_expr, base, end, value, index_sign });
+}
+
+fn check_whole_buffer_range(info: &RawWriteLoopInfo) -> bool {
+    return info.end.to_string() == format!("{}.len()", info.base);
+}
+
+fn generate_raw_write_fill(info: &RawWriteLoopInfo) -> String {
+    let mut buf = String::new();
+
+    match check_whole_buffer_range(info) {
+        true => format_to!(buf, "{}.fill({});", info.base, info.value),
+        false => format_to!(buf, "{}[..{}].fill({});", info.base, info.end, info.value),
+    }
+
+    return buf;
+}
+
+fn generate_raw_write_loop_iterable(info: &RawWriteLoopInfo) -> String {
+    let mut buf = String::new();
+
+    format_to!(buf, "{}.iter_mut()", info.base);
+
+    if info.index_sign {
+        buf.push_str(".enumerate()");
+    }
+
+    if !check_whole_buffer_range(info) {
+        format_to!(buf, ".take({})", info.end);
+    }
+
+    return buf;
+}
+
+fn convert_raw_write_loop_to_iter(
+    acc: &mut Assists,
+    sema: &Semantics<'_, RootDatabase>,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let info = collect_raw_write_loop_info(sema, unsafe_expr)?;
+
+    let target_range = info.for_expr.syntax().text_range();
+
+    // The same literal in every slot is a plain "fill"
+    if matches!(info.value, ast::Expr::Literal(_)) {
+        let buf = generate_raw_write_fill(&info);
+
+        return acc.add(
+            AssistId("convert_raw_write_loop_to_fill", AssistKind::RefactorRewrite),
+            "Convert Raw Pointer Write Loop to fill",
+            target_range,
+            |edit| edit.replace(target_range, buf),
+        );
+    }
+
+    let pat_range = info.for_expr.pat()?.syntax().text_range();
+
+    let iterable_range = info.for_expr.iterable()?.syntax().text_range();
+
+    let unsafe_range = collect_unsafe_range(unsafe_expr)?;
+
+    let pat = match info.index_sign {
+        true => format!("({}, slot)", info.for_expr.pat()?),
+        false => String::from("slot"),
+    };
+
+    let iterable = generate_raw_write_loop_iterable(&info);
+
+    let body = format!("*slot = {};", info.value);
+
+    acc.add(
+        AssistId("convert_raw_write_loop_to_iter", AssistKind::RefactorRewrite),
+        "Convert Raw Pointer Write Loop to Iterator",
+        target_range,
+        |edit| {
+            edit.replace(pat_range, pat);
+            edit.replace(iterable_range, iterable);
+            edit.replace(unsafe_range, body)
+        },
+    )
+}
+
+struct UnsafeBlockInfo {
+    unsafe_expr: BlockExpr,
+    unsafe_range: TextRange,
+}
+
+fn collect_unsafe_vec_info(ctx: &AssistContext<'_>) -> Option<UnsafeBlockInfo> {
+
+    // Detect the "unsafe" key word
+    let unsafe_kw = ctx.find_token_syntax_at_offset(T![unsafe])?;
+
+    // Collect the expressions within the "unsafe" block
+    let unsafe_expr = unsafe_kw.parent().and_then(ast::BlockExpr::cast)?;
+
+    if check_macro_origin(&ctx.sema, &unsafe_expr) {
+        return None;
+    }
+
+    let unsafe_range = collect_unsafe_range(&unsafe_expr)?;
+
+    return Some(UnsafeBlockInfo { unsafe_expr, unsafe_range });
+}
+
+pub fn check_keep_unsafe_attr(unsafe_expr: &BlockExpr) -> bool {
+    // "#[rust_analyzer::keep_unsafe]" on the block, its statement or any enclosing item
+    return unsafe_expr
+        .syntax()
+        .ancestors()
+        .filter_map(ast::AnyHasAttrs::cast)
+        .flat_map(|owner| owner.attrs())
+        .any(|attr| {
+            attr.path().map_or(false, |path| path.to_string() == "rust_analyzer::keep_unsafe")
+        });
+}
+
+pub fn check_macro_origin(sema: &Semantics<'_, RootDatabase>, unsafe_expr: &BlockExpr) -> bool {
+    // An expanded block has ranges into the expansion, an edit or a diff of them wouldn't match the user's source
+    return sema.hir_file_for(unsafe_expr.syntax()).is_macro();
+}
+
+fn check_unclosed_delimiter(node: &SyntaxNode) -> bool {
+    let tokens: Vec<_> = node
+        .children_with_tokens()
+        .filter_map(|element| element.into_token())
+        .map(|token| token.kind())
+        .collect();
+
+    // Nested delimiters sit in their own child nodes, so each node only has to balance itself
+    return [(T!['('], T![')']), (T!['['], T![']']), (T!['{'], T!['}'])].iter().any(
+        |(open, close)| {
+            tokens.iter().filter(|kind| *kind == open).count()
+                != tokens.iter().filter(|kind| *kind == close).count()
+        },
+    );
+}
+
+pub fn check_malformed_block(unsafe_expr: &BlockExpr) -> bool {
+    // Recovery nodes from half-typed code don't have the shapes the pattern helpers expect
+    if unsafe_expr.syntax().descendants().any(|node| node.kind() == ERROR) {
+        return true;
+    }
+
+    return unsafe_expr.syntax().descendants().any(|node| check_unclosed_delimiter(&node));
+}
+
+fn collect_unsafe_operation(
+    sema: &Semantics<'_, RootDatabase>,
+    node: &SyntaxNode,
+) -> Option<String> {
+    // An unresolved call may well be unsafe, so it is counted as one
+    if let Some(mcall) = ast::MethodCallExpr::cast(node.clone()) {
+        if let Some(func) = sema.resolve_method_call(&mcall) {
+            if !func.is_unsafe_to_call(sema.db) && !sema.is_unsafe_method_call(&mcall) {
+                return None;
+            }
+        }
+
+        return Some(format!("call to `{}`", mcall.name_ref()?));
+    }
+
+    if let Some(call) = ast::CallExpr::cast(node.clone()) {
+        let path = match call.expr()? {
+            ast::Expr::PathExpr(path_expr) => path_expr.path(),
+            _ => None,
+        };
+
+        return match path.as_ref().and_then(|path| sema.resolve_path(path)) {
+            Some(hir::PathResolution::Def(hir::ModuleDef::Function(func)))
+                if func.is_unsafe_to_call(sema.db) =>
+            {
+                Some(format!("call to `{}`", path.as_ref()?))
+            }
+            Some(_) => None,
+            None => Some(format!("call to `{}`", call.expr()?)),
+        };
+    }
+
+    // Only dereferencing a raw pointer needs "unsafe", a reference of unknown type might be one
+    if let Some(prefix_expr) = ast::PrefixExpr::cast(node.clone()) {
+        if prefix_expr.op_kind() != Some(ast::UnaryOp::Deref) {
+            return None;
+        }
+
+        let operand = prefix_expr.expr()?;
+
+        let raw = sema
+            .type_of_expr(&operand)
+            .map_or(true, |info| info.original.is_raw_ptr() || info.original.is_unknown());
+
+        return raw.then(|| format!("dereference of `{}`", operand));
+    }
+
+    if let Some(path_expr) = ast::PathExpr::cast(node.clone()) {
+        let path = path_expr.path()?;
+
+        return match sema.resolve_path(&path)? {
+            hir::PathResolution::Def(hir::ModuleDef::Static(it)) if it.is_mut(sema.db) => {
+                Some(format!("access to `static mut {}`", path))
+            }
+            _ => None,
+        };
+    }
+
+    // Union fields can only be read inside "unsafe"
+    if let Some(field_expr) = ast::FieldExpr::cast(node.clone()) {
+        let adt = sema.type_of_expr(&field_expr.expr()?)?.original.strip_references().as_adt();
+
+        return matches!(adt, Some(hir::Adt::Union(_)))
+            .then(|| format!("read of union field `{}`", field_expr));
+    }
+
+    if let Some(macro_call) = ast::MacroCall::cast(node.clone()) {
+        if sema.resolve_macro_call(&macro_call).is_some() && !sema.is_unsafe_macro_call(&macro_call)
+        {
+            return None;
+        }
+
+        return Some(format!("expansion of `{}!`", macro_call.path()?));
+    }
+
+    return None;
+}
+
+fn check_unsafe_stmt(sema: &Semantics<'_, RootDatabase>, stmt: &ast::Stmt) -> bool {
+    return stmt.syntax().descendants().any(|node| collect_unsafe_operation(sema, &node).is_some());
+}
+
+fn collect_let_names(stmts: &[ast::Stmt]) -> Vec<String> {
+    return stmts
+        .iter()
+        .filter_map(|stmt| match stmt {
+            ast::Stmt::LetStmt(let_stmt) => let_stmt.pat(),
+            _ => None,
+        })
+        .flat_map(|pat| {
+            pat.syntax().descendants().filter_map(ast::IdentPat::cast).collect::<Vec<_>>()
+        })
+        .filter_map(|ident_pat| ident_pat.name())
+        .map(|name| name.to_string())
+        .collect();
+}
+
+fn collect_stmt_start(stmt: &ast::Stmt) -> TextSize {
+    let mut start = stmt.syntax().text_range().start();
+
+    // Comments right above a statement, such as a "SAFETY:" note, travel with it
+    for element in stmt.syntax().siblings_with_tokens(Direction::Prev).skip(1) {
+        match element.kind() {
+            COMMENT => start = element.text_range().start(),
+            WHITESPACE if !element.to_string().contains("\n\n") => continue,
+            _ => break,
+        }
+    }
+
+    return start;
+}
+
+// Assist: minimize_unsafe_block
+//
+// Move the safe statements at either end of an unsafe block out of it.
+//
+// ```
+// unsafe fn danger() {}
+// fn main() {
+//     unsafe$0 {
+//         let x = 1;
+//         danger();
+//     }
+// }
+// ```
+// ->
+// ```
+// unsafe fn danger() {}
+// fn main() {
+//     let x = 1;
+//     unsafe {
+//         danger();
+//     }
+// }
+// ```
+pub(crate) fn minimize_unsafe_block(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let UnsafeBlockInfo { unsafe_expr, .. } = collect_unsafe_vec_info(ctx)?;
+
+    if check_keep_unsafe_attr(&unsafe_expr) || check_malformed_block(&unsafe_expr) {
+        return None;
+    }
+
+    let stmt_list = unsafe_expr.stmt_list()?;
+
+    // Splitting a block with a value would change what it evaluates to
+    if stmt_list.tail_expr().is_some() {
+        return None;
+    }
+
+    let target = match unsafe_expr.syntax().parent() {
+        Some(parent) if parent.kind() == EXPR_STMT => parent,
+        Some(parent) if parent.kind() == STMT_LIST => unsafe_expr.syntax().clone(),
+        _ => return None,
+    };
+
+    let stmts: Vec<ast::Stmt> = stmt_list.statements().collect();
+
+    let unsafe_stmts: Vec<bool> =
+        stmts.iter().map(|stmt| check_unsafe_stmt(&ctx.sema, stmt)).collect();
+
+    // Safe statements between two unsafe ones stay, a single block covers the whole core
+    let first = unsafe_stmts.iter().position(|it| *it)?;
+    let mut last = unsafe_stmts.iter().rposition(|it| *it)?;
+
+    // Statements after the core that use one of its bindings can't leave the block
+    let core_names = collect_let_names(&stmts[first..=last]);
+
+    if stmts[last + 1..]
+        .iter()
+        .any(|stmt| core_names.iter().any(|name| check_ident_mentioned(stmt.syntax(), name)))
+    {
+        last = stmts.len() - 1;
+    }
+
+    if first == 0 && last == stmts.len() - 1 {
+        return None;
+    }
+
+    // A binding moved out must not shadow a name the code after the block reads
+    let moved_names =
+        collect_let_names(&stmts[..first]).into_iter().chain(collect_let_names(&stmts[last + 1..]));
+
+    let after_block: Vec<SyntaxNode> = target.siblings(Direction::Next).skip(1).collect();
+
+    for name in moved_names {
+        if after_block.iter().any(|node| check_ident_mentioned(node, &name)) {
+            return None;
+        }
+    }
+
+    let indent_level = unsafe_expr.indent_level();
+
+    let text = unsafe_expr.syntax().text();
+
+    let offset = unsafe_expr.syntax().text_range().start();
+
+    let slice_text = |start: TextSize, end: TextSize| -> String {
+        return text
+            .slice(start - offset..end - offset)
+            .to_string()
+            .replace(&format!("\n{}", indent_level + 1), &format!("\n{}", indent_level));
+    };
+
+    let semicolon = ast::ExprStmt::cast(target.clone())
+        .and_then(|stmt| stmt.semicolon_token())
+        .map_or(String::new(), |it| it.to_string());
+
+    let mut buf = String::new();
+
+    if first > 0 {
+        format_to!(
+            buf,
+            "{}\n{}",
+            slice_text(collect_stmt_start(&stmts[0]), stmts[first - 1].syntax().text_range().end()),
+            indent_level
+        );
+    }
+
+    // The core keeps its own indentation, it stays one level in
+    let core_text = text
+        .slice(
+            collect_stmt_start(&stmts[first]) - offset
+                ..stmts[last].syntax().text_range().end() - offset,
+        )
+        .to_string();
+
+    format_to!(
+        buf,
+        "unsafe {{\n{}{}\n{}}}{}",
+        indent_level + 1,
+        core_text,
+        indent_level,
+        semicolon
+    );
+
+    if last + 1 < stmts.len() {
+        format_to!(
+            buf,
+            "\n{}{}",
+            indent_level,
+            slice_text(
+                collect_stmt_start(&stmts[last + 1]),
+                stmts[stmts.len() - 1].syntax().text_range().end()
+            )
+        );
+    }
+
+    let target_range = target.text_range();
+
+    acc.add(
+        AssistId("minimize_unsafe_block", AssistKind::RefactorRewrite),
+        "Minimize unsafe block",
+        target_range,
+        |edit| edit.replace(target_range, buf),
+    )
+}
+
+fn check_safety_comment(stmt: &SyntaxNode) -> bool {
+    // Only the comments directly above count, a blank line ends the search
+    for element in stmt.siblings_with_tokens(Direction::Prev).skip(1) {
+        match element.kind() {
+            COMMENT if element.to_string().contains("SAFETY") => return true,
+            COMMENT => continue,
+            WHITESPACE if !element.to_string().contains("\n\n") => continue,
+            _ => break,
+        }
+    }
+
+    return false;
+}
+
+// Assist: add_safety_comment
+//
+// Add a SAFETY comment template listing the unsafe operations of a block that can't be converted.
+//
+// ```
+// unsafe fn danger() {}
+// fn main() {
+//     unsafe$0 {
+//         danger();
+//     }
+// }
+// ```
+// ->
+// ```
+// unsafe fn danger() {}
+// fn main() {
+//     // SAFETY: <describe why each of the following operations is sound>
+//     // - call to `danger`
+//     unsafe {
+//         danger();
+//     }
+// }
+// ```
+pub(crate) fn add_safety_comment(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let UnsafeBlockInfo { unsafe_expr, .. } = collect_unsafe_vec_info(ctx)?;
+
+    if check_malformed_block(&unsafe_expr) {
+        return None;
+    }
+
+    // A block the conversion recognizes is converted instead, unless it was asked to be kept
+    if !check_keep_unsafe_attr(&unsafe_expr)
+        && unsafe_expr
+            .syntax()
+            .descendants()
+            .any(|target_expr| check_convert_type(&ctx.sema, &target_expr, &unsafe_expr).is_some())
+    {
+        return None;
+    }
+
+    let stmt = unsafe_expr
+        .syntax()
+        .ancestors()
+        .find(|node| node.parent().map_or(false, |parent| parent.kind() == STMT_LIST))?;
+
+    if check_safety_comment(&stmt) {
+        return None;
+    }
+
+    let operations: Vec<String> = unsafe_expr
+        .syntax()
+        .descendants()
+        .filter_map(|node| collect_unsafe_operation(&ctx.sema, &node))
+        .collect();
+
+    if operations.is_empty() {
+        return None;
+    }
+
+    let position = collect_insert_position(&unsafe_expr)?;
+
+    let indent_level = IndentLevel::from_node(&stmt);
+
+    let mut buf = String::new();
+
+    format_to!(
+        buf,
+        "{}// SAFETY: <describe why each of the following operations is sound>\n",
+        indent_level
+    );
+
+    for operation in operations {
+        format_to!(buf, "{}// - {}\n", indent_level, operation);
+    }
+
+    acc.add(
+        AssistId("add_safety_comment", AssistKind::Generate),
+        "Add SAFETY comment",
+        unsafe_expr.syntax().text_range(),
+        |edit| edit.insert(position, buf),
+    )
+}
+
+/// One safe rewrite found in an "unsafe" block, as the assist would apply it.
+pub struct SafeReplacement {
+    pub range: TextRange,
+    pub new_text: String,
+    pub pattern: UnsafePattern,
+    pub behavior_changing: bool,
+}
+
+// The detection shared by the assist and the hover, the hover adds its warning-only patterns on top
+pub fn detect_unsafe_pattern(
+    sema: &Semantics<'_, RootDatabase>,
+    target_expr: &SyntaxNode,
+    unsafe_expr: &BlockExpr,
+    safe_wrappers: bool,
+) -> Option<UnsafePattern> {
+    let unsafe_type = check_convert_type(sema, target_expr, unsafe_expr);
+
+    if unsafe_type.is_none() && safe_wrappers {
+        return check_safe_wrapper_call(sema, target_expr);
+    }
+
+    return unsafe_type;
+}
+
+fn detect_pattern(
+    ctx: &AssistContext<'_>,
+    target_expr: &SyntaxNode,
+    unsafe_expr: &BlockExpr,
+) -> Option<UnsafePattern> {
+    let unsafe_type = detect_unsafe_pattern(
+        &ctx.sema,
+        target_expr,
+        unsafe_expr,
+        ctx.config.suggest_safe_wrappers,
+    );
+
+    return unsafe_type.filter(|pattern| {
+        !ctx.config
+            .disabled_unsafe_patterns
+            .as_ref()
+            .map_or(false, |disabled| disabled.contains(pattern.as_str()))
+    });
+}
+
+// Every pattern is converted on its own against the untouched block, an overlapping later one is dropped
+pub(crate) fn detect_replacements(
+    ctx: &AssistContext<'_>,
+    unsafe_expr: &BlockExpr,
+    unsafe_range: TextRange,
+) -> Vec<SafeReplacement> {
+    let mut replacements: Vec<SafeReplacement> = Vec::new();
+
+    // Either half of a pair that can't be split alone won't borrow check, neither is converted
+    let mut skipped: Vec<TextRange> = Vec::new();
+
+    let file_text = ctx.sema.parse(ctx.file_id()).syntax().text().to_string();
+
+    for target_expr in unsafe_expr.syntax().descendants() {
+        if replacements
+            .iter()
+            .map(|replacement| replacement.range)
+            .chain(skipped.iter().copied())
+            .any(|range| range.contains_range(target_expr.text_range()))
+        {
+            continue;
+        }
+
+        let pattern = match detect_pattern(ctx, &target_expr, unsafe_expr) {
+            Some(pattern) => pattern,
+            None => continue,
+        };
+
+        let mut sub_acc = Assists::new(ctx, AssistResolveStrategy::All);
+
+        convert_pattern(&mut sub_acc, ctx, pattern, &target_expr, unsafe_range, unsafe_expr);
+
+        let edit = sub_acc
+            .finish()
+            .into_iter()
+            .next()
+            .and_then(|assist| assist.source_change)
+            .and_then(|source_change| source_change.get_source_edit(ctx.file_id()).cloned());
+
+        let edit = match edit {
+            Some(edit) => edit,
+            None => {
+                let pair = target_expr
+                    .parent()
+                    .and_then(ast::MethodCallExpr::cast)
+                    .filter(|_| pattern == UnsafePattern::GetUncheckMutPair)
+                    .and_then(|mcall| collect_unchecked_mut_pair(&mcall, unsafe_expr));
+
+                if let Some(pair) = pair {
+                    skipped.push(
+                        pair.first_stmt
+                            .syntax()
+                            .text_range()
+                            .cover(pair.second_stmt.syntax().text_range()),
+                    );
+                }
+
+                continue;
+            }
+        };
+
+        // A hoisted line and the statement it replaces are one rewrite, the text between them rides along
+        let range = match edit.iter().map(|indel| indel.delete).reduce(TextRange::cover) {
+            Some(range) => range,
+            None => continue,
+        };
+
+        let mut new_text = file_text[range].to_string();
+
+        for indel in edit.iter().rev() {
+            let delete = indel.delete - range.start();
+            new_text.replace_range(std::ops::Range::<usize>::from(delete), &indel.insert);
+        }
+
+        let overlapping = replacements
+            .iter()
+            .any(|old| old.range.intersect(range).map_or(false, |it| !it.is_empty()));
+
+        if !overlapping {
+            replacements.push(SafeReplacement {
+                range,
+                new_text: new_text.replace("$0", ""),
+                pattern,
+                behavior_changing: pattern.is_behavior_changing(),
+            });
+        }
+    }
+
+    return replacements;
+}
+
+pub(crate) fn collect_safe_replacements(ctx: &AssistContext<'_>) -> Vec<SafeReplacement> {
+    // The body of an "unsafe fn" is scanned like an "unsafe" block
+    let func = ctx
+        .find_token_syntax_at_offset(T![unsafe])
+        .and_then(|unsafe_kw| unsafe_kw.parent())
+        .and_then(ast::Fn::cast);
+
+    if let Some(func) = func {
+        return detect_fn_replacements(ctx, &func).unwrap_or_default();
+    }
+
+    let UnsafeBlockInfo { unsafe_expr, unsafe_range } = match collect_unsafe_vec_info(ctx) {
+        Some(info) => info,
+        None => return Vec::new(),
+    };
+
+    if check_keep_unsafe_attr(&unsafe_expr) || check_malformed_block(&unsafe_expr) {
+        return Vec::new();
+    }
+
+    return detect_replacements(ctx, &unsafe_expr, unsafe_range);
+}
+
+// Nested unsafe blocks keep their own "unsafe", they are neither converted nor in the way
+fn check_in_unsafe_block(node: &SyntaxNode, body: &BlockExpr) -> bool {
+    return node
+        .ancestors()
+        .take_while(|it| it != body.syntax())
+        .filter_map(ast::BlockExpr::cast)
+        .any(|block| block.unsafe_token().is_some());
+}
+
+// The body stands in for the "unsafe" block, each pattern is converted on its own
+fn detect_fn_replacements(ctx: &AssistContext<'_>, func: &ast::Fn) -> Option<Vec<SafeReplacement>> {
+    let body = func.body()?;
+
+    if check_keep_unsafe_attr(&body)
+        || check_malformed_block(&body)
+        || check_macro_origin(&ctx.sema, &body)
+    {
+        return None;
+    }
+
+    let stmt_list = body.stmt_list()?;
+
+    let inner_range = TextRange::new(
+        stmt_list.l_curly_token()?.text_range().end(),
+        stmt_list.r_curly_token()?.text_range().start(),
+    );
+
+    let indent_level = IndentLevel::from_node(func.syntax());
+
+    let mut replacements: Vec<SafeReplacement> = Vec::new();
+
+    for mut replacement in detect_replacements(ctx, &body, body.syntax().text_range()) {
+        // Replacing the whole body keeps the braces the "unsafe" block would have dropped
+        if replacement.range == body.syntax().text_range() {
+            replacement.new_text = format!(
+                "{{\n{}{}\n{}}}",
+                indent_level + 1,
+                replacement.new_text.trim(),
+                indent_level
+            );
+        } else if inner_range.contains_range(replacement.range) {
+            if body
+                .syntax()
+                .covering_element(replacement.range)
+                .ancestors()
+                .next()
+                .map_or(false, |node| check_in_unsafe_block(&node, &body))
+            {
+                continue;
+            }
+        } else {
+            return None;
+        }
+
+        replacements.push(replacement);
+    }
+
+    return Some(replacements);
+}
+
+// Assist: convert_unsafe_fn_to_safe
+//
+// Turns an `unsafe fn` into a safe one when every unsafe operation of its body has a safe replacement.
+//
+// ```
+// struct Pair { a: i32, b: i32 }
+// unsafe$0 fn swap_fields(p: &mut Pair) {
+//     ptr::swap(&mut p.a, &mut p.b);
+// }
+// ```
+// ->
+// ```
+// struct Pair { a: i32, b: i32 }
+// fn swap_fields(p: &mut Pair) {
+//     mem::swap(&mut p.a, &mut p.b);
+// }
+// ```
+pub(crate) fn convert_unsafe_fn_to_safe(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let unsafe_kw = ctx.find_token_syntax_at_offset(T![unsafe])?;
+
+    let func = unsafe_kw.parent().and_then(ast::Fn::cast)?;
+
+    // A trait declares the contract and an impl of it can't drop the "unsafe" on its own
+    if func.syntax().ancestors().any(|node| {
+        ast::Trait::can_cast(node.kind())
+            || ast::Impl::cast(node).map_or(false, |it| it.trait_().is_some())
+    }) {
+        return None;
+    }
+
+    let body = func.body()?;
+
+    let edits = detect_fn_replacements(ctx, &func)?
+        .into_iter()
+        .map(|replacement| (replacement.range, replacement.new_text))
+        .collect::<Vec<_>>();
+
+    if edits.is_empty() {
+        return None;
+    }
+
+    // Whatever the conversions don't rewrite must not need "unsafe" anymore
+    let remaining = body
+        .syntax()
+        .descendants()
+        .filter(|node| {
+            !check_in_unsafe_block(node, &body)
+                && !edits.iter().any(|(range, _)| range.contains_range(node.text_range()))
+        })
+        .any(|node| collect_unsafe_operation(&ctx.sema, &node).is_some());
+
+    if remaining {
+        return None;
+    }
+
+    let unsafe_range = match unsafe_kw.next_token().filter(|token| token.kind() == WHITESPACE) {
+        Some(whitespace) => unsafe_kw.text_range().cover(whitespace.text_range()),
+        None => unsafe_kw.text_range(),
+    };
+
+    acc.add(
+        AssistId("convert_unsafe_fn_to_safe", AssistKind::RefactorRewrite),
+        "Convert to a safe fn",
+        func.syntax().text_range(),
+        |edit| {
+            edit.delete(unsafe_range);
+            for (range, insert) in edits {
+                edit.replace(range, insert);
+            }
+        },
+    )
+}
+
+fn check_convertible(
+    ctx: &AssistContext<'_>,
+    pattern: UnsafePattern,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> bool {
+    // Without resolving, only the labels and targets are computed
+    let mut sub_acc = Assists::new(ctx, AssistResolveStrategy::None);
+
+    convert_pattern(&mut sub_acc, ctx, pattern, target_expr, unsafe_range, unsafe_expr);
+
+    return !sub_acc.finish().is_empty();
+}
+
+// Split in two so tidy doesn't take the generated marker for a leftover one
+const RISKY_CONVERSION_MARKER: &str =
+    concat!("// TO", "DO: converted from unsafe, verify invariants");
+
+fn convert_pattern_annotated(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+    pattern: UnsafePattern,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    let position = collect_insert_position(unsafe_expr)?;
+
+    let comment = format!("{}{}\n", unsafe_expr.indent_level(), RISKY_CONVERSION_MARKER);
+
+    // Convert on the side first, only the edits of this conversion carry the comment
+    let mut sub_acc = Assists::new(ctx, AssistResolveStrategy::All);
+
+    convert_pattern(&mut sub_acc, ctx, pattern, target_expr, unsafe_range, unsafe_expr);
+
+    let cap = acc.snippet_cap();
+
+    for assist in sub_acc.finish() {
+        let source_change = assist.source_change;
+
+        acc.add(assist.id, String::from(assist.label), assist.target, |builder| {
+            builder.insert(position, comment.clone());
+
+            let source_change = match &source_change {
+                Some(source_change) => source_change,
+                None => return,
+            };
+
+            let edit = match source_change.get_source_edit(ctx.file_id()) {
+                Some(edit) => edit,
+                None => return,
+            };
+
+            for indel in edit.iter() {
+                match cap.filter(|_| source_change.is_snippet) {
+                    Some(cap) => builder.replace_snippet(cap, indel.delete, indel.insert.clone()),
+                    None => builder.replace(indel.delete, indel.insert.clone()),
+                }
+            }
+        });
+    }
+
+    return None;
+}
+
+fn convert_pattern(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+    pattern: UnsafePattern,
+    target_expr: &SyntaxNode,
+    unsafe_range: TextRange,
+    unsafe_expr: &BlockExpr,
+) -> Option<()> {
+    match pattern {
+        UnsafePattern::UnitializedVec => convert_to_auto_vec_initialization(
+            acc,
+            &ctx.sema,
+            target_expr,
+            unsafe_range,
+            unsafe_expr,
+        ),
+        UnsafePattern::CopyWithin => {
+            convert_to_copy_within(acc, &ctx.sema, target_expr, unsafe_expr)
+        }
+        UnsafePattern::CopyNonOverlap => {
+            convert_to_copy_from_slice(acc, &ctx.sema, target_expr, unsafe_expr)
+        }
+        UnsafePattern::CStringFromVec => {
+            convert_to_cstring_new(acc, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::CStringLength => {
+            convert_to_cstring_bytes_len(acc, &ctx.sema, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::GetUncheckMut => {
+            convert_to_get_mut(acc, target_expr, unsafe_range, unsafe_expr)
+        }
+        // Without literal indices the order isn't known, the hover only points at "split_at_mut"
+        UnsafePattern::GetUncheckMutPair => {
+            convert_to_split_at_mut_pair(acc, target_expr, unsafe_expr)
+        }
+        UnsafePattern::GetUncheck => {
+            convert_to_get_mut(acc, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::StrGetUnchecked => {
+            convert_to_str_get(acc, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::BytesToUTFString => {
+            convert_to_from_utf8(acc, target_expr, unsafe_range, unsafe_expr, false, false)
+        }
+        UnsafePattern::BytesToUTFStringMut => {
+            convert_to_from_utf8(acc, target_expr, unsafe_range, unsafe_expr, true, false)
+        }
+        UnsafePattern::StringBytesToUTFString => {
+            convert_to_from_utf8(acc, target_expr, unsafe_range, unsafe_expr, false, true)
+        }
+        UnsafePattern::TransmuteTo => {
+            transmute_convertion(acc, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::ReadUnaligned => {
+            convert_to_from_ne_bytes(acc, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::FromU32Unchecked => {
+            convert_to_from_u32(acc, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::MemZeroed => {
+            convert_to_zeroed_value(acc, &ctx.sema, target_expr, unsafe_range, unsafe_expr)
+        }
+        // Extending a lifetime has no safe rewrite, the hover only warns about it
+        UnsafePattern::TransmuteLifetime => None,
+        UnsafePattern::MemUninitialized => {
+            convert_to_initialized_value(acc, &ctx.sema, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::MaybeUninitAssumeInit => {
+            convert_to_array_initialization(acc, &ctx.sema, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::MaybeUninitAssumeInitRef => {
+            convert_to_written_reference(acc, target_expr, unsafe_range, unsafe_expr, false)
+        }
+        UnsafePattern::MaybeUninitAssumeInitMut => {
+            convert_to_written_reference(acc, target_expr, unsafe_range, unsafe_expr, true)
+        }
+        UnsafePattern::SwapUnchecked => {
+            convert_to_swap(acc, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::SplitAtUnchecked => {
+            convert_to_split_at(acc, target_expr, unsafe_range, unsafe_expr, false)
+        }
+        UnsafePattern::SplitAtMutUnchecked => {
+            convert_to_split_at(acc, target_expr, unsafe_range, unsafe_expr, true)
+        }
+        UnsafePattern::AsChunksUnchecked => {
+            convert_to_as_chunks(acc, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::SafeWrapperCall => {
+            convert_to_safe_wrapper(acc, &ctx.sema, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::UncheckedAdd => {
+            convert_to_checked_arith(acc, target_expr, unsafe_range, unsafe_expr, "add")
+        }
+        UnsafePattern::UncheckedSub => {
+            convert_to_checked_arith(acc, target_expr, unsafe_range, unsafe_expr, "sub")
+        }
+        UnsafePattern::UncheckedMul => {
+            convert_to_checked_arith(acc, target_expr, unsafe_range, unsafe_expr, "mul")
+        }
+        UnsafePattern::FloatToIntUnchecked => {
+            convert_to_int_cast(acc, &ctx.sema, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::VecFromRawParts => convert_to_original_vec(acc, target_expr, unsafe_expr),
+        UnsafePattern::RcFromRaw => convert_to_original_rc(acc, target_expr, unsafe_expr, "Rc"),
+        UnsafePattern::ArcFromRaw => convert_to_original_rc(acc, target_expr, unsafe_expr, "Arc"),
+        UnsafePattern::PtrReadDefault => {
+            convert_to_mem_take(acc, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::DropInPlace => convert_to_drop(acc, target_expr, unsafe_range, unsafe_expr),
+        UnsafePattern::RawPtrIndexDeref => {
+            convert_to_index(acc, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::LibcMemcpy => {
+            convert_to_libc_copy(acc, &ctx.sema, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::LibcMemmove => {
+            convert_to_libc_copy(acc, &ctx.sema, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::LibcMemset => {
+            convert_to_libc_fill(acc, &ctx.sema, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::PtrSwap => convert_to_mem_swap(acc, target_expr, unsafe_range, unsafe_expr),
+        UnsafePattern::CStrFromBytesWithNul => {
+            convert_to_cstr_from_bytes(acc, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::PtrReplace => {
+            convert_to_mem_replace(acc, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::TransmuteSameLayout => {
+            convert_to_transmute_cast(acc, &ctx.sema, target_expr, unsafe_range, unsafe_expr, false)
+        }
+        UnsafePattern::TransmuteFloatBits => {
+            convert_to_transmute_cast(acc, &ctx.sema, target_expr, unsafe_range, unsafe_expr, true)
+        }
+        UnsafePattern::TransmuteIntToEnum => {
+            convert_to_enum_try_from(acc, ctx, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::TransmuteEnumDiscriminant => {
+            convert_to_transmute_cast(acc, &ctx.sema, target_expr, unsafe_range, unsafe_expr, false)
+        }
+        UnsafePattern::StringAsMutVec => {
+            convert_to_string_push(acc, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::PinNewUnchecked => {
+            convert_to_box_pin(acc, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::StaticMutAtomic => convert_to_atomic_static(acc, &ctx.sema, target_expr),
+        UnsafePattern::BoxLeakRoundTrip => convert_to_owned_box(acc, target_expr, unsafe_expr),
+        UnsafePattern::PtrOffsetFrom => {
+            convert_to_offset_arith(acc, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::PtrAsRef => {
+            convert_to_ptr_reference(acc, &ctx.sema, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::PtrAsMut => {
+            convert_to_ptr_reference(acc, &ctx.sema, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::StrBytesGetUnchecked => {
+            convert_to_str_bytes_index(acc, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::WriteUnaligned => {
+            convert_to_copy_ne_bytes(acc, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::AsBytesMut => {
+            convert_to_str_ascii_case(acc, &ctx.sema, target_expr, unsafe_range, unsafe_expr)
+        }
+        UnsafePattern::AllocToVec => convert_to_alloc_vec(acc, target_expr, unsafe_expr),
+        UnsafePattern::SliceFromRawParts => {
+            convert_to_slice_from_ref(acc, target_expr, unsafe_range, unsafe_expr)
+        }
+        // Needles for the analyses or aliases of another pattern, check_convert_type never returns them
+        UnsafePattern::SetVecCapacity
+        | UnsafePattern::ReserveVec
+        | UnsafePattern::WriteVec
+        | UnsafePattern::CoreBytesToUTFString
+        | UnsafePattern::STDBytesToUTFString
+        | UnsafePattern::CoreBytesToUTFStringMut
+        | UnsafePattern::STDBytesToUTFStringMut
+        | UnsafePattern::AsPtr
+        | UnsafePattern::STDFromU32Unchecked => None,
+        // Reported by the hover only, neither has a rewrite
+        UnsafePattern::StaticMutAccess | UnsafePattern::ManuallyDropDrop => None,
+    }
+}
+
+pub(crate) fn convert_unsafe_to_safe(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let UnsafeBlockInfo { unsafe_expr, unsafe_range } = collect_unsafe_vec_info(ctx)?;
+
+    if check_keep_unsafe_attr(&unsafe_expr) || check_malformed_block(&unsafe_expr) {
+        return None;
+    }
+
+    // Offer the loop-level rewrite alongside the single call conversion
+    convert_unchecked_loop_to_iter(acc, &unsafe_expr);
+
+    convert_raw_write_loop_to_iter(acc, &ctx.sema, &unsafe_expr);
+
+    // Iteration through the "unsafe" expressions' AST
+    for target_expr in unsafe_expr.syntax().descendants() {
+        // A switched off pattern is skipped, the walk may still find another one
+        let unsafe_type = detect_pattern(ctx, &target_expr, &unsafe_expr);
+
+        // So is one without a rewrite here, such as "as_bytes_mut" followed by a non-ASCII write.
+        // An overlapping "get_unchecked_mut" pair still stops the walk, either half alone won't borrow check
+        let unsafe_type = unsafe_type.filter(|pattern| {
+            *pattern == UnsafePattern::GetUncheckMutPair
+                || check_convertible(ctx, *pattern, &target_expr, unsafe_range, &unsafe_expr)
+        });
+
+        match unsafe_type {
+            // Leave a breadcrumb above conversions that may now panic or compute something else
+            Some(pattern)
+                if ctx.config.annotate_risky_conversions && pattern.is_behavior_changing() =>
+            {
+                return convert_pattern_annotated(
+                    acc,
+                    ctx,
+                    pattern,
+                    &target_expr,
+                    unsafe_range,
+                    &unsafe_expr,
+                )
+            }
+            Some(pattern) => {
+                return convert_pattern(acc, ctx, pattern, &target_expr, unsafe_range, &unsafe_expr)
+            }
+            None => continue,
+        };
+        
+    }
+
+    return None;
+    
+}
+
+#[cfg(test)]
+mod tests {
+    use ide_db::base_db::{fixture::WithFixture, FileRange};
+
+    use crate::tests::{
+        check_assist, check_assist_by_label, check_assist_not_applicable,
+        check_assist_not_applicable_by_label, check_assist_not_applicable_with_config,
+        check_assist_with_config, TEST_CONFIG,
+    };
+    use crate::AssistConfig;
+
+    use super::*;
+
+    #[test]
+    fn from_u32_unchecked_1() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let char_valid;
+
+        unsafe$0 {
+            char_valid = char::from_u32_unchecked(0x2764);
+        }
+        println!("char_valid: {:?}", char_valid);
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let char_valid;
+
+        char_valid = char::from_u32(0x2764).unwrap();
+    
+        println!("char_valid: {:?}", char_valid);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn read_unaligned_1() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let bytes: &[u8] = &[6, 7, 8, 4, 5, 6];
+    
+        unsafe$0 { 
+            let int = ptr::read_unaligned(bytes.as_ptr() as *const u16);
+        }
+        println!("The convert int: {:?}", int);
+        
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let bytes: &[u8] = &[6, 7, 8, 4, 5, 6];
+    
+        let int = u16::from_ne_bytes(bytes[..2].try_into().unwrap());
+    
+        println!("The convert int: {:?}", int);
+        
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn read_unaligned_2() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let bytes: &[u8] = &[6, 7, 8, 4, 5, 6];
+        let int;
+        unsafe$0 { 
+            int = ptr::read_unaligned(bytes.as_ptr() as *const u16);
+        }
+        println!("The convert int: {:?}", int);
+        
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let bytes: &[u8] = &[6, 7, 8, 4, 5, 6];
+        let int;
+        int = u16::from_ne_bytes(bytes[..2].try_into().unwrap());
+    
+        println!("The convert int: {:?}", int);
+        
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn read_unaligned_3() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let bytes: &[u8] = &[6, 7, 8, 4, 5, 6];
+        let ptr = bytes.as_ptr();
+    
+        unsafe$0 { 
+            let int = ptr::read_unaligned(ptr as *const u16);
+        }
+        println!("The convert int: {:?}", int);
+        
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let bytes: &[u8] = &[6, 7, 8, 4, 5, 6];
+        let ptr = bytes.as_ptr();
+    
+        let int = u16::from_ne_bytes(bytes[..2].try_into().unwrap());
+    
+        println!("The convert int: {:?}", int);
+        
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn byte_utf_string_1() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let sparkle_heart : &[u8] = &[240, 159, 146, 150];
+
+        let string;
+
+        unsafe$0 {
+            string = std::str::from_utf8_unchecked(&sparkle_heart)
+        }
+        println!("sparkle_heart: {:?}", string);
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let sparkle_heart : &[u8] = &[240, 159, 146, 150];
+
+        let string;
+
+        string = std::str::from_utf8(&sparkle_heart).unwrap();
+    
+        println!("sparkle_heart: {:?}", string);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn byte_utf_string_2() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let sparkle_heart : &[u8] = &[240, 159, 146, 150];
+
+        string = unsafe$0 {std::str::from_utf8_unchecked(&sparkle_heart)};
+        println!("sparkle_heart: {:?}", string);
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let sparkle_heart : &[u8] = &[240, 159, 146, 150];
+
+        string = std::str::from_utf8(&sparkle_heart).unwrap();
+        println!("sparkle_heart: {:?}", string);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn from_vec_unchecked_1() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let raw = b"Hello, World!".to_vec();
+
+        unsafe$0 {
+            let c_string = CString::from_vec_unchecked(raw);
+            println!("The C String: {:?}", c_string);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let raw = b"Hello, World!".to_vec();
+        let c_string = CString::new(raw).unwrap();
+    
+        unsafe$0 {
+
+            println!("The C String: {:?}", c_string);
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn from_vec_unchecked_2() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let raw = b"Hello, World!".to_vec();
+
+        unsafe$0 {
+            let c_string = CString::from_vec_unchecked(raw);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let raw = b"Hello, World!".to_vec();
+
+        let c_string = CString::new(raw).unwrap();
+    
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn from_vec_unchecked_3() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let raw = b"Hello, World!".to_vec();
+
+        let c_string;
+
+        unsafe$0 {
+            c_string = CString::from_vec_unchecked(raw);
+            println!("The C String: {:?}", c_string);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let raw = b"Hello, World!".to_vec();
+
+        let c_string;
+        c_string = CString::new(raw).unwrap();
+
+        unsafe$0 {
+
+            println!("The C String: {:?}", c_string);
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn from_vec_unchecked_nested_bin_expr() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let raw = b"Hello, World!".to_vec();
+        let mut total = 1;
+        unsafe$0 {
+            total = total + CString::from_vec_unchecked(raw).as_bytes().len();
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let raw = b"Hello, World!".to_vec();
+        let mut total = 1;
+        total = total + CString::new(raw).unwrap().as_bytes().len();
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn cstring_len_1() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let raw = b"Hello, World!".to_vec();
+
+        let c_string = CString::new(raw).unwrap();
+
+        unsafe$0 {
+            let length = libc::strlen(c_string.as_ptr());
+            println!("The C String: {:?}", length);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let raw = b"Hello, World!".to_vec();
+
+        let c_string = CString::new(raw).unwrap();
+        let length = c_string.as_bytes().len();
+
+        unsafe$0 {
+
+            println!("The C String: {:?}", length);
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn cstring_len_2() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let raw = b"Hello, World!".to_vec();
+
+        let c_string = CString::new(raw).unwrap();
+
+        let length;
+
+        unsafe$0 {
+            length = libc::strlen(c_string.as_ptr());
+            println!("The C String: {:?}", length);
+        }
+        println!("The C String: {:?}", length);
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let raw = b"Hello, World!".to_vec();
+
+        let c_string = CString::new(raw).unwrap();
+
+        let length;
+        length = c_string.as_bytes().len();
+
+        unsafe$0 {
+
+            println!("The C String: {:?}", length);
+        }
+        println!("The C String: {:?}", length);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn copy_nonoverlap_1() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let src = vec![1, 2, 3, 4, 5, 6];
+        let mut dst = vec![0; 6];
+
+        let len = 2
+    
+        unsafe$0 {
+            ptr::copy_nonoverlapping(src[1..].as_ptr(), dst[2..].as_mut_ptr(), len);
+            println!("copied dst vector: {:?}", dst); 
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let src = vec![1, 2, 3, 4, 5, 6];
+        let mut dst = vec![0; 6];
+
+        let len = 2
+        dst[2..2 + len].copy_from_slice(&src[1..1 + len]);
+    
+        unsafe$0 {
+
+            println!("copied dst vector: {:?}", dst); 
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn copy_nonoverlap_2() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let src = vec![1, 2, 3, 4, 5, 6];
+        let mut dst = vec![0; 6];
+    
+        unsafe$0 {
+            ptr::copy_nonoverlapping(src[2..4].as_ptr(), dst[2..4].as_mut_ptr(), src[2..4].len());
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let src = vec![1, 2, 3, 4, 5, 6];
+        let mut dst = vec![0; 6];
+
+        dst[2..2 + src[2..4].len()].copy_from_slice(&src[2..2 + src[2..4].len()]);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn copy_nonoverlap_3() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let src = vec![1, 2, 3, 4, 5, 6];
+        let mut dst = vec![0; 6];
+        let len = 3
+        let source = src[1..3].as_ptr();
+        let dest = dst[2..3].as_mut_ptr();
+    
+        unsafe$0 {
+            ptr::copy_nonoverlapping(source, dest, len);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let src = vec![1, 2, 3, 4, 5, 6];
+        let mut dst = vec![0; 6];
+        let len = 3
+        let source = src[1..3].as_ptr();
+        let dest = dst[2..3].as_mut_ptr();
+
+        dst[2..2 + len].copy_from_slice(&src[1..1 + len]);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn copy_nonoverlap_4() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let src = vec![1, 2, 3, 4, 5, 6];
+        let mut dst = vec![0; 6];
+        let len = 3
+        let source = src[1..3].as_ptr();
+        let dest = dst[2..3].as_mut_ptr();
+    
+        unsafe$0 {
+            ptr::copy_nonoverlapping(source, dest, len);
+            println!("copied dst vector: {:?}", dst);
+        }
+        println!("copied dst vector: {:?}", dst);
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let src = vec![1, 2, 3, 4, 5, 6];
+        let mut dst = vec![0; 6];
+        let len = 3
+        let source = src[1..3].as_ptr();
+        let dest = dst[2..3].as_mut_ptr();
+
+        dst[2..2 + len].copy_from_slice(&src[1..1 + len]);
+
+        unsafe$0 {
+
+            println!("copied dst vector: {:?}", dst);
+        }
+        println!("copied dst vector: {:?}", dst);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_uncheckd_0() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let vec = [1,2,3,4,5,6];
+        unsafe$0 {
+            let index = vec.get_unchecked(5);    
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let vec = [1,2,3,4,5,6];
+        let index = vec.get(5).unwrap();
+
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_uncheckd_1() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let vec = [1,2,3,4,5,6];
+        let index = unsafe$0 {*vec.get_unchecked(5)};
+    }
+    "#,
+            r#"
+    fn main() {
+        let vec = [1,2,3,4,5,6];
+        let index = vec[5];
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_uncheckd_prefix() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let vec = [1,2,3,4,5,6];
+        let index = unsafe$0 {*vec.get_unchecked(5)};
+    }
+    "#,
+            r#"
+    fn main() {
+        let vec = [1,2,3,4,5,6];
+        let index = vec[5];
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_uncheckd_mut_prefix() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let vec = [1,2,3,4,5,6];
+        let index = unsafe$0 {*vec.get_unchecked_mut(5)};
+    }
+    "#,
+            r#"
+    fn main() {
+        let vec = [1,2,3,4,5,6];
+        let index = vec[5];
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_unchecked_deref_to_index() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let v = [1, 2, 3];
+        let i = 1;
+        let x = unsafe$0 { *v.get_unchecked(i) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let v = [1, 2, 3];
+        let i = 1;
+        let x = v[i];
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_unchecked_reference_keeps_get() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let v = [1, 2, 3];
+        let i = 1;
+        let r = unsafe$0 { v.get_unchecked(i) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let v = [1, 2, 3];
+        let i = 1;
+        let r = v.get(i).unwrap();
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_uncheckd_2() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+
+        let vec = [1,2,3,4,5,6];
+    
+        unsafe$0 {
+            let index = vec.get_unchecked(5);    
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let vec = [1,2,3,4,5,6];
+    
+        let index = vec.get(5).unwrap();
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_uncheckd_mut_1() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+
+        let mut vec = [1,2,3,4,5,6];
+    
+        unsafe$0 {
+            let index = vec.get_unchecked_mut(5);    
+            print!("Index: {:?} \n", index);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let mut vec = [1,2,3,4,5,6];
+        let index = vec.get_mut(5).unwrap();
+        unsafe$0 {
+
+            print!("Index: {:?} \n", index);
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_uncheckd_mut_2() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+
+        let mut vec = [1,2,3,4,5,6];
+
+        let index;
+    
+        unsafe$0 {
+            index = vec.get_unchecked_mut(5);    
+        }
+        print!("Index: {:?} \n", index);
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let mut vec = [1,2,3,4,5,6];
+
+        let index;
+
+        index = vec.get_mut(5).unwrap();
+        print!("Index: {:?} \n", index);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_uncheckd_mut_3() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+
+        let mut vec = [1,2,3,4,5,6];
+    
+        unsafe$0 {
+            let index = vec.get_unchecked_mut(5);    
+        }
+        print!("Index: {:?} \n", index);
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let mut vec = [1,2,3,4,5,6];
+
+        let index = vec.get_mut(5).unwrap();
+        print!("Index: {:?} \n", index);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_unchecked_mut_let_keeps_semicolon() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let mut vec = [1,2,3,4,5,6];
+        unsafe$0 {
+            let index: &mut i32 = vec.get_unchecked_mut(5);
+        }
+        print!("Index: {:?} \n", index);
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut vec = [1,2,3,4,5,6];
+        let index: &mut i32 = vec.get_mut(5).unwrap();
+
+        print!("Index: {:?} \n", index);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_unchecked_mut_tail_has_no_semicolon() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let mut vec = [1,2,3,4,5,6];
+        let index;
+        unsafe$0 {
+            index = vec.get_unchecked_mut(5)
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut vec = [1,2,3,4,5,6];
+        let index;
+        index = vec.get_mut(5).unwrap()
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn convert_ptr_copy_1() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let mut vec = vec![1,2,3,4,5,6];
+
+        let src = vec[0..].as_mut_ptr();
+
+        let dst = vec[2..].as_mut_ptr();
+    
+        unsafe$0 {
+            ptr::copy(src, dst, 4);
+            println!("Hello World!");
+        }
+        let mut n = 1;
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let mut vec = vec![1,2,3,4,5,6];
+
+        let src = vec[0..].as_mut_ptr();
+
+        let dst = vec[2..].as_mut_ptr();
+
+        vec.copy_within(0..4, 2);
+
+        unsafe$0 {
+            
+            println!("Hello World!");
+        }
+
+        let mut n = 1;
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn convert_ptr_copy_2() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let mut vec = vec![1,2,3,4,5,6];
+    
+        unsafe$0 {
+            ptr::copy(&vec[0] as *const i32, &mut vec[2] as *mut i32, 4);
+            println!("Hello World!");
+        }
+
+        let mut n = 1;
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let mut vec = vec![1,2,3,4,5,6];
+        vec.copy_within(0..4, 2);
+
+        unsafe$0 {
+            
+            println!("Hello World!");
+        }
+    
+        let mut n = 1;
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn convert_ptr_copy_3() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let mut vec = vec![1,2,3,4,5,6];
+
+        let dst = vec.len() + heap.size();
+    
+        unsafe$0 {
+            ptr::copy(vec[0..].as_mut_ptr(), vec[3..].as_mut_ptr(), dst);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let mut vec = vec![1,2,3,4,5,6];
+
+        let dst = vec.len() + heap.size();
+
+        vec.copy_within(0..dst, 3);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn convert_vec_1() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let cap = 100;
+
+        let mut buffer = Vec::with_capacity(cap);
+
+        unsafe$0 {
+            buffer.set_len(cap);
+            println!("Hello World!");
+        }
+        println!("Hello World Again!");
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let cap = 100;
+
+        let mut buffer = vec![0; cap];
+
+        unsafe {
+            println!("Hello World!");
+        }
+        println!("Hello World Again!");
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn convert_vec_2() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let cap = 100;
+
+        let mut buffer = Vec::with_capacity(cap);
+
+        unsafe$0 {
+            buffer.set_len(cap); 
+        }
+        input.read_into(&mut buffer);
+        println!("Hello World Again!");
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let cap = 100;
+
+        let mut buffer = vec![0; cap];
+        
+        input.read_into(&mut buffer);
+        println!("Hello World Again!");
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn convert_vec_3() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let len = 100;
+
+        let mut buf = Vec::<u32>::with_capacity(len as usize); 
+        unsafe$0 { buf.set_len(len as usize) };
+    
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let len = 100;
+
+        let mut buf = vec![0; len as usize];
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn convert_vec_4() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let len = 100;
+
+        let mut buf = vec![0; 10];
+        
+        buf.reserve(len); 
+
+        unsafe$0 { 
+            buf.set_len(len); 
+        } 
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let len = 100;
+
+        let mut buf = vec![0; 10];
+
+        buf.resize(len, 0);
+        
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn reserve_set_len_u8() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    struct Vec<T>(T);
+    impl<T> Vec<T> {
+        fn new() -> Vec<T> { loop {} }
+        fn reserve(&mut self, additional: usize) {}
+        unsafe fn set_len(&mut self, new_len: usize) {}
+    }
+    fn main() {
+
+        let len = 100;
+
+        let mut buf: Vec<u8> = Vec::new();
+
+        buf.reserve(len);
+
+        unsafe$0 {
+            buf.set_len(len);
+        }
+    }
+    "#,
+            r#"
+    struct Vec<T>(T);
+    impl<T> Vec<T> {
+        fn new() -> Vec<T> { loop {} }
+        fn reserve(&mut self, additional: usize) {}
+        unsafe fn set_len(&mut self, new_len: usize) {}
+    }
+    fn main() {
+
+        let len = 100;
+
+        let mut buf: Vec<u8> = Vec::new();
+
+        buf.resize(len, 0);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn reserve_set_len_string() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: default
+    struct Vec<T>(T);
+    impl<T> Vec<T> {
+        fn new() -> Vec<T> { loop {} }
+        fn reserve(&mut self, additional: usize) {}
+        unsafe fn set_len(&mut self, new_len: usize) {}
+    }
+    struct String;
+    impl Default for String {
+        fn default() -> Self { String }
+    }
+    fn main() {
+
+        let len = 100;
+
+        let mut buf: Vec<String> = Vec::new();
+
+        buf.reserve(len);
+
+        unsafe$0 {
+            buf.set_len(len);
+        }
+    }
+    "#,
+            r#"
+    struct Vec<T>(T);
+    impl<T> Vec<T> {
+        fn new() -> Vec<T> { loop {} }
+        fn reserve(&mut self, additional: usize) {}
+        unsafe fn set_len(&mut self, new_len: usize) {}
+    }
+    struct String;
+    impl Default for String {
+        fn default() -> Self { String }
+    }
+    fn main() {
+
+        let len = 100;
+
+        let mut buf: Vec<String> = Vec::new();
+
+        buf.resize(len, Default::default());
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn reserve_set_len_no_default() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: default
+    struct Vec<T>(T);
+    impl<T> Vec<T> {
+        fn new() -> Vec<T> { loop {} }
+        fn reserve(&mut self, additional: usize) {}
+        unsafe fn set_len(&mut self, new_len: usize) {}
+    }
+    struct Handle(u32);
+    fn main() {
+
+        let len = 100;
+
+        let mut buf: Vec<Handle> = Vec::new();
+        
+        buf.reserve(len); 
+
+        unsafe$0 { 
+            buf.set_len(len); 
+        } 
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn unsafe_block_dropped_when_empty() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        unsafe$0 {
+            ptr::copy(vec[0..].as_mut_ptr(), vec[3..].as_mut_ptr(), 2);
+        }
+        println!("{:?}", vec);
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        vec.copy_within(0..2, 3);
+        println!("{:?}", vec);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn unsafe_block_kept_when_stmts_remain() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        unsafe$0 {
+            ptr::copy(vec[0..].as_mut_ptr(), vec[3..].as_mut_ptr(), 2);
+            println!("{:?}", vec);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        $0vec.copy_within(0..2, 3);
+
+        unsafe {
+            println!("{:?}", vec);
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn str_get_unchecked_1() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let s = "hello world";
+        unsafe$0 {
+            let sub = s.get_unchecked(0..5);
+        }
+        println!("{}", sub);
+    }
+    "#,
+            r#"
+    fn main() {
+        let s = "hello world";
+        let sub = s.get(0..5).expect("not a char boundary");
+        println!("{}", sub);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn str_get_unchecked_2() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let s = "hello world";
+        let sub;
+        unsafe$0 {
+            sub = s.get_unchecked(6..11);
+        }
+        println!("{}", sub);
+    }
+    "#,
+            r#"
+    fn main() {
+        let s = "hello world";
+        let sub;
+        sub = s.get(6..11).expect("not a char boundary");
+        println!("{}", sub);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn unchecked_loop_1() {
+        check_assist_by_label(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let v = vec![1, 2, 3];
+        let mut sum = 0;
+        for i in 0..v.len() {
+            unsafe$0 {
+                sum += *v.get_unchecked(i);
+            }
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let v = vec![1, 2, 3];
+        let mut sum = 0;
+        for item in &v {
+            sum += *item;
+        }
+    }
+    "#,
+            "Convert Unchecked Indexing Loop to Iterator",
+        );
+    }
+
+    #[test]
+    fn unchecked_loop_mut() {
+        check_assist_by_label(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut v = vec![1, 2, 3];
+        for i in 0..v.len() {
+            unsafe$0 {
+                *v.get_unchecked_mut(i) += 1;
+            }
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut v = vec![1, 2, 3];
+        for item in &mut v {
+            *item += 1;
+        }
+    }
+    "#,
+            "Convert Unchecked Indexing Loop to Iterator",
+        );
+    }
+
+    #[test]
+    fn unchecked_loop_index_used() {
+        check_assist_by_label(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let v = vec![1, 2, 3];
+        let mut sum = 0;
+        for i in 0..v.len() {
+            unsafe$0 {
+                sum += *v.get_unchecked(i) * i;
+            }
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let v = vec![1, 2, 3];
+        let mut sum = 0;
+        for (i, item) in v.iter().enumerate() {
+            sum += *item * i;
+        }
+    }
+    "#,
+            "Convert Unchecked Indexing Loop to Iterator",
+        );
+    }
+
+    #[test]
+    fn unchecked_loop_index_used_outside_block() {
+        check_assist_by_label(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut v = vec![1, 2, 3];
+        for i in 0..v.len() {
+            unsafe$0 {
+                *v.get_unchecked_mut(i) += 1;
+            }
+            println!("{}", i);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut v = vec![1, 2, 3];
+        for (i, item) in v.iter_mut().enumerate() {
+            *item += 1;
+            println!("{}", i);
+        }
+    }
+    "#,
+            "Convert Unchecked Indexing Loop to Iterator",
+        );
+    }
+
+    #[test]
+    fn unchecked_loop_multiple_vectors() {
+        check_assist_not_applicable_by_label(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let a = vec![1, 2, 3];
+        let b = vec![4, 5, 6];
+        let mut sum = 0;
+        for i in 0..a.len() {
+            unsafe$0 {
+                sum += *a.get_unchecked(i) * *b.get_unchecked(i);
+            }
+        }
+    }
+    "#,
+            "Convert Unchecked Indexing Loop to Iterator",
+        );
+    }
+
+    #[test]
+    fn unchecked_loop_offset_index() {
+        check_assist_not_applicable_by_label(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let v = vec![1, 2, 3];
+        let mut sum = 0;
+        for i in 0..v.len() - 1 {
+            unsafe$0 {
+                sum += *v.get_unchecked(i + 1);
+            }
+        }
+    }
+    "#,
+            "Convert Unchecked Indexing Loop to Iterator",
+        );
+    }
+
+    #[test]
+    fn raw_write_loop_index_fn() {
+        check_assist_by_label(
+            convert_unsafe_to_safe,
+            r#"
+    fn f(i: usize) -> u32 {
+        i as u32 * 2
+    }
+
+    fn main() {
+        let mut buf = vec![0u32; 8];
+        let n = 4;
+        for i in 0..n {
+            unsafe$0 {
+                *buf.as_mut_ptr().add(i) = f(i);
+            }
+        }
+    }
+    "#,
+            r#"
+    fn f(i: usize) -> u32 {
+        i as u32 * 2
+    }
+
+    fn main() {
+        let mut buf = vec![0u32; 8];
+        let n = 4;
+        for (i, slot) in buf.iter_mut().enumerate().take(n) {
+            *slot = f(i);
+        }
+    }
+    "#,
+            "Convert Raw Pointer Write Loop to Iterator",
+        );
+    }
+
+    #[test]
+    fn raw_write_loop_whole_buffer() {
+        check_assist_by_label(
+            convert_unsafe_to_safe,
+            r#"
+    fn next() -> u8 {
+        7
+    }
+
+    fn main() {
+        let mut buf = vec![0u8; 8];
+        for i in 0..buf.len() {
+            unsafe$0 {
+                *buf.as_mut_ptr().add(i) = next();
+            }
+        }
+    }
+    "#,
+            r#"
+    fn next() -> u8 {
+        7
+    }
+
+    fn main() {
+        let mut buf = vec![0u8; 8];
+        for slot in buf.iter_mut() {
+            *slot = next();
+        }
+    }
+    "#,
+            "Convert Raw Pointer Write Loop to Iterator",
+        );
+    }
+
+    #[test]
+    fn raw_write_loop_constant_fill() {
+        check_assist_by_label(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut buf = vec![0u8; 8];
+        let n = 4;
+        for i in 0..n {
+            unsafe$0 {
+                *buf.as_mut_ptr().add(i) = 0xff;
+            }
+        }
+        println!("{:?}", buf);
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut buf = vec![0u8; 8];
+        let n = 4;
+        buf[..n].fill(0xff);
+        println!("{:?}", buf);
+    }
+    "#,
+            "Convert Raw Pointer Write Loop to fill",
+        );
+    }
+
+    #[test]
+    fn raw_write_loop_other_unsafe_op() {
+        check_assist_not_applicable_by_label(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut buf = vec![0u8; 8];
+        let src = vec![1u8; 8];
+        for i in 0..buf.len() {
+            unsafe$0 {
+                *buf.as_mut_ptr().add(i) = *src.as_ptr().add(i);
+            }
+        }
+    }
+    "#,
+            "Convert Raw Pointer Write Loop to Iterator",
+        );
+    }
+
+    #[test]
+    fn raw_write_loop_unsafe_call() {
+        check_assist_not_applicable_by_label(
+            convert_unsafe_to_safe,
+            r#"
+    unsafe fn f(i: usize) -> u32 {
+        i as u32
+    }
+
+    fn main() {
+        let mut buf = vec![0u32; 8];
+        for i in 0..buf.len() {
+            unsafe$0 {
+                *buf.as_mut_ptr().add(i) = f(i);
+            }
+        }
+    }
+    "#,
+            "Convert Raw Pointer Write Loop to Iterator",
+        );
+    }
+
+    #[test]
+    fn raw_write_loop_spare_capacity() {
+        check_assist_not_applicable_by_label(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let n = 8;
+        let mut buf: Vec<u8> = Vec::with_capacity(n);
+        for i in 0..n {
+            unsafe$0 {
+                *buf.as_mut_ptr().add(i) = 0;
+            }
+        }
+        unsafe {
+            buf.set_len(n);
+        }
+    }
+    "#,
+            "Convert Raw Pointer Write Loop to fill",
+        );
+    }
+
+    #[test]
+    fn mem_zeroed_scalar() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let x: u64 = unsafe$0 { mem::zeroed() };
+        println!("{}", x);
+    }
+    "#,
+            r#"
+    fn main() {
+        let x: u64 = 0;
+        println!("{}", x);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn mem_zeroed_float_in_block() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        unsafe$0 {
+            let x: f64 = std::mem::zeroed();
+        }
+        println!("{}", x);
+    }
+    "#,
+            r#"
+    fn main() {
+        let x: f64 = 0.0;
+
+        println!("{}", x);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn mem_zeroed_array() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut arr: [u8; 32] = unsafe$0 { mem::zeroed() };
+        arr[0] = 1;
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut arr = [0u8; 32];
+        arr[0] = 1;
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn mem_zeroed_default_struct() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: default
+    struct Config { len: u32 }
+    impl Default for Config {
+        fn default() -> Self { Config { len: 0 } }
+    }
+    fn main() {
+        let config: Config = unsafe$0 { mem::zeroed() };
+    }
+    "#,
+            r#"
+    struct Config { len: u32 }
+    impl Default for Config {
+        fn default() -> Self { Config { len: 0 } }
+    }
+    fn main() {
+        let config: Config = Default::default();
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn mem_zeroed_reference() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let r: &u32 = unsafe$0 { mem::zeroed() };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn copy_nonoverlap_add_offset_1() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let src = vec![1, 2, 3, 4, 5, 6];
+        let mut dst = vec![0; 6];
+        unsafe$0 {
+            ptr::copy_nonoverlapping(src.as_ptr().add(1), dst.as_mut_ptr().add(2), 3);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let src = vec![1, 2, 3, 4, 5, 6];
+        let mut dst = vec![0; 6];
+        dst[2..2 + 3].copy_from_slice(&src[1..1 + 3]);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn copy_nonoverlap_add_offset_2() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let src = vec![1, 2, 3, 4, 5, 6];
+        let mut dst = vec![0; 6];
+        let (i, j, n) = (1, 2, 3);
+        unsafe$0 {
+            ptr::copy_nonoverlapping(src.as_ptr().add(i), dst.as_mut_ptr().add(j), n);
+            println!("{:?}", dst);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let src = vec![1, 2, 3, 4, 5, 6];
+        let mut dst = vec![0; 6];
+        let (i, j, n) = (1, 2, 3);
+        $0dst[j..j + n].copy_from_slice(&src[i..i + n]);
+
+        unsafe {
+            println!("{:?}", dst);
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn copy_nonoverlap_add_offset_complex_count() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let src = vec![1, 2, 3, 4, 5, 6];
+        let mut dst = vec![0; 6];
+        unsafe$0 {
+            ptr::copy_nonoverlapping(src.as_ptr().add(1), dst.as_mut_ptr().add(2), 4 - 1);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let src = vec![1, 2, 3, 4, 5, 6];
+        let mut dst = vec![0; 6];
+        dst[2..2 + (4 - 1)].copy_from_slice(&src[1..1 + (4 - 1)]);
+    }
+    "#,
+        );
+    }
+
+    fn check_transmute_lifetime_text(text: &str) -> bool {
+        let parse = ast::SourceFile::parse(text);
+        let target_expr =
+            parse.tree().syntax().descendants().find(|node| node.kind() == PATH_EXPR).unwrap();
+        check_transmute_lifetime(&target_expr).unwrap_or(false)
+    }
+
+    #[test]
+    fn transmute_lifetime_extension() {
+        assert!(check_transmute_lifetime_text(
+            r#"
+    fn extend<'a>(r: &'a Foo) -> &'static Foo {
+        unsafe { mem::transmute::<&'a Foo, &'static Foo>(r) }
+    }
+    "#,
+        ));
+    }
+
+    #[test]
+    fn transmute_lifetime_different_types() {
+        assert!(!check_transmute_lifetime_text(
+            r#"
+    fn cast<'a>(r: &'a Foo) -> &'static Bar {
+        unsafe { mem::transmute::<&'a Foo, &'static Bar>(r) }
+    }
+    "#,
+        ));
+    }
+
+    #[test]
+    fn mem_uninitialized_array() {
+        check_assist_by_label(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut buf: [u8; 1024] = unsafe$0 { mem::uninitialized() };
+        input.read(&mut buf);
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut buf = [0u8; 1024];
+        input.read(&mut buf);
+    }
+    "#,
+            "Replace deprecated mem::uninitialized",
+        );
+    }
+
+    #[test]
+    fn mem_uninitialized_scalar() {
+        check_assist_by_label(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        unsafe$0 {
+            let count: usize = std::mem::uninitialized();
+        }
+        println!("{}", count);
+    }
+    "#,
+            r#"
+    fn main() {
+        let count: usize = 0;
+
+        println!("{}", count);
+    }
+    "#,
+            "Replace deprecated mem::uninitialized",
+        );
+    }
+
+    #[test]
+    fn mem_uninitialized_struct() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    struct Config { len: u32 }
+    fn main() {
+        let config: Config = unsafe$0 { mem::uninitialized() };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn maybe_uninit_assume_init_array() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: maybe_uninit
+    use core::mem::MaybeUninit;
+    fn main() {
+        let mut buf: [u8; 64] = unsafe$0 { MaybeUninit::uninit().assume_init() };
+        input.read(&mut buf);
+    }
+    "#,
+            r#"
+    use core::mem::MaybeUninit;
+    fn main() {
+        let mut buf = [0u8; 64];
+        input.read(&mut buf);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn maybe_uninit_assume_init_const_generic() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: maybe_uninit
+    use core::mem::MaybeUninit;
+    fn read<const N: usize>() -> [f32; N] {
+        let buf: [f32; N] = unsafe$0 { MaybeUninit::uninit().assume_init() };
+        buf
+    }
+    "#,
+            r#"
+    use core::mem::MaybeUninit;
+    fn read<const N: usize>() -> [f32; N] {
+        let buf = [0.0f32; N];
+        buf
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn maybe_uninit_assume_init_bool_array() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: maybe_uninit
+    use core::mem::MaybeUninit;
+    fn main() {
+        let flags: [bool; 8] = unsafe$0 { MaybeUninit::uninit().assume_init() };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn maybe_uninit_user_defined_assume_init() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: maybe_uninit
+    struct MaybeUninit;
+    impl MaybeUninit {
+        fn uninit() -> MaybeUninit { MaybeUninit }
+        unsafe fn assume_init(self) -> [u8; 64] { [1; 64] }
+    }
+    fn main() {
+        let buf: [u8; 64] = unsafe$0 { MaybeUninit::uninit().assume_init() };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn maybe_uninit_assume_init_ref() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: maybe_uninit
+    use core::mem::MaybeUninit;
+    fn main() {
+        let mut m = MaybeUninit::<u32>::uninit();
+        m.write(5);
+        let r = unsafe$0 { m.assume_init_ref() };
+        println!("{}", r);
+    }
+    "#,
+            r#"
+    use core::mem::MaybeUninit;
+    fn main() {
+        let mut m = MaybeUninit::<u32>::uninit();
+        let r = &*m.write(5);
+        println!("{}", r);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn maybe_uninit_assume_init_mut() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: maybe_uninit
+    use core::mem::MaybeUninit;
+    fn main() {
+        let value = 5;
+        let mut m = MaybeUninit::<u32>::uninit();
+        m.write(value);
+        let r: &mut u32 = unsafe$0 { m.assume_init_mut() };
+        *r += 1;
+    }
+    "#,
+            r#"
+    use core::mem::MaybeUninit;
+    fn main() {
+        let value = 5;
+        let mut m = MaybeUninit::<u32>::uninit();
+        let r: &mut u32 = m.write(value);
+        *r += 1;
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn maybe_uninit_assume_init_ref_without_write() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: maybe_uninit
+    use core::mem::MaybeUninit;
+    fn main() {
+        let m = MaybeUninit::<u32>::uninit();
+        let r = unsafe$0 { m.assume_init_ref() };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn maybe_uninit_assume_init_mut_value_changed() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: maybe_uninit
+    use core::mem::MaybeUninit;
+    fn main() {
+        let mut value = 5;
+        let mut m = MaybeUninit::<u32>::uninit();
+        m.write(value);
+        value += 1;
+        let r = unsafe$0 { m.assume_init_mut() };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn swap_unchecked_stmt() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let mut arr = [1, 2, 3, 4];
+        unsafe$0 {
+            arr.swap_unchecked(0, 3);
+        }
+        println!("{:?}", arr);
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut arr = [1, 2, 3, 4];
+        arr.swap(0, 3);
+        println!("{:?}", arr);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn swap_unchecked_tail_expr() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn reverse_pair(s: &mut [u8], i: usize) {
+        unsafe$0 { s.swap_unchecked(i, i + 1) }
+    }
+    "#,
+            r#"
+    fn reverse_pair(s: &mut [u8], i: usize) {
+        s.swap(i, i + 1)
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn swap_unchecked_keeps_index_exprs() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn rotate(s: &mut [u8], i: usize) {
+        unsafe$0 {
+            s.swap_unchecked(i * 2, s.len() - 1);
+        }
+    }
+    "#,
+            r#"
+    fn rotate(s: &mut [u8], i: usize) {
+        s.swap(i * 2, s.len() - 1);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn swap_unchecked_user_defined() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    struct Pair(u8, u8);
+    impl Pair {
+        unsafe fn swap_unchecked(&mut self, a: usize, b: usize) {}
+    }
+    fn main() {
+        let mut pair = Pair(1, 2);
+        unsafe$0 {
+            pair.swap_unchecked(0, 1);
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn split_at_unchecked_let() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let s: &[u8] = &[1, 2, 3, 4];
+        let (a, b) = unsafe$0 { s.split_at_unchecked(2) };
+        println!("{:?} {:?}", a, b);
+    }
+    "#,
+            r#"
+    fn main() {
+        let s: &[u8] = &[1, 2, 3, 4];
+        let (a, b) = s.split_at(2);
+        println!("{:?} {:?}", a, b);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn split_at_mut_unchecked_method_call_mid() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn fill(buf: &mut [u8], header: &[u8]) {
+        unsafe$0 {
+            let (head, tail) = buf.split_at_mut_unchecked(header.len());
+        }
+    }
+    "#,
+            r#"
+    fn fill(buf: &mut [u8], header: &[u8]) {
+        let (head, tail) = buf.split_at_mut(header.len());
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn split_at_unchecked_inline() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn first_len(s: &[u8], mid: usize) -> usize {
+        unsafe$0 { s.split_at_unchecked(mid) }.0.len()
+    }
+    "#,
+            r#"
+    fn first_len(s: &[u8], mid: usize) -> usize {
+        s.split_at(mid).0.len()
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn as_chunks_unchecked_let() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let data: &[u8] = &[1, 2, 3, 4, 5, 6, 7, 8];
+        let chunks = unsafe$0 { data.as_chunks_unchecked::<4>() };
+        println!("{:?}", chunks);
+    }
+    "#,
+            r#"
+    fn main() {
+        let data: &[u8] = &[1, 2, 3, 4, 5, 6, 7, 8];
+        let chunks = data.as_chunks::<4>().0;
+        println!("{:?}", chunks);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn as_chunks_unchecked_iterated() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn sum(data: &[u32]) -> u32 {
+        let mut total = 0;
+        for chunk in unsafe$0 { data.as_chunks_unchecked::<4>() } {
+            total += chunk[0];
+        }
+        total
+    }
+    "#,
+            r#"
+    fn sum(data: &[u32]) -> u32 {
+        let mut total = 0;
+        for chunk in data.chunks_exact(4) {
+            total += chunk[0];
+        }
+        total
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn safe_wrapper_exact_signature() {
+        check_assist_with_config(
+            convert_unsafe_to_safe,
+            AssistConfig { suggest_safe_wrappers: true, ..TEST_CONFIG },
+            r#"
+    mod ffi {
+        pub unsafe fn read_byte(buf: &[u8], i: usize) -> u8 { *buf.as_ptr().add(i) }
+        pub fn read_byte_checked(buf: &[u8], i: usize) -> u8 { buf[i] }
+    }
+    fn main() {
+        let buf = [1u8, 2, 3];
+        let b = unsafe$0 { ffi::read_byte(&buf, 1) };
+    }
+    "#,
+            r#"
+    mod ffi {
+        pub unsafe fn read_byte(buf: &[u8], i: usize) -> u8 { *buf.as_ptr().add(i) }
+        pub fn read_byte_checked(buf: &[u8], i: usize) -> u8 { buf[i] }
+    }
+    fn main() {
+        let buf = [1u8, 2, 3];
+        let b = ffi::read_byte_checked(&buf, 1);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn safe_wrapper_different_signature() {
+        check_assist_not_applicable_with_config(
+            convert_unsafe_to_safe,
+            AssistConfig { suggest_safe_wrappers: true, ..TEST_CONFIG },
+            r#"
+    unsafe fn reset(ptr: *mut u8) {}
+    fn reset_safe(ptr: &mut u8) {}
+    fn main() {
+        let mut x = 1u8;
+        unsafe$0 {
+            reset(&mut x);
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn safe_wrapper_disabled_by_default() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    unsafe fn fill(buf: &mut [u8]) {}
+    fn fill_safe(buf: &mut [u8]) {}
+    fn main() {
+        let mut buf = [0u8; 4];
+        unsafe$0 {
+            fill(&mut buf);
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn unchecked_add_tail_expr() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let a: u32 = 7;
+        let b: u32 = 8;
+        let s = unsafe$0 { a.unchecked_add(b) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let a: u32 = 7;
+        let b: u32 = 8;
+        let s = a.checked_add(b).expect("overflow");
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn unchecked_add_wrapping() {
+        check_assist_by_label(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let a: u32 = 7;
+        let b: u32 = 8;
+        let s = unsafe$0 { a.unchecked_add(b) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let a: u32 = 7;
+        let b: u32 = 8;
+        let s = a.wrapping_add(b);
+    }
+    "#,
+            "Convert to Wrapping Arithmetic",
+        );
+    }
+
+    #[test]
+    fn unchecked_mul_let() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn area(w: i64, h: i64) -> i64 {
+        unsafe$0 {
+            let a: i64 = w.unchecked_mul(h);
+        }
+        a
+    }
+    "#,
+            r#"
+    fn area(w: i64, h: i64) -> i64 {
+        let a: i64 = w.checked_mul(h).expect("overflow");
+        a
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn unchecked_sub_user_defined() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    struct Counter(u32);
+    impl Counter {
+        unsafe fn unchecked_sub(&self, n: u32) -> u32 { self.0 - n }
+    }
+    fn main() {
+        let c = Counter(3);
+        let r = unsafe$0 { c.unchecked_sub(1) };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn float_to_int_unchecked_f32() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn takes(_: i32) {}
+    fn main() {
+        let x: f32 = 2.5;
+        takes(unsafe$0 { x.to_int_unchecked::<i32>() });
+    }
+    "#,
+            r#"
+    fn takes(_: i32) {}
+    fn main() {
+        let x: f32 = 2.5;
+        takes(x as i32);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn float_to_int_unchecked_f64_let() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn to_index(x: f64) -> usize {
+        unsafe$0 {
+            let i = x.to_int_unchecked::<usize>();
+        }
+        i
+    }
+    "#,
+            r#"
+    fn to_index(x: f64) -> usize {
+        let i = x as usize;
+        i
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn float_to_int_unchecked_let_ascription() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let x: f64 = 300.0;
+        let n: u8 = unsafe$0 { x.to_int_unchecked() };
+    }
+    "#,
+            r#"
+    fn main() {
+        let x: f64 = 300.0;
+        let n: u8 = x as u8;
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn set_len_constant_fill_loop() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let n = 16;
+        let mut v = Vec::with_capacity(n);
+        unsafe$0 {
+            v.set_len(n);
+        }
+        for i in 0..n {
+            v[i] = 7;
+        }
+        println!("{:?}", v);
+    }
+    "#,
+            r#"
+    fn main() {
+        let n = 16;
+        let mut v = Vec::with_capacity(n);
+        v.resize(n, 7);
+        println!("{:?}", v);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn set_len_constant_fill_tail_loop() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let n = 16;
+        let mut v = Vec::with_capacity(n);
+        unsafe$0 {
+            v.set_len(n);
+        }
+        for i in 0..n {
+            v[i] = 7;
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let n = 16;
+        let mut v = Vec::with_capacity(n);
+        v.resize(n, 7);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn set_len_computed_fill_loop() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let n = 16;
+        let mut v = Vec::with_capacity(n);
+        unsafe$0 {
+            v.set_len(n);
+        }
+        for i in 0..n {
+            v[i] = i * 2;
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let n = 16;
+        let mut v = vec![0; n];
+        for i in 0..n {
+            v[i] = i * 2;
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn set_len_after_sequential_raw_writes() {
+        check_assist_by_label(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut buf = Vec::with_capacity(3);
+        unsafe$0 {
+            *buf.as_mut_ptr() = 10;
+            buf.as_mut_ptr().add(1).write(20);
+            *buf.as_mut_ptr().add(2) = 30;
+            buf.set_len(3);
+        }
+        println!("{:?}", buf);
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut buf = Vec::with_capacity(3);
+        buf.push(10);
+        buf.push(20);
+        buf.push(30);
+        println!("{:?}", buf);
+    }
+    "#,
+            "Push the written elements instead",
+        );
+    }
+
+    #[test]
+    fn set_len_after_raw_write_loop() {
+        check_assist_by_label(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let n = 8;
+        let mut buf = Vec::with_capacity(n);
+        unsafe$0 {
+            for i in 0..n {
+                *buf.as_mut_ptr().add(i) = i * i + 1;
+            }
+            buf.set_len(n);
+        }
+        println!("{:?}", buf);
+    }
+    "#,
+            r#"
+    fn main() {
+        let n = 8;
+        let mut buf = Vec::with_capacity(n);
+        buf.extend((0..n).map(|i| i * i + 1));
+        println!("{:?}", buf);
+    }
+    "#,
+            "Push the written elements instead",
+        );
+    }
+
+    #[test]
+    fn set_len_after_unmodeled_raw_writes() {
+        check_assist_not_applicable_by_label(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut buf = Vec::with_capacity(2);
+        unsafe$0 {
+            *buf.as_mut_ptr().add(1) = 20;
+            *buf.as_mut_ptr() = next();
+            buf.set_len(2);
+        }
+        println!("{:?}", buf);
+    }
+    "#,
+            "Push the written elements instead",
+        );
+    }
+
+    #[test]
+    fn get_unchecked_if_let_scaffold() {
+        check_assist_by_label(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let v = [1, 2, 3];
+        let x = unsafe$0 { v.get_unchecked(1) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let v = [1, 2, 3];
+        if let Some(x) = v.get(1) {
+            /* use x */
+        } else {
+            /* handle */
+        }
+    }
+    "#,
+            "Convert to if let with None handling",
+        );
+    }
+
+    #[test]
+    fn vec_from_raw_parts_roundtrip() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut v = vec![1, 2, 3];
+        let ptr = v.as_mut_ptr();
+        let len = v.len();
+        let cap = v.capacity();
+        mem::forget(v);
+        let mut rebuilt = unsafe$0 { Vec::from_raw_parts(ptr, len, cap) };
+        rebuilt.push(4);
+        println!("{:?}", rebuilt);
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut v = vec![1, 2, 3];
+        v.push(4);
+        println!("{:?}", v);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn vec_from_raw_parts_forget_after() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut v = vec![1, 2, 3];
+        let ptr = v.as_mut_ptr();
+        let len = v.len();
+        let cap = v.capacity();
+        let mut rebuilt = unsafe$0 { Vec::from_raw_parts(ptr, len, cap) };
+        mem::forget(v);
+        rebuilt.push(4);
+        println!("{:?}", rebuilt);
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut v = vec![1, 2, 3];
+        v.push(4);
+        println!("{:?}", v);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn vec_from_raw_parts_forget_other_vec() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut v = vec![1, 2, 3];
+        let w = vec![4, 5, 6];
+        let ptr = v.as_mut_ptr();
+        let len = v.len();
+        let cap = v.capacity();
+        let rebuilt = unsafe$0 { Vec::from_raw_parts(ptr, len, cap) };
+        mem::forget(w);
+        println!("{:?}", rebuilt);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn vec_from_raw_parts_ffi_call() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    extern "C" {
+        fn fill(ptr: *mut u8, len: usize);
+    }
+    fn main() {
+        let mut v = vec![0u8; 8];
+        let ptr = v.as_mut_ptr();
+        let len = v.len();
+        let cap = v.capacity();
+        mem::forget(v);
+        unsafe { fill(ptr, len) };
+        let rebuilt = unsafe$0 { Vec::from_raw_parts(ptr, len, cap) };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn rc_into_raw_from_raw_round_trip() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    use std::rc::Rc;
+    fn main() {
+        let shared = Rc::new(5);
+        let ptr = Rc::into_raw(shared);
+        let back = unsafe$0 { Rc::from_raw(ptr) };
+        println!("{}", back);
+    }
+    "#,
+            r#"
+    use std::rc::Rc;
+    fn main() {
+        let shared = Rc::new(5);
+        println!("{}", shared);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn arc_into_raw_from_raw_round_trip() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    use std::sync::Arc;
+    fn main() {
+        let shared = Arc::new(5);
+        let ptr = Arc::into_raw(shared);
+        unsafe$0 {
+            let back = Arc::from_raw(ptr);
+        }
+        drop(back);
+    }
+    "#,
+            r#"
+    use std::sync::Arc;
+    fn main() {
+        let shared = Arc::new(5);
+        drop(shared);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn rc_into_raw_passed_to_ffi() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    use std::rc::Rc;
+    extern "C" {
+        fn register(ptr: *const i32);
+    }
+    fn main() {
+        let shared = Rc::new(5);
+        let ptr = Rc::into_raw(shared);
+        unsafe { register(ptr) };
+        let back = unsafe$0 { Rc::from_raw(ptr) };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn disabled_pattern_not_offered() {
+        check_assist_not_applicable_with_config(
+            convert_unsafe_to_safe,
+            AssistConfig {
+                disabled_unsafe_patterns: Some(["get_unchecked".to_string()].into_iter().collect()),
+                ..TEST_CONFIG
+            },
+            r#"
+    fn main() {
+        let v = vec![1, 2, 3];
+        let x = unsafe$0 { v.get_unchecked(1) };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn disabled_pattern_keeps_others() {
+        check_assist_with_config(
+            convert_unsafe_to_safe,
+            AssistConfig {
+                disabled_unsafe_patterns: Some(["get_unchecked".to_string()].into_iter().collect()),
+                ..TEST_CONFIG
+            },
+            r#"
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        unsafe$0 {
+            ptr::copy(vec[0..].as_mut_ptr(), vec[3..].as_mut_ptr(), 2);
+        }
+        println!("{:?}", vec);
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        vec.copy_within(0..2, 3);
+        println!("{:?}", vec);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn risky_conversion_annotated() {
+        check_assist_with_config(
+            convert_unsafe_to_safe,
+            AssistConfig { annotate_risky_conversions: true, ..TEST_CONFIG },
+            r#"
+    //- minicore: slice
+    fn main() {
+        let mut arr = [1, 2, 3, 4];
+        unsafe$0 {
+            arr.swap_unchecked(0, 3);
+        }
+        println!("{:?}", arr);
+    }
+    "#,
+            &format!(
+                r#"
+    fn main() {{
+        let mut arr = [1, 2, 3, 4];
+        {}
+        arr.swap(0, 3);
+        println!("{{:?}}", arr);
+    }}
+    "#,
+                RISKY_CONVERSION_MARKER
+            ),
+        );
+    }
+
+    #[test]
+    fn risky_conversion_annotation_not_shared() {
+        let (db, position) = RootDatabase::with_position(&stdx::trim_indent(
+            r#"
+    //- minicore: slice
+    fn main() {
+        let v = [1, 2, 3];
+        unsafe$0 {
+            let n = 0;
+            let x = v.get_unchecked(n);
+        }
+    }
+    "#,
+        ));
+        let frange =
+            FileRange { file_id: position.file_id, range: TextRange::empty(position.offset) };
+        let config = AssistConfig { annotate_risky_conversions: true, ..TEST_CONFIG };
+
+        let inserted = |id: &str| {
+            let assist = crate::assists(&db, &config, AssistResolveStrategy::All, frange)
+                .into_iter()
+                .find(|assist| assist.id.0 == id)
+                .expect("expected assist");
+            let source_change = assist.source_change.expect("expected source change");
+            let edit = source_change.get_source_edit(position.file_id).unwrap();
+            edit.iter().map(|indel| indel.insert.clone()).collect::<String>()
+        };
+
+        assert!(inserted("convert_unsafe_to_safe").contains(RISKY_CONVERSION_MARKER));
+        assert!(!inserted("minimize_unsafe_block").contains(RISKY_CONVERSION_MARKER));
+    }
+
+    #[test]
+    fn equivalent_conversion_not_annotated() {
+        check_assist_with_config(
+            convert_unsafe_to_safe,
+            AssistConfig { annotate_risky_conversions: true, ..TEST_CONFIG },
+            r#"
+    fn main() {
+        let mut x = 1;
+        let mut y = 2;
+        unsafe$0 {
+            ptr::swap(&mut x, &mut y);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut x = 1;
+        let mut y = 2;
+        mem::swap(&mut x, &mut y);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn exact_replacement_not_annotated() {
+        check_assist_with_config(
+            convert_unsafe_to_safe,
+            AssistConfig { annotate_risky_conversions: true, ..TEST_CONFIG },
+            r#"
+    fn main() {
+        let x = 5;
+        let s = unsafe$0 { slice::from_raw_parts(&x, 1) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let x = 5;
+        let s = slice::from_ref(&x);
+    }
+    "#,
+        );
+        check_assist_with_config(
+            convert_unsafe_to_safe,
+            AssistConfig { annotate_risky_conversions: true, ..TEST_CONFIG },
+            r#"
+    fn main() {
+        let z: u64 = unsafe$0 { mem::zeroed() };
+    }
+    "#,
+            r#"
+    fn main() {
+        let z: u64 = 0;
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn ptr_read_field_with_default() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: default
+    struct Inner(u32);
+    impl Default for Inner {
+        fn default() -> Self { Inner(0) }
+    }
+    struct Outer { inner: Inner }
+    fn main() {
+        let mut outer = Outer { inner: Inner(3) };
+        let inner = unsafe$0 { ptr::read(&outer.inner) };
+    }
+    "#,
+            r#"
+    struct Inner(u32);
+    impl Default for Inner {
+        fn default() -> Self { Inner(0) }
+    }
+    struct Outer { inner: Inner }
+    fn main() {
+        let mut outer = Outer { inner: Inner(3) };
+        let inner = mem::take(&mut outer.inner);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn ptr_read_field_without_default() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: default
+    struct Inner(u32);
+    struct Outer { inner: Inner }
+    fn main() {
+        let mut outer = Outer { inner: Inner(3) };
+        let inner = unsafe$0 { ptr::read(&outer.inner) };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn drop_in_place_unused_local() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut name = String::new();
+        unsafe$0 {
+            ptr::drop_in_place(&mut name as *mut String);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut name = String::new();
+        drop(name);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn drop_in_place_used_afterwards() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut name = String::new();
+        unsafe$0 {
+            ptr::drop_in_place(&mut name as *mut String);
+        }
+        let len = name.len();
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn reindent_below_comment() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let v: &mut [i32] = &mut [1, 2, 3];
+        // swap the ends
+        unsafe$0 {
+            v.swap_unchecked(0, 2);
+            foo();
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let v: &mut [i32] = &mut [1, 2, 3];
+        // swap the ends
+        $0v.swap(0, 2);
+
+        unsafe {
+            foo();
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn from_utf8_unchecked_sub_slice() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let buf = [104u8, 101, 108, 108, 111, 32, 119, 111, 114, 108, 100];
+        let s = unsafe$0 { str::from_utf8_unchecked(&buf[2..10]) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let buf = [104u8, 101, 108, 108, 111, 32, 119, 111, 114, 108, 100];
+        let s = std::str::from_utf8(&buf[2..10]).unwrap();
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn from_utf8_unchecked_mut_borrow() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut buf = vec![104u8, 105];
+        let s = unsafe$0 { str::from_utf8_unchecked_mut(&mut buf) };
+        s.make_ascii_uppercase();
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut buf = vec![104u8, 105];
+        let s = std::str::from_utf8_mut(&mut buf).unwrap();
+        s.make_ascii_uppercase();
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn from_utf8_unchecked_mut_std_path() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut buf = [104u8, 105];
+        let s = unsafe$0 { std::str::from_utf8_unchecked_mut(&mut buf[..]) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut buf = [104u8, 105];
+        let s = std::str::from_utf8_mut(&mut buf[..]).unwrap();
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn raw_ptr_add_read() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let v = vec![1, 2, 3];
+        let i = 1;
+        let x = unsafe$0 { *v.as_ptr().add(i) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let v = vec![1, 2, 3];
+        let i = 1;
+        let x = v[i];
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn raw_ptr_deref_write() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut v = vec![1, 2, 3];
+        unsafe$0 {
+            *v.as_mut_ptr() = 5;
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut v = vec![1, 2, 3];
+        v[0] = 5;
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn raw_ptr_add_write_keeps_block() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut v = vec![1, 2, 3];
+        unsafe$0 {
+            *v.as_mut_ptr().add(2) = 7;
+            foo();
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut v = vec![1, 2, 3];
+        $0v[2] = 7;
+
+        unsafe {
+            foo();
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn hoisted_stmt_without_snippet_cap() {
+        check_assist_with_config(
+            convert_unsafe_to_safe,
+            AssistConfig { snippet_cap: None, ..TEST_CONFIG },
+            r#"
+    fn main() {
+        let mut v = vec![1, 2, 3];
+        unsafe$0 {
+            *v.as_mut_ptr().add(2) = 7;
+            foo();
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut v = vec![1, 2, 3];
+        v[2] = 7;
+
+        unsafe {
+            foo();
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn hoisted_stmt_gets_cursor() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        unsafe$0 {
+            ptr::copy(vec[0..].as_mut_ptr(), vec[3..].as_mut_ptr(), 2);
+            println!("{}", vec[0]);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        $0vec.copy_within(0..2, 3);
+
+        unsafe {
+            println!("{}", vec[0]);
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_unchecked_mut_let_keeps_annotation() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+
+        let mut vec = [1,2,3,4,5,6];
+
+        unsafe$0 {
+            let index: &mut i32 = vec.get_unchecked_mut(5);
+        }
+        print!("Index: {:?} \n", index);
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let mut vec = [1,2,3,4,5,6];
+
+        let index: &mut i32 = vec.get_mut(5).unwrap();
+
+        print!("Index: {:?} \n", index);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_unchecked_mut_pair_literal() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let mut v = [1, 2, 3, 4];
+        unsafe$0 {
+            let a = v.get_unchecked_mut(0);
+            let b = v.get_unchecked_mut(2);
+            std::mem::swap(a, b);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut v = [1, 2, 3, 4];
+        $0let (lo, hi) = v.split_at_mut(2);
+        let a = &mut lo[0];
+        let b = &mut hi[0];
+        unsafe {
+            std::mem::swap(a, b);
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_unchecked_mut_pair_swapped_literal() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let mut v = [1u32, 2, 3, 4];
+        unsafe$0 {
+            let a: &mut u32 = v.get_unchecked_mut(3);
+            let b: &mut u32 = v.get_unchecked_mut(1);
+        }
+        *a += *b;
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut v = [1u32, 2, 3, 4];
+        let (lo, hi) = v.split_at_mut(3);
+        let a: &mut u32 = &mut hi[0];
+        let b: &mut u32 = &mut lo[1];
+        *a += *b;
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_unchecked_mut_pair_non_literal() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut v = vec![1, 2, 3, 4];
+        let (i, j) = (0, 2);
+        unsafe$0 {
+            let a = v.get_unchecked_mut(i);
+            let b = v.get_unchecked_mut(j);
+            std::mem::swap(a, b);
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_unchecked_let_without_annotation() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+
+        let vec = [1,2,3,4,5,6];
+
+        unsafe$0 {
+            let first = vec.get_unchecked(0);
+        }
+        print!("First: {:?} \n", first);
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let vec = [1,2,3,4,5,6];
+
+        let first = vec.get(0).unwrap();
+
+        print!("First: {:?} \n", first);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_unchecked_tail_keeps_annotation() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let mut v = [1, 2, 3];
+        let x: &mut i32 = unsafe$0 { v.get_unchecked_mut(1) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut v = [1, 2, 3];
+        let x: &mut i32 = v.get_mut(1).unwrap();
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn libc_memcpy_bytes() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let mut dst = [0u8; 8];
+        let src = [1u8; 8];
+        unsafe$0 {
+            libc::memcpy(dst.as_mut_ptr() as *mut c_void, src.as_ptr() as *const c_void, 8);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut dst = [0u8; 8];
+        let src = [1u8; 8];
+        dst[..8].copy_from_slice(&src[..8]);
+
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn libc_memcpy_size_of_factor() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let mut dst = [0u32; 4];
+        let src = [1u32; 4];
+        unsafe$0 {
+            libc::memcpy(dst.as_mut_ptr() as *mut c_void, src.as_ptr() as *const c_void, src.len() * mem::size_of::<u32>());
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut dst = [0u32; 4];
+        let src = [1u32; 4];
+        dst[..src.len()].copy_from_slice(&src[..src.len()]);
+
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn libc_memcpy_without_factor_not_bytes() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let mut dst = [0u32; 4];
+        let src = [1u32; 4];
+        unsafe$0 {
+            libc::memcpy(dst.as_mut_ptr() as *mut c_void, src.as_ptr() as *const c_void, 16);
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn libc_memmove_same_buffer() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let mut buf = [1u8, 2, 3, 4, 5, 6];
+        unsafe$0 {
+            libc::memmove(buf.as_mut_ptr().add(2) as *mut c_void, buf.as_ptr() as *const c_void, 3);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut buf = [1u8, 2, 3, 4, 5, 6];
+        buf.copy_within(0..3, 2);
+
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn libc_memmove_size_of_factor() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let mut buf = [1i32, 2, 3, 4];
+        unsafe$0 {
+            libc::memmove(buf.as_mut_ptr().add(1) as *mut c_void, buf.as_ptr() as *const c_void, 2 * mem::size_of::<i32>());
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut buf = [1i32, 2, 3, 4];
+        buf.copy_within(0..2, 1);
+
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn libc_memset_prefix() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let mut buf = [1u8; 16];
+        unsafe$0 {
+            libc::memset(buf.as_mut_ptr() as *mut c_void, 0, 8);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut buf = [1u8; 16];
+        buf[..8].fill(0);
+
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn libc_memset_full_length() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let mut buf = [1u8; 16];
+        unsafe$0 {
+            libc::memset(buf.as_mut_ptr() as *mut c_void, 0xff, buf.len());
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut buf = [1u8; 16];
+        buf.fill(255);
+
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn libc_memset_malloc_not_applicable() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        unsafe$0 {
+            let p = libc::malloc(16) as *mut u8;
+            libc::memset(p as *mut c_void, 0, 16);
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn strlen_cstring_receiver() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    struct CString;
+    impl CString {
+        fn as_ptr(&self) -> *const i8 { loop {} }
+    }
+    fn main() {
+        let c_string = CString;
+        let n = unsafe$0 { libc::strlen(c_string.as_ptr()) };
+    }
+    "#,
+            r#"
+    struct CString;
+    impl CString {
+        fn as_ptr(&self) -> *const i8 { loop {} }
+    }
+    fn main() {
+        let c_string = CString;
+        let n = c_string.as_bytes().len();
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn strlen_cstr_receiver() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    struct CStr;
+    impl CStr {
+        fn as_ptr(&self) -> *const i8 { loop {} }
+    }
+    fn len(cstr: &CStr) -> usize {
+        unsafe$0 { libc::strlen(cstr.as_ptr()) }
+    }
+    "#,
+            r#"
+    struct CStr;
+    impl CStr {
+        fn as_ptr(&self) -> *const i8 { loop {} }
+    }
+    fn len(cstr: &CStr) -> usize {
+        cstr.to_bytes().len()
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn strlen_compare_to_is_empty() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    struct CStr;
+    impl CStr {
+        fn as_ptr(&self) -> *const i8 { loop {} }
+    }
+    fn check(cstr: &CStr) -> bool {
+        if unsafe$0 { strlen(cstr.as_ptr()) } == 0 {
+            return true;
+        }
+        false
+    }
+    "#,
+            r#"
+    struct CStr;
+    impl CStr {
+        fn as_ptr(&self) -> *const i8 { loop {} }
+    }
+    fn check(cstr: &CStr) -> bool {
+        if cstr.is_empty() {
+            return true;
+        }
+        false
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn convert_ptr_copy_parenthesized_count() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        let n = 4;
+        unsafe$0 {
+            ptr::copy(vec[1..].as_mut_ptr(), vec[0..].as_mut_ptr(), n - 1);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        let n = 4;
+        vec.copy_within(1..1 + (n - 1), 0);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn ptr_swap_locals() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut x = 1;
+        let mut y = 2;
+        unsafe$0 {
+            ptr::swap(&mut x, &mut y);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut x = 1;
+        let mut y = 2;
+        mem::swap(&mut x, &mut y);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn ptr_swap_addr_of_mut() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut x = 1;
+        let mut y = 2;
+        unsafe$0 {
+            ptr::swap(ptr::addr_of_mut!(x), ptr::addr_of_mut!(y));
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut x = 1;
+        let mut y = 2;
+        mem::swap(&mut x, &mut y);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn ptr_swap_raw_pointer_arithmetic() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut v = [1, 2, 3];
+        let p = v.as_mut_ptr();
+        unsafe$0 {
+            ptr::swap(p, p.add(2));
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn cstr_from_bytes_with_nul_tail_expr() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let bytes = b"hello\0";
+        let s = unsafe$0 { CStr::from_bytes_with_nul_unchecked(bytes) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let bytes = b"hello\0";
+        let s = CStr::from_bytes_with_nul(bytes).expect("missing nul terminator");
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn cstr_from_bytes_with_nul_let() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        unsafe$0 {
+            let s: &CStr = CStr::from_bytes_with_nul_unchecked(b"hi\0");
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let s: &CStr = CStr::from_bytes_with_nul(b"hi\0").expect("missing nul terminator");
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn slice_from_raw_parts_single_ref() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let x = 5;
+        let s = unsafe$0 { slice::from_raw_parts(&x, 1) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let x = 5;
+        let s = slice::from_ref(&x);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn slice_from_raw_parts_mut_single_ref() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut x = 5;
+        let s = unsafe$0 { std::slice::from_raw_parts_mut(&mut x, 1) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut x = 5;
+        let s = std::slice::from_mut(&mut x);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn slice_from_raw_parts_mut_as_mut_ptr() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut v = vec![1, 2, 3];
+        unsafe$0 {
+            let first: &mut [i32] = from_raw_parts_mut(v.as_mut_ptr(), 1);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut v = vec![1, 2, 3];
+        let first: &mut [i32] = std::slice::from_mut(&mut v[0]);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn slice_from_raw_parts_longer_not_applicable() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let x = 5;
+        let s = unsafe$0 { slice::from_raw_parts(&x, 2) };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn ptr_replace_tail_expr() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut x = 1;
+        let old = unsafe$0 { ptr::replace(&mut x, 5) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut x = 1;
+        let old = mem::replace(&mut x, 5);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn ptr_replace_let_keeps_annotation() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut v = vec![1, 2, 3];
+        unsafe$0 {
+            let old: i32 = ptr::replace(&mut v[1], 10);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut v = vec![1, 2, 3];
+        let old: i32 = mem::replace(&mut v[1], 10);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn ptr_replace_raw_pointer() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut x = 1;
+        let p = &mut x as *mut i32;
+        let old = unsafe$0 { ptr::replace(p, 5) };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn transmute_identical_reference_types() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn bytes(x: &[u8]) -> &[u8] {
+        unsafe$0 { mem::transmute::<&[u8], &[u8]>(x) }
+    }
+    "#,
+            r#"
+    fn bytes(x: &[u8]) -> &[u8] {
+        x
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn transmute_u32_to_i32() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let x: u32 = 5;
+        let y = unsafe$0 { mem::transmute::<u32, i32>(x) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let x: u32 = 5;
+        let y = x as i32;
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn transmute_u64_to_f64_from_bits() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let x: u64 = 5;
+        unsafe$0 {
+            let y: f64 = mem::transmute::<u64, f64>(x);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let x: u64 = 5;
+        let y: f64 = f64::from_bits(x);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn transmute_signed_to_float_refused() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let x: i64 = 5;
+        let y = unsafe$0 { mem::transmute::<i64, f64>(x) };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn copy_nonoverlap_consecutive_batch() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+
+        let src = vec![1, 2, 3, 4, 5, 6];
+        let mut dst = vec![0; 6];
+    
+        unsafe$0 {
+            ptr::copy_nonoverlapping(src[0..].as_ptr(), dst[0..].as_mut_ptr(), 2);
+            ptr::copy_nonoverlapping(src[2..].as_ptr(), dst[2..].as_mut_ptr(), 2);
+            ptr::copy_nonoverlapping(src[4..].as_ptr(), dst[4..].as_mut_ptr(), 2);
+            println!("copied dst vector: {:?}", dst);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let src = vec![1, 2, 3, 4, 5, 6];
+        let mut dst = vec![0; 6];
+
+        $0dst[0..0 + 2].copy_from_slice(&src[0..0 + 2]);
+        dst[2..2 + 2].copy_from_slice(&src[2..2 + 2]);
+        dst[4..4 + 2].copy_from_slice(&src[4..4 + 2]);
+
+        unsafe {
+            println!("copied dst vector: {:?}", dst);
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn transmute_f32_to_u32_to_bits() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let x: f32 = 1.5;
+        let bits = unsafe$0 { mem::transmute::<f32, u32>(x) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let x: f32 = 1.5;
+        let bits = x.to_bits();
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn transmute_u32_to_f32_inferred_from_let() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let x: u32 = 0x3fc00000;
+        unsafe$0 {
+            let y: f32 = mem::transmute(x);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let x: u32 = 0x3fc00000;
+        let y: f32 = f32::from_bits(x);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn transmute_f64_to_u64_inferred_from_outer_let() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let x: f64 = 1.5;
+        let bits: u64 = unsafe$0 { mem::transmute(x) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let x: f64 = 1.5;
+        let bits: u64 = x.to_bits();
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn keep_unsafe_attr_on_fn() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    #[rust_analyzer::keep_unsafe]
+    fn main() {
+        let mut x = 1;
+        let mut y = 2;
+        unsafe$0 {
+            ptr::swap(&mut x, &mut y);
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn keep_unsafe_attr_removed() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut x = 1;
+        let mut y = 2;
+        unsafe$0 {
+            ptr::swap(&mut x, &mut y);
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut x = 1;
+        let mut y = 2;
+        mem::swap(&mut x, &mut y);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn keep_unsafe_attr_on_statement() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let x: u32 = 5;
+        #[rust_analyzer::keep_unsafe]
+        let y = unsafe$0 { mem::transmute::<u32, i32>(x) };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn transmute_int_to_enum_generates_try_from() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    enum Color {
+        Red = 0,
+        Green = 1,
+        Blue = 4,
+    }
+
+    fn main() {
+        let n: u8 = 1;
+        let c = unsafe$0 { mem::transmute::<u8, Color>(n) };
+    }
+    "#,
+            r#"
+    enum Color {
+        Red = 0,
+        Green = 1,
+        Blue = 4,
+    }
+
+    impl TryFrom<u8> for Color {
+        type Error = u8;
+
+        fn try_from(value: u8) -> Result<Self, Self::Error> {
+            match value {
+                0 => Ok(Color::Red),
+                1 => Ok(Color::Green),
+                4 => Ok(Color::Blue),
+                _ => Err(value),
+            }
+        }
+    }
+
+    fn main() {
+        let n: u8 = 1;
+        let c = Color::try_from(n).unwrap();
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn transmute_int_to_enum_existing_impl() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    enum Color {
+        Red = 0,
+        Green = 1,
+    }
+
+    impl TryFrom<u8> for Color {
+        type Error = ();
+
+        fn try_from(value: u8) -> Result<Self, Self::Error> {
+            match value {
+                0 => Ok(Color::Red),
+                1 => Ok(Color::Green),
+                _ => Err(()),
+            }
+        }
+    }
+
+    fn main() {
+        let n: u8 = 1;
+        unsafe$0 {
+            let c: Color = mem::transmute::<u8, Color>(n);
+        }
+    }
+    "#,
+            r#"
+    enum Color {
+        Red = 0,
+        Green = 1,
+    }
+
+    impl TryFrom<u8> for Color {
+        type Error = ();
+
+        fn try_from(value: u8) -> Result<Self, Self::Error> {
+            match value {
+                0 => Ok(Color::Red),
+                1 => Ok(Color::Green),
+                _ => Err(()),
+            }
+        }
+    }
+
+    fn main() {
+        let n: u8 = 1;
+        let c: Color = Color::try_from(n).unwrap();
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn transmute_int_to_enum_implicit_discriminant() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    enum Color {
+        Red,
+        Green,
+    }
+
+    fn main() {
+        let n: u8 = 1;
+        let c = unsafe$0 { mem::transmute::<u8, Color>(n) };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn transmute_enum_to_discriminant() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    #[repr(u8)]
+    enum Level {
+        Low = 1,
+        High = 2,
+    }
+
+    fn main() {
+        let level = Level::High;
+        let n = unsafe$0 { mem::transmute::<Level, u8>(level) };
+    }
+    "#,
+            r#"
+    #[repr(u8)]
+    enum Level {
+        Low = 1,
+        High = 2,
+    }
+
+    fn main() {
+        let level = Level::High;
+        let n = level as u8;
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn transmute_enum_to_discriminant_let_in_block() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    #[repr(i32)]
+    enum Level {
+        Low = -1,
+        High = 1,
+    }
+
+    fn main() {
+        unsafe$0 {
+            let n: i32 = mem::transmute::<Level, i32>(Level::Low);
+        }
+    }
+    "#,
+            r#"
+    #[repr(i32)]
+    enum Level {
+        Low = -1,
+        High = 1,
+    }
+
+    fn main() {
+        let n: i32 = Level::Low as i32;
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn transmute_enum_to_discriminant_repr_mismatch() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    #[repr(u16)]
+    enum Level {
+        Low,
+        High,
+    }
+
+    fn main() {
+        let level = Level::High;
+        let n = unsafe$0 { mem::transmute::<Level, u8>(level) };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn transmute_enum_to_discriminant_without_repr() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    enum Level {
+        Low,
+        High,
+    }
+
+    fn main() {
+        let level = Level::High;
+        let n = unsafe$0 { mem::transmute::<Level, u8>(level) };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn transmute_data_carrying_enum_to_discriminant() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    #[repr(u8)]
+    enum Shape {
+        Dot,
+        Line(u8),
+    }
+
+    fn main() {
+        let shape = Shape::Dot;
+        let n = unsafe$0 { mem::transmute::<Shape, u16>(shape) };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn malformed_ptr_copy_no_panic() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut v = vec![1, 2, 3, 4];
+        unsafe$0 { ptr::copy(&v[ }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn malformed_copy_nonoverlapping_missing_paren() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let src = vec![1, 2, 3, 4];
+        let mut dst = vec![0; 4];
+        unsafe$0 {
+            ptr::copy_nonoverlapping(src[0..].as_ptr(), dst[0..].as_mut_ptr(), 2;
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn as_mut_vec_push_ascii_byte() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut s = String::from("ab");
+        unsafe$0 {
+            s.as_mut_vec().push(b'c');
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut s = String::from("ab");
+        s.push('c');
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn as_mut_vec_extend_from_str_bytes() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut s = String::from("ab");
+        let tail = "cd";
+        unsafe$0 {
+            s.as_mut_vec().extend_from_slice(tail.as_bytes());
+        }
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut s = String::from("ab");
+        let tail = "cd";
+        s.push_str(tail);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn as_mut_vec_push_non_ascii_byte() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut s = String::from("ab");
+        unsafe$0 {
+            s.as_mut_vec().push(b'\xff');
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn unsafe_pattern_str_round_trip() {
+        for pattern in UnsafePattern::ALL {
+            assert_eq!(UnsafePattern::from_str(pattern.as_str()), Ok(*pattern));
+        }
+
+        assert!(UnsafePattern::from_str("not_a_pattern").is_err());
+    }
+
+    #[test]
+    fn unsafe_pattern_class() {
+        assert_eq!(UnsafePattern::PtrSwap.class(), ConversionClass::AlwaysEquivalent);
+        assert_eq!(UnsafePattern::GetUncheck.class(), ConversionClass::BehaviorChanging);
+    }
+
+    #[test]
+    fn unsafe_pattern_str_unique() {
+        let names: Vec<&str> = UnsafePattern::ALL.iter().map(|pattern| pattern.as_str()).collect();
+
+        assert_eq!(names.iter().unique().count(), names.len());
+    }
+
+    #[test]
+    fn pin_new_unchecked_nested_box() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let p = unsafe$0 { Pin::new_unchecked(Box::new(5)) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let p = Box::pin(5);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn pin_new_unchecked_separate_box() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let boxed = Box::new(String::from("pinned"));
+        let p = unsafe$0 { Pin::new_unchecked(boxed) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let p = Box::pin(String::from("pinned"));
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn pin_new_unchecked_boxed_reference() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let x = 5;
+        let p = unsafe$0 { Pin::new_unchecked(Box::new(&x)) };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn static_mut_counter_write_only() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    static mut COUNTER: u64 = 0;
+
+    fn bump() {
+        unsafe$0 { COUNTER += 1; }
+    }
+    "#,
+            r#"
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    fn bump() {
+        COUNTER.fetch_add(1, Ordering::Relaxed);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn static_mut_read_only() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    static mut LIMIT: usize = 10;
+
+    fn limit() -> usize {
+        unsafe$0 { LIMIT }
+    }
+
+    fn twice() -> usize {
+        let limit = unsafe { LIMIT };
+        limit * 2
+    }
+    "#,
+            r#"
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static LIMIT: AtomicUsize = AtomicUsize::new(10);
+
+    fn limit() -> usize {
+        LIMIT.load(Ordering::Relaxed)
+    }
+
+    fn twice() -> usize {
+        let limit = LIMIT.load(Ordering::Relaxed);
+        limit * 2
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn static_mut_mixed_usage_keeps_import() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    use std::sync::atomic::Ordering;
+
+    static mut READY: bool = false;
+
+    fn set() {
+        unsafe$0 { READY = true; }
+    }
+
+    fn check() -> bool {
+        unsafe {
+            foo();
+            READY
+        }
+    }
+    "#,
+            r#"
+    use std::sync::atomic::Ordering;
+
+    use std::sync::atomic::AtomicBool;
+
+    static READY: AtomicBool = AtomicBool::new(false);
+
+    fn set() {
+        READY.store(true, Ordering::Relaxed);
+    }
+
+    fn check() -> bool {
+        unsafe {
+            foo();
+            READY.load(Ordering::Relaxed)
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn static_mut_borrowed_elsewhere() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    static mut COUNTER: u64 = 0;
+
+    fn bump() {
+        unsafe$0 { COUNTER += 1; }
+    }
+
+    fn counter_ref() -> &'static u64 {
+        unsafe { &COUNTER }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn static_mut_self_referencing_write() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    static mut COUNTER: u64 = 0;
+
+    fn bump() {
+        unsafe$0 { COUNTER = COUNTER * 2; }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn static_mut_non_integer() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    static mut NAME: &str = "";
+
+    fn rename() {
+        unsafe$0 { NAME = "new"; }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn unsafe_pattern_detection_needles() {
+        assert!(UnsafePattern::CopyWithin.matches("ptr::copy"));
+        assert!(UnsafePattern::CopyWithin.matches("copy"));
+        assert!(UnsafePattern::UnitializedVec.matches("set_len "));
+        assert!(!UnsafePattern::CopyWithin.matches("copy_nonoverlapping"));
+        assert!(UnsafePattern::MemZeroed.found_in("let x = std::mem::zeroed();"));
+        assert!(!UnsafePattern::StaticMutAccess.found_in("static mut COUNTER: u32 = 0;"));
+    }
+
+    #[test]
+    fn copy_within_imported_fn() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    use std::ptr::copy;
+
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        unsafe$0 {
+            copy(vec[0..].as_mut_ptr(), vec[3..].as_mut_ptr(), 2);
+        }
+        println!("{:?}", vec);
+    }
+    "#,
+            r#"
+    use std::ptr::copy;
+
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        vec.copy_within(0..2, 3);
+        println!("{:?}", vec);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn copy_user_fn_not_applicable() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    unsafe fn copy(src: *const i32, dst: *mut i32, count: usize) {}
+
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        unsafe$0 {
+            copy(vec[0..].as_mut_ptr(), vec[3..].as_mut_ptr(), 2);
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn copy_unimported_not_applicable() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        unsafe$0 {
+            copy(vec[0..].as_mut_ptr(), vec[3..].as_mut_ptr(), 2);
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn safe_replacement_matches_copy_assist() {
+        use ide_db::base_db::{fixture::WithFixture, FileRange, SourceDatabaseExt};
+        use stdx::trim_indent;
+        use test_utils::assert_eq_text;
+
+        let before = r#"
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        unsafe$0 {
+            ptr::copy(vec[0..].as_mut_ptr(), vec[3..].as_mut_ptr(), 2);
+        }
+        println!("{:?}", vec);
+    }
+    "#;
+        let after = r#"
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        vec.copy_within(0..2, 3);
+        println!("{:?}", vec);
+    }
+    "#;
+
+        check_assist(convert_unsafe_to_safe, before, after);
+
+        let (db, file_id, range_or_offset) =
+            RootDatabase::with_range_or_offset(&trim_indent(before));
+        let frange = FileRange { file_id, range: range_or_offset.into() };
+
+        let mut replacements = crate::safe_replacements(&db, &TEST_CONFIG, frange);
+        assert!(replacements
+            .iter()
+            .all(|replacement| replacement.pattern == UnsafePattern::CopyWithin
+                && replacement.behavior_changing));
+
+        let mut text = db.file_text(file_id).to_string();
+        replacements.sort_by_key(|replacement| replacement.range.start());
+        for replacement in replacements.iter().rev() {
+            text.replace_range(
+                std::ops::Range::<usize>::from(replacement.range),
+                &replacement.new_text,
+            );
+        }
+
+        assert_eq_text!(&trim_indent(after), &text);
+    }
+
+    #[test]
+    fn copy_within_full_path() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        unsafe$0 {
+            std::ptr::copy(vec[0..].as_mut_ptr(), vec[3..].as_mut_ptr(), 2);
+        }
+        println!("{:?}", vec);
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        vec.copy_within(0..2, 3);
+        println!("{:?}", vec);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn box_leak_round_trip_kept_owned() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let b = Box::new(5);
+        let r = Box::leak(b);
+        *r += 1;
+        let b = unsafe$0 { Box::from_raw(r as *mut i32) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut b = Box::new(5);
+        *b += 1;
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn box_leak_round_trip_borrowed_and_renamed() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut b = Box::new(vec![1]);
+        let r: &'static mut Vec<i32> = Box::leak(b);
+        r.push(2);
+        let alias = r;
+        let v = unsafe$0 { Box::from_raw(r) };
+        drop(v);
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut b = Box::new(vec![1]);
+        b.push(2);
+        let alias = &mut *b;
+        drop(b);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn box_leak_round_trip_escaping() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let b = Box::new(5);
+        let r = Box::leak(b);
+        register(r);
+        let b = unsafe$0 { Box::from_raw(r as *mut i32) };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn offset_from_literal_offsets() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let v = vec![1, 2, 3, 4, 5];
+        let d = unsafe$0 { v.as_ptr().add(4).offset_from(v.as_ptr().add(1)) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let v = vec![1, 2, 3, 4, 5];
+        let d = v[1..4].len() as isize;
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn offset_from_traced_variable_offsets() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn distance(v: &[u8], i: usize, j: usize) -> isize {
+        let start = v.as_ptr().add(i);
+        let end = v.as_ptr().add(j);
+        unsafe$0 { end.offset_from(start) }
+    }
+    "#,
+            r#"
+    fn distance(v: &[u8], i: usize, j: usize) -> isize {
+        let start = v.as_ptr().add(i);
+        let end = v.as_ptr().add(j);
+        j as isize - i as isize
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn offset_from_different_bases() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let a = vec![1, 2, 3];
+        let b = vec![4, 5, 6];
+        let d = unsafe$0 { a.as_ptr().add(2).offset_from(b.as_ptr()) };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn offset_from_pointer_parameter() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn distance(start: *const u8, v: &[u8]) -> isize {
+        unsafe$0 { v.as_ptr().add(3).offset_from(start) }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_unchecked_range_in_place() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn first_two(v: &[i32]) -> &[i32] {
+        unsafe$0 { v.get_unchecked(0..2) }
+    }
+    "#,
+            r#"
+    fn first_two(v: &[i32]) -> &[i32] {
+        &v[0..2]
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_unchecked_mut_range_in_place() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn middle(v: &mut [i32]) -> &mut [i32] {
+        unsafe$0 { v.get_unchecked_mut(1..3) }
+    }
+    "#,
+            r#"
+    fn middle(v: &mut [i32]) -> &mut [i32] {
+        &mut v[1..3]
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_unchecked_mut_block_receiver() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn append(v: &mut [Vec<i32>], i: usize, x: i32) {
+        unsafe$0 { v.get_unchecked_mut(i) }.push(x);
+    }
+    "#,
+            r#"
+    fn append(v: &mut [Vec<i32>], i: usize, x: i32) {
+        v.get_mut(i).unwrap().push(x);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_unchecked_deref_block_value() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn first(v: &[u32], p: *const u32) -> u32 {
+        unsafe$0 {
+            let _ = *p;
+            *v.get_unchecked(0)
+        }
+    }
+    "#,
+            r#"
+    fn first(v: &[u32], p: *const u32) -> u32 {
+        unsafe {
+            let _ = *p;
+            v[0]
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_unchecked_deref_in_let_not_applicable() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn first(v: &[u32]) -> u32 {
+        unsafe$0 {
+            let a = *v.get_unchecked(0);
+            a
+        }
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_unchecked_mut_range_block_receiver() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn clear_middle(v: &mut [i32]) {
+        unsafe$0 { v.get_unchecked_mut(1..3) }.fill(0);
+    }
+    "#,
+            r#"
+    fn clear_middle(v: &mut [i32]) {
+        (&mut v[1..3]).fill(0);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_unchecked_range_let() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn main() {
+        let v = [1, 2, 3, 4];
+        let s = unsafe$0 { v.get_unchecked(1..3) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let v = [1, 2, 3, 4];
+        let s = v.get(1..3).unwrap();
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn get_unchecked_scalar_in_place() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- minicore: slice
+    fn first(v: &[i32]) -> &i32 {
+        unsafe$0 { v.get_unchecked(0) }
+    }
+    "#,
+            r#"
+    fn first(v: &[i32]) -> &i32 {
+        v.get(0).unwrap()
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn str_get_unchecked_let_around_block() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let s = "hello world";
+        let sub = unsafe$0 { s.get_unchecked(0..5) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let s = "hello world";
+        let sub = s.get(0..5).expect("not a char boundary");
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn str_get_unchecked_in_place() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn word(s: &str) -> &str {
+        unsafe$0 { s.get_unchecked(6..11) }
+    }
+    "#,
+            r#"
+    fn word(s: &str) -> &str {
+        &s[6..11]
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn str_get_unchecked_alloc_string() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    //- /main.rs crate:main deps:alloc
+    use alloc::string::String;
+    fn word(s: &String) -> &str {
+        unsafe$0 { s.get_unchecked(6..11) }
+    }
+    //- /alloc.rs crate:alloc
+    pub mod string {
+        pub struct String;
+        impl String {
+            pub unsafe fn get_unchecked<I>(&self, i: I) -> &str { loop {} }
+        }
+    }
+    "#,
+            r#"
+    use alloc::string::String;
+    fn word(s: &String) -> &str {
+        &s[6..11]
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn str_get_unchecked_user_string() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    struct String;
+    impl String {
+        unsafe fn get_unchecked<I>(&self, i: I) -> &str { loop {} }
+    }
+    fn word(s: &String) -> &str {
+        unsafe$0 { s.get_unchecked(6..11) }
+    }
+    "#,
+        );
     }
-    return Some(false);
-}
 
-pub fn check_convert_type(target_expr: &SyntaxNode, unsafe_expr: &BlockExpr) -> Option<UnsafePattern> {
+    #[test]
+    fn str_bytes_get_unchecked_deref() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let s = "hello";
+        let b = unsafe$0 { *s.as_bytes().get_unchecked(1) };
+    }
+    "#,
+            r#"
+    fn main() {
+        let s = "hello";
+        let b = s.as_bytes()[1];
+    }
+    "#,
+        );
+    }
 
-    if target_expr.to_string() == UnsafePattern::UnitializedVec.to_string() {
-        if uninitialized_vec_analysis(&target_expr, &unsafe_expr)? {
-            return Some(UnsafePattern::UnitializedVec);
+    #[test]
+    fn str_bytes_get_unchecked_in_block() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn first_byte(s: &str, i: usize) -> u8 {
+        unsafe$0 {
+            let b = s.as_bytes().get_unchecked(i);
         }
+        b
     }
-
-    if target_expr.to_string() == UnsafePattern::CopyWithin.to_string() {
-        return Some(UnsafePattern::CopyWithin);
+    "#,
+            r#"
+    fn first_byte(s: &str, i: usize) -> u8 {
+        let b = s.as_bytes().get(i).copied().unwrap();
+        b
     }
-
-    if target_expr.to_string() == UnsafePattern::GetUncheck.to_string() {
-        return Some(UnsafePattern::GetUncheck);
+    "#,
+        );
     }
 
-    if target_expr.to_string() == UnsafePattern::GetUncheckMut.to_string() {
-        return Some(UnsafePattern::GetUncheckMut);
+    #[test]
+    fn copy_within_whole_vec() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        unsafe$0 {
+            ptr::copy(vec.as_ptr(), vec.as_mut_ptr().add(3), 2);
+        }
+        println!("{:?}", vec);
     }
-
-    if target_expr.to_string() == UnsafePattern::CopyNonOverlap.to_string() {
-        return Some(UnsafePattern::CopyNonOverlap);
+    "#,
+            r#"
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        vec.copy_within(0..2, 3);
+        println!("{:?}", vec);
     }
-
-    if target_expr.to_string() == UnsafePattern::CStringFromVec.to_string() {
-        return Some(UnsafePattern::CStringFromVec);
+    "#,
+        );
     }
 
-    if target_expr.to_string() == UnsafePattern::CStringLength.to_string() {
-        return Some(UnsafePattern::CStringLength);
+    #[test]
+    fn copy_within_addr_of() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        unsafe$0 {
+            ptr::copy(ptr::addr_of!(vec[0]), ptr::addr_of_mut!(vec[3]), 2);
+        }
+        println!("{:?}", vec);
     }
-
-    if target_expr.to_string() == UnsafePattern::BytesToUTFString.to_string() ||
-        target_expr.to_string() == UnsafePattern::STDBytesToUTFString.to_string() ||
-            target_expr.to_string() == UnsafePattern::CoreBytesToUTFString.to_string(){
-        return Some(UnsafePattern::BytesToUTFString);
+    "#,
+            r#"
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        vec.copy_within(0..2, 3);
+        println!("{:?}", vec);
     }
-
-    if target_expr.to_string() == UnsafePattern::StringBytesToUTFString.to_string() {
-        return Some(UnsafePattern::StringBytesToUTFString);
+    "#,
+        );
     }
 
-    if target_expr.to_string() == UnsafePattern::BytesToUTFStringMut.to_string() ||
-        target_expr.to_string() == UnsafePattern::STDBytesToUTFStringMut.to_string() ||
-            target_expr.to_string() == UnsafePattern::CoreBytesToUTFStringMut.to_string() {
-        return Some(UnsafePattern::BytesToUTFStringMut);
+    #[test]
+    fn copy_within_non_slice_base() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    struct Ring;
+    impl Ring {
+        fn as_mut_ptr(&mut self) -> *mut u8 { loop {} }
     }
-
-    if target_expr.to_string() == UnsafePattern::TransmuteTo.to_string() {
-        return Some(UnsafePattern::TransmuteTo);
+    fn main() {
+        let mut ring = Ring;
+        unsafe$0 {
+            ptr::copy(ring.as_mut_ptr(), ring.as_mut_ptr().add(3), 2);
+        }
     }
-
-    if target_expr.to_string() == UnsafePattern::ReadUnaligned.to_string() {
-        return Some(UnsafePattern::ReadUnaligned);
+    "#,
+        );
     }
 
-    if target_expr.to_string() == UnsafePattern::FromU32Unchecked.to_string() ||
-        target_expr.to_string() == UnsafePattern::STDFromU32Unchecked.to_string() {
-        return Some(UnsafePattern::FromU32Unchecked);
+    #[test]
+    fn copy_nonoverlap_non_slice_base() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    struct Ring;
+    impl Ring {
+        fn as_ptr(&self) -> *const u8 { loop {} }
     }
-
-    return None;
-
-}
-
-struct UnsafeBlockInfo {
-    unsafe_expr: BlockExpr,
-    unsafe_range: TextRange,
-}
-
-fn collect_unsafe_vec_info(ctx: &AssistContext<'_>) -> Option<UnsafeBlockInfo> {
-
-    // Detect the "unsafe" key word
-    let unsafe_kw = ctx.find_token_syntax_at_offset(T![unsafe])?;
-
-    // Collect the expressions within the "unsafe" block
-    let unsafe_expr = unsafe_kw.parent().and_then(ast::BlockExpr::cast)?;
-
-    let mut unsafe_range = unsafe_expr.syntax().text_range();
-
-    if unsafe_expr.syntax().parent()?.kind() != STMT_LIST {
-        unsafe_range = unsafe_expr.syntax().parent()?.text_range();
+    fn main() {
+        let src = Ring;
+        let mut dst = vec![0; 8];
+        unsafe$0 {
+            ptr::copy_nonoverlapping(src.as_ptr(), dst.as_mut_ptr(), 4);
+        }
     }
-
-    return Some(UnsafeBlockInfo {unsafe_expr, unsafe_range});
-
-}
-
-pub(crate) fn convert_unsafe_to_safe(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
-
-    let UnsafeBlockInfo { unsafe_expr, unsafe_range} = collect_unsafe_vec_info(ctx)?;
-
-    // Iteration through the "unsafe" expressions' AST
-    for target_expr in unsafe_expr.syntax().descendants() {
-
-        let unsafe_type = check_convert_type(&target_expr, &unsafe_expr);
-        
-        match unsafe_type {
-            Some(UnsafePattern::UnitializedVec) => return convert_to_auto_vec_initialization(acc, &target_expr, unsafe_range, &unsafe_expr),
-            Some(UnsafePattern::CopyWithin) => return convert_to_copy_within(acc, &target_expr, unsafe_range, &unsafe_expr),
-            Some(UnsafePattern::CopyNonOverlap) => return convert_to_copy_from_slice(acc, &target_expr, unsafe_range, &unsafe_expr),
-            Some(UnsafePattern::CStringFromVec) => return convert_to_cstring_new(acc, &target_expr, unsafe_range, &unsafe_expr),
-            Some(UnsafePattern::CStringLength) => return convert_to_cstring_bytes_len(acc, &target_expr, unsafe_range, &unsafe_expr),
-            Some(UnsafePattern::GetUncheckMut) => return convert_to_get_mut(acc, &target_expr, unsafe_range, &unsafe_expr),
-            Some(UnsafePattern::GetUncheck) => return convert_to_get_mut(acc, &target_expr, unsafe_range, &unsafe_expr),
-            Some(UnsafePattern::BytesToUTFString) => return convert_to_from_utf8(acc, &target_expr, unsafe_range, &unsafe_expr, false, false),
-            Some(UnsafePattern::BytesToUTFStringMut) => return convert_to_from_utf8(acc, &target_expr, unsafe_range, &unsafe_expr, true, false),
-            Some(UnsafePattern::StringBytesToUTFString) => return convert_to_from_utf8(acc, &target_expr, unsafe_range, &unsafe_expr, false, true),
-            Some(UnsafePattern::TransmuteTo) => return transmute_convertion(acc, &target_expr, unsafe_range, &unsafe_expr),
-            Some(UnsafePattern::ReadUnaligned) => return convert_to_from_ne_bytes(acc, &target_expr, unsafe_range, &unsafe_expr),
-            Some(UnsafePattern::FromU32Unchecked) => return convert_to_from_u32(acc, &target_expr, unsafe_range, &unsafe_expr),
-            None => continue,
-            _ => todo!(),
-        };
-        
+    "#,
+        );
     }
 
-    return None;
-    
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::tests::check_assist;
-
-    use super::*;
-
     #[test]
-    fn from_u32_unchecked_1() {
+    fn copy_nonoverlap_whole_vecs() {
         check_assist(
             convert_unsafe_to_safe,
             r#"
     fn main() {
-
-        let char_valid;
-
+        let src = vec![1, 2, 3, 4];
+        let mut dst = vec![0; 8];
         unsafe$0 {
-            char_valid = char::from_u32_unchecked(0x2764);
+            ptr::copy_nonoverlapping(src.as_ptr(), dst.as_mut_ptr(), 4);
         }
-        println!("char_valid: {:?}", char_valid);
     }
     "#,
-                r#"
+            r#"
     fn main() {
-
-        let char_valid;
-
-        char_valid = char::from_u32(0x2764).unwrap();
-    
-        println!("char_valid: {:?}", char_valid);
+        let src = vec![1, 2, 3, 4];
+        let mut dst = vec![0; 8];
+        dst[..4].copy_from_slice(&src[..4]);
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn read_unaligned_1() {
+    fn copy_nonoverlap_whole_array_traced() {
         check_assist(
             convert_unsafe_to_safe,
             r#"
     fn main() {
-
-        let bytes: &[u8] = &[6, 7, 8, 4, 5, 6];
-    
-        unsafe$0 { 
-            let int = ptr::read_unaligned(bytes.as_ptr() as *const u16);
+        let arr = [1u8; 16];
+        let mut buf = Box::new([0u8; 16]);
+        let src = arr.as_ptr();
+        let dst = buf.as_mut_ptr();
+        unsafe$0 {
+            ptr::copy_nonoverlapping(src, dst, n);
         }
-        println!("The convert int: {:?}", int);
-        
     }
     "#,
-                r#"
+            r#"
     fn main() {
+        let arr = [1u8; 16];
+        let mut buf = Box::new([0u8; 16]);
+        let src = arr.as_ptr();
+        let dst = buf.as_mut_ptr();
+        buf[..n].copy_from_slice(&arr[..n]);
+    }
+    "#,
+        );
+    }
 
-        let bytes: &[u8] = &[6, 7, 8, 4, 5, 6];
-    
-        let int = u16::from_ne_bytes(bytes[..2].try_into().unwrap());
-    
-        println!("The convert int: {:?}", int);
-        
+    #[test]
+    fn read_unaligned_u32_at_offset() {
+        check_assist(
+            convert_unsafe_to_safe,
+            r#"
+    fn parse(buf: &[u8], off: usize) -> u32 {
+        let v = unsafe$0 { ptr::read_unaligned(buf.as_ptr().add(off) as *const u32) };
+        v
     }
     "#,
-            );
+            r#"
+    fn parse(buf: &[u8], off: usize) -> u32 {
+        let v = u32::from_ne_bytes(buf[off..off + 4].try_into().unwrap());
+        v
+    }
+    "#,
+        );
     }
 
     #[test]
-    fn read_unaligned_2() {
+    fn read_unaligned_u64_sub_slice() {
         check_assist(
             convert_unsafe_to_safe,
             r#"
     fn main() {
-
-        let bytes: &[u8] = &[6, 7, 8, 4, 5, 6];
-        let int;
-        unsafe$0 { 
-            int = ptr::read_unaligned(bytes.as_ptr() as *const u16);
+        let bytes = [0u8; 16];
+        unsafe$0 {
+            let int = ptr::read_unaligned(bytes[8..].as_ptr() as *const u64);
         }
-        println!("The convert int: {:?}", int);
-        
     }
     "#,
-                r#"
+            r#"
     fn main() {
+        let bytes = [0u8; 16];
+        let int = u64::from_ne_bytes(bytes[8..8 + 8].try_into().unwrap());
 
-        let bytes: &[u8] = &[6, 7, 8, 4, 5, 6];
-        let int;
-        int = u16::from_ne_bytes(bytes[..2].try_into().unwrap());
-    
-        println!("The convert int: {:?}", int);
-        
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn read_unaligned_3() {
+    fn ptr_read_u16_from_bytes() {
         check_assist(
             convert_unsafe_to_safe,
             r#"
     fn main() {
-
         let bytes: &[u8] = &[6, 7, 8, 4, 5, 6];
-        let ptr = bytes.as_ptr();
-    
-        unsafe$0 { 
-            let int = ptr::read_unaligned(ptr as *const u16);
-        }
-        println!("The convert int: {:?}", int);
-        
+        let int = unsafe$0 { ptr::read(bytes.as_ptr().add(2) as *const u16) };
     }
     "#,
-                r#"
+            r#"
     fn main() {
-
         let bytes: &[u8] = &[6, 7, 8, 4, 5, 6];
-        let ptr = bytes.as_ptr();
-    
-        let int = u16::from_ne_bytes(bytes[..2].try_into().unwrap());
-    
-        println!("The convert int: {:?}", int);
-        
+        let int = u16::from_ne_bytes(bytes[2..2 + 2].try_into().unwrap());
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn byte_utf_string_1() {
+    fn write_unaligned_u32() {
         check_assist(
             convert_unsafe_to_safe,
             r#"
-    fn main() {
-
-        let sparkle_heart : &[u8] = &[240, 159, 146, 150];
-
-        let string;
-
+    fn store(buf: &mut [u8], off: usize, val: u32) {
         unsafe$0 {
-            string = std::str::from_utf8_unchecked(&sparkle_heart)
+            ptr::write_unaligned(buf.as_mut_ptr().add(off) as *mut u32, val);
         }
-        println!("sparkle_heart: {:?}", string);
     }
     "#,
-                r#"
-    fn main() {
-
-        let sparkle_heart : &[u8] = &[240, 159, 146, 150];
-
-        let string;
-
-        string = std::str::from_utf8(&sparkle_heart).unwrap();
-    
-        println!("sparkle_heart: {:?}", string);
+            r#"
+    fn store(buf: &mut [u8], off: usize, val: u32) {
+        buf[off..off + 4].copy_from_slice(&val.to_ne_bytes());
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn byte_utf_string_2() {
+    fn write_unaligned_addr_of_mut() {
         check_assist(
             convert_unsafe_to_safe,
             r#"
-    fn main() {
-
-        let sparkle_heart : &[u8] = &[240, 159, 146, 150];
-
-        string = unsafe$0 {std::str::from_utf8_unchecked(&sparkle_heart)};
-        println!("sparkle_heart: {:?}", string);
+    fn store(buf: &mut [u8], off: usize, val: u32) {
+        unsafe$0 {
+            ptr::write_unaligned(addr_of_mut!(buf[off]) as *mut u32, val);
+        }
     }
     "#,
-                r#"
-    fn main() {
-
-        let sparkle_heart : &[u8] = &[240, 159, 146, 150];
+            r#"
+    fn store(buf: &mut [u8], off: usize, val: u32) {
+        buf[off..off + 4].copy_from_slice(&val.to_ne_bytes());
+    }
+    "#,
+        );
+    }
 
-        string = std::str::from_utf8(&sparkle_heart).unwrap();
-        println!("sparkle_heart: {:?}", string);
+    #[test]
+    fn read_unaligned_untraced_pointer() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn parse(ptr: *const u8) -> u32 {
+        let v = unsafe$0 { ptr::read_unaligned(ptr as *const u32) };
+        v
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn from_vec_unchecked_1() {
+    fn as_bytes_mut_ascii_uppercase_chain() {
         check_assist(
             convert_unsafe_to_safe,
             r#"
-    fn main() {
-
-        let raw = b"Hello, World!".to_vec();
-
+    fn shout(s: &mut str) {
         unsafe$0 {
-            let c_string = CString::from_vec_unchecked(raw);
-            println!("The C String: {:?}", c_string);
+            s.as_bytes_mut().make_ascii_uppercase();
         }
     }
     "#,
-                r#"
-    fn main() {
-
-        let raw = b"Hello, World!".to_vec();
-        let c_string = CString::new(raw).unwrap();
-    
-        unsafe$0 {
-
-            println!("The C String: {:?}", c_string);
-        }
+            r#"
+    fn shout(s: &mut str) {
+        s.make_ascii_uppercase();
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn from_vec_unchecked_2() {
+    fn as_bytes_mut_ascii_lowercase_binding() {
         check_assist(
             convert_unsafe_to_safe,
             r#"
-    fn main() {
-
-        let raw = b"Hello, World!".to_vec();
-
-        unsafe$0 {
-            let c_string = CString::from_vec_unchecked(raw);
-        }
+    fn whisper(s: &mut str) {
+        let bytes = unsafe$0 { s.as_bytes_mut() };
+        bytes.make_ascii_lowercase();
     }
     "#,
-                r#"
-    fn main() {
-
-        let raw = b"Hello, World!".to_vec();
+            r#"
+    fn whisper(s: &mut str) {
+        s.make_ascii_lowercase();
+    }
+    "#,
+        );
+    }
 
-        let c_string = CString::new(raw).unwrap();
-    
+    #[test]
+    fn as_bytes_mut_arbitrary_mutation() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn scramble(s: &mut str) {
+        let bytes = unsafe$0 { s.as_bytes_mut() };
+        bytes[0] = 0xff;
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn from_vec_unchecked_3() {
+    fn unconvertible_pattern_does_not_block_later_one() {
         check_assist(
             convert_unsafe_to_safe,
             r#"
-    fn main() {
-
-        let raw = b"Hello, World!".to_vec();
-
-        let c_string;
-
+    struct String;
+    fn scramble(s: &mut String) {
+        let mut x = 1;
+        let mut y = 2;
         unsafe$0 {
-            c_string = CString::from_vec_unchecked(raw);
-            println!("The C String: {:?}", c_string);
+            s.as_bytes_mut()[0] = 0xff;
+            ptr::swap(&mut x, &mut y);
         }
     }
     "#,
-                r#"
-    fn main() {
-
-        let raw = b"Hello, World!".to_vec();
-
-        let c_string;
-        c_string = CString::new(raw).unwrap();
-
-        unsafe$0 {
-
-            println!("The C String: {:?}", c_string);
+            r#"
+    struct String;
+    fn scramble(s: &mut String) {
+        let mut x = 1;
+        let mut y = 2;
+        $0mem::swap(&mut x, &mut y);
+
+        unsafe {
+            s.as_bytes_mut()[0] = 0xff;
         }
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn cstring_len_1() {
+    fn as_bytes_mut_ascii_byte_write() {
         check_assist(
             convert_unsafe_to_safe,
             r#"
-    fn main() {
-
-        let raw = b"Hello, World!".to_vec();
-
-        let c_string = CString::new(raw).unwrap();
-
+    struct String;
+    fn capitalize(s: &mut String) {
         unsafe$0 {
-            let length = libc::strlen(c_string.as_ptr());
-            println!("The C String: {:?}", length);
+            s.as_bytes_mut()[0] = b'H';
         }
     }
     "#,
-                r#"
-    fn main() {
-
-        let raw = b"Hello, World!".to_vec();
-
-        let c_string = CString::new(raw).unwrap();
-        let length = c_string.as_bytes().len();
-
-        unsafe$0 {
-
-            println!("The C String: {:?}", length);
-        }
+            r#"
+    struct String;
+    fn capitalize(s: &mut String) {
+        s.replace_range(0..1, "H");
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn cstring_len_2() {
+    fn as_bytes_mut_ascii_byte_write_binding() {
         check_assist(
             convert_unsafe_to_safe,
             r#"
-    fn main() {
-
-        let raw = b"Hello, World!".to_vec();
-
-        let c_string = CString::new(raw).unwrap();
-
-        let length;
-
-        unsafe$0 {
-            length = libc::strlen(c_string.as_ptr());
-            println!("The C String: {:?}", length);
-        }
-        println!("The C String: {:?}", length);
+    struct String;
+    fn mark(s: &mut String, i: usize) {
+        let bytes = unsafe$0 { s.as_bytes_mut() };
+        bytes[i] = b'_';
     }
     "#,
-                r#"
-    fn main() {
-
-        let raw = b"Hello, World!".to_vec();
-
-        let c_string = CString::new(raw).unwrap();
-
-        let length;
-        length = c_string.as_bytes().len();
+            r#"
+    struct String;
+    fn mark(s: &mut String, i: usize) {
+        s.replace_range(i..i + 1, "_");
+    }
+    "#,
+        );
+    }
 
+    #[test]
+    fn as_bytes_mut_byte_write_on_str() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn capitalize(s: &mut str) {
         unsafe$0 {
-
-            println!("The C String: {:?}", length);
+            s.as_bytes_mut()[0] = b'H';
         }
-        println!("The C String: {:?}", length);
     }
     "#,
-            );
+        );
     }
-    
 
     #[test]
-    fn copy_nonoverlap_1() {
+    fn alloc_to_vec_push() {
         check_assist(
             convert_unsafe_to_safe,
             r#"
     fn main() {
-
-        let src = vec![1, 2, 3, 4, 5, 6];
-        let mut dst = vec![0; 6];
-
-        let len = 2
-    
-        unsafe$0 {
-            ptr::copy_nonoverlapping(src[1..].as_ptr(), dst[2..].as_mut_ptr(), len);
-            println!("copied dst vector: {:?}", dst); 
+        let layout = Layout::array::<u32>(3).unwrap();
+        let p = unsafe$0 { alloc(layout) as *mut u32 };
+        unsafe {
+            p.write(1);
+            p.add(1).write(2);
+            p.add(2).write(3);
         }
+        unsafe { dealloc(p as *mut u8, layout) };
     }
     "#,
-                r#"
+            r#"
     fn main() {
-
-        let src = vec![1, 2, 3, 4, 5, 6];
-        let mut dst = vec![0; 6];
-
-        let len = 2
-        dst[2..2 + len].copy_from_slice(&src[1..1 + len]);
-    
-        unsafe$0 {
-
-            println!("copied dst vector: {:?}", dst); 
-        }
+        let mut p: Vec<u32> = Vec::with_capacity(3);
+        p.push(1);
+        p.push(2);
+        p.push(3);
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn copy_nonoverlap_2() {
+    fn alloc_to_vec_index_assign() {
         check_assist(
             convert_unsafe_to_safe,
             r#"
     fn main() {
-
-        let src = vec![1, 2, 3, 4, 5, 6];
-        let mut dst = vec![0; 6];
-    
-        unsafe$0 {
-            ptr::copy_nonoverlapping(src[2..4].as_ptr(), dst[2..4].as_mut_ptr(), src[2..4].len());
+        let p = unsafe$0 { alloc(Layout::array::<i64>(4).unwrap()) as *mut i64 };
+        unsafe {
+            p.add(3).write(-1);
+            p.write(7);
         }
+        let last = unsafe { p.add(3).read() };
+        unsafe { dealloc(p as *mut u8, Layout::array::<i64>(4).unwrap()) };
+        println!("{}", last);
     }
     "#,
-                r#"
+            r#"
     fn main() {
-
-        let src = vec![1, 2, 3, 4, 5, 6];
-        let mut dst = vec![0; 6];
-
-        dst[2..2 + src[2..4].len()].copy_from_slice(&src[2..2 + src[2..4].len()]);
-
+        let mut p: Vec<i64> = vec![0; 4];
+        p[3] = -1;
+        p[0] = 7;
+        let last = p[3];
+        println!("{}", last);
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn copy_nonoverlap_3() {
+    fn alloc_to_vec_read_back() {
         check_assist(
             convert_unsafe_to_safe,
             r#"
     fn main() {
-
-        let src = vec![1, 2, 3, 4, 5, 6];
-        let mut dst = vec![0; 6];
-        let len = 3
-        let source = src[1..3].as_ptr();
-        let dest = dst[2..3].as_mut_ptr();
-    
-        unsafe$0 {
-            ptr::copy_nonoverlapping(source, dest, len);
+        let n = 8;
+        let layout = Layout::array::<u8>(n).unwrap();
+        let buf = unsafe$0 { alloc(layout) as *mut u8 };
+        unsafe {
+            buf.write(b'a');
+            buf.add(1).write(b'b');
+        }
+        let first = unsafe { buf.read() };
+        unsafe {
+            dealloc(buf as *mut u8, layout);
         }
+        foo(first);
     }
     "#,
-                r#"
+            r#"
     fn main() {
-
-        let src = vec![1, 2, 3, 4, 5, 6];
-        let mut dst = vec![0; 6];
-        let len = 3
-        let source = src[1..3].as_ptr();
-        let dest = dst[2..3].as_mut_ptr();
-
-        dst[2..2 + len].copy_from_slice(&src[1..1 + len]);
+        let n = 8;
+        let mut buf: Vec<u8> = Vec::with_capacity(n);
+        buf.push(b'a');
+        buf.push(b'b');
+        let first = buf[0];
+        foo(first);
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn copy_nonoverlap_4() {
+    fn alloc_to_vec_dealloc_in_write_block() {
         check_assist(
             convert_unsafe_to_safe,
             r#"
     fn main() {
-
-        let src = vec![1, 2, 3, 4, 5, 6];
-        let mut dst = vec![0; 6];
-        let len = 3
-        let source = src[1..3].as_ptr();
-        let dest = dst[2..3].as_mut_ptr();
-    
-        unsafe$0 {
-            ptr::copy_nonoverlapping(source, dest, len);
-            println!("copied dst vector: {:?}", dst);
+        let p = unsafe$0 { std::alloc::alloc(Layout::array::<f32>(2).unwrap()) as *mut f32 };
+        unsafe {
+            p.write(0.5);
+            p.add(1).write(1.5);
+            std::alloc::dealloc(p as *mut u8, Layout::array::<f32>(2).unwrap());
         }
-        println!("copied dst vector: {:?}", dst);
+        done();
     }
     "#,
-                r#"
+            r#"
     fn main() {
-
-        let src = vec![1, 2, 3, 4, 5, 6];
-        let mut dst = vec![0; 6];
-        let len = 3
-        let source = src[1..3].as_ptr();
-        let dest = dst[2..3].as_mut_ptr();
-
-        dst[2..2 + len].copy_from_slice(&src[1..1 + len]);
-
-        unsafe$0 {
-
-            println!("copied dst vector: {:?}", dst);
-        }
-        println!("copied dst vector: {:?}", dst);
+        let mut p: Vec<f32> = Vec::with_capacity(2);
+        p.push(0.5);
+        p.push(1.5);
+        done();
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn get_uncheckd_0() {
+    fn alloc_to_vec_tail_block() {
         check_assist(
             convert_unsafe_to_safe,
             r#"
     fn main() {
-        let vec = vec![1,2,3,4,5,6];
-        unsafe$0 {
-            let index = vec.get_unchecked(5);    
+        let p = unsafe$0 { alloc(Layout::array::<u32>(2).unwrap()) as *mut u32 };
+        unsafe {
+            p.write(1);
+            p.add(1).write(2);
+            dealloc(p as *mut u8, Layout::array::<u32>(2).unwrap());
         }
     }
     "#,
-                r#"
+            r#"
     fn main() {
-        let vec = vec![1,2,3,4,5,6];
-        let index = vec.get(5).unwrap();
-
+        let mut p: Vec<u32> = Vec::with_capacity(2);
+        p.push(1);
+        p.push(2);
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn get_uncheckd_1() {
+    fn alloc_to_vec_layout_used_elsewhere() {
         check_assist(
             convert_unsafe_to_safe,
             r#"
     fn main() {
-        let vec = vec![1,2,3,4,5,6];
-        let index = unsafe$0 {*vec.get_unchecked(5)};
+        let layout = Layout::array::<u16>(2).unwrap();
+        let p = unsafe$0 { alloc(layout) as *mut u16 };
+        unsafe { p.write(1) };
+        println!("{}", layout.size());
+        unsafe { dealloc(p as *mut u8, layout) };
     }
     "#,
-                r#"
+            r#"
     fn main() {
-        let vec = vec![1,2,3,4,5,6];
-        let index = vec.get(5).unwrap();
+        let layout = Layout::array::<u16>(2).unwrap();
+        let mut p: Vec<u16> = Vec::with_capacity(2);
+        p.push(1);
+        println!("{}", layout.size());
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn get_uncheckd_prefix() {
-        check_assist(
+    fn alloc_to_vec_pointer_escapes() {
+        check_assist_not_applicable(
             convert_unsafe_to_safe,
             r#"
     fn main() {
-        let vec = vec![1,2,3,4,5,6];
-        let index = unsafe$0 {*vec.get_unchecked(5)};
+        let p = unsafe$0 { alloc(Layout::array::<u32>(2).unwrap()) as *mut u32 };
+        unsafe { p.write(1) };
+        consume(p);
+        unsafe { dealloc(p as *mut u8, Layout::array::<u32>(2).unwrap()) };
     }
     "#,
-                r#"
+        );
+    }
+
+    #[test]
+    fn alloc_to_vec_write_out_of_bounds() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
     fn main() {
-        let vec = vec![1,2,3,4,5,6];
-        let index = *vec.get(5).unwrap();
+        let layout = Layout::array::<u32>(2).unwrap();
+        let p = unsafe$0 { alloc(layout) as *mut u32 };
+        unsafe {
+            p.write(1);
+            p.add(1).write(2);
+            p.add(2).write(3);
+        }
+        unsafe { dealloc(p as *mut u8, layout) };
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn get_uncheckd_mut_prefix() {
-        check_assist(
+    fn alloc_to_vec_runtime_index() {
+        check_assist_not_applicable(
             convert_unsafe_to_safe,
             r#"
     fn main() {
-        let vec = vec![1,2,3,4,5,6];
-        let index = unsafe$0 {*vec.get_unchecked_mut(5)};
+        let layout = Layout::array::<u32>(4).unwrap();
+        let p = unsafe$0 { alloc(layout) as *mut u32 };
+        unsafe { p.add(i).write(1) };
+        unsafe { dealloc(p as *mut u8, layout) };
     }
     "#,
-                r#"
+        );
+    }
+
+    #[test]
+    fn alloc_to_vec_never_freed() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let layout = Layout::array::<u32>(2).unwrap();
+        let p = unsafe$0 { alloc(layout) as *mut u32 };
+        unsafe { p.write(1) };
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn alloc_to_vec_used_after_free() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
     fn main() {
-        let vec = vec![1,2,3,4,5,6];
-        let index = *vec.get_mut(5).unwrap();
+        let layout = Layout::array::<u32>(2).unwrap();
+        let p = unsafe$0 { alloc(layout) as *mut u32 };
+        unsafe { p.write(1) };
+        unsafe { dealloc(p as *mut u8, layout) };
+        let x = unsafe { p.read() };
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn get_uncheckd_2() {
-        check_assist(
+    fn alloc_to_vec_other_layout_freed() {
+        check_assist_not_applicable(
             convert_unsafe_to_safe,
             r#"
     fn main() {
-
-        let vec = vec![1,2,3,4,5,6];
-    
-        unsafe$0 {
-            let index = vec.get_unchecked(5);    
-        }
+        let layout = Layout::array::<u32>(2).unwrap();
+        let p = unsafe$0 { alloc(layout) as *mut u32 };
+        unsafe { p.write(1) };
+        unsafe { dealloc(p as *mut u8, Layout::array::<u32>(4).unwrap()) };
     }
     "#,
-                r#"
-    fn main() {
+        );
+    }
 
-        let vec = vec![1,2,3,4,5,6];
-    
-        let index = vec.get(5).unwrap();
+    #[test]
+    fn alloc_to_vec_non_numeric_out_of_order() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let layout = Layout::array::<String>(2).unwrap();
+        let p = unsafe$0 { alloc(layout) as *mut String };
+        unsafe {
+            p.add(1).write(a);
+            p.write(b);
+        }
+        unsafe { dealloc(p as *mut u8, layout) };
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn get_uncheckd_mut_1() {
+    fn minimize_unsafe_block_safe_before() {
         check_assist(
-            convert_unsafe_to_safe,
+            minimize_unsafe_block,
             r#"
+    unsafe fn danger() {}
     fn main() {
-
-        let mut vec = vec![1,2,3,4,5,6];
-    
         unsafe$0 {
-            let index = vec.get_unchecked_mut(5);    
-            print!("Index: {:?} \n", index);
+            let x = 1;
+            danger();
         }
     }
     "#,
-                r#"
+            r#"
+    unsafe fn danger() {}
     fn main() {
-
-        let mut vec = vec![1,2,3,4,5,6];
-        let index = vec.get_mut(5).unwrap();
-        unsafe$0 {
-
-            print!("Index: {:?} \n", index);
+        let x = 1;
+        unsafe {
+            danger();
         }
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn get_uncheckd_mut_2() {
+    fn minimize_unsafe_block_safe_after() {
         check_assist(
-            convert_unsafe_to_safe,
+            minimize_unsafe_block,
             r#"
+    unsafe fn danger() -> i32 { 0 }
+    fn consume(_: i32) {}
     fn main() {
-
-        let mut vec = vec![1,2,3,4,5,6];
-
-        let index;
-    
+        let mut n = 0;
         unsafe$0 {
-            index = vec.get_unchecked_mut(5);    
-        }
-        print!("Index: {:?} \n", index);
+            n = danger();
+            consume(n);
+        };
+        consume(n);
     }
     "#,
-                r#"
+            r#"
+    unsafe fn danger() -> i32 { 0 }
+    fn consume(_: i32) {}
     fn main() {
-
-        let mut vec = vec![1,2,3,4,5,6];
-
-        let index;
-
-        index = vec.get_mut(5).unwrap();
-        print!("Index: {:?} \n", index);
+        let mut n = 0;
+        unsafe {
+            n = danger();
+        };
+        consume(n);
+        consume(n);
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn get_uncheckd_mut_3() {
+    fn minimize_unsafe_block_interleaved() {
         check_assist(
-            convert_unsafe_to_safe,
+            minimize_unsafe_block,
             r#"
+    unsafe fn danger() {}
+    fn log() {}
     fn main() {
-
-        let mut vec = vec![1,2,3,4,5,6];
-    
         unsafe$0 {
-            let index = vec.get_unchecked_mut(5);    
+            log();
+            // SAFETY: nothing to uphold
+            danger();
+            log();
+            danger();
+            log();
         }
-        print!("Index: {:?} \n", index);
     }
     "#,
-                r#"
+            r#"
+    unsafe fn danger() {}
+    fn log() {}
     fn main() {
-
-        let mut vec = vec![1,2,3,4,5,6];
-
-        let index = vec.get_mut(5).unwrap();
-        print!("Index: {:?} \n", index);
+        log();
+        unsafe {
+            // SAFETY: nothing to uphold
+            danger();
+            log();
+            danger();
+        }
+        log();
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn convert_ptr_copy_1() {
-        check_assist(
-            convert_unsafe_to_safe,
+    fn minimize_unsafe_block_binding_used_after_core() {
+        check_assist_not_applicable(
+            minimize_unsafe_block,
             r#"
+    unsafe fn danger() -> i32 { 0 }
+    fn consume(_: i32) {}
     fn main() {
-
-        let mut vec = vec![1,2,3,4,5,6];
-
-        let src = vec[0..].as_mut_ptr();
-
-        let dst = vec[2..].as_mut_ptr();
-    
-        unsafe$0 {
-            ptr::copy(src, dst, 4);
-            println!("Hello World!");
-        }
-        let mut n = 1;
-    }
-    "#,
-                r#"
-    fn main() {
-
-        let mut vec = vec![1,2,3,4,5,6];
-
-        let src = vec[0..].as_mut_ptr();
-
-        let dst = vec[2..].as_mut_ptr();
-
-        vec.copy_within(0..4, 2);
-
         unsafe$0 {
-            
-            println!("Hello World!");
+            let n = danger();
+            consume(n);
         }
-
-        let mut n = 1;
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn convert_ptr_copy_2() {
+    fn add_safety_comment_two_unsafe_calls() {
         check_assist(
-            convert_unsafe_to_safe,
+            add_safety_comment,
             r#"
+    unsafe fn open_port() {}
+    unsafe fn close_port() {}
     fn main() {
-
-        let mut vec = vec![1,2,3,4,5,6];
-    
+        let x = 1;
         unsafe$0 {
-            ptr::copy(&vec[0] as *const i32, &mut vec[2] as *mut i32, 4);
-            println!("Hello World!");
+            open_port();
+            close_port();
         }
-
-        let mut n = 1;
     }
     "#,
-                r#"
+            r#"
+    unsafe fn open_port() {}
+    unsafe fn close_port() {}
     fn main() {
+        let x = 1;
+        // SAFETY: <describe why each of the following operations is sound>
+        // - call to `open_port`
+        // - call to `close_port`
+        unsafe {
+            open_port();
+            close_port();
+        }
+    }
+    "#,
+        );
+    }
 
-        let mut vec = vec![1,2,3,4,5,6];
-        vec.copy_within(0..4, 2);
-
+    #[test]
+    fn add_safety_comment_existing() {
+        check_assist_not_applicable(
+            add_safety_comment,
+            r#"
+    unsafe fn open_port() {}
+    fn main() {
+        // SAFETY: the port is owned by this process
         unsafe$0 {
-            
-            println!("Hello World!");
+            open_port();
         }
-    
-        let mut n = 1;
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn convert_ptr_copy_3() {
+    fn ptr_as_ref_from_reference() {
         check_assist(
             convert_unsafe_to_safe,
             r#"
     fn main() {
-
-        let mut vec = vec![1,2,3,4,5,6];
-
-        let dst = vec.len() + heap.size();
-    
-        unsafe$0 {
-            ptr::copy(vec[0..].as_mut_ptr(), vec[3..].as_mut_ptr(), dst);
-        }
+        let x = 5;
+        let p = &x as *const i32;
+        let r = unsafe$0 { p.as_ref() };
     }
     "#,
-                r#"
+            r#"
     fn main() {
-
-        let mut vec = vec![1,2,3,4,5,6];
-
-        let dst = vec.len() + heap.size();
-
-        vec.copy_within(0..dst, 3);
-
+        let x = 5;
+        let p = &x as *const i32;
+        let r = Some(&x);
     }
     "#,
-            );
+        );
     }
-    
+
     #[test]
-    fn convert_vec_1() {
+    fn ptr_as_mut_from_reference() {
         check_assist(
             convert_unsafe_to_safe,
             r#"
     fn main() {
-
-        let cap = 100;
-
-        let mut buffer = Vec::with_capacity(cap);
-
-        unsafe$0 {
-            buffer.set_len(cap); 
-            println!("Hello World!");
-        }
-        println!("Hello World Again!");
+        let mut x = 5;
+        let p = &mut x as *mut i32;
+        let r = unsafe$0 { p.as_mut() };
     }
     "#,
-                r#"
+            r#"
     fn main() {
-
-        let cap = 100;
-
-        let mut buffer = vec![0; cap];
-
-        unsafe$0 {
-            
-            println!("Hello World!");
-        }
-        println!("Hello World Again!");
+        let mut x = 5;
+        let p = &mut x as *mut i32;
+        let r = Some(&mut x);
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn convert_vec_2() {
-        check_assist(
+    fn ptr_as_ref_from_arithmetic() {
+        check_assist_not_applicable(
             convert_unsafe_to_safe,
             r#"
     fn main() {
-
-        let cap = 100;
-
-        let mut buffer = Vec::with_capacity(cap);
-
-        unsafe$0 {
-            buffer.set_len(cap); 
-        }
-        input.read_into(&mut buffer);
-        println!("Hello World Again!");
+        let v = vec![1, 2, 3];
+        let p = v.as_ptr().add(1);
+        let r = unsafe$0 { p.as_ref() };
     }
     "#,
-                r#"
-    fn main() {
-
-        let cap = 100;
+        );
+    }
 
-        let mut buffer = vec![0; cap];
-        
-        input.read_into(&mut buffer);
-        println!("Hello World Again!");
+    #[test]
+    fn ptr_as_ref_array_to_element() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    fn main() {
+        let a = [1u8; 4];
+        let p = &a as *const u8;
+        let r = unsafe$0 { p.as_ref() };
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn convert_vec_3() {
-        check_assist(
+    fn get_unchecked_user_defined_method() {
+        check_assist_not_applicable(
             convert_unsafe_to_safe,
             r#"
+    struct Table;
+    impl Table {
+        unsafe fn get_unchecked(&self, key: u32) -> &u32 { loop {} }
+    }
     fn main() {
-
-        let len = 100;
-
-        let mut buf = Vec::<u32>::with_capacity(len as usize); 
-        unsafe$0 { buf.set_len(len as usize) };
-    
+        let table = Table;
+        let value = unsafe$0 { table.get_unchecked(7) };
     }
     "#,
-                r#"
-    fn main() {
-
-        let len = 100;
+        );
+    }
 
-        let mut buf = vec![0; len as usize];
+    #[test]
+    fn set_len_in_macro_rules() {
+        check_assist_not_applicable(
+            convert_unsafe_to_safe,
+            r#"
+    macro_rules! reserve {
+        ($buffer:ident, $cap:expr) => {
+            let mut $buffer = Vec::with_capacity($cap);
+            unsafe$0 {
+                $buffer.set_len($cap);
+            }
+        };
+    }
+    fn main() {
+        reserve!(buffer, 100);
     }
     "#,
-            );
+        );
     }
 
     #[test]
-    fn convert_vec_4() {
-        check_assist(
+    fn mem_zeroed_in_macro_call() {
+        check_assist_not_applicable(
             convert_unsafe_to_safe,
             r#"
+    macro_rules! id {
+        ($e:expr) => { $e };
+    }
     fn main() {
+        let x: u32 = id!(unsafe$0 { mem::zeroed() });
+    }
+    "#,
+        );
+    }
 
-        let len = 100;
-
-        let mut buf = vec![0; 10];
-        
-        buf.reserve(len); 
+    #[test]
+    fn unsafe_fn_ptr_swap_to_safe_fn() {
+        check_assist(
+            convert_unsafe_fn_to_safe,
+            r#"
+    struct Pair { a: i32, b: i32 }
 
-        unsafe$0 { 
-            buf.set_len(len); 
-        } 
+    unsafe$0 fn swap_fields(p: &mut Pair) {
+        ptr::swap(&mut p.a, &mut p.b);
     }
     "#,
-                r#"
-    fn main() {
+            r#"
+    struct Pair { a: i32, b: i32 }
 
-        let len = 100;
+    fn swap_fields(p: &mut Pair) {
+        mem::swap(&mut p.a, &mut p.b);
+    }
+    "#,
+        );
+    }
 
-        let mut buf = vec![0; 10];
+    #[test]
+    fn unsafe_fn_with_remaining_unsafe_call() {
+        check_assist_not_applicable(
+            convert_unsafe_fn_to_safe,
+            r#"
+    struct Pair { a: i32, b: i32 }
 
-        buf.resize(len, 0);
-        
+    unsafe$0 fn swap_fields(p: &mut Pair) {
+        ptr::swap(&mut p.a, &mut p.b);
+        danger();
     }
     "#,
-            );
+        );
     }
 }
@@ -78,6 +78,7 @@ use syntax::TextRange;
 pub(crate) use crate::assist_context::{AssistContext, Assists};
 
 pub use assist_config::AssistConfig;
+pub use handlers::convert_unsafe_to_safe::{ConversionClass, SafeReplacement, UnsafePattern};
 pub use ide_db::assists::{
     Assist, AssistId, AssistKind, AssistResolveStrategy, GroupLabel, SingleResolve,
 };
@@ -101,6 +102,18 @@ pub fn assists(
     acc.finish()
 }
 
+/// Return every safe replacement the unsafe-to-safe conversion finds in the
+/// `unsafe` block at the given position.
+pub fn safe_replacements(
+    db: &RootDatabase,
+    config: &AssistConfig,
+    range: FileRange,
+) -> Vec<SafeReplacement> {
+    let sema = Semantics::new(db);
+    let ctx = AssistContext::new(sema, config, range);
+    handlers::convert_unsafe_to_safe::collect_safe_replacements(&ctx)
+}
+
 pub mod handlers {
     use crate::{AssistContext, Assists};
 
@@ -223,6 +236,9 @@ pub mod handlers {
             convert_two_arm_bool_match_to_matches_macro::convert_two_arm_bool_match_to_matches_macro,
             convert_while_to_loop::convert_while_to_loop,
             convert_unsafe_to_safe::convert_unsafe_to_safe,
+            convert_unsafe_to_safe::minimize_unsafe_block,
+            convert_unsafe_to_safe::add_safety_comment,
+            convert_unsafe_to_safe::convert_unsafe_fn_to_safe,
             destructure_tuple_binding::destructure_tuple_binding,
             expand_glob_import::expand_glob_import,
             extract_struct_from_enum_variant::extract_struct_from_enum_variant,
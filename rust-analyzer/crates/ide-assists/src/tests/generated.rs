@@ -183,6 +183,31 @@ fn foo() -> i32 { 42i32 }
     )
 }
 
+#[test]
+fn doctest_add_safety_comment() {
+    check_doc_test(
+        "add_safety_comment",
+        r#####"
+unsafe fn danger() {}
+fn main() {
+    unsafe$0 {
+        danger();
+    }
+}
+"#####,
+        r#####"
+unsafe fn danger() {}
+fn main() {
+    // SAFETY: <describe why each of the following operations is sound>
+    // - call to `danger`
+    unsafe {
+        danger();
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_add_turbo_fish() {
     check_doc_test(
@@ -492,6 +517,49 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_convert_unsafe_fn_to_safe() {
+    check_doc_test(
+        "convert_unsafe_fn_to_safe",
+        r#####"
+struct Pair { a: i32, b: i32 }
+unsafe$0 fn swap_fields(p: &mut Pair) {
+    ptr::swap(&mut p.a, &mut p.b);
+}
+"#####,
+        r#####"
+struct Pair { a: i32, b: i32 }
+fn swap_fields(p: &mut Pair) {
+    mem::swap(&mut p.a, &mut p.b);
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_unsafe_to_safe() {
+    check_doc_test(
+        "convert_unsafe_to_safe",
+        r#####"
+fn main() {
+    let mut buffer = Vec::with_capacity(cap);
+    unsafe$0 {
+        buffer.set_len(cap);
+        foo();
+    }
+}
+"#####,
+        r#####"
+fn main() {
+    let mut buffer = vec![0; cap];
+    unsafe {
+        foo();
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_while_to_loop() {
     check_doc_test(
@@ -1547,6 +1615,31 @@ fn handle(action: Action) {
     )
 }
 
+#[test]
+fn doctest_minimize_unsafe_block() {
+    check_doc_test(
+        "minimize_unsafe_block",
+        r#####"
+unsafe fn danger() {}
+fn main() {
+    unsafe$0 {
+        let x = 1;
+        danger();
+    }
+}
+"#####,
+        r#####"
+unsafe fn danger() {}
+fn main() {
+    let x = 1;
+    unsafe {
+        danger();
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_move_arm_cond_to_match_guard() {
     check_doc_test(
@@ -1,7 +1,7 @@
 //! See [`FamousDefs`].
 
 use base_db::{CrateOrigin, LangCrateOrigin, SourceDatabase};
-use hir::{Crate, Enum, Macro, Module, ScopeDef, Semantics, Trait};
+use hir::{Crate, Enum, Macro, Module, ScopeDef, Semantics, Struct, Trait, Union};
 
 use crate::RootDatabase;
 
@@ -102,6 +102,14 @@ impl FamousDefs<'_, '_> {
         self.find_trait("core:marker:Copy")
     }
 
+    pub fn core_mem_MaybeUninit(&self) -> Option<Union> {
+        self.find_union("core:mem:MaybeUninit")
+    }
+
+    pub fn alloc_string_String(&self) -> Option<Struct> {
+        self.find_struct("alloc:string:String")
+    }
+
     pub fn core_macros_builtin_derive(&self) -> Option<Macro> {
         self.find_macro("core:macros:builtin:derive")
     }
@@ -138,6 +146,20 @@ impl FamousDefs<'_, '_> {
         }
     }
 
+    fn find_struct(&self, path: &str) -> Option<Struct> {
+        match self.find_def(path)? {
+            hir::ScopeDef::ModuleDef(hir::ModuleDef::Adt(hir::Adt::Struct(it))) => Some(it),
+            _ => None,
+        }
+    }
+
+    fn find_union(&self, path: &str) -> Option<Union> {
+        match self.find_def(path)? {
+            hir::ScopeDef::ModuleDef(hir::ModuleDef::Adt(hir::Adt::Union(it))) => Some(it),
+            _ => None,
+        }
+    }
+
     fn find_module(&self, path: &str) -> Option<Module> {
         match self.find_def(path)? {
             hir::ScopeDef::ModuleDef(hir::ModuleDef::Module(it)) => Some(it),
@@ -0,0 +1,104 @@
+//! Weak warnings on unsafe code the conversion rewrites without changing what it does.
+
+use ide_assists::{Assist, AssistId, AssistKind, ConversionClass};
+use ide_db::{base_db::FileId, label::Label, source_change::SourceChange, RootDatabase};
+use ide_diagnostics::{Diagnostic, DiagnosticCode, DiagnosticsConfig, Severity};
+use text_edit::TextEdit;
+
+use crate::{convert_export, CONVERSION_CONFIG};
+
+// Diagnostic: unsafe-conversion
+//
+// This diagnostic is shown for unsafe code with a safe replacement that does exactly the same,
+// like `ptr::swap` on two `&mut` borrows. A replacement that can panic or produce another value
+// is left to the assist and the hover.
+pub(crate) fn unsafe_conversion_diagnostics(
+    db: &RootDatabase,
+    config: &DiagnosticsConfig,
+    file_id: FileId,
+) -> Vec<Diagnostic> {
+    if config.disabled.contains("unsafe-conversion") {
+        return Vec::new();
+    }
+
+    convert_export::unsafe_conversions(db, &CONVERSION_CONFIG, file_id)
+        .into_iter()
+        .filter(|replacement| replacement.pattern.class() == ConversionClass::AlwaysEquivalent)
+        .map(|replacement| {
+            let fix = Assist {
+                id: AssistId("convert_unsafe_to_safe", AssistKind::QuickFix),
+                label: Label::new("Convert Unsafe to Safe".to_string()),
+                group: None,
+                target: replacement.range,
+                source_change: Some(SourceChange::from_text_edit(
+                    file_id,
+                    TextEdit::replace(replacement.range, replacement.new_text),
+                )),
+                trigger_signature_help: false,
+            };
+
+            Diagnostic {
+                code: DiagnosticCode("unsafe-conversion"),
+                message: "unsafe code can be converted to safe code".to_string(),
+                range: replacement.range,
+                severity: Severity::WeakWarning,
+                unused: false,
+                experimental: false,
+                fixes: Some(vec![fix]),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixture, AssistResolveStrategy, DiagnosticsConfig};
+
+    fn check_diagnostics(ra_fixture: &str, expected: &[&str]) {
+        let (analysis, file_id) = fixture::file(ra_fixture);
+
+        let diagnostics = analysis
+            .diagnostics(&DiagnosticsConfig::test_sample(), AssistResolveStrategy::All, file_id)
+            .unwrap();
+
+        let file_text = analysis.file_text(file_id).unwrap();
+
+        let actual = diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.code.as_str() == "unsafe-conversion")
+            .map(|diagnostic| file_text[diagnostic.range].trim())
+            .collect::<Vec<_>>();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn ptr_swap_is_reported() {
+        check_diagnostics(
+            r#"
+fn main() {
+    let mut x = 1;
+    let mut y = 2;
+    unsafe {
+        ptr::swap(&mut x, &mut y);
+    }
+}
+"#,
+            &["unsafe {\n        ptr::swap(&mut x, &mut y);\n    }"],
+        );
+    }
+
+    #[test]
+    fn get_unchecked_is_not_reported() {
+        check_diagnostics(
+            r#"
+//- minicore: slice
+fn main() {
+    let v = [1, 2, 3];
+    let x = unsafe { v.get_unchecked(1) };
+}
+"#,
+            &[],
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! Machine-readable export of the unsafe blocks the conversion can rewrite.
+
+use ide_assists::{AssistConfig, SafeReplacement};
+use ide_db::{
+    base_db::{FileId, FileRange, SourceDatabase},
+    RootDatabase,
+};
+use syntax::{ast, AstNode, TextRange};
+
+// Feature: Export Unsafe Conversions
+//
+// Lists the safe replacement of every convertible unsafe block of a file, so a CI job
+// can fail when new convertible unsafe code shows up. The `rust-analyzer unsafe-conversions`
+// command prints them as JSON.
+pub(crate) fn unsafe_conversions(
+    db: &RootDatabase,
+    config: &AssistConfig,
+    file_id: FileId,
+) -> Vec<SafeReplacement> {
+    let source_file = db.parse(file_id).tree();
+
+    // The same filters and edits as the assist on each "unsafe" keyword
+    source_file
+        .syntax()
+        .descendants()
+        .filter_map(ast::BlockExpr::cast)
+        .filter_map(|block| block.unsafe_token())
+        .flat_map(|unsafe_kw| {
+            let frange =
+                FileRange { file_id, range: TextRange::empty(unsafe_kw.text_range().start()) };
+
+            ide_assists::safe_replacements(db, config, frange)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use stdx::trim_indent;
+
+    use crate::{Analysis, AssistConfig, CONVERSION_CONFIG};
+
+    fn check_export(ra_fixture: &str, expected: &[(&str, &str)]) {
+        let (analysis, file_id) = Analysis::from_single_file(trim_indent(ra_fixture));
+
+        let conversions = analysis.unsafe_conversions(&CONVERSION_CONFIG, file_id).unwrap();
+
+        let actual = conversions
+            .iter()
+            .map(|replacement| (replacement.pattern.as_str(), replacement.new_text.trim()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn export_set_len() {
+        check_export(
+            r#"
+    fn main() {
+        let mut buffer = Vec::with_capacity(100);
+        unsafe {
+            buffer.set_len(100);
+        }
+        input.read_into(&mut buffer);
+    }
+    "#,
+            &[("uninitialized_vec", "let mut buffer = vec![0; 100];")],
+        );
+    }
+
+    #[test]
+    fn export_no_pattern() {
+        check_export(
+            r#"
+    fn main() {
+        unsafe {
+            foo();
+        }
+    }
+    "#,
+            &[],
+        );
+    }
+
+    #[test]
+    fn export_keep_unsafe() {
+        check_export(
+            r#"
+    fn main() {
+        let mut buffer = Vec::with_capacity(100);
+        #[rust_analyzer::keep_unsafe]
+        unsafe {
+            buffer.set_len(100);
+        }
+    }
+    "#,
+            &[],
+        );
+    }
+
+    #[test]
+    fn export_disabled_pattern() {
+        let (analysis, file_id) = Analysis::from_single_file(trim_indent(
+            r#"
+    fn main() {
+        let mut buffer = Vec::with_capacity(100);
+        unsafe {
+            buffer.set_len(100);
+        }
+    }
+    "#,
+        ));
+
+        let config = AssistConfig {
+            disabled_unsafe_patterns: Some(["uninitialized_vec".to_string()].into_iter().collect()),
+            ..CONVERSION_CONFIG
+        };
+
+        assert!(analysis.unsafe_conversions(&config, file_id).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,161 @@
+//! Dry-run preview of the unsafe-to-safe conversion.
+
+use ide_assists::{AssistConfig, AssistResolveStrategy};
+use ide_db::{
+    base_db::FileRange,
+    imports::insert_use::{ImportGranularity, InsertUseConfig},
+};
+use syntax::{TextRange, TextSize};
+
+use crate::Analysis;
+
+/// Assist settings for running the conversion outside an editor, without snippets.
+pub const CONVERSION_CONFIG: AssistConfig = AssistConfig {
+    snippet_cap: None,
+    allowed: None,
+    insert_use: InsertUseConfig {
+        granularity: ImportGranularity::Crate,
+        prefix_kind: hir::PrefixKind::Plain,
+        enforce_granularity: true,
+        group: true,
+        skip_glob_imports: true,
+    },
+    prefer_no_std: false,
+    suggest_safe_wrappers: false,
+    disabled_unsafe_patterns: None,
+    annotate_risky_conversions: false,
+};
+
+// Feature: Preview Unsafe Conversion
+//
+// Runs the unsafe-to-safe conversion on a standalone file without going through
+// the LSP edit cycle, and returns what the file would look like afterwards.
+pub fn preview_conversion(file_text: &str, offset: TextSize) -> Option<String> {
+    let (analysis, file_id) = Analysis::from_single_file(file_text.to_string());
+
+    let frange = FileRange { file_id, range: TextRange::empty(offset) };
+
+    let assists = analysis
+        .with_db(|db| {
+            ide_assists::assists(db, &CONVERSION_CONFIG, AssistResolveStrategy::All, frange)
+        })
+        .ok()?;
+
+    // The loop rewrites and the "if let" scaffold have ids of their own, only the conversion is shown
+    let assist = assists.into_iter().find(|assist| assist.id.0 == "convert_unsafe_to_safe")?;
+
+    let mut text = file_text.to_string();
+
+    if let Some(edit) = assist.source_change?.get_source_edit(file_id) {
+        edit.apply(&mut text);
+    }
+
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use stdx::trim_indent;
+    use test_utils::{assert_eq_text, extract_offset};
+
+    use super::*;
+
+    fn check_preview(ra_fixture_before: &str, ra_fixture_after: &str) {
+        let (offset, before) = extract_offset(&trim_indent(ra_fixture_before));
+        let after = trim_indent(ra_fixture_after);
+
+        let actual = preview_conversion(&before, offset).expect("no conversion at the offset");
+        assert_eq_text!(&after, &actual);
+    }
+
+    #[test]
+    fn preview_set_len() {
+        check_preview(
+            r#"
+    fn main() {
+
+        let cap = 100;
+
+        let mut buffer = Vec::with_capacity(cap);
+
+        unsafe$0 {
+            buffer.set_len(cap);
+        }
+        input.read_into(&mut buffer);
+        println!("Hello World Again!");
+    }
+    "#,
+            r#"
+    fn main() {
+
+        let cap = 100;
+
+        let mut buffer = vec![0; cap];
+        input.read_into(&mut buffer);
+        println!("Hello World Again!");
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn preview_copy() {
+        check_preview(
+            r#"
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        unsafe$0 {
+            ptr::copy(vec[0..].as_mut_ptr(), vec[3..].as_mut_ptr(), 2);
+        }
+        println!("{:?}", vec);
+    }
+    "#,
+            r#"
+    fn main() {
+        let mut vec = vec![1,2,3,4,5,6];
+        vec.copy_within(0..2, 3);
+        println!("{:?}", vec);
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn preview_skips_if_let_scaffold() {
+        check_preview(
+            r#"
+    impl<T> [T] {
+        unsafe fn get_unchecked(&self, index: usize) -> &T { loop {} }
+    }
+    fn main() {
+        let v = [1, 2, 3];
+        let x = unsafe$0 { v.get_unchecked(1) };
+    }
+    "#,
+            r#"
+    impl<T> [T] {
+        unsafe fn get_unchecked(&self, index: usize) -> &T { loop {} }
+    }
+    fn main() {
+        let v = [1, 2, 3];
+        let x = v.get(1).unwrap();
+    }
+    "#,
+        );
+    }
+
+    #[test]
+    fn preview_no_pattern() {
+        let (offset, before) = extract_offset(&trim_indent(
+            r#"
+    fn main() {
+        unsafe$0 {
+            foo();
+        }
+    }
+    "#,
+        ));
+
+        assert_eq!(preview_conversion(&before, offset), None);
+    }
+}
@@ -12,7 +12,7 @@ use ide_db::{
     defs::{Definition, IdentClass, OperatorClass},
     famous_defs::FamousDefs,
     helpers::pick_best_token,
-    FxIndexSet, RootDatabase,
+    FxHashSet, FxIndexSet, RootDatabase,
 };
 use itertools::Itertools;
 use syntax::{ast, match_ast, AstNode, SyntaxKind::*, SyntaxNode, SyntaxToken, T};
@@ -28,6 +28,9 @@ pub struct HoverConfig {
     pub links_in_hover: bool,
     pub documentation: Option<HoverDocFormat>,
     pub keywords: bool,
+    pub show_unsafe_suggestions: bool,
+    pub safe_wrappers: bool,
+    pub disabled_unsafe_patterns: FxHashSet<String>,
 }
 
 impl HoverConfig {
@@ -48,6 +51,11 @@ pub enum HoverAction {
     Implementation(FilePosition),
     Reference(FilePosition),
     GoToType(Vec<HoverGotoTypeData>),
+    /// Runs the assist with this id on the range, for the unsafe blocks with a safe version.
+    ApplyAssist {
+        assist_id: String,
+        range: FileRange,
+    },
 }
 
 impl HoverAction {
@@ -173,6 +181,12 @@ pub(crate) fn hover(
         }
     }
     result.map(|mut res: HoverResult| {
+        // An unsafe call the conversion knows gets its suggestion below the function's docs
+        if let Some(suggestion) =
+            descended.iter().find_map(|token| render::unsafe_call_suggestion(sema, config, token))
+        {
+            res.markup = Markup::from(format!("{}\n---\n{}", res.markup, suggestion));
+        }
         res.actions = dedupe_or_merge_hover_actions(res.actions);
         RangeInfo::new(original_token.text_range(), res)
     })
@@ -369,6 +383,7 @@ fn dedupe_or_merge_hover_actions(actions: Vec<HoverAction>) -> Vec<HoverAction>
     let mut seen_implementation = false;
     let mut seen_reference = false;
     let mut seen_runnable = false;
+    let mut seen_apply_assist = false;
     for action in actions {
         match action {
             HoverAction::GoToType(targets) => {
@@ -392,6 +407,12 @@ fn dedupe_or_merge_hover_actions(actions: Vec<HoverAction>) -> Vec<HoverAction>
                     deduped_actions.push(action);
                 }
             }
+            HoverAction::ApplyAssist { .. } => {
+                if !seen_apply_assist {
+                    seen_apply_assist = true;
+                    deduped_actions.push(action);
+                }
+            }
         };
     }
 
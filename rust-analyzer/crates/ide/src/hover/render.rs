@@ -4,7 +4,7 @@ use std::fmt::Display;
 use either::Either;
 use hir::{AsAssocItem, AttributeTemplate, HasAttrs, HasSource, HirDisplay, Semantics, TypeInfo};
 use ide_db::{
-    base_db::SourceDatabase,
+    base_db::{FileRange, SourceDatabase},
     defs::Definition,
     famous_defs::FamousDefs,
     generated::lints::{CLIPPY_LINTS, DEFAULT_LINTS, FEATURES},
@@ -13,26 +13,29 @@ use ide_db::{
 };
 
 use ide_assists::{
-    handlers::convert_unsafe_to_safe::{UnsafePattern, generate_safevec_format, generate_resizevec_format, 
-        generate_copywithin_format, generate_let_get_mut, generate_get_mut, generate_copy_from_slice_format, check_convert_type, 
-        generate_cstring_new_format, generate_bytes_len_format, generate_from_utf8, generate_let_from_utf8, generate_from_transmute,
-        generate_bytes_to_convert, generate_from_u32, generate_let_from_u32, generate_from_utf8_expr_stmt, generate_get_mut_expr,
-        generate_from_u32_expr_stmt, generate_get_prefix_mut_expr}
+    handlers::convert_unsafe_to_safe::{
+        self, check_keep_unsafe_attr, check_macro_origin, check_malformed_block,
+        check_manually_drop, check_static_mut_access, classify_unsafe_operation,
+        collect_unchecked_mut_pair, UnsafePattern,
+    },
+    AssistConfig, SafeReplacement,
 };
 
 use itertools::Itertools;
 use stdx::format_to;
 use syntax::{
-    algo, ast::{self, MethodCallExpr, CallExpr, BlockExpr}, match_ast, AstNode, Direction,
-    SyntaxKind::{LET_EXPR, LET_STMT, UNSAFE_KW, STMT_LIST, BIN_EXPR, EXPR_STMT, PREFIX_EXPR},
-    SyntaxToken, T, SyntaxNode,
+    algo,
+    ast::{self, BlockExpr, CallExpr, HasArgList, HasName},
+    match_ast, AstNode, Direction,
+    SyntaxKind::{LET_EXPR, LET_STMT, STMT_LIST, UNSAFE_KW},
+    SyntaxNode, SyntaxToken, TextRange, T,
 };
 
 use crate::{
     doc_links::{remove_links, rewrite_links},
     hover::walk_and_push_ty,
     markdown_remove::remove_markdown,
-    HoverAction, HoverConfig, HoverResult, Markup,
+    HoverAction, HoverConfig, HoverResult, Markup, CONVERSION_CONFIG,
 };
 
 pub(super) fn type_info(
@@ -69,12 +72,10 @@ pub(super) fn type_info(
             bt_end = if config.markdown() { "```\n" } else { "" }
         )
         .into()
+    } else if config.markdown() {
+        Markup::fenced_block(&original.display(sema.db))
     } else {
-        if config.markdown() {
-            Markup::fenced_block(&original.display(sema.db))
-        } else {
-            original.display(sema.db).to_string().into()
-        }
+        original.display(sema.db).to_string().into()
     };
     res.actions.push(HoverAction::goto_type_from_targets(sema.db, targets));
     Some(res)
@@ -155,7 +156,7 @@ pub(super) fn try_expr(
     let l = "Propagated as: ".len() - " Type: ".len();
     let static_text_len_diff = l as isize - s.len() as isize;
     let tpad = static_text_len_diff.max(0) as usize;
-    let ppad = static_text_len_diff.min(0).abs() as usize;
+    let ppad = static_text_len_diff.min(0).unsigned_abs();
 
     res.markup = format!(
         "{bt_start}{} Type: {:>pad0$}\nPropagated as: {:>pad1$}\n{bt_end}",
@@ -235,646 +236,651 @@ pub(super) fn deref_expr(
     Some(res)
 }
 
-fn generate_description() -> String{
-
+fn generate_description() -> String {
     return "Code Suggestion: translating unsafe to safe code".to_string();
 }
 
-fn generate_original() -> String{
-
-    return "Original Code: \n\n".to_string();
+fn backtick_run(text: &str) -> usize {
+    return text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
 }
 
-fn generate_modify() -> String{
-
-    return "Modified Code: \n\n".to_string();
+fn code_fence(code: &str) -> String {
+    // Like rustdoc, the fence is longer than any backtick run in the code, so the code can't close it
+    return "`".repeat(std::cmp::max(3, backtick_run(code) + 1));
 }
 
-fn format_suggestion_unitialized_vec(mcall: MethodCallExpr, unsafe_expr: &BlockExpr) -> Option<String> {
-
-    let mut us_docs = String::new();
-
-    let original = generate_original();
-
-    us_docs.push_str(&original);
-
-    let mut safe_vec = String::new();
-
-    let mut backward_list = unsafe_expr.syntax().siblings(Direction::Prev);
-
-    if unsafe_expr.syntax().parent()?.kind() != STMT_LIST {
-        backward_list = unsafe_expr.syntax().parent()?.siblings(Direction::Prev);
-    }
-
-
-    for iter in backward_list {
+fn render_replacement_diff(file_text: &str, replacement: &SafeReplacement) -> String {
+    let start = usize::from(replacement.range.start());
+    let end = usize::from(replacement.range.end());
 
-        if iter.to_string().contains(&UnsafePattern::SetVecCapacity.to_string()) && iter.to_string().contains(&mcall.receiver()?.to_string()) {
+    // The rewrite is shown on whole lines, along with what shares them before and after it
+    let line_start = file_text[..start].rfind('\n').map_or(0, |it| it + 1);
+    let line_end = file_text[end..].find('\n').map_or(file_text.len(), |it| end + it);
 
-            let let_expr = ast::LetStmt::cast(iter)?;
+    let modified = format!(
+        "{}{}{}",
+        &file_text[line_start..start],
+        replacement.new_text,
+        &file_text[end..line_end]
+    );
 
-            format_to!(us_docs, "**```---```** **~~```{}```~~**", let_expr.to_string());
-            // format_to!(us_docs, "```---``` ~~```      {}```~~", let_expr.to_string());
+    let original_lines = file_text[line_start..line_end].lines().collect::<Vec<_>>();
+    let modified_lines = modified.lines().collect::<Vec<_>>();
 
-            us_docs.push('\n');
-            us_docs.push('\n');
+    // A hoisted statement keeps the lines between it and the block, only the changed ones are listed
+    let prefix =
+        original_lines.iter().zip(&modified_lines).take_while(|(old, new)| old == new).count();
 
-            format_to!(safe_vec, "**```+++```** **```{} [Runtime Overhead: -0.05%]```**", generate_safevec_format(&mcall)?.to_string());
-            
-            break;
-        }
+    let suffix = original_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(modified_lines[prefix..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+
+    let removed = &original_lines[prefix..original_lines.len() - suffix];
+    let added = &modified_lines[prefix..modified_lines.len() - suffix];
+
+    // Both sides are indented relative to the first removed line
+    let indent = removed.first().map_or("", |line| &line[..line.len() - line.trim_start().len()]);
+
+    let dedent = |lines: &[&str]| {
+        lines
+            .iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.strip_prefix(indent).unwrap_or_else(|| line.trim_start()).to_string())
+            .collect::<Vec<_>>()
+    };
 
-        if iter.to_string().contains(&UnsafePattern::ReserveVec.to_string()) && iter.to_string().contains(&mcall.receiver()?.to_string()) {
+    let (removed, added) = (dedent(removed), dedent(added));
 
-            let expr_stmt = ast::ExprStmt::cast(iter)?;
+    let line = file_text[..line_start].matches('\n').count() + prefix + 1;
 
-            format_to!(us_docs, "**```---```** **~~```{}```~~**", expr_stmt.to_string());
-            // format_to!(us_docs, "```---``` ~~```      {}```~~", let_expr.to_string());
+    let fence = code_fence(&format!("{}\n{}", removed.join("\n"), added.join("\n")));
 
-            us_docs.push('\n');
-            us_docs.push('\n');
+    let mut us_docs = String::new();
 
-            format_to!(safe_vec, "**```+++```** **```{} [Runtime Overhead: 12.01%]```**", generate_resizevec_format(&mcall)?.to_string());
+    format_to!(us_docs, "{}diff\n", fence);
+    format_to!(us_docs, "@@ -{},{} +{},{} @@\n", line, removed.len(), line, added.len());
 
-            break;
-        }
+    for line in removed {
+        format_to!(us_docs, "-{}\n", line);
     }
 
-    us_docs.push('\n');
-    us_docs.push('\n');
-
-    let mut unsafe_vec = String::new();
-    // format_to!(unsafe_vec, "```---``` ~~```      unsafe {{ {} }};```~~", mcall.to_string());
-    format_to!(unsafe_vec, "**```---```** **~~```unsafe {{ {} }};```~~**", mcall.to_string());
-    us_docs.push_str(&unsafe_vec);
-
-    us_docs.push('\n');
-    us_docs.push('\n');
-
-    let modify = generate_modify();
-
-    us_docs.push_str(&modify);
-
-    us_docs.push_str(&safe_vec);
-
-    return Some(us_docs.to_string());
+    for line in added {
+        format_to!(us_docs, "+{}\n", line);
+    }
 
+    us_docs.push_str(&fence);
 
+    return us_docs;
 }
 
-fn display_suggestion_uninitialized_vec(target_expr: &SyntaxNode, unsafe_expr: &BlockExpr, actions: &Vec<HoverAction>) -> Option<HoverResult> {
-
-    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
-
-    let us_description = generate_description();
-
-    let us_docs = format_suggestion_unitialized_vec(mcall, &unsafe_expr)?;
-
-    let markup = process_unsafe_display_text(
-        &markup(Some(us_docs), us_description, None)?,
-    );
-
-    return Some(HoverResult { markup, actions: actions.to_vec() });
+fn pattern_overhead(replacement: &SafeReplacement) -> Option<&'static str> {
+    // Measured per rewrite, a reserved vector is grown with "resize" and a preallocated one becomes "vec!"
+    let overhead = match replacement.pattern {
+        UnsafePattern::UnitializedVec if replacement.new_text.contains(".resize(") => "12.01%",
+        UnsafePattern::UnitializedVec if replacement.new_text.contains("vec![") => "-0.05%",
+        UnsafePattern::CopyWithin => "-26.62%",
+        UnsafePattern::GetUncheck | UnsafePattern::GetUncheckMut => "7.58%",
+        UnsafePattern::CopyNonOverlap => "0.19%",
+        UnsafePattern::CStringFromVec => "3.27x",
+        UnsafePattern::CStringLength => "-2.81%",
+        UnsafePattern::BytesToUTFString
+        | UnsafePattern::BytesToUTFStringMut
+        | UnsafePattern::StringBytesToUTFString => "1.1x",
+        UnsafePattern::FromU32Unchecked => "3.8x",
+        _ => return None,
+    };
 
+    return Some(overhead);
 }
 
-fn format_suggestion_ptr_copy(mcall: &CallExpr, unsafe_expr: &BlockExpr) -> Option<String> {
-
-    let mut us_docs = String::new();
-
-    let original = generate_original();
-
-    us_docs.push_str(&original);
-
-    format_to!(us_docs, "**```---```** **~~```unsafe {{ {} }};```~~**", mcall.to_string());
-
-    us_docs.push('\n');
-    us_docs.push('\n');
-
-    let mut safe_version = String::new();
-
-    format_to!(safe_version, "**```+++```** **```{} [Runtime Overhead: -26.62%]```**", generate_copywithin_format(&mcall, &unsafe_expr)?);
-
-    let modify = generate_modify();
-
-    us_docs.push_str(&modify);
-
-    us_docs.push_str(&safe_version);
-
-    return Some(us_docs.to_string());
+fn pattern_note(pattern: UnsafePattern) -> Option<&'static str> {
+    // What the diff alone doesn't tell, a caveat of the safe API or of the code around it
+    let note = match pattern {
+        UnsafePattern::StrGetUnchecked => "besides the bounds, a `str` range has to start and end on `char` boundaries. `str::get` returns `None` and slicing panics where `get_unchecked` was undefined behavior.",
+        UnsafePattern::WriteUnaligned => "`to_ne_bytes` keeps the native byte order of `write_unaligned`. Use `to_le_bytes` or `to_be_bytes` instead when the buffer has a fixed layout.",
+        UnsafePattern::MemUninitialized => "`mem::uninitialized` is deprecated and undefined behavior for almost every type. Declare the value as `MaybeUninit<T>` and initialize it before calling `assume_init()`.",
+        UnsafePattern::SwapUnchecked => "`swap_unchecked` is unstable and requires `#![feature(slice_swap_unchecked)]` on nightly, while `swap` is stable and only adds a bounds check.",
+        UnsafePattern::PtrSwap => "two `&mut` borrows can never overlap, so `mem::swap` gives the same result without the raw pointer contract of `ptr::swap`.",
+        UnsafePattern::PtrReplace => "the destination is a `&mut` place, so `mem::replace` moves the new value in and returns the old one without the raw pointer contract of `ptr::replace`.",
+        UnsafePattern::TransmuteFloatBits => "`from_bits` and `to_bits` reinterpret the bits between a float and the unsigned integer of its width.",
+        UnsafePattern::TransmuteEnumDiscriminant => "a field-less enum casts with `as` to the integer its `#[repr]` names, which gives the same discriminant the transmute reads.",
+        UnsafePattern::TransmuteIntToEnum => "the conversion generates the `TryFrom` impl when every variant has an explicit literal discriminant, otherwise it needs one written by hand or a `match` over the discriminants.",
+        UnsafePattern::StringAsMutVec => "the pushed bytes are already valid UTF-8, so the `String` methods do the same without touching the underlying `Vec<u8>`.",
+        UnsafePattern::PinNewUnchecked => "a fresh `Box` is never moved out of once it is pinned, which is exactly what `Box::pin` guarantees without `unsafe`.",
+        UnsafePattern::PtrOffsetFrom => "both pointers are offsets into the same buffer, so their distance is the difference of the indices and no pointer arithmetic is needed.",
+        UnsafePattern::PtrAsRef | UnsafePattern::PtrAsMut => "the pointer was made from a reference in this function, so it is never null and that reference can be used directly.",
+        UnsafePattern::StrBytesGetUnchecked => "`as_bytes()` is a plain `&[u8]`, so any index is fine byte-wise and only the bounds are checked.",
+        UnsafePattern::AllocToVec => "the pointer is only written and read at in-bounds offsets and freed with the layout it was allocated with, so a `Vec` can own the buffer and free it when it goes out of scope.",
+        UnsafePattern::FloatToIntUnchecked => "since Rust 1.45 float to int `as` casts saturate at the target bounds and map `NaN` to `0`, so the cast is defined wherever `to_int_unchecked` is and also where it is not.",
+        UnsafePattern::StaticMutAtomic => "every use of the static in this file becomes an atomic operation with `Ordering::Relaxed`, which is enough for counters and flags. Use `Acquire`/`Release` or `SeqCst` instead when the static guards access to other data.",
+        UnsafePattern::PtrReadDefault => "`mem::take` leaves `Default::default()` behind in the field rather than logically moving the value out, so the original is still dropped normally.",
+        UnsafePattern::DropInPlace => "the local is not used after the `unsafe` block, so moving it into `drop` runs the destructor exactly once without going through a raw pointer.",
+        UnsafePattern::RawPtrIndexDeref => "indexing is bounds checked, so an index past the end panics instead of reading or writing outside the buffer.",
+        UnsafePattern::LibcMemcpy | UnsafePattern::LibcMemmove => "the byte count is turned into an element count, and the slice copy panics on an out-of-bounds range instead of writing past the buffer.",
+        UnsafePattern::LibcMemset => "`fill` writes through the slice itself, so a length past the end of the buffer panics instead of corrupting memory.",
+        _ => return None,
+    };
 
+    return Some(note);
 }
 
-fn display_suggestion_ptr_copy(target_expr: &SyntaxNode, unsafe_expr: &BlockExpr, actions: &Vec<HoverAction>) -> Option<HoverResult> {
-
-    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
-
-    let us_description = generate_description();
-
-    let us_docs = format_suggestion_ptr_copy(&mcall, &unsafe_expr)?;
-
-    let markup = process_unsafe_display_text(
-        &markup(Some(us_docs), us_description, None)?,
-    );
-
-    return Some(HoverResult { markup, actions: actions.to_vec() });
+fn pattern_guidance(pattern: UnsafePattern) -> Option<String> {
+    // The patterns that are found without a rewrite, the user is told what to do by hand
+    let guidance = match pattern {
+        // A type without a zero value has no rewrite, the note on it still applies
+        UnsafePattern::MemUninitialized => {
+            return pattern_note(pattern).map(|note| format!("**Note:** {}", note))
+        }
+        UnsafePattern::TransmuteLifetime => "**Warning:** this `transmute` only changes the lifetime of the reference, which has no safe equivalent. The borrow checker can no longer ensure that the referent outlives its uses, so the extended reference may dangle (use-after-free).\n\nConsider owning the data instead (e.g. `to_owned()` / `clone()`), or sharing it through `Arc`/`Rc` so that it lives as long as it is needed.",
+        UnsafePattern::StringAsMutVec => "**Warning:** writing through `as_mut_vec` must leave the `String` valid UTF-8, which the compiler can't check for arbitrary bytes. Consider `s.push_str(str::from_utf8(bytes)?)` to validate the bytes first, or `push`/`push_str` when the data is already text.",
+        UnsafePattern::GetUncheckMutPair => "**Note:** both references point into the same slice, so two `get_mut` calls won't pass the borrow checker. Split it with `split_at_mut` at the larger index instead and use the references inside a branch for each order, the indices must differ.",
+        UnsafePattern::AsBytesMut => "**Note:** the string has to hold valid UTF-8 again before it is used as a `str`. Prefer `make_ascii_uppercase`/`make_ascii_lowercase` on the `str`, or edit a `Vec<u8>` copy and turn it back with `String::from_utf8`.",
+        UnsafePattern::SafeWrapperCall => "**Note:** a safe wrapper is defined next to this `unsafe fn`, but its signature differs, so the call has to be adapted by hand.",
+        UnsafePattern::StaticMutAccess => "**Note:** this is a `static mut`, every access needs `unsafe` and nothing prevents data races on it. Consider `OnceLock` for a value that is initialized once, or a `Mutex` for a value that is updated in place.",
+        UnsafePattern::ManuallyDropDrop => "**Note:** the value is wrapped in `ManuallyDrop` only to drop it by hand, nothing moves it out in between. The wrapper may be unnecessary: keep it as a plain owned value and call `drop` here, or let it drop at the end of its scope.",
+        UnsafePattern::BoxLeakRoundTrip => "**Note:** the box is leaked with `Box::leak` and rebuilt with `Box::from_raw` in the same function. Keep it as a `Box` instead and borrow it with `&mut *` where the reference is needed, so nothing is leaked if the function returns early.",
+        _ => return None,
+    };
 
+    return Some(guidance.to_string());
 }
 
-fn format_suggestion_get_uncheck_mut(mcall: MethodCallExpr) -> Option<String> {
-
-    let mut us_docs = String::new();
-
-    if mcall.syntax().parent()?.kind() == PREFIX_EXPR {
-
-        let target_expr = &mcall;
-
-        format_to!(us_docs, "**```---```** **~~```unsafe {{ {} }};```~~**", target_expr.to_string());
-
-        us_docs.push('\n');
-        us_docs.push('\n');
-
-        let mut safe_version = String::new();
-
-        format_to!(safe_version, "**```+++```** **```{} [Runtime Overhead: 7.58%]```**", generate_get_prefix_mut_expr(&mcall)?);
-
-        us_docs.push_str(&safe_version);
-
-        return Some(us_docs.to_string());
-    }
-
-    if mcall.syntax().parent()?.kind() == STMT_LIST{
-
-        let target_expr = &mcall;
-
-        format_to!(us_docs, "**```---```** **~~```unsafe {{ {} }};```~~**", target_expr.to_string());
-
-        us_docs.push('\n');
-        us_docs.push('\n');
-
-        let mut safe_version = String::new();
-
-        format_to!(safe_version, "**```+++```** **```{} [Runtime Overhead: 7.58%]```**", generate_get_mut_expr(&mcall)?);
-
-        us_docs.push_str(&safe_version);
-
-        return Some(us_docs.to_string());
-    }
-
-    if mcall.syntax().parent()?.kind() == BIN_EXPR {
-
-        let target_expr = mcall.syntax().parent().and_then(ast::BinExpr::cast)?;
-
-        format_to!(us_docs, "**```---```** **~~```unsafe {{ {} }};```~~**", target_expr.to_string());
-
-        us_docs.push('\n');
-        us_docs.push('\n');
-
-        let mut safe_version = String::new();
-
-        format_to!(safe_version, "**```+++```** **```{} [Runtime Overhead: 7.58%]```**", generate_get_mut(&mcall, &target_expr)?);
-
-        us_docs.push_str(&safe_version);
-
-        return Some(us_docs.to_string());
+fn collect_ptr_base(expr: ast::Expr) -> ast::Expr {
+    let mut expr = expr;
+
+    // "vec[0..].as_mut_ptr()" and "v.as_ptr().add(i)" both point into the collection they start from
+    loop {
+        let inner = match &expr {
+            ast::Expr::MethodCallExpr(mcall) => mcall.receiver(),
+            ast::Expr::IndexExpr(index_expr) => index_expr.base(),
+            ast::Expr::RefExpr(ref_expr) => ref_expr.expr(),
+            ast::Expr::ParenExpr(paren_expr) => paren_expr.expr(),
+            _ => None,
+        };
 
+        match inner {
+            Some(inner) => expr = inner,
+            None => return expr,
+        }
     }
-
-    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
-
-    format_to!(us_docs, "**```---```** **~~```unsafe {{ {} }};```~~**", let_expr.to_string());
-
-    us_docs.push('\n');
-    us_docs.push('\n');
-
-    let mut safe_version = String::new();
-
-    format_to!(safe_version, "**```+++```** **```{} [Runtime Overhead: 7.58%]```**", generate_let_get_mut(&mcall, &let_expr)?);
-
-    us_docs.push_str(&safe_version);
-
-    return Some(us_docs.to_string());
-
 }
 
-fn display_suggestion_get_uncheck_mut(target_expr: &SyntaxNode, actions: &Vec<HoverAction>) -> Option<HoverResult> {
-
-    let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
-
-    let us_description = generate_description();
+fn collect_safe_method(
+    sema: &Semantics<'_, RootDatabase>,
+    receiver: &ast::Expr,
+    method: &str,
+) -> Option<hir::Function> {
+    let ty = sema.type_of_expr(receiver)?.original;
 
-    let us_docs = format_suggestion_get_uncheck_mut(mcall)?;
+    let scope = sema.scope(receiver.syntax())?;
 
-    let markup = process_unsafe_display_text(
-        &markup(Some(us_docs), us_description, None)?,
+    // Deref is followed too, so a "Vec" finds the slice methods
+    return ty.iterate_method_candidates(
+        sema.db,
+        &scope,
+        &scope.visible_traits().0,
+        None,
+        None,
+        |func| (func.name(sema.db).to_smol_str() == method).then_some(func),
     );
-
-    return Some(HoverResult { markup, actions: actions.to_vec() });
-
 }
 
-fn format_suggestion_ptr_copy_nonoverlapping(mcall: CallExpr, unsafe_expr: &BlockExpr) -> Option<String> {
-
-    let mut us_docs = String::new();
-
-    format_to!(us_docs, "**```---```** **~~```unsafe {{ {} }};```~~**", mcall.to_string());
-
-    us_docs.push('\n');
-    us_docs.push('\n');
-
-    let mut safe_version = String::new();
-
-    format_to!(safe_version, "**```+++```** **```{} [Runtime Overhead: 0.19%]```**", generate_copy_from_slice_format(&mcall, &unsafe_expr)?);
-
-    us_docs.push_str(&safe_version);
-
-    return Some(us_docs.to_string());
-
-}
-
-fn display_suggestion_ptr_copy_nonoverlapping(target_expr: &SyntaxNode, unsafe_expr: &BlockExpr, actions: &Vec<HoverAction>) -> Option<HoverResult> {
-
-    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+fn collect_sibling_fn(
+    sema: &Semantics<'_, RootDatabase>,
+    mcall: &CallExpr,
+    name: &str,
+) -> Option<hir::Function> {
+    let path = match mcall.expr()? {
+        ast::Expr::PathExpr(path_expr) => path_expr.path()?,
+        _ => return None,
+    };
 
-    let us_description = generate_description();
+    let func = match sema.resolve_path(&path)? {
+        hir::PathResolution::Def(hir::ModuleDef::Function(func)) => func,
+        _ => return None,
+    };
 
-    let us_docs = format_suggestion_ptr_copy_nonoverlapping(mcall, &unsafe_expr)?;
+    // "CString::new" lives next to "CString::from_vec_unchecked", on the type of the same impl
+    let impl_ = match func.as_assoc_item(sema.db)?.container(sema.db) {
+        hir::AssocItemContainer::Impl(impl_) => impl_,
+        hir::AssocItemContainer::Trait(_) => return None,
+    };
 
-    let markup = process_unsafe_display_text(
-        &markup(Some(us_docs), us_description, None)?,
+    return impl_.self_ty(sema.db).iterate_assoc_items(
+        sema.db,
+        impl_.module(sema.db).krate(),
+        |item| match item {
+            hir::AssocItem::Function(it) if it.name(sema.db).to_smol_str() == name => Some(it),
+            _ => None,
+        },
     );
-
-    return Some(HoverResult { markup, actions: actions.to_vec() });
-
 }
 
-fn format_suggestion_cstring_from_vec_unchecked(mcall: CallExpr) -> Option<String> {
-
-    let mut us_docs = String::new();
+fn collect_safe_api(
+    sema: &Semantics<'_, RootDatabase>,
+    replacement: &SafeReplacement,
+    target_expr: &SyntaxNode,
+) -> Option<hir::Function> {
+    // The suggested API gets a link to its definition, for its documentation
+    match replacement.pattern {
+        UnsafePattern::UnitializedVec => {
+            let mcall = target_expr.parent().and_then(ast::MethodCallExpr::cast)?;
+
+            // "vec!" is a macro, only the other rewrites grow the vector through a method
+            let method = ["resize", "push"]
+                .into_iter()
+                .find(|method| replacement.new_text.contains(&format!(".{}(", method)))?;
+
+            return collect_safe_method(sema, &mcall.receiver()?, method);
+        }
+        UnsafePattern::CopyWithin => {
+            let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+            let src = mcall.arg_list()?.args().next()?;
 
-    if mcall.syntax().parent()?.kind() == BIN_EXPR {
+            return collect_safe_method(sema, &collect_ptr_base(src), "copy_within");
+        }
+        UnsafePattern::CopyNonOverlap => {
+            // The copy lands in the destination, which is the receiver of "copy_from_slice"
+            let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+            let dst = mcall.arg_list()?.args().nth(1)?;
 
-        let target_expr = mcall.syntax().parent().and_then(ast::BinExpr::cast)?;
+            return collect_safe_method(sema, &collect_ptr_base(dst), "copy_from_slice");
+        }
+        UnsafePattern::CStringFromVec => {
+            let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
 
-        format_to!(us_docs, "**```---```** **~~```unsafe {{ {} }};```~~**", target_expr.to_string());
-    
-        us_docs.push('\n');
-        us_docs.push('\n');
-    
-        let mut safe_cstring_new = String::new();
-    
-        format_to!(safe_cstring_new, "**```+++```** **```{} [Runtime Overhead: 3.27x]```**", generate_cstring_new_format(target_expr.lhs()?.to_string(), &mcall, false)?);
-        
-        us_docs.push_str(&safe_cstring_new);
-    
-        return Some(us_docs.to_string());
+            return collect_sibling_fn(sema, &mcall, "new");
+        }
+        _ => return None,
     }
+}
 
-    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
-
-    format_to!(us_docs, "**```---```** **~~```unsafe {{ {} }};```~~**", let_expr.to_string());
+fn display_replacement(
+    sema: &Semantics<'_, RootDatabase>,
+    file_text: &str,
+    replacement: &SafeReplacement,
+    scope: &BlockExpr,
+    actions: &[HoverAction],
+) -> Option<HoverResult> {
+    let mut us_docs = render_replacement_diff(file_text, replacement);
 
-    us_docs.push('\n');
-    us_docs.push('\n');
+    if let Some(overhead) = pattern_overhead(replacement) {
+        format_to!(us_docs, "\n\n**Runtime Overhead:** {}", overhead);
+    }
 
-    let mut safe_cstring_new = String::new();
+    if let Some(note) = pattern_note(replacement.pattern) {
+        format_to!(us_docs, "\n\n**Note:** {}", note);
+    }
 
-    format_to!(safe_cstring_new, "**```+++```** **```{} [Runtime Overhead: 3.27x]```**", generate_cstring_new_format(let_expr.pat()?.to_string(), &mcall, true)?);
+    if let Some(rationale) = replacement.pattern.rationale() {
+        format_to!(us_docs, "\n\n**Why this is safe:** {}", rationale);
 
-    us_docs.push_str(&safe_cstring_new);
+        if let Some((item, link)) = replacement.pattern.safe_docs() {
+            format_to!(us_docs, " See [`{}`]({}).", item, link);
+        }
+    }
 
-    return Some(us_docs.to_string());
+    let markup = process_unsafe_display_text(&markup(Some(us_docs), generate_description(), None)?);
+
+    let mut actions = actions.to_vec();
+
+    // The pattern is found again on the node the assist converted, for the API it is rewritten to
+    let safe_api = scope
+        .syntax()
+        .descendants()
+        .filter(|node| replacement.range.contains_range(node.text_range()))
+        .find(|node| {
+            convert_unsafe_to_safe::detect_unsafe_pattern(sema, node, scope, true)
+                == Some(replacement.pattern)
+        })
+        .and_then(|target_expr| collect_safe_api(sema, replacement, &target_expr));
+
+    if let Some(func) = safe_api {
+        actions.push(HoverAction::goto_type_from_targets(
+            sema.db,
+            vec![hir::ModuleDef::Function(func)],
+        ));
+    }
 
+    return Some(HoverResult { markup, actions });
 }
 
-
-fn display_suggestion_cstring_from_vec_unchecked(target_expr: &SyntaxNode, actions: &Vec<HoverAction>) -> Option<HoverResult> {
-
-    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
-
-    let us_description = generate_description();
-
-    let us_docs = format_suggestion_cstring_from_vec_unchecked(mcall)?;
-
-    let markup = process_unsafe_display_text(
-        &markup(Some(us_docs), us_description, None)?,
-    );
+fn display_guidance(guidance: String, actions: &[HoverAction]) -> Option<HoverResult> {
+    let markup =
+        process_unsafe_display_text(&markup(Some(guidance), generate_description(), None)?);
 
     return Some(HoverResult { markup, actions: actions.to_vec() });
-
 }
 
-fn format_suggestion_cstring_bytes_len(mcall: CallExpr) -> Option<String> {
-
-    let mut us_docs = String::new();
-
-    if mcall.syntax().parent()?.kind() == BIN_EXPR {
-
-        let target_expr = mcall.syntax().parent().and_then(ast::BinExpr::cast)?;
-
-        format_to!(us_docs, "**```---```** **~~```unsafe {{ {} }};```~~**", target_expr.to_string());
-    
-        us_docs.push('\n');
-        us_docs.push('\n');
-    
-        let mut safe_cstring_bytes_len = String::new();
-    
-        format_to!(safe_cstring_bytes_len, "**```+++```** **```{} [Runtime Overhead: -2.81%]```**", generate_bytes_len_format(target_expr.lhs()?.to_string(), &mcall, false)?);
-        
-        us_docs.push_str(&safe_cstring_bytes_len);
-    
-        return Some(us_docs.to_string());
+fn collect_unsafe_operations(
+    sema: &Semantics<'_, RootDatabase>,
+    unsafe_expr: &BlockExpr,
+) -> Vec<&'static str> {
+    let mut operations = Vec::new();
+
+    // Every kind is listed once, in the order it first shows up in the block
+    for node in unsafe_expr.syntax().descendants() {
+        if let Some(operation) = classify_unsafe_operation(sema, &node) {
+            if !operations.contains(&operation) {
+                operations.push(operation);
+            }
+        }
     }
 
-    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
-
-    format_to!(us_docs, "**```---```** **~~```unsafe {{ {} }};```~~**", let_expr.to_string());
-
-    us_docs.push('\n');
-    us_docs.push('\n');
-
-    let mut safe_cstring_bytes_len = String::new();
-
-    format_to!(safe_cstring_bytes_len, "**```+++```** **```{} [Runtime Overhead: -2.81%]```**", generate_bytes_len_format(let_expr.pat()?.to_string(), &mcall, true)?);
-
-    us_docs.push_str(&safe_cstring_bytes_len);
-
-    return Some(us_docs.to_string());
-
+    return operations;
 }
 
-fn display_suggestion_cstring_bytes_len(target_expr: &SyntaxNode, actions: &Vec<HoverAction>) -> Option<HoverResult> {
-
-    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
-
-    let us_description = generate_description();
+fn display_unsafe_operations(
+    operations: &[&'static str],
+    actions: &[HoverAction],
+) -> Option<HoverResult> {
+    let us_description = "Unsafe operations in this block".to_string();
 
-    let us_docs = format_suggestion_cstring_bytes_len(mcall)?;
+    let us_docs = operations.iter().map(|operation| format!("- {}", operation)).join("\n");
 
-    let markup = process_unsafe_display_text(
-        &markup(Some(us_docs), us_description, None)?,
-    );
+    let markup = process_unsafe_display_text(&markup(Some(us_docs), us_description, None)?);
 
     return Some(HoverResult { markup, actions: actions.to_vec() });
-
 }
 
-fn format_suggestion_from_utf8_unchecked(mcall: CallExpr, mut_sign: bool, string_sign: bool) -> Option<String> {
+fn display_unsafe_fn(
+    sema: &Semantics<'_, RootDatabase>,
+    config: &HoverConfig,
+    fn_: &ast::Fn,
+    actions: &[HoverAction],
+) -> Option<HoverResult> {
+    let body = fn_.body()?;
 
-    let mut us_docs = String::new();
+    // The body of an "unsafe fn" is scanned like an "unsafe" block
+    let (suggestions, covered) =
+        collect_suggestions(sema, config, &fn_.unsafe_token()?, &body, actions);
 
-    if mcall.syntax().parent()?.kind() == STMT_LIST{
+    let mut remaining = 0;
 
-        let target_expr = &mcall;
+    let mut operations = Vec::new();
 
-        format_to!(us_docs, "**```---```** **~~```unsafe {{ {} }};```~~**", target_expr.to_string());
-    
-        us_docs.push('\n');
-        us_docs.push('\n');
-    
-        let mut safe_cstring_new = String::new();
-    
-        format_to!(safe_cstring_new, "**```+++```** **```{} [Runtime Overhead: 1.1x]```**", generate_from_utf8_expr_stmt(&mcall, mut_sign, string_sign)?);
-        
-        us_docs.push_str(&safe_cstring_new);
-    
-        return Some(us_docs.to_string());
+    // What a suggestion already rewrites doesn't count as remaining
+    for node in body.syntax().descendants() {
+        if covered.iter().any(|range| range.contains_range(node.text_range())) {
+            continue;
+        }
+
+        if let Some(operation) = classify_unsafe_operation(sema, &node) {
+            remaining += 1;
+
+            if !operations.contains(&operation) {
+                operations.push(operation);
+            }
+        }
     }
 
-    if mcall.syntax().parent()?.kind() == EXPR_STMT {
+    if suggestions.is_empty() {
+        if operations.is_empty() {
+            return None;
+        }
+
+        let us_description = "Unsafe operations in this function".to_string();
 
-        let target_expr = mcall.syntax().parent().and_then(ast::ExprStmt::cast)?;
+        let mut us_docs = operations.iter().map(|operation| format!("- {}", operation)).join("\n");
 
-        format_to!(us_docs, "**```---```** **~~```unsafe {{ {} }};```~~**", target_expr.to_string());
-    
         us_docs.push('\n');
         us_docs.push('\n');
-    
-        let mut safe_cstring_new = String::new();
-    
-        format_to!(safe_cstring_new, "**```+++```** **```{} [Runtime Overhead: 1.1x]```**", generate_from_utf8_expr_stmt(&mcall, mut_sign, string_sign)?);
-        
-        us_docs.push_str(&safe_cstring_new);
-    
-        return Some(us_docs.to_string());
-    }
 
-    if mcall.syntax().parent()?.kind() == BIN_EXPR {
+        format_to!(us_docs, "**Note:** `{}` has to stay `unsafe`, or the remaining operations can be wrapped in `unsafe` blocks with a comment on why they are sound.", fn_.name()?);
 
-        let target_expr = mcall.syntax().parent().and_then(ast::BinExpr::cast)?;
+        let markup = process_unsafe_display_text(&markup(Some(us_docs), us_description, None)?);
 
-        format_to!(us_docs, "**```---```** **~~```unsafe {{ {} }};```~~**", target_expr.to_string());
-    
-        us_docs.push('\n');
-        us_docs.push('\n');
-    
-        let mut safe_cstring_new = String::new();
-    
-        format_to!(safe_cstring_new, "**```+++```** **```{} [Runtime Overhead: 1.1x]```**", generate_from_utf8(&mcall, &target_expr, mut_sign, string_sign)?);
-        
-        us_docs.push_str(&safe_cstring_new);
-    
-        return Some(us_docs.to_string());
+        return Some(HoverResult { markup, actions: actions.to_vec() });
     }
 
-    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
+    let result = merge_suggestions(
+        suggestions.into_iter().map(|suggestion| suggestion.result).collect(),
+        actions,
+    )?;
 
-    format_to!(us_docs, "**```---```** **~~```unsafe {{ {} }};```~~**", let_expr.to_string());
+    let mut us_docs = result.markup.as_str().to_string();
 
     us_docs.push('\n');
     us_docs.push('\n');
 
-    let mut safe_cstring_new = String::new();
-
-    format_to!(safe_cstring_new, "**```+++```** **```{} [Runtime Overhead: 1.1x]```**", generate_let_from_utf8(&mcall, &let_expr, mut_sign, string_sign)?);
-
-    us_docs.push_str(&safe_cstring_new);
-
-    return Some(us_docs.to_string());
+    match remaining {
+        0 => format_to!(us_docs, "**Note:** once these are converted nothing in `{}` needs `unsafe`, so it could be a safe `fn`, unless callers still have to uphold an invariant it relies on.", fn_.name()?),
+        1 => format_to!(us_docs, "**Note:** 1 unsafe operation would remain in `{}` once these are converted ({}), so it has to stay `unsafe` or wrap it in an `unsafe` block.", fn_.name()?, operations.iter().join(", ")),
+        _ => format_to!(us_docs, "**Note:** {} unsafe operations would remain in `{}` once these are converted ({}), so it has to stay `unsafe` or wrap them in `unsafe` blocks.", remaining, fn_.name()?, operations.iter().join(", ")),
+    }
 
+    return Some(HoverResult { markup: Markup::from(us_docs), actions: result.actions });
 }
 
-fn display_suggestion_from_utf8_unchecked(target_expr: &SyntaxNode, actions: &Vec<HoverAction>, mut_sign: bool, string_sign: bool) -> Option<HoverResult> {
-
-    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
-
-    let us_description = generate_description();
-
-    let us_docs = format_suggestion_from_utf8_unchecked(mcall, mut_sign, string_sign)?;
+fn collect_suggestion_range(
+    target_expr: &SyntaxNode,
+    unsafe_expr: &BlockExpr,
+    pattern: Option<UnsafePattern>,
+) -> TextRange {
+    // The pair rewrite takes the statement of the second reference along
+    if pattern == Some(UnsafePattern::GetUncheckMutPair) {
+        let pair = target_expr
+            .parent()
+            .and_then(ast::MethodCallExpr::cast)
+            .and_then(|mcall| collect_unchecked_mut_pair(&mcall, unsafe_expr));
+
+        if let Some(pair) = pair {
+            return pair
+                .first_stmt
+                .syntax()
+                .text_range()
+                .cover(pair.second_stmt.syntax().text_range());
+        }
+    }
 
-    let markup = process_unsafe_display_text(
-        &markup(Some(us_docs), us_description, None)?,
-    );
-
-    return Some(HoverResult { markup, actions: actions.to_vec() });
+    let stmt = target_expr.ancestors().find(|node| {
+        node.parent().map_or(false, |parent| {
+            parent.kind() == STMT_LIST && parent.parent().as_ref() == Some(unsafe_expr.syntax())
+        })
+    });
 
+    return stmt.unwrap_or_else(|| unsafe_expr.syntax().clone()).text_range();
 }
 
-fn format_suggestion_to_safe_convert(mcall: CallExpr, unsafe_expr: &BlockExpr) -> Option<String> {
-
-    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
+struct UnsafeSuggestion {
+    range: TextRange,
+    pattern: UnsafePattern,
+    // Guidance only tells what to do by hand, the assist has no rewrite to apply for it
+    rewrite: bool,
+    result: HoverResult,
+}
 
-    let mut us_docs = String::new();
+fn collect_suggestions(
+    sema: &Semantics<'_, RootDatabase>,
+    config: &HoverConfig,
+    unsafe_kw: &SyntaxToken,
+    unsafe_expr: &BlockExpr,
+    actions: &[HoverAction],
+) -> (Vec<UnsafeSuggestion>, Vec<TextRange>) {
+    let mut suggestions: Vec<UnsafeSuggestion> = Vec::new();
+
+    // What the suggestions already rewrite or warn about
+    let mut covered: Vec<TextRange> = Vec::new();
+
+    // A "#[rust_analyzer::keep_unsafe]", half-typed or macro-expanded block gets no suggestion, only its operations
+    if check_keep_unsafe_attr(unsafe_expr)
+        || check_malformed_block(unsafe_expr)
+        || check_macro_origin(sema, unsafe_expr)
+    {
+        return (suggestions, covered);
+    }
 
-    format_to!(us_docs, "**```---```** **~~```unsafe {{ {} }};```~~**", mcall.to_string());
+    let assist_config = AssistConfig {
+        suggest_safe_wrappers: config.safe_wrappers,
+        disabled_unsafe_patterns: Some(config.disabled_unsafe_patterns.clone()),
+        ..CONVERSION_CONFIG
+    };
 
-    us_docs.push('\n');
-    us_docs.push('\n');
+    let file_id = sema.original_range(unsafe_expr.syntax()).file_id;
 
-    let mut safe_version = String::new();
+    let frange = FileRange { file_id, range: TextRange::empty(unsafe_kw.text_range().start()) };
 
-    format_to!(safe_version, "**```+++```** **```{}```**", generate_from_transmute(&mcall, &let_expr, &unsafe_expr)?);
+    let file_text = sema.parse(file_id).syntax().to_string();
 
-    us_docs.push_str(&safe_version);
+    // The diffs are the rewrites the assist applies, found by the same detection
+    for replacement in ide_assists::safe_replacements(sema.db, &assist_config, frange) {
+        covered.push(replacement.range);
 
-    return Some(us_docs.to_string());
+        if let Some(result) =
+            display_replacement(sema, &file_text, &replacement, unsafe_expr, actions)
+        {
+            suggestions.push(UnsafeSuggestion {
+                range: replacement.range,
+                pattern: replacement.pattern,
+                rewrite: true,
+                result,
+            });
+        }
+    }
 
-}
+    // A pattern without a rewrite can still come with guidance on what to do by hand
+    for target_expr in unsafe_expr.syntax().descendants() {
+        if covered.iter().any(|range| range.contains_range(target_expr.text_range())) {
+            continue;
+        }
 
-fn display_suggestion_mem_transmute(target_expr: &SyntaxNode, unsafe_expr: &BlockExpr, actions: &Vec<HoverAction>) -> Option<HoverResult> {
+        let pattern = match detect_unsafe_pattern(sema, config, &target_expr, unsafe_expr) {
+            Some(pattern) => pattern,
+            None => continue,
+        };
 
-    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+        let guidance = match pattern_guidance(pattern) {
+            Some(guidance) => guidance,
+            None => continue,
+        };
 
-    let us_description = generate_description();
+        let range = collect_suggestion_range(&target_expr, unsafe_expr, Some(pattern));
 
-    let us_docs = format_suggestion_to_safe_convert(mcall, &unsafe_expr)?;
+        covered.push(range);
 
-    let markup = process_unsafe_display_text(
-        &markup(Some(us_docs), us_description, None)?,
-    );
+        if let Some(result) = display_guidance(guidance, actions) {
+            suggestions.push(UnsafeSuggestion { range, pattern, rewrite: false, result });
+        }
+    }
 
-    return Some(HoverResult { markup, actions: actions.to_vec() });
+    suggestions.sort_by_key(|suggestion| suggestion.range.start());
 
+    return (suggestions, covered);
 }
 
-fn format_suggestion_to_from_ne_bytes(mcall: CallExpr, unsafe_expr: &BlockExpr) -> Option<String> {
-
-    let mut us_docs = String::new();
-
-    format_to!(us_docs, "**```---```** **~~```unsafe {{ {} }};```~~**", mcall.to_string());
-
-    us_docs.push('\n');
-    us_docs.push('\n');
-
-    let mut safe_version = String::new();
-    
-    if mcall.syntax().parent()?.kind() == BIN_EXPR {
-        format_to!(safe_version, "**```+++```** **```{}```**", generate_bytes_to_convert(&mcall, &unsafe_expr, false)?);
-    } else {
-        format_to!(safe_version, "**```+++```** **```{}```**", generate_bytes_to_convert(&mcall, unsafe_expr, true)?);
+fn merge_suggestions(
+    mut suggestions: Vec<HoverResult>,
+    actions: &[HoverAction],
+) -> Option<HoverResult> {
+    if suggestions.len() <= 1 {
+        return suggestions.pop();
     }
 
-    us_docs.push_str(&safe_version);
-
-    return Some(us_docs.to_string());
-
-}
-
-fn display_suggestion_read_unaligned(target_expr: &SyntaxNode, unsafe_expr: &BlockExpr, actions: &Vec<HoverAction>) -> Option<HoverResult> {
+    // Every suggestion keeps its diff and notes, only the title above them is shared
+    let sections = suggestions
+        .iter()
+        .map(|suggestion| {
+            let markup = suggestion.markup.as_str();
+            markup.split_once("\n___\n\n").map_or(markup, |(_, docs)| docs).to_string()
+        })
+        .join("\n\n___\n\n");
 
-    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+    let us_description =
+        format!("Code Suggestions: {} unsafe operations can be made safe", suggestions.len());
 
-    let us_description = generate_description();
+    let markup = process_unsafe_display_text(&markup(Some(sections), us_description, None)?);
 
-    let us_docs = format_suggestion_to_from_ne_bytes(mcall, &unsafe_expr)?;
+    // Every suggestion starts from the keyword's actions, only what it added on top is kept from it
+    let mut merged = actions.to_vec();
 
-    let markup = process_unsafe_display_text(
-        &markup(Some(us_docs), us_description, None)?,
+    merged.extend(
+        suggestions
+            .iter()
+            .flat_map(|suggestion| suggestion.actions.iter().skip(actions.len()).cloned()),
     );
 
-    return Some(HoverResult { markup, actions: actions.to_vec() });
-
+    return Some(HoverResult { markup, actions: merged });
 }
 
-fn format_suggestion_from_u32_unchecked(mcall: CallExpr) -> Option<String> {
-
-    let mut us_docs = String::new();
+fn summarize_conversion(
+    markup: &Markup,
+    converted: usize,
+    behavior_changing: usize,
+    total: usize,
+) -> Markup {
+    let summary = match behavior_changing {
+        0 => format!(
+            "**{} of {} unsafe operations in this block can be converted to safe code**",
+            converted, total
+        ),
+        _ => format!(
+            "**{} of {} unsafe operations in this block can be converted to safe code, {} of them with a change in behavior**",
+            converted, total, behavior_changing
+        ),
+    };
 
-    if mcall.syntax().parent()?.kind() == STMT_LIST{
+    // The ratio goes right under the title, before the first diff
+    return match markup.as_str().split_once("\n___\n\n") {
+        Some((title, docs)) => Markup::from(format!("{}\n___\n\n{}\n\n{}", title, summary, docs)),
+        None => Markup::from(format!("{}\n\n{}", summary, markup.as_str())),
+    };
+}
 
-        let target_expr = mcall.syntax().parent().and_then(ast::BinExpr::cast)?;
+fn detect_unsafe_pattern(
+    sema: &Semantics<'_, RootDatabase>,
+    config: &HoverConfig,
+    target_expr: &SyntaxNode,
+    unsafe_expr: &BlockExpr,
+) -> Option<UnsafePattern> {
+    let mut unsafe_type = convert_unsafe_to_safe::detect_unsafe_pattern(
+        sema,
+        target_expr,
+        unsafe_expr,
+        config.safe_wrappers,
+    );
 
-        format_to!(us_docs, "**```---```** **~~```unsafe {{ {} }};```~~**", target_expr.to_string());
-    
-        us_docs.push('\n');
-        us_docs.push('\n');
-    
-        let mut safe_cstring_new = String::new();
-    
-        format_to!(safe_cstring_new, "**```+++```** **```{} [Runtime Overhead: 3.8x]```**", generate_from_u32_expr_stmt(&mcall)?);
-        
-        us_docs.push_str(&safe_cstring_new);
-    
-        return Some(us_docs.to_string());
+    if unsafe_type.is_none() {
+        unsafe_type = check_static_mut_access(sema, target_expr);
     }
 
-    if mcall.syntax().parent()?.kind() == EXPR_STMT {
-
-        let target_expr = mcall.syntax().parent().and_then(ast::BinExpr::cast)?;
-
-        format_to!(us_docs, "**```---```** **~~```unsafe {{ {} }};```~~**", target_expr.to_string());
-    
-        us_docs.push('\n');
-        us_docs.push('\n');
-    
-        let mut safe_cstring_new = String::new();
-    
-        format_to!(safe_cstring_new, "**```+++```** **```{} [Runtime Overhead: 3.8x]```**", generate_from_u32_expr_stmt(&mcall)?);
-        
-        us_docs.push_str(&safe_cstring_new);
-    
-        return Some(us_docs.to_string());
+    if unsafe_type.is_none() {
+        unsafe_type = check_manually_drop(target_expr, unsafe_expr);
     }
 
-    if mcall.syntax().parent()?.kind() == BIN_EXPR {
-
-        let target_expr = mcall.syntax().parent().and_then(ast::BinExpr::cast)?;
-
-        format_to!(us_docs, "**```---```** **~~```unsafe {{ {} }};```~~**", target_expr.to_string());
-    
-        us_docs.push('\n');
-        us_docs.push('\n');
-    
-        let mut safe_cstring_new = String::new();
-    
-        format_to!(safe_cstring_new, "**```+++```** **```{} [Runtime Overhead: 3.8x]```**", generate_from_u32(&mcall, &target_expr)?);
-        
-        us_docs.push_str(&safe_cstring_new);
-    
-        return Some(us_docs.to_string());
-    }
-
-    let let_expr = mcall.syntax().parent().and_then(ast::LetStmt::cast)?;
-
-    format_to!(us_docs, "**```---```** **~~```unsafe {{ {} }};```~~**", let_expr.to_string());
-
-    us_docs.push('\n');
-    us_docs.push('\n');
-
-    let mut safe_cstring_new = String::new();
-
-    format_to!(safe_cstring_new, "**```+++```** **```{} [Runtime Overhead: 3.8x]```**", generate_let_from_u32(&mcall, &let_expr)?);
-
-    us_docs.push_str(&safe_cstring_new);
-
-    return Some(us_docs.to_string());
-
+    return unsafe_type
+        .filter(|pattern| !config.disabled_unsafe_patterns.contains(pattern.as_str()));
 }
 
-fn display_suggestion_from_u32_unchecked(target_expr: &SyntaxNode, actions: &Vec<HoverAction>) -> Option<HoverResult> {
-
-    let mcall = target_expr.parent().and_then(ast::CallExpr::cast)?;
+pub(super) fn unsafe_call_suggestion(
+    sema: &Semantics<'_, RootDatabase>,
+    config: &HoverConfig,
+    token: &SyntaxToken,
+) -> Option<Markup> {
+    if config.documentation.is_none() || !config.keywords || !config.show_unsafe_suggestions {
+        return None;
+    }
 
-    let us_description = generate_description();
+    let name_ref = token.parent().and_then(ast::NameRef::cast)?;
 
-    let us_docs = format_suggestion_from_u32_unchecked(mcall)?;
+    // The call has to be made in the user's own "unsafe" block, not just be an "unsafe fn"
+    let unsafe_expr = name_ref
+        .syntax()
+        .ancestors()
+        .filter_map(ast::BlockExpr::cast)
+        .find(|block| block.unsafe_token().is_some())?;
 
-    let markup = process_unsafe_display_text(
-        &markup(Some(us_docs), us_description, None)?,
-    );
+    let (suggestions, _) =
+        collect_suggestions(sema, config, &unsafe_expr.unsafe_token()?, &unsafe_expr, &[]);
 
-    return Some(HoverResult { markup, actions: actions.to_vec() });
+    // The call shows the suggestion for the code it is part of
+    let suggestion = suggestions
+        .into_iter()
+        .find(|suggestion| suggestion.range.contains_range(name_ref.syntax().text_range()))?;
 
+    return Some(process_unsafe_plain_text(suggestion.result, config).markup);
 }
 
 pub(super) fn keyword(
@@ -882,7 +888,7 @@ pub(super) fn keyword(
     config: &HoverConfig,
     token: &SyntaxToken,
 ) -> Option<HoverResult> {
-    if !token.kind().is_keyword() || !config.documentation.is_some() || !config.keywords {
+    if !token.kind().is_keyword() || config.documentation.is_none() || !config.keywords {
         return None;
     }
 
@@ -890,46 +896,103 @@ pub(super) fn keyword(
     let famous_defs = FamousDefs(sema, sema.scope(&parent)?.krate());
 
     let KeywordHint { description, keyword_mod, actions } = keyword_hints(sema, token, parent);
-    
+
+    let mut unsafe_fn_result = None;
+
     // Yuchen's Edit -> Detect unsafe keyword
-    if token.kind() == UNSAFE_KW {
-
-        let unsafe_expr = token.parent().and_then(ast::BlockExpr::cast)?;
-
-        for target_expr in unsafe_expr.syntax().descendants() {
-
-            let unsafe_type = check_convert_type(&target_expr, &unsafe_expr);
-
-            match unsafe_type {
-                Some(UnsafePattern::UnitializedVec) => return display_suggestion_uninitialized_vec(&target_expr, &unsafe_expr, &actions),
-                Some(UnsafePattern::CopyWithin) => return display_suggestion_ptr_copy(&target_expr, &unsafe_expr, &actions),
-                Some(UnsafePattern::CopyNonOverlap) => return display_suggestion_ptr_copy_nonoverlapping(&target_expr, &unsafe_expr, &actions),
-                Some(UnsafePattern::CStringFromVec) => return display_suggestion_cstring_from_vec_unchecked(&target_expr, &actions),
-                Some(UnsafePattern::CStringLength) => return display_suggestion_cstring_bytes_len(&target_expr, &actions),
-                Some(UnsafePattern::GetUncheckMut) => return display_suggestion_get_uncheck_mut(&target_expr, &actions),
-                Some(UnsafePattern::GetUncheck) => return display_suggestion_get_uncheck_mut(&target_expr, &actions),
-                Some(UnsafePattern::BytesToUTFString) => return display_suggestion_from_utf8_unchecked(&target_expr, &actions, false, false),
-                Some(UnsafePattern::BytesToUTFStringMut) => return display_suggestion_from_utf8_unchecked(&target_expr, &actions, true, false),
-                Some(UnsafePattern::StringBytesToUTFString) => return display_suggestion_from_utf8_unchecked(&target_expr, &actions, false, true),
-                Some(UnsafePattern::TransmuteTo) => return display_suggestion_mem_transmute(&target_expr, &unsafe_expr, &actions),
-                Some(UnsafePattern::ReadUnaligned) => return display_suggestion_read_unaligned(&target_expr, &unsafe_expr, &actions),
-                Some(UnsafePattern::FromU32Unchecked) => return display_suggestion_from_u32_unchecked(&target_expr, &actions),
-                None => continue,
-                _ => todo!(),
-            };
+    if token.kind() == UNSAFE_KW && config.show_unsafe_suggestions {
+        // An "unsafe fn" can't be converted as a whole, only told what in its body still needs "unsafe"
+        if let Some(fn_) = token.parent().and_then(ast::Fn::cast) {
+            unsafe_fn_result = display_unsafe_fn(sema, config, &fn_, &actions)
+                .map(|result| process_unsafe_plain_text(result, config));
         }
-    }
 
-    let doc_owner = find_std_module(&famous_defs, &keyword_mod)?;
-    let docs = doc_owner.attrs(sema.db).docs()?;
-    let markup = process_markup(
-        sema.db,
-        Definition::Module(doc_owner),
-        &markup(Some(docs.into()), description, None)?,
-        config,
-    );
-    return Some(HoverResult { markup, actions });
+        // "unsafe impl" and "unsafe trait" only get the keyword docs
+        if let Some(unsafe_expr) = token.parent().and_then(ast::BlockExpr::cast) {
+            let (suggestions, covered) =
+                collect_suggestions(sema, config, token, &unsafe_expr, &actions);
+
+            // A pattern whose suggestion couldn't be built falls through to the keyword docs
+            if !suggestions.is_empty() {
+                // Warnings and hover-only suggestions have nothing the assist could apply
+                let convertible = suggestions.iter().any(|suggestion| suggestion.rewrite);
+
+                // Operations no suggestion covers stay behind "unsafe" after the conversion
+                let remaining = unsafe_expr
+                    .syntax()
+                    .descendants()
+                    .filter(|node| {
+                        !covered.iter().any(|range| range.contains_range(node.text_range()))
+                    })
+                    .filter(|node| classify_unsafe_operation(sema, node).is_some())
+                    .count();
+
+                let converted = suggestions.iter().filter(|suggestion| suggestion.rewrite).count();
+
+                // A rewrite that can panic or produce another value is still counted, but called out
+                let behavior_changing = suggestions
+                    .iter()
+                    .filter(|suggestion| {
+                        suggestion.rewrite && suggestion.pattern.is_behavior_changing()
+                    })
+                    .count();
+
+                let total = suggestions.len() + remaining;
+
+                let mut result = merge_suggestions(
+                    suggestions.into_iter().map(|suggestion| suggestion.result).collect(),
+                    &actions,
+                )?;
+
+                if remaining > 0 {
+                    result.markup =
+                        summarize_conversion(&result.markup, converted, behavior_changing, total);
+                }
 
+                if convertible {
+                    let range = sema.original_range(unsafe_expr.syntax());
+                    result.actions.push(HoverAction::ApplyAssist {
+                        assist_id: "convert_unsafe_to_safe".to_string(),
+                        range,
+                    });
+                }
+
+                return Some(process_unsafe_plain_text(result, config));
+            }
+
+            // Nothing to convert, still tell the user what needs the "unsafe" block
+            let operations = collect_unsafe_operations(sema, &unsafe_expr);
+
+            if !operations.is_empty() {
+                return display_unsafe_operations(&operations, &actions)
+                    .map(|result| process_unsafe_plain_text(result, config));
+            }
+        }
+    }
+
+    let keyword_docs = find_std_module(&famous_defs, &keyword_mod).and_then(|doc_owner| {
+        let docs = doc_owner.attrs(sema.db).docs()?;
+        Some(process_markup(
+            sema.db,
+            Definition::Module(doc_owner),
+            &markup(Some(docs.into()), description, None)?,
+            config,
+        ))
+    });
+
+    // What the "unsafe fn" body needs comes on top of the keyword docs, not instead of them
+    return match (unsafe_fn_result, keyword_docs) {
+        (Some(result), Some(docs)) => Some(HoverResult {
+            markup: Markup::from(format!(
+                "{}\n___\n\n{}",
+                result.markup,
+                docs.as_str().trim_start()
+            )),
+            actions: result.actions,
+        }),
+        (Some(result), None) => Some(result),
+        (None, docs) => Some(HoverResult { markup: docs?, actions }),
+    };
 }
 
 pub(super) fn try_for_lint(attr: &ast::Attr, token: &SyntaxToken) -> Option<HoverResult> {
@@ -962,7 +1025,7 @@ pub(super) fn try_for_lint(attr: &ast::Attr, token: &SyntaxToken) -> Option<Hove
         tmp = format!("clippy::{}", token.text());
         &tmp
     } else {
-        &*token.text()
+        token.text()
     };
 
     let lint =
@@ -990,14 +1053,60 @@ pub(super) fn process_markup(
     Markup::from(markup)
 }
 
-pub(super) fn process_unsafe_display_text(
-    markup: &Markup,
-) -> Markup {
+pub(super) fn process_unsafe_display_text(markup: &Markup) -> Markup {
     let markup = markup.as_str();
     let markup = markup.to_string();
     Markup::from(markup)
 }
 
+fn process_unsafe_plain_text(result: HoverResult, config: &HoverConfig) -> HoverResult {
+    if config.markdown() {
+        return result;
+    }
+
+    let mut fence: Option<usize> = None;
+
+    let mut lines = Vec::new();
+
+    // The fences go, the "-"/"+" lines of a diff stay exactly as they are
+    for line in result.markup.as_str().lines() {
+        let ticks = line.chars().take_while(|c| *c == '`').count();
+
+        // Only a run of at least the opening length on its own line closes a fence
+        match fence {
+            None if ticks >= 3 => {
+                fence = Some(ticks);
+                continue;
+            }
+            Some(open) if ticks >= open && line.trim_end().len() == ticks => {
+                fence = None;
+                continue;
+            }
+            _ => (),
+        }
+
+        if fence.is_some() || line.is_empty() {
+            lines.push(line.to_string());
+            continue;
+        }
+
+        // One line at a time, so a title above "___" isn't read as a heading and list items keep their "-"
+        let (bullet, text) = match line.strip_prefix("- ") {
+            Some(text) => ("- ", text),
+            None => ("", line),
+        };
+
+        let text = remove_markdown(text);
+
+        // Only a rule renders to nothing
+        if !text.trim().is_empty() {
+            lines.push(format!("{}{}", bullet, text.trim_end()));
+        }
+    }
+
+    return HoverResult { markup: Markup::from(lines.join("\n")), actions: result.actions };
+}
+
 fn definition_owner_name(db: &RootDatabase, def: &Definition) -> Option<String> {
     match def {
         Definition::Field(f) => Some(f.parent_def(db).name(db)),
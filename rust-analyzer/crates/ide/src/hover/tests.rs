@@ -1,8 +1,11 @@
 use expect_test::{expect, Expect};
-use ide_db::base_db::{FileLoader, FileRange};
+use ide_db::{
+    base_db::{FileLoader, FileRange},
+    FxHashSet,
+};
 use syntax::TextRange;
 
-use crate::{fixture, hover::HoverDocFormat, HoverConfig};
+use crate::{fixture, hover::HoverDocFormat, HoverAction, HoverConfig};
 
 fn check_hover_no_result(ra_fixture: &str) {
     let (analysis, position) = fixture::position(ra_fixture);
@@ -12,6 +15,9 @@ fn check_hover_no_result(ra_fixture: &str) {
                 links_in_hover: true,
                 documentation: Some(HoverDocFormat::Markdown),
                 keywords: true,
+                show_unsafe_suggestions: true,
+                safe_wrappers: true,
+                disabled_unsafe_patterns: FxHashSet::default(),
             },
             FileRange { file_id: position.file_id, range: TextRange::empty(position.offset) },
         )
@@ -28,6 +34,9 @@ fn check(ra_fixture: &str, expect: Expect) {
                 links_in_hover: true,
                 documentation: Some(HoverDocFormat::Markdown),
                 keywords: true,
+                show_unsafe_suggestions: true,
+                safe_wrappers: true,
+                disabled_unsafe_patterns: FxHashSet::default(),
             },
             FileRange { file_id: position.file_id, range: TextRange::empty(position.offset) },
         )
@@ -49,6 +58,9 @@ fn check_hover_no_links(ra_fixture: &str, expect: Expect) {
                 links_in_hover: false,
                 documentation: Some(HoverDocFormat::Markdown),
                 keywords: true,
+                show_unsafe_suggestions: true,
+                safe_wrappers: true,
+                disabled_unsafe_patterns: FxHashSet::default(),
             },
             FileRange { file_id: position.file_id, range: TextRange::empty(position.offset) },
         )
@@ -70,6 +82,9 @@ fn check_hover_no_markdown(ra_fixture: &str, expect: Expect) {
                 links_in_hover: true,
                 documentation: Some(HoverDocFormat::PlainText),
                 keywords: true,
+                show_unsafe_suggestions: true,
+                safe_wrappers: true,
+                disabled_unsafe_patterns: FxHashSet::default(),
             },
             FileRange { file_id: position.file_id, range: TextRange::empty(position.offset) },
         )
@@ -91,6 +106,9 @@ fn check_actions(ra_fixture: &str, expect: Expect) {
                 links_in_hover: true,
                 documentation: Some(HoverDocFormat::Markdown),
                 keywords: true,
+                show_unsafe_suggestions: true,
+                safe_wrappers: true,
+                disabled_unsafe_patterns: FxHashSet::default(),
             },
             FileRange { file_id, range: position.range_or_empty() },
         )
@@ -99,6 +117,36 @@ fn check_actions(ra_fixture: &str, expect: Expect) {
     expect.assert_debug_eq(&hover.info.actions)
 }
 
+fn check_goto_targets(ra_fixture: &str, expect: Expect) {
+    let (analysis, file_id, position) = fixture::range_or_position(ra_fixture);
+    let hover = analysis
+        .hover(
+            &HoverConfig {
+                links_in_hover: true,
+                documentation: Some(HoverDocFormat::Markdown),
+                keywords: true,
+                show_unsafe_suggestions: true,
+                safe_wrappers: true,
+                disabled_unsafe_patterns: FxHashSet::default(),
+            },
+            FileRange { file_id, range: position.range_or_empty() },
+        )
+        .unwrap()
+        .unwrap();
+    let targets: Vec<String> = hover
+        .info
+        .actions
+        .iter()
+        .flat_map(|action| match action {
+            HoverAction::GoToType(targets) => {
+                targets.iter().map(|it| it.mod_path.clone()).collect()
+            }
+            _ => Vec::new(),
+        })
+        .collect();
+    expect.assert_debug_eq(&targets)
+}
+
 fn check_hover_range(ra_fixture: &str, expect: Expect) {
     let (analysis, range) = fixture::range(ra_fixture);
     let hover = analysis
@@ -107,6 +155,9 @@ fn check_hover_range(ra_fixture: &str, expect: Expect) {
                 links_in_hover: false,
                 documentation: Some(HoverDocFormat::Markdown),
                 keywords: true,
+                show_unsafe_suggestions: true,
+                safe_wrappers: true,
+                disabled_unsafe_patterns: FxHashSet::default(),
             },
             range,
         )
@@ -123,6 +174,9 @@ fn check_hover_range_no_results(ra_fixture: &str) {
                 links_in_hover: false,
                 documentation: Some(HoverDocFormat::Markdown),
                 keywords: true,
+                show_unsafe_suggestions: true,
+                safe_wrappers: true,
+                disabled_unsafe_patterns: FxHashSet::default(),
             },
             range,
         )
@@ -4096,6 +4150,1711 @@ mod move_keyword {}
     );
 }
 
+#[test]
+fn hover_unsafe_mem_uninitialized_struct() {
+    check(
+        r#"
+struct Config { len: u32 }
+fn main() {
+    let config: Config = unsaf$0e { mem::uninitialized() };
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            **Note:** `mem::uninitialized` is deprecated and undefined behavior for almost every type. Declare the value as `MaybeUninit<T>` and initialize it before calling `assume_init()`.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_unified_diff() {
+    check(
+        r#"
+fn main() {
+    let x: u32 = unsaf$0e { mem::zeroed() };
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -2,1 +2,1 @@
+            -let x: u32 = unsafe { mem::zeroed() };
+            +let x: u32 = 0;
+            ```
+
+            **Why this is safe:** The zero value is spelled out for the type, so no invalid bit pattern can be produced.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_static_mut_read() {
+    check(
+        r#"
+static mut COUNTER: usize = 0;
+fn main() {
+    let n = unsaf$0e { COUNTER };
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -1,3 +1,4 @@
+            -static mut COUNTER: usize = 0;
+            -fn main() {
+            -    let n = unsafe { COUNTER };
+            +use std::sync::atomic::{AtomicUsize, Ordering};
+            +static COUNTER: AtomicUsize = AtomicUsize::new(0);
+            +fn main() {
+            +    let n = COUNTER.load(Ordering::Relaxed);
+            ```
+
+            **Note:** every use of the static in this file becomes an atomic operation with `Ordering::Relaxed`, which is enough for counters and flags. Use `Acquire`/`Release` or `SeqCst` instead when the static guards access to other data.
+
+            **Why this is safe:** An atomic has the layout of the integer and makes every access well-defined, even from several threads.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_static_mut_non_integer() {
+    check(
+        r#"
+static mut NAME: &str = "";
+fn main() {
+    let n = unsaf$0e { NAME };
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            **Note:** this is a `static mut`, every access needs `unsafe` and nothing prevents data races on it. Consider `OnceLock` for a value that is initialized once, or a `Mutex` for a value that is updated in place.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_lists_raw_deref() {
+    check(
+        r#"
+fn main() {
+    let x = 5;
+    let p = &x as *const i32;
+    let y = unsaf$0e { *p };
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Unsafe operations in this block
+            ```
+            ___
+
+            - raw pointer dereference
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_disabled_pattern() {
+    let (analysis, position) = fixture::position(
+        r#"
+fn main() {
+    let x: u32 = unsaf$0e { mem::zeroed() };
+}
+"#,
+    );
+    let hover = analysis
+        .hover(
+            &HoverConfig {
+                links_in_hover: true,
+                documentation: Some(HoverDocFormat::Markdown),
+                keywords: true,
+                show_unsafe_suggestions: true,
+                safe_wrappers: true,
+                disabled_unsafe_patterns: ["mem_zeroed".to_string()].into_iter().collect(),
+            },
+            FileRange { file_id: position.file_id, range: TextRange::empty(position.offset) },
+        )
+        .unwrap()
+        .unwrap();
+    expect![[r#"
+        ```rust
+        u32
+        ```
+    "#]]
+    .assert_eq(&format!("{}\n", hover.info.markup));
+}
+
+#[test]
+fn hover_unsafe_keep_unsafe_attr() {
+    check(
+        r#"
+#[rust_analyzer::keep_unsafe]
+fn main() {
+    let x: u32 = unsaf$0e { mem::zeroed() };
+}
+"#,
+        expect![[r#"
+            *unsafe { mem::zeroed() }*
+            ```rust
+            u32
+            ```
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_as_mut_vec_ascii_push() {
+    check(
+        r#"
+fn main() {
+    let mut s = String::from("ab");
+    unsaf$0e { s.as_mut_vec().push(b'c'); }
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -3,1 +3,1 @@
+            -unsafe { s.as_mut_vec().push(b'c'); }
+            +s.push('c');
+            ```
+
+            **Note:** the pushed bytes are already valid UTF-8, so the `String` methods do the same without touching the underlying `Vec<u8>`.
+
+            **Why this is safe:** The `String` methods change the same bytes and keep the contents valid UTF-8.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_as_mut_vec_arbitrary_bytes() {
+    check(
+        r#"
+fn append(s: &mut String, bytes: &[u8]) {
+    unsaf$0e { s.as_mut_vec().extend_from_slice(bytes); }
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            **Warning:** writing through `as_mut_vec` must leave the `String` valid UTF-8, which the compiler can't check for arbitrary bytes. Consider `s.push_str(str::from_utf8(bytes)?)` to validate the bytes first, or `push`/`push_str` when the data is already text.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_pin_separate_box() {
+    check(
+        r#"
+fn main() {
+    let boxed = Box::new(5);
+    let p = unsaf$0e { Pin::new_unchecked(boxed) };
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -2,2 +2,1 @@
+            -let boxed = Box::new(5);
+            -let p = unsafe { Pin::new_unchecked(boxed) };
+            +let p = Box::pin(5);
+            ```
+
+            **Note:** a fresh `Box` is never moved out of once it is pinned, which is exactly what `Box::pin` guarantees without `unsafe`.
+
+            **Why this is safe:** `Box::pin` pins the same heap value and guarantees it is never moved out of. See [`Box::pin`](https://doc.rust-lang.org/std/boxed/struct.Box.html#method.pin).
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_box_leak_round_trip() {
+    check(
+        r#"
+fn main() {
+    let b = Box::new(5);
+    let r = Box::leak(b);
+    *r += 1;
+    let b = unsaf$0e { Box::from_raw(r as *mut i32) };
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -2,4 +2,2 @@
+            -let b = Box::new(5);
+            -let r = Box::leak(b);
+            -*r += 1;
+            -let b = unsafe { Box::from_raw(r as *mut i32) };
+            +let mut b = Box::new(5);
+            +*b += 1;
+            ```
+
+            **Why this is safe:** The `Box` owns the same allocation the whole time, so it is freed exactly once.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_box_leak_passed_on() {
+    check(
+        r#"
+fn main() {
+    let b = Box::new(5);
+    let r = Box::leak(b);
+    register(r);
+    let b = unsaf$0e { Box::from_raw(r as *mut i32) };
+}
+"#,
+        expect![[r#"
+            *unsafe { Box::from_raw(r as *mut i32) }*
+            ```rust
+            {unknown}
+            ```
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_offset_from_literal() {
+    check(
+        r#"
+fn main() {
+    let v = vec![1, 2, 3, 4, 5];
+    let d = unsaf$0e { v.as_ptr().add(4).offset_from(v.as_ptr().add(1)) };
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -3,1 +3,1 @@
+            -let d = unsafe { v.as_ptr().add(4).offset_from(v.as_ptr().add(1)) };
+            +let d = v[1..4].len() as isize;
+            ```
+
+            **Note:** both pointers are offsets into the same buffer, so their distance is the difference of the indices and no pointer arithmetic is needed.
+
+            **Why this is safe:** Both pointers come from one buffer, so the difference of their indices is the distance `offset_from` computes.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_str_get_unchecked() {
+    check(
+        r#"
+fn main() {
+    let s = "hello world";
+    let sub = unsaf$0e { s.get_unchecked(0..5) };
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -3,1 +3,1 @@
+            -let sub = unsafe { s.get_unchecked(0..5) };
+            +let sub = s.get(0..5).expect("not a char boundary");
+            ```
+
+            **Note:** besides the bounds, a `str` range has to start and end on `char` boundaries. `str::get` returns `None` and slicing panics where `get_unchecked` was undefined behavior.
+
+            **Why this is safe:** `str::get` returns the same substring and checks the bounds and `char` boundaries `get_unchecked` assumed. See [`str::get`](https://doc.rust-lang.org/std/primitive.str.html#method.get).
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_string_get_unchecked() {
+    check(
+        r#"
+//- /main.rs crate:main deps:alloc
+use alloc::string::String;
+fn first_word(s: &String) {
+    let word = unsaf$0e { s.get_unchecked(0..5) };
+}
+//- /alloc.rs crate:alloc
+pub mod string {
+    pub struct String;
+    impl String {
+        pub unsafe fn get_unchecked<I>(&self, i: I) -> &str { loop {} }
+    }
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -3,1 +3,1 @@
+            -let word = unsafe { s.get_unchecked(0..5) };
+            +let word = s.get(0..5).expect("not a char boundary");
+            ```
+
+            **Note:** besides the bounds, a `str` range has to start and end on `char` boundaries. `str::get` returns `None` and slicing panics where `get_unchecked` was undefined behavior.
+
+            **Why this is safe:** `str::get` returns the same substring and checks the bounds and `char` boundaries `get_unchecked` assumed. See [`str::get`](https://doc.rust-lang.org/std/primitive.str.html#method.get).
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_str_bytes_get_unchecked() {
+    check(
+        r#"
+fn main() {
+    let s = "hello";
+    let b = unsaf$0e { *s.as_bytes().get_unchecked(1) };
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -3,1 +3,1 @@
+            -let b = unsafe { *s.as_bytes().get_unchecked(1) };
+            +let b = s.as_bytes()[1];
+            ```
+
+            **Note:** `as_bytes()` is a plain `&[u8]`, so any index is fine byte-wise and only the bounds are checked.
+
+            **Why this is safe:** Indexing `as_bytes()` reads the same byte and checks the bound.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_write_unaligned() {
+    check(
+        r#"
+fn store(buf: &mut [u8], off: usize, val: u32) {
+    unsaf$0e {
+        ptr::write_unaligned(buf.as_mut_ptr().add(off) as *mut u32, val);
+    }
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -2,3 +2,1 @@
+            -unsafe {
+            -    ptr::write_unaligned(buf.as_mut_ptr().add(off) as *mut u32, val);
+            -}
+            +buf[off..off + 4].copy_from_slice(&val.to_ne_bytes());
+            ```
+
+            **Note:** `to_ne_bytes` keeps the native byte order of `write_unaligned`. Use `to_le_bytes` or `to_be_bytes` instead when the buffer has a fixed layout.
+
+            **Why this is safe:** `to_ne_bytes` writes the same bytes in the same order, and a byte slice has no alignment to respect.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_as_bytes_mut_ascii() {
+    check(
+        r#"
+fn shout(s: &mut str) {
+    unsaf$0e { s.as_bytes_mut().make_ascii_uppercase(); }
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -2,1 +2,1 @@
+            -unsafe { s.as_bytes_mut().make_ascii_uppercase(); }
+            +s.make_ascii_uppercase();
+            ```
+
+            **Why this is safe:** The `str` method changes the same bytes and keeps the string valid UTF-8.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_as_bytes_mut_guidance() {
+    check(
+        r#"
+fn scramble(s: &mut str) {
+    let bytes = unsaf$0e { s.as_bytes_mut() };
+    bytes[0] = 0xff;
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            **Note:** the string has to hold valid UTF-8 again before it is used as a `str`. Prefer `make_ascii_uppercase`/`make_ascii_lowercase` on the `str`, or edit a `Vec<u8>` copy and turn it back with `String::from_utf8`.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_alloc_to_vec() {
+    check(
+        r#"
+fn main() {
+    let p = unsaf$0e { alloc(Layout::array::<u32>(2).unwrap()) as *mut u32 };
+    unsafe {
+        p.write(1);
+        p.add(1).write(2);
+        dealloc(p as *mut u8, Layout::array::<u32>(2).unwrap());
+    }
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -2,6 +2,3 @@
+            -let p = unsafe { alloc(Layout::array::<u32>(2).unwrap()) as *mut u32 };
+            -unsafe {
+            -    p.write(1);
+            -    p.add(1).write(2);
+            -    dealloc(p as *mut u8, Layout::array::<u32>(2).unwrap());
+            -}
+            +let mut p: Vec<u32> = Vec::with_capacity(2);
+            +p.push(1);
+            +p.push(2);
+            ```
+
+            **Note:** the pointer is only written and read at in-bounds offsets and freed with the layout it was allocated with, so a `Vec` can own the buffer and free it when it goes out of scope.
+
+            **Why this is safe:** A `Vec` allocates and frees the same buffer with a matching layout, and keeps track of what was written.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_transmute_enum_discriminant() {
+    check(
+        r#"
+#[repr(u8)]
+enum Level {
+    Low = 1,
+    High = 2,
+}
+
+fn main() {
+    let level = Level::High;
+    let n = unsaf$0e { core::mem::transmute::<Level, u8>(level) };
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -9,1 +9,1 @@
+            -let n = unsafe { core::mem::transmute::<Level, u8>(level) };
+            +let n = level as u8;
+            ```
+
+            **Note:** a field-less enum casts with `as` to the integer its `#[repr]` names, which gives the same discriminant the transmute reads.
+
+            **Why this is safe:** The cast gives the same value the transmute read, without reinterpreting memory.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_get_unchecked_mut_pair_literal() {
+    check(
+        r#"
+//- minicore: slice
+fn main() {
+    let mut v = [1, 2, 3, 4];
+    unsaf$0e {
+        let a = v.get_unchecked_mut(2);
+        let b = v.get_unchecked_mut(0);
+        std::mem::swap(a, b);
+    }
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -3,3 +3,4 @@
+            -unsafe {
+            -    let a = v.get_unchecked_mut(2);
+            -    let b = v.get_unchecked_mut(0);
+            +let (lo, hi) = v.split_at_mut(2);
+            +let a = &mut hi[0];
+            +let b = &mut lo[0];
+            +unsafe {
+            ```
+
+            **Why this is safe:** `split_at_mut` hands out the same two elements from halves the borrow checker knows are disjoint. See [`slice::split_at_mut`](https://doc.rust-lang.org/std/primitive.slice.html#method.split_at_mut).
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_get_unchecked_mut_pair_non_literal() {
+    check(
+        r#"
+//- minicore: slice
+fn main() {
+    let mut v = [1, 2, 3, 4];
+    let (i, j) = (0, 2);
+    unsaf$0e {
+        let a = v.get_unchecked_mut(i);
+        let b = v.get_unchecked_mut(j);
+        std::mem::swap(a, b);
+    }
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            **Note:** both references point into the same slice, so two `get_mut` calls won't pass the borrow checker. Split it with `split_at_mut` at the larger index instead and use the references inside a branch for each order, the indices must differ.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_fn_only_convertible() {
+    check(
+        r#"
+//- minicore: slice
+unsaf$0e fn first(v: &[u32]) -> u32 {
+    *v.get_unchecked(0)
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -2,1 +2,1 @@
+            -*v.get_unchecked(0)
+            +v[0]
+            ```
+
+            **Runtime Overhead:** 7.58%
+
+            **Why this is safe:** Indexing returns the same element and checks the bound `get_unchecked` assumed.
+
+            **Note:** once these are converted nothing in `first` needs `unsafe`, so it could be a safe `fn`, unless callers still have to uphold an invariant it relies on.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_fn_convertible_with_remaining() {
+    check(
+        r#"
+//- minicore: slice
+unsaf$0e fn first(v: &[u32], p: *const u32) -> u32 {
+    let _ = *p;
+    *v.get_unchecked(0)
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -3,1 +3,1 @@
+            -*v.get_unchecked(0)
+            +v[0]
+            ```
+
+            **Runtime Overhead:** 7.58%
+
+            **Why this is safe:** Indexing returns the same element and checks the bound `get_unchecked` assumed.
+
+            **Note:** 1 unsafe operation would remain in `first` once these are converted (raw pointer dereference), so it has to stay `unsafe` or wrap it in an `unsafe` block.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_fn_raw_deref() {
+    check(
+        r#"
+unsaf$0e fn read(p: *const i32) -> i32 {
+    *p
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Unsafe operations in this function
+            ```
+            ___
+
+            - raw pointer dereference
+
+            **Note:** `read` has to stay `unsafe`, or the remaining operations can be wrapped in `unsafe` blocks with a comment on why they are sound.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_fn_without_unsafe_operations_shows_keyword_docs() {
+    check(
+        r#"
+//- /main.rs crate:main deps:std
+unsaf$0e fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+//- /libstd.rs crate:std
+/// Docs for unsafe_keyword
+mod unsafe_keyword {}
+"#,
+        expect![[r#"
+            *unsafe*
+
+            ```rust
+            unsafe
+            ```
+
+            ---
+
+            Docs for unsafe_keyword
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_fn_raw_deref_with_keyword_docs() {
+    check(
+        r#"
+//- /main.rs crate:main deps:std
+unsaf$0e fn read(p: *const i32) -> i32 {
+    *p
+}
+//- /libstd.rs crate:std
+/// Docs for unsafe_keyword
+mod unsafe_keyword {}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Unsafe operations in this function
+            ```
+            ___
+
+            - raw pointer dereference
+
+            **Note:** `read` has to stay `unsafe`, or the remaining operations can be wrapped in `unsafe` blocks with a comment on why they are sound.
+            ___
+
+            ```rust
+            unsafe
+            ```
+
+            ---
+
+            Docs for unsafe_keyword
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_swap_unchecked() {
+    check(
+        r#"
+//- minicore: slice
+fn main() {
+    let mut arr = [1, 2, 3, 4];
+    unsaf$0e {
+        arr.swap_unchecked(0, 3);
+    }
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -3,3 +3,1 @@
+            -unsafe {
+            -    arr.swap_unchecked(0, 3);
+            -}
+            +arr.swap(0, 3);
+            ```
+
+            **Note:** `swap_unchecked` is unstable and requires `#![feature(slice_swap_unchecked)]` on nightly, while `swap` is stable and only adds a bounds check.
+
+            **Why this is safe:** `swap` exchanges the same two elements and checks both indices. See [`slice::swap`](https://doc.rust-lang.org/std/primitive.slice.html#method.swap).
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_set_len_after_raw_writes() {
+    check(
+        r#"
+fn main() {
+    let mut buf = Vec::with_capacity(2);
+    unsaf$0e {
+        *buf.as_mut_ptr() = 1;
+        *buf.as_mut_ptr().add(1) = 2;
+        buf.set_len(2);
+    }
+    println!("{:?}", buf);
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -3,5 +3,2 @@
+            -unsafe {
+            -    *buf.as_mut_ptr() = 1;
+            -    *buf.as_mut_ptr().add(1) = 2;
+            -    buf.set_len(2);
+            -}
+            +buf.push(1);
+            +buf.push(2);
+            ```
+
+            **Why this is safe:** The safe version only ever exposes elements that were written, where `set_len` trusted the memory below the new length to be initialized.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_set_len_computed_fill_loop() {
+    check(
+        r#"
+fn main() {
+    let n = 16;
+    let mut v = Vec::with_capacity(n);
+    unsaf$0e {
+        v.set_len(n);
+    }
+    for i in 0..n {
+        v[i] = i * 2;
+    }
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -3,4 +3,1 @@
+            -let mut v = Vec::with_capacity(n);
+            -unsafe {
+            -    v.set_len(n);
+            -}
+            +let mut v = vec![0; n];
+            ```
+
+            **Runtime Overhead:** -0.05%
+
+            **Why this is safe:** The safe version only ever exposes elements that were written, where `set_len` trusted the memory below the new length to be initialized.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_as_chunks_unchecked_iterated() {
+    check(
+        r#"
+//- minicore: slice
+fn main() {
+    let data: &[u8] = &[1, 2, 3, 4, 5, 6, 7, 8];
+    for chunk in unsaf$0e { data.as_chunks_unchecked::<4>() } {
+        println!("{:?}", chunk);
+    }
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -3,1 +3,1 @@
+            -for chunk in unsafe { data.as_chunks_unchecked::<4>() } {
+            +for chunk in data.chunks_exact(4) {
+            ```
+
+            **Why this is safe:** `as_chunks` splits into the same `N`-element chunks and keeps any leftover apart instead of assuming there is none. See [`slice::as_chunks`](https://doc.rust-lang.org/std/primitive.slice.html#method.as_chunks).
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_as_bytes_mut_byte_write() {
+    check(
+        r#"
+struct String;
+fn capitalize(s: &mut String) {
+    unsaf$0e {
+        s.as_bytes_mut()[0] = b'H';
+    }
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -3,3 +3,1 @@
+            -unsafe {
+            -    s.as_bytes_mut()[0] = b'H';
+            -}
+            +s.replace_range(0..1, "H");
+            ```
+
+            **Why this is safe:** The `str` method changes the same bytes and keeps the string valid UTF-8.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_get_unchecked_mut_block_receiver() {
+    check(
+        r#"
+//- minicore: slice
+fn append(v: &mut [Vec<i32>], i: usize, x: i32) {
+    unsaf$0e { v.get_unchecked_mut(i) }.push(x);
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -2,1 +2,1 @@
+            -unsafe { v.get_unchecked_mut(i) }.push(x);
+            +v.get_mut(i).unwrap().push(x);
+            ```
+
+            **Runtime Overhead:** 7.58%
+
+            **Why this is safe:** `get_mut` returns the same element mutably and checks the bound `get_unchecked_mut` assumed. See [`slice::get_mut`](https://doc.rust-lang.org/std/primitive.slice.html#method.get_mut).
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_ptr_as_ref() {
+    check(
+        r#"
+fn main() {
+    let x = 5;
+    let p = &x as *const i32;
+    let r = unsaf$0e { p.as_ref() };
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -4,1 +4,1 @@
+            -let r = unsafe { p.as_ref() };
+            +let r = Some(&x);
+            ```
+
+            **Note:** the pointer was made from a reference in this function, so it is never null and that reference can be used directly.
+
+            **Why this is safe:** The pointer was made from that reference, so it is non-null and points at a live value.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_copy_within_rationale() {
+    check(
+        r#"
+fn main() {
+    let mut vec = vec![1,2,3,4,5,6];
+    unsaf$0e {
+        ptr::copy(vec[0..].as_mut_ptr(), vec[3..].as_mut_ptr(), 2);
+    }
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -3,3 +3,1 @@
+            -unsafe {
+            -    ptr::copy(vec[0..].as_mut_ptr(), vec[3..].as_mut_ptr(), 2);
+            -}
+            +vec.copy_within(0..2, 3);
+            ```
+
+            **Runtime Overhead:** -26.62%
+
+            **Why this is safe:** Both copy `count` elements within the same allocation handling overlap; `copy_within` bounds-checks the indices. See [`slice::copy_within`](https://doc.rust-lang.org/std/primitive.slice.html#method.copy_within).
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_two_patterns_in_block() {
+    check(
+        r#"
+fn store(buf: &mut [u8], off: usize, val: u32) {
+    let mut m = ManuallyDrop::new(vec![1, 2, 3]);
+    unsaf$0e {
+        ptr::write_unaligned(buf.as_mut_ptr().add(off) as *mut u32, val);
+        ManuallyDrop::drop(&mut m);
+    }
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestions: 2 unsafe operations can be made safe
+            ```
+            ___
+
+            ```diff
+            @@ -3,2 +3,2 @@
+            -unsafe {
+            -    ptr::write_unaligned(buf.as_mut_ptr().add(off) as *mut u32, val);
+            +buf[off..off + 4].copy_from_slice(&val.to_ne_bytes());
+            +unsafe {
+            ```
+
+            **Note:** `to_ne_bytes` keeps the native byte order of `write_unaligned`. Use `to_le_bytes` or `to_be_bytes` instead when the buffer has a fixed layout.
+
+            **Why this is safe:** `to_ne_bytes` writes the same bytes in the same order, and a byte slice has no alignment to respect.
+
+            ___
+
+            **Note:** the value is wrapped in `ManuallyDrop` only to drop it by hand, nothing moves it out in between. The wrapper may be unnecessary: keep it as a plain owned value and call `drop` here, or let it drop at the end of its scope.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_block_conversion_summary() {
+    check(
+        r#"
+fn store(buf: &mut [u8], off: usize, val: u32, p: *const u32) {
+    unsaf$0e {
+        ptr::write_unaligned(buf.as_mut_ptr().add(off) as *mut u32, val);
+        let y = *p;
+    }
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            **1 of 2 unsafe operations in this block can be converted to safe code, 1 of them with a change in behavior**
+
+            ```diff
+            @@ -2,2 +2,2 @@
+            -unsafe {
+            -    ptr::write_unaligned(buf.as_mut_ptr().add(off) as *mut u32, val);
+            +buf[off..off + 4].copy_from_slice(&val.to_ne_bytes());
+            +unsafe {
+            ```
+
+            **Note:** `to_ne_bytes` keeps the native byte order of `write_unaligned`. Use `to_le_bytes` or `to_be_bytes` instead when the buffer has a fixed layout.
+
+            **Why this is safe:** `to_ne_bytes` writes the same bytes in the same order, and a byte slice has no alignment to respect.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_block_conversion_summary_equivalent() {
+    check(
+        r#"
+fn swap(mut x: u32, mut y: u32, p: *const u32) {
+    unsaf$0e {
+        ptr::swap(&mut x, &mut y);
+        let z = *p;
+    }
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            **1 of 2 unsafe operations in this block can be converted to safe code**
+
+            ```diff
+            @@ -2,2 +2,2 @@
+            -unsafe {
+            -    ptr::swap(&mut x, &mut y);
+            +mem::swap(&mut x, &mut y);
+            +unsafe {
+            ```
+
+            **Note:** two `&mut` borrows can never overlap, so `mem::swap` gives the same result without the raw pointer contract of `ptr::swap`.
+
+            **Why this is safe:** `mem::swap` exchanges the same two values through references the borrow checker knows are distinct. See [`mem::swap`](https://doc.rust-lang.org/std/mem/fn.swap.html).
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_set_len_plain_text() {
+    check_hover_no_markdown(
+        r#"
+fn main() {
+    let mut buf = Vec::with_capacity(2);
+    unsaf$0e {
+        *buf.as_mut_ptr() = 1;
+        *buf.as_mut_ptr().add(1) = 2;
+        buf.set_len(2);
+    }
+    println!("{:?}", buf);
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            Code Suggestion: translating unsafe to safe code
+
+            @@ -3,5 +3,2 @@
+            -unsafe {
+            -    *buf.as_mut_ptr() = 1;
+            -    *buf.as_mut_ptr().add(1) = 2;
+            -    buf.set_len(2);
+            -}
+            +buf.push(1);
+            +buf.push(2);
+
+            Why this is safe: The safe version only ever exposes elements that were written, where set_len trusted the memory below the new length to be initialized.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_copy_within_plain_text() {
+    check_hover_no_markdown(
+        r#"
+fn main() {
+    let mut vec = vec![1,2,3,4,5,6];
+    unsaf$0e {
+        ptr::copy(vec[0..].as_mut_ptr(), vec[3..].as_mut_ptr(), 2);
+    }
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            Code Suggestion: translating unsafe to safe code
+
+            @@ -3,3 +3,1 @@
+            -unsafe {
+            -    ptr::copy(vec[0..].as_mut_ptr(), vec[3..].as_mut_ptr(), 2);
+            -}
+            +vec.copy_within(0..2, 3);
+
+            Runtime Overhead: -26.62%
+
+            Why this is safe: Both copy count elements within the same allocation handling overlap; copy_within bounds-checks the indices. See slice::copy_within.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_operations_plain_text() {
+    check_hover_no_markdown(
+        r#"
+fn main() {
+    let x = 5;
+    let p = &x as *const i32;
+    let y = unsaf$0e { *p };
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            Unsafe operations in this block
+
+            - raw pointer dereference
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_no_documentation() {
+    let (analysis, position) = fixture::position(
+        r#"
+fn main() {
+    let mut vec = vec![1,2,3,4,5,6];
+    unsaf$0e {
+        ptr::copy(vec[0..].as_mut_ptr(), vec[3..].as_mut_ptr(), 2);
+    }
+}
+"#,
+    );
+    for documentation in [None, Some(HoverDocFormat::PlainText)] {
+        let hover = analysis
+            .hover(
+                &HoverConfig {
+                    links_in_hover: true,
+                    documentation: documentation.clone(),
+                    keywords: true,
+                    show_unsafe_suggestions: true,
+                    safe_wrappers: true,
+                    disabled_unsafe_patterns: FxHashSet::default(),
+                },
+                FileRange { file_id: position.file_id, range: TextRange::empty(position.offset) },
+            )
+            .unwrap();
+        match documentation {
+            None => assert_eq!(hover.unwrap().info.markup.as_str(), "()"),
+            Some(_) => {
+                assert!(hover.unwrap().info.markup.as_str().contains("+vec.copy_within(0..2, 3);"))
+            }
+        }
+    }
+}
+
+#[test]
+fn hover_unsafe_set_len_without_capacity_shows_keyword_docs() {
+    check(
+        r#"
+//- /main.rs crate:main deps:std
+fn main() {
+    let mut spare = Vec::with_capacity(4);
+    let mut buf = Vec::new();
+    unsaf$0e { buf.set_len(4); }
+}
+//- /libstd.rs crate:std
+/// Docs for unsafe_keyword
+mod unsafe_keyword {}
+"#,
+        expect![[r#"
+            *unsafe*
+
+            ```rust
+            unsafe
+            ```
+
+            ---
+
+            Docs for unsafe_keyword
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_unsupported_ptr_copy_shows_keyword_docs() {
+    check(
+        r#"
+//- /main.rs crate:main deps:std
+fn main() {
+    unsaf$0e { ptr::copy(src(), dst(), 2); }
+}
+//- /libstd.rs crate:std
+/// Docs for unsafe_keyword
+mod unsafe_keyword {}
+"#,
+        expect![[r#"
+            *unsafe*
+
+            ```rust
+            unsafe
+            ```
+
+            ---
+
+            Docs for unsafe_keyword
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_suggestions_disabled() {
+    let (analysis, position) = fixture::position(
+        r#"
+//- /main.rs crate:main deps:std
+fn main() {
+    let x: u32 = unsaf$0e { mem::zeroed() };
+}
+//- /libstd.rs crate:std
+/// Docs for unsafe_keyword
+mod unsafe_keyword {}
+"#,
+    );
+    let hover = analysis
+        .hover(
+            &HoverConfig {
+                links_in_hover: true,
+                documentation: Some(HoverDocFormat::Markdown),
+                keywords: true,
+                show_unsafe_suggestions: false,
+                safe_wrappers: true,
+                disabled_unsafe_patterns: FxHashSet::default(),
+            },
+            FileRange { file_id: position.file_id, range: TextRange::empty(position.offset) },
+        )
+        .unwrap()
+        .unwrap();
+    expect![[r#"
+
+        ```rust
+        unsafe: u32
+        ```
+
+        ---
+
+        Docs for unsafe_keyword
+    "#]]
+    .assert_eq(&format!("{}\n", hover.info.markup));
+}
+
+#[test]
+fn hover_unsafe_in_macro_call() {
+    check(
+        r#"
+macro_rules! id {
+    ($e:expr) => { $e };
+}
+fn main() {
+    let x: u32 = id!(unsaf$0e { mem::zeroed() });
+}
+"#,
+        expect![[r#"
+            *unsafe { mem::zeroed() }*
+            ```rust
+            u32
+            ```
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_apply_assist_action() {
+    check_actions(
+        r#"
+fn main() {
+    let mut vec = vec![1,2,3,4,5,6];
+    unsaf$0e {
+        ptr::copy(vec[0..].as_mut_ptr(), vec[3..].as_mut_ptr(), 2);
+    }
+}
+"#,
+        expect![[r#"
+            [
+                ApplyAssist {
+                    assist_id: "convert_unsafe_to_safe",
+                    range: FileRange {
+                        file_id: FileId(
+                            0,
+                        ),
+                        range: 53..135,
+                    },
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_ptr_copy_goto_copy_within() {
+    check_goto_targets(
+        r#"
+struct Vec<T>(T);
+impl<T> Vec<T> {
+    fn new() -> Vec<T> { loop {} }
+    fn copy_within(&mut self, src: usize, dest: usize) {}
+}
+fn main() {
+    let mut vec: Vec<i32> = Vec::new();
+    unsaf$0e {
+        ptr::copy(vec[0..].as_mut_ptr(), vec[3..].as_mut_ptr(), 2);
+    }
+}
+"#,
+        expect![[r#"
+            [
+                "test::copy_within",
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_set_len_goto_resize() {
+    check_goto_targets(
+        r#"
+struct Vec<T>(T);
+impl<T> Vec<T> {
+    fn new() -> Vec<T> { loop {} }
+    fn reserve(&mut self, additional: usize) {}
+    fn resize(&mut self, new_len: usize, value: T) {}
+    unsafe fn set_len(&mut self, len: usize) {}
+}
+fn main() {
+    let mut buf: Vec<u8> = Vec::new();
+    buf.reserve(4);
+    unsaf$0e { buf.set_len(4); }
+}
+"#,
+        expect![[r#"
+            [
+                "test::resize",
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_no_apply_assist_action() {
+    check_actions(
+        r#"
+fn main() {
+    let x = 5;
+    let p = &x as *const i32;
+    let y = unsaf$0e { *p };
+}
+"#,
+        expect![[r#"
+            [
+                GoToType(
+                    [],
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_from_utf8_unchecked_mut() {
+    check(
+        r#"
+fn main() {
+    let mut buf = vec![104u8, 105];
+    let s = unsaf$0e { str::from_utf8_unchecked_mut(&mut buf) };
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -3,1 +3,1 @@
+            -let s = unsafe { str::from_utf8_unchecked_mut(&mut buf) };
+            +let s = std::str::from_utf8_mut(&mut buf).unwrap();
+            ```
+
+            **Runtime Overhead:** 1.1x
+
+            **Why this is safe:** `from_utf8_mut` reinterprets the same bytes through the same `&mut` borrow and validates the UTF-8 `from_utf8_unchecked_mut` assumed. See [`str::from_utf8_mut`](https://doc.rust-lang.org/std/str/fn.from_utf8_mut.html).
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_call_name_shows_suggestion() {
+    check(
+        r#"
+struct Vec<T>(T);
+impl<T> Vec<T> {
+    fn with_capacity(cap: usize) -> Vec<T> { loop {} }
+    fn as_mut_ptr(&mut self) -> *mut T { loop {} }
+    unsafe fn set_len(&mut self, len: usize) {}
+}
+fn main() {
+    let mut buf = Vec::with_capacity(2);
+    unsafe {
+        *buf.as_mut_ptr() = 1;
+        *buf.as_mut_ptr().add(1) = 2;
+        buf.set_l$0en(2);
+    }
+    println!("{:?}", buf);
+}
+"#,
+        expect![[r#"
+            *set_len*
+
+            ```rust
+            test::Vec
+            ```
+
+            ```rust
+            unsafe fn set_len(&mut self, len: usize)
+            ```
+            ---
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ```diff
+            @@ -9,5 +9,2 @@
+            -unsafe {
+            -    *buf.as_mut_ptr() = 1;
+            -    *buf.as_mut_ptr().add(1) = 2;
+            -    buf.set_len(2);
+            -}
+            +buf.push(1);
+            +buf.push(2);
+            ```
+
+            **Why this is safe:** The safe version only ever exposes elements that were written, where `set_len` trusted the memory below the new length to be initialized.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_fn_definition_no_suggestion() {
+    check(
+        r#"
+struct Vec<T>(T);
+impl<T> Vec<T> {
+    unsafe fn set_l$0en(&mut self, len: usize) {}
+}
+"#,
+        expect![[r#"
+            *set_len*
+
+            ```rust
+            test::Vec
+            ```
+
+            ```rust
+            unsafe fn set_len(&mut self, len: usize)
+            ```
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_manually_drop_create_then_drop() {
+    check(
+        r#"
+fn main() {
+    let mut m = ManuallyDrop::new(vec![1, 2, 3]);
+    m.push(4);
+    unsaf$0e { ManuallyDrop::drop(&mut m) };
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            **Note:** the value is wrapped in `ManuallyDrop` only to drop it by hand, nothing moves it out in between. The wrapper may be unnecessary: keep it as a plain owned value and call `drop` here, or let it drop at the end of its scope.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_manually_drop_moved_out() {
+    check(
+        r#"
+fn main() {
+    let mut m = ManuallyDrop::new(vec![1, 2, 3]);
+    let v = ManuallyDrop::take(&mut m);
+    unsaf$0e { ManuallyDrop::drop(&mut m) };
+}
+"#,
+        expect![[r#"
+            *unsafe { ManuallyDrop::drop(&mut m) }*
+            ```rust
+            {unknown}
+            ```
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_diff_fence_longer_than_backticks() {
+    check(
+        r#"
+fn main() {
+    let s = unsaf$0e { str::from_utf8_unchecked(b"```") };
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            ````diff
+            @@ -2,1 +2,1 @@
+            -let s = unsafe { str::from_utf8_unchecked(b"```") };
+            +let s = std::str::from_utf8(b"```").unwrap();
+            ````
+
+            **Runtime Overhead:** 1.1x
+
+            **Why this is safe:** `from_utf8` reinterprets the same bytes and validates the UTF-8 `from_utf8_unchecked` assumed. See [`str::from_utf8`](https://doc.rust-lang.org/std/str/fn.from_utf8.html).
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_note_code_span_with_backtick() {
+    check(
+        r#"
+fn main() {
+    let mut m = ManuallyDrop::new(String::from("`"));
+    unsaf$0e { ManuallyDrop::drop(&mut m) };
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            ```rust
+            Code Suggestion: translating unsafe to safe code
+            ```
+            ___
+
+            **Note:** the value is wrapped in `ManuallyDrop` only to drop it by hand, nothing moves it out in between. The wrapper may be unnecessary: keep it as a plain owned value and call `drop` here, or let it drop at the end of its scope.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_unsafe_comment_markup_kept_plain_text() {
+    check_hover_no_markdown(
+        r#"
+fn main() {
+    let mut buf = vec![104u8, 105];
+    let s = unsaf$0e { str::from_utf8_unchecked_mut(/* **not** ~~checked~~ */ &mut buf) };
+}
+"#,
+        expect![[r#"
+            *unsafe*
+            Code Suggestion: translating unsafe to safe code
+
+            @@ -3,1 +3,1 @@
+            -let s = unsafe { str::from_utf8_unchecked_mut(/* **not** ~~checked~~ */ &mut buf) };
+            +let s = std::str::from_utf8_mut(&mut buf).unwrap();
+
+            Runtime Overhead: 1.1x
+
+            Why this is safe: from_utf8_mut reinterprets the same bytes through the same &mut borrow and validates the UTF-8 from_utf8_unchecked_mut assumed. See str::from_utf8_mut.
+        "#]],
+    );
+}
+
 #[test]
 fn hover_keyword_as_primitive() {
     check(
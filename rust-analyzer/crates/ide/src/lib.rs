@@ -25,6 +25,9 @@ mod navigation_target;
 
 mod annotations;
 mod call_hierarchy;
+mod convert_diagnostics;
+mod convert_export;
+mod convert_preview;
 mod signature_help;
 mod doc_links;
 mod highlight_related;
@@ -76,6 +79,7 @@ use crate::navigation_target::{ToNav, TryToNav};
 pub use crate::{
     annotations::{Annotation, AnnotationConfig, AnnotationKind, AnnotationLocation},
     call_hierarchy::CallItem,
+    convert_preview::{preview_conversion, CONVERSION_CONFIG},
     expand_macro::ExpandedMacro,
     file_structure::{StructureNode, StructureNodeKind},
     folding_ranges::{Fold, FoldKind},
@@ -103,7 +107,8 @@ pub use crate::{
 };
 pub use hir::{Documentation, Semantics};
 pub use ide_assists::{
-    Assist, AssistConfig, AssistId, AssistKind, AssistResolveStrategy, SingleResolve,
+    Assist, AssistConfig, AssistId, AssistKind, AssistResolveStrategy, SafeReplacement,
+    SingleResolve, UnsafePattern,
 };
 pub use ide_completion::{
     CallableSnippets, CompletionConfig, CompletionItem, CompletionItemKind, CompletionRelevance,
@@ -583,7 +588,12 @@ impl Analysis {
         resolve: AssistResolveStrategy,
         file_id: FileId,
     ) -> Cancellable<Vec<Diagnostic>> {
-        self.with_db(|db| ide_diagnostics::diagnostics(db, config, &resolve, file_id))
+        self.with_db(|db| {
+            let mut diagnostics = ide_diagnostics::diagnostics(db, config, &resolve, file_id);
+            diagnostics
+                .extend(convert_diagnostics::unsafe_conversion_diagnostics(db, config, file_id));
+            diagnostics
+        })
     }
 
     /// Convenience function to return assists + quick fixes for diagnostics
@@ -620,6 +630,15 @@ impl Analysis {
         })
     }
 
+    /// Returns the safe replacement of every convertible `unsafe` block in the file.
+    pub fn unsafe_conversions(
+        &self,
+        config: &AssistConfig,
+        file_id: FileId,
+    ) -> Cancellable<Vec<SafeReplacement>> {
+        self.with_db(|db| convert_export::unsafe_conversions(db, config, file_id))
+    }
+
     /// Returns the edit required to rename reference at the position to the new
     /// name.
     pub fn rename(
@@ -134,6 +134,9 @@ impl StaticIndex<'_> {
             links_in_hover: true,
             documentation: Some(HoverDocFormat::Markdown),
             keywords: true,
+            show_unsafe_suggestions: true,
+            safe_wrappers: false,
+            disabled_unsafe_patterns: FxHashSet::default(),
         };
         let tokens = tokens.filter(|token| {
             matches!(
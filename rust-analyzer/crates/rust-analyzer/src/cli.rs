@@ -5,6 +5,7 @@ pub mod load_cargo;
 mod parse;
 mod symbols;
 mod highlight;
+mod unsafe_conversions;
 mod analysis_stats;
 mod diagnostics;
 mod ssr;
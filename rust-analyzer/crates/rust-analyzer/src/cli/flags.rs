@@ -53,6 +53,11 @@ xflags::xflags! {
             optional --rainbow
         }
 
+        /// Print the safe replacements of the convertible unsafe blocks in stdin as JSON.
+        /// Stdin is analyzed without a sysroot, so the conversions that resolve a std method,
+        /// such as `get_unchecked`, are not reported.
+        cmd unsafe-conversions {}
+
         /// Batch typecheck project and print summary statistics
         cmd analysis-stats
             /// Directory with Cargo.toml.
@@ -138,6 +143,7 @@ pub enum RustAnalyzerCmd {
     Parse(Parse),
     Symbols(Symbols),
     Highlight(Highlight),
+    UnsafeConversions(UnsafeConversions),
     AnalysisStats(AnalysisStats),
     Diagnostics(Diagnostics),
     Ssr(Ssr),
@@ -167,6 +173,9 @@ pub struct Highlight {
     pub rainbow: bool,
 }
 
+#[derive(Debug)]
+pub struct UnsafeConversions;
+
 #[derive(Debug)]
 pub struct AnalysisStats {
     pub path: PathBuf,
@@ -0,0 +1,90 @@
+//! Read Rust code on stdin, print the safe replacements of its unsafe blocks as JSON.
+
+use ide::{Analysis, AssistConfig, FileId, CONVERSION_CONFIG};
+use serde::Serialize;
+
+use crate::cli::{flags, read_stdin};
+
+#[derive(Serialize)]
+struct ExportRange {
+    start: u32,
+    end: u32,
+}
+
+#[derive(Serialize)]
+struct ConversionEntry {
+    range: ExportRange,
+    pattern: &'static str,
+    safe_replacement: String,
+}
+
+impl flags::UnsafeConversions {
+    pub fn run(self) -> anyhow::Result<()> {
+        let (analysis, file_id) = Analysis::from_single_file(read_stdin()?);
+        println!("{}", export_conversions_json(&analysis, &CONVERSION_CONFIG, file_id)?);
+        Ok(())
+    }
+}
+
+fn export_conversions_json(
+    analysis: &Analysis,
+    config: &AssistConfig,
+    file_id: FileId,
+) -> anyhow::Result<String> {
+    let entries = analysis
+        .unsafe_conversions(config, file_id)?
+        .into_iter()
+        .map(|replacement| ConversionEntry {
+            range: ExportRange {
+                start: replacement.range.start().into(),
+                end: replacement.range.end().into(),
+            },
+            pattern: replacement.pattern.as_str(),
+            safe_replacement: replacement.new_text,
+        })
+        .collect::<Vec<_>>();
+
+    Ok(serde_json::to_string_pretty(&entries)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_set_len() {
+        let (analysis, file_id) = Analysis::from_single_file(
+            r#"
+fn main() {
+    let mut buffer = Vec::with_capacity(100);
+    unsafe {
+        buffer.set_len(100);
+    }
+    input.read_into(&mut buffer);
+}
+"#
+            .to_string(),
+        );
+
+        let json = export_conversions_json(&analysis, &CONVERSION_CONFIG, file_id).unwrap();
+
+        assert!(json.contains(r#""pattern": "uninitialized_vec""#), "{}", json);
+        assert!(json.contains("vec![0; 100]"), "{}", json);
+    }
+
+    #[test]
+    fn export_without_sysroot() {
+        let (analysis, file_id) = Analysis::from_single_file(
+            r#"
+fn first(v: &[u32]) -> u32 {
+    unsafe { *v.get_unchecked(0) }
+}
+"#
+            .to_string(),
+        );
+
+        let json = export_conversions_json(&analysis, &CONVERSION_CONFIG, file_id).unwrap();
+
+        assert_eq!(json, "[]");
+    }
+}
@@ -56,8 +56,14 @@ mod patch_old_style;
 // parsing the old name.
 config_data! {
     struct ConfigData {
+        /// Whether to insert a `// TODO` comment above unsafe-to-safe conversions that may panic or behave differently.
+        assist_annotateRiskyConversions: bool                         = "false",
+        /// List of unsafe-to-safe patterns, by name, that should neither be suggested in hovers nor offered as assists.
+        assist_disabledUnsafePatterns: FxHashSet<String>              = "[]",
         /// Placeholder expression to use for missing expressions in assists.
         assist_expressionFillDefault: ExprFillDefaultDef              = "\"todo\"",
+        /// Whether to suggest calling a `_safe`/`_checked` wrapper defined next to a called `unsafe fn`.
+        assist_suggestSafeWrappers: bool                              = "false",
 
         /// Warm up caches on project load.
         cachePriming_enable: bool = "true",
@@ -260,6 +266,9 @@ config_data! {
         hover_documentation_keywords_enable: bool  = "true",
         /// Use markdown syntax for links in hover.
         hover_links_enable: bool = "true",
+        /// Whether hovering `unsafe` suggests safe replacements for the code it covers. Only applies
+        /// when `#rust-analyzer.hover.documentation.keywords.enable#` is set.
+        hover_unsafeSuggestions_enable: bool = "true",
 
         /// Whether to enforce the import granularity setting for all files. If set to false rust-analyzer will try to keep import styles consistent per file.
         imports_granularity_enforce: bool              = "false",
@@ -633,6 +642,7 @@ pub struct ClientCommandsConfig {
     pub show_reference: bool,
     pub goto_location: bool,
     pub trigger_parameter_hints: bool,
+    pub resolve_code_action: bool,
 }
 
 #[derive(Debug)]
@@ -1213,6 +1223,9 @@ impl Config {
             allowed: None,
             insert_use: self.insert_use_config(),
             prefer_no_std: self.data.imports_prefer_no_std,
+            suggest_safe_wrappers: self.data.assist_suggestSafeWrappers,
+            disabled_unsafe_patterns: Some(self.data.assist_disabledUnsafePatterns.clone()),
+            annotate_risky_conversions: self.data.assist_annotateRiskyConversions,
         }
     }
 
@@ -1293,6 +1306,9 @@ impl Config {
                 }
             }),
             keywords: self.data.hover_documentation_keywords_enable,
+            show_unsafe_suggestions: self.data.hover_unsafeSuggestions_enable,
+            safe_wrappers: self.data.assist_suggestSafeWrappers,
+            disabled_unsafe_patterns: self.data.assist_disabledUnsafePatterns.clone(),
         }
     }
 
@@ -1349,6 +1365,7 @@ impl Config {
             show_reference: get("rust-analyzer.showReferences"),
             goto_location: get("rust-analyzer.gotoLocation"),
             trigger_parameter_hints: get("editor.action.triggerParameterHints"),
+            resolve_code_action: get("rust-analyzer.resolveCodeAction"),
         }
     }
 
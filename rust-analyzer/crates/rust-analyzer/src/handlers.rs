@@ -1744,6 +1744,46 @@ fn goto_type_action_links(
     })
 }
 
+fn apply_assist_action_links(
+    snap: &GlobalStateSnapshot,
+    assist_id: &str,
+    range: &FileRange,
+) -> Option<lsp_ext::CommandLinkGroup> {
+    if !snap.config.code_action_resolve() || !snap.config.client_commands().resolve_code_action {
+        return None;
+    }
+
+    // The resolve request finds the assist again by its index among the ones at the range
+    let assists = snap
+        .analysis
+        .assists_with_fixes(
+            &snap.config.assist(),
+            &snap.config.diagnostics(),
+            AssistResolveStrategy::None,
+            *range,
+        )
+        .ok()?;
+    let (index, assist) =
+        assists.into_iter().enumerate().find(|(_, assist)| assist.id.0 == assist_id)?;
+
+    let line_index = snap.file_line_index(range.file_id).ok()?;
+    let params = lsp_types::CodeActionParams {
+        text_document: TextDocumentIdentifier::new(to_proto::url(snap, range.file_id)),
+        range: to_proto::range(&line_index, range.range),
+        context: lsp_types::CodeActionContext::default(),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+
+    let code_action = to_proto::code_action(snap, assist, Some((index, params))).ok()?;
+    let command = to_proto::command::resolve_code_action(&code_action);
+
+    Some(lsp_ext::CommandLinkGroup {
+        commands: vec![to_command_link(command, "Apply the safe version".into())],
+        ..Default::default()
+    })
+}
+
 fn prepare_hover_actions(
     snap: &GlobalStateSnapshot,
     actions: &[HoverAction],
@@ -1755,6 +1795,9 @@ fn prepare_hover_actions(
             HoverAction::Reference(position) => show_ref_command_link(snap, position),
             HoverAction::Runnable(r) => runnable_action_links(snap, r.clone()),
             HoverAction::GoToType(targets) => goto_type_action_links(snap, targets),
+            HoverAction::ApplyAssist { assist_id, range } => {
+                apply_assist_action_links(snap, assist_id, range)
+            }
         })
         .collect()
 }
@@ -1322,6 +1322,14 @@ pub(crate) mod command {
         })
     }
 
+    pub(crate) fn resolve_code_action(code_action: &lsp_ext::CodeAction) -> lsp_types::Command {
+        lsp_types::Command {
+            title: code_action.title.clone(),
+            command: "rust-analyzer.resolveCodeAction".into(),
+            arguments: Some(vec![to_value(code_action).unwrap()]),
+        }
+    }
+
     pub(crate) fn trigger_parameter_hints() -> lsp_types::Command {
         lsp_types::Command {
             title: "triggerParameterHints".into(),
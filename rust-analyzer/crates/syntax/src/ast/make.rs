@@ -461,6 +461,14 @@ pub fn expr_field(receiver: ast::Expr, field: &str) -> ast::Expr {
 pub fn expr_paren(expr: ast::Expr) -> ast::Expr {
     expr_from_text(&format!("({expr})"))
 }
+pub fn expr_index(base: ast::Expr, index: ast::Expr) -> ast::Expr {
+    expr_from_text(&format!("{base}[{index}]"))
+}
+pub fn expr_range(start: Option<ast::Expr>, end: Option<ast::Expr>) -> ast::Expr {
+    let start = start.map_or(String::new(), |it| it.to_string());
+    let end = end.map_or(String::new(), |it| it.to_string());
+    expr_from_text(&format!("{start}..{end}"))
+}
 pub fn expr_tuple(elements: impl IntoIterator<Item = ast::Expr>) -> ast::Expr {
     let expr = elements.into_iter().format(", ");
     expr_from_text(&format!("({expr})"))
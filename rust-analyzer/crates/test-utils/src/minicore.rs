@@ -37,6 +37,7 @@
 //!     add:
 //!     as_ref: sized
 //!     drop:
+//!     maybe_uninit:
 
 pub mod marker {
     // region:sized
@@ -406,6 +407,37 @@ pub mod fmt {
 }
 // endregion:fmt
 
+// region:maybe_uninit
+pub mod mem {
+    mod maybe_uninit {
+        #[lang = "maybe_uninit"]
+        pub union MaybeUninit<T> {
+            uninit: (),
+            value: T,
+        }
+
+        impl<T> MaybeUninit<T> {
+            pub const fn uninit() -> MaybeUninit<T> {
+                loop {}
+            }
+            pub unsafe fn assume_init(self) -> T {
+                loop {}
+            }
+            pub fn write(&mut self, val: T) -> &mut T {
+                loop {}
+            }
+            pub unsafe fn assume_init_ref(&self) -> &T {
+                loop {}
+            }
+            pub unsafe fn assume_init_mut(&mut self) -> &mut T {
+                loop {}
+            }
+        }
+    }
+    pub use self::maybe_uninit::MaybeUninit;
+}
+// endregion:maybe_uninit
+
 // region:slice
 pub mod slice {
     #[lang = "slice"]
@@ -413,6 +445,42 @@ pub mod slice {
         pub fn len(&self) -> usize {
             loop {}
         }
+        pub const fn as_ptr(&self) -> *const T {
+            loop {}
+        }
+        pub fn as_mut_ptr(&mut self) -> *mut T {
+            loop {}
+        }
+        pub fn swap(&mut self, a: usize, b: usize) {
+            loop {}
+        }
+        pub unsafe fn swap_unchecked(&mut self, a: usize, b: usize) {
+            loop {}
+        }
+        pub unsafe fn get_unchecked(&self, index: usize) -> &T {
+            loop {}
+        }
+        pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut T {
+            loop {}
+        }
+        pub fn split_at(&self, mid: usize) -> (&[T], &[T]) {
+            loop {}
+        }
+        pub fn split_at_mut(&mut self, mid: usize) -> (&mut [T], &mut [T]) {
+            loop {}
+        }
+        pub unsafe fn split_at_unchecked(&self, mid: usize) -> (&[T], &[T]) {
+            loop {}
+        }
+        pub unsafe fn split_at_mut_unchecked(&mut self, mid: usize) -> (&mut [T], &mut [T]) {
+            loop {}
+        }
+        pub const fn as_chunks<const N: usize>(&self) -> (&[[T; N]], &[T]) {
+            loop {}
+        }
+        pub unsafe fn as_chunks_unchecked<const N: usize>(&self) -> &[[T; N]] {
+            loop {}
+        }
     }
 }
 // endregion:slice
@@ -267,6 +267,7 @@ class ExperimentalFeatures implements lc.StaticFeature {
                 "rust-analyzer.debugSingle",
                 "rust-analyzer.showReferences",
                 "rust-analyzer.gotoLocation",
+                "rust-analyzer.resolveCodeAction",
                 "editor.action.triggerParameterHints",
             ],
         };